@@ -1,14 +1,130 @@
 // 인증 관련 Tauri 커맨드
 // 프론트엔드에서 호출할 수 있는 인증 관련 함수들을 정의합니다.
 
-use crate::{models::PinComplexity, AppState};
-use std::sync::Mutex;
+use crate::{
+    models::{
+        KdfCalibration, PinComplexity, PinComplexityRequirement, RecoveryKeyStatus,
+        RecoveryKeyValidationResult, RecoveryLoginResult, RecoverySheetFormat, SecretKey,
+        VaultError,
+    },
+    services::{
+        auth::{DEFAULT_UNLOCK_TARGET_MS, MAX_UNLOCK_TARGET_MS, STEP_UP_WINDOW_SECONDS},
+        recovery_sheet::RecoverySheet,
+        AuthService, CryptoService,
+    },
+    AppState,
+};
+use crate::commands::guard::{ensure_unlocked, lock_unlocked};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tauri::State;
+use zeroize::Zeroize;
+
+/// 키 파일로 사용할 수 있는 최대 파일 크기 (바이트)
+const MAX_KEY_FILE_SIZE: u64 = 16 * 1024 * 1024;
+
+/// 키 파일을 읽습니다.
+///
+/// # 매개변수
+/// * `path` - 키 파일 경로
+///
+/// # 반환값
+/// * `Result<Vec<u8>, String>` - 키 파일 내용
+fn read_key_file(path: &str) -> Result<Vec<u8>, String> {
+    let path = Path::new(path);
+    let metadata = std::fs::metadata(path)
+        .map_err(|_| "키 파일을 찾을 수 없습니다. 키 파일이 있는 USB를 연결해주세요.".to_string())?;
+
+    if !metadata.is_file() || metadata.len() == 0 || metadata.len() > MAX_KEY_FILE_SIZE {
+        return Err("키 파일로 사용할 수 없는 파일입니다.".to_string());
+    }
+
+    std::fs::read(path).map_err(|e| format!("키 파일 읽기 실패: {}", e))
+}
+
+/// 언래핑된 볼트 데이터 키를 암호화/파일/데이터베이스 서비스에 설정합니다.
+///
+/// 일반 PIN과 강압 PIN 모두 같은 경로로 처리되어 로그와 동작이 구분되지 않습니다.
+/// 데이터 키를 교체한 뒤 재암호화가 끝나지 않았다면 이전 데이터 키도 읽기용으로 설정합니다.
+///
+/// # 매개변수
+/// * `app_state` - 애플리케이션 상태
+/// * `data_key` - 볼트 데이터 키 (서비스들이 복사하지 않고 공유)
+pub(crate) fn install_master_key(
+    app_state: &mut AppState,
+    data_key: Arc<SecretKey>,
+) -> Result<(), String> {
+    app_state
+        .crypto_service
+        .set_master_key(Arc::clone(&data_key));
+    let previous_key = app_state
+        .auth_service
+        .retired_data_key(&data_key)
+        .map(Arc::new);
+
+    let vault_root = app_state.auth_service.active_vault_root();
+    crate::set_vault_root(Some(vault_root.clone()));
+    let vault_path = vault_root.to_string_lossy().to_string();
+
+    {
+        let mut file_service = app_state
+            .file_service
+            .lock()
+            .map_err(|_| "파일 서비스 잠금 실패")?;
+        file_service.set_previous_master_key(previous_key.clone());
+        file_service.set_vault_info(&vault_path, Arc::clone(&data_key));
+        if let Err(e) = file_service.load_encryption_algorithm() {
+            log::error!("볼트 암호화 알고리즘 설정 로드 실패: {}", e);
+            return Err("인증 처리 중 오류가 발생했습니다.".to_string());
+        }
+        app_state
+            .crypto_service
+            .set_default_algorithm(file_service.encryption_algorithm().clone());
+    }
+
+    let mut database_service = app_state
+        .database_service
+        .lock()
+        .map_err(|_| "데이터베이스 서비스 잠금 실패")?;
+    let db_key = CryptoService::derive_database_key(&data_key);
+    let previous_db_key = previous_key
+        .as_ref()
+        .map(|previous_key| CryptoService::derive_database_key(previous_key));
+    if let Err(e) =
+        database_service.initialize_or_rekey(&vault_path, &db_key, previous_db_key.as_ref())
+    {
+        log::error!("데이터베이스 초기화 실패: {}", e);
+        return Err("인증 처리 중 오류가 발생했습니다.".to_string());
+    }
+
+    // 보관 중인 로그와 이후 로그를 이 볼트의 로그 디렉토리에 기록
+    let log_key = app_state
+        .auth_service
+        .get_security_config()
+        .encrypt_logs
+        .then(|| CryptoService::derive_log_key(&data_key));
+    if let Err(e) = crate::services::log_sink::attach(
+        &crate::services::log_sink::log_directory(&vault_root),
+        log_key,
+    ) {
+        log::warn!("로그 파일을 열 수 없어 로그를 메모리에만 보관합니다: {}", e);
+    }
+
+    // 로그인 직후부터 유휴 시간 계산
+    crate::services::idle_lock::record_activity();
+
+    log::info!("파일 서비스 초기화 완료");
+    Ok(())
+}
 
 /// PIN으로 인증합니다.
 ///
+/// 키 파일이 등록된 볼트는 `key_file_path`도 함께 제공해야 합니다.
+///
 /// # 매개변수
 /// * `pin` - 사용자가 입력한 PIN
+/// * `key_file_path` - 키 파일 경로 (등록된 경우)
 /// * `state` - 애플리케이션 상태
 ///
 /// # 반환값
@@ -16,40 +132,32 @@ use tauri::State;
 #[tauri::command]
 pub async fn authenticate_pin(
     pin: String,
+    key_file_path: Option<String>,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<bool, String> {
+    let key_file = match key_file_path.as_deref() {
+        Some(path) => Some(read_key_file(path)?),
+        None => None,
+    };
+
     let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    app_state.auth_service.provide_key_file(key_file.as_deref());
 
     match app_state.auth_service.verify_pin(&pin) {
         Ok(result) => {
             use crate::models::PinValidationResult;
             match result {
                 PinValidationResult::Valid => {
-                    // 암호화 서비스 초기화
-                    if let Some(pin_info) = app_state.auth_service.get_pin_info() {
-                        // pin_info를 복사하여 borrow 문제 해결
-                        let salt = pin_info.salt.clone();
-                        if let Err(e) = app_state.crypto_service.derive_master_key(&pin, &salt) {
-                            log::error!("마스터 키 유도 실패: {}", e);
+                    // 볼트 데이터 키 언래핑
+                    let data_key = match app_state.auth_service.unlock_with_pin(&pin) {
+                        Ok(key) => key,
+                        Err(e) => {
+                            log::error!("볼트 키 언래핑 실패: {}", e);
                             return Err("인증 처리 중 오류가 발생했습니다.".to_string());
                         }
+                    };
 
-                        // 파일 서비스 초기화
-                        if let Some(master_key) = app_state.crypto_service.get_master_key() {
-                            let vault_path = std::env::current_dir()
-                                .unwrap_or_else(|_| std::path::PathBuf::from("."))
-                                .to_string_lossy()
-                                .to_string();
-
-                            let mut file_service = app_state
-                                .file_service
-                                .lock()
-                                .map_err(|_| "파일 서비스 잠금 실패")?;
-                            log::info!("인증 성공: 마스터 키 설정 중 (Key0={:02X})", master_key[0]);
-                            file_service.set_vault_info(&vault_path, master_key);
-                            log::info!("파일 서비스 초기화 완료");
-                        }
-                    }
+                    install_master_key(&mut app_state, Arc::new(data_key))?;
                     Ok(true)
                 }
                 PinValidationResult::Invalid => Ok(false),
@@ -62,6 +170,12 @@ pub async fn authenticate_pin(
                 PinValidationResult::Expired => {
                     Err("PIN이 만료되었습니다. 새로운 PIN을 설정해주세요.".to_string())
                 }
+                PinValidationResult::KeyFileRequired => {
+                    Err("키 파일이 필요합니다. 등록된 키 파일을 선택해주세요.".to_string())
+                }
+                PinValidationResult::KeyFileMismatch => {
+                    Err("키 파일이 올바르지 않습니다. 등록된 키 파일을 선택해주세요.".to_string())
+                }
             }
         }
         Err(e) => {
@@ -73,17 +187,29 @@ pub async fn authenticate_pin(
 
 /// PIN을 설정합니다.
 ///
+/// 기존 볼트의 PIN을 재설정하려면 로그인되어 있어야 하며, PIN으로 로그인한 세션은
+/// `current_pin`으로 현재 PIN을 확인해야 합니다. 복구 키로 로그인한 세션은 생략할 수 있습니다.
+///
+/// 키 파일이 등록된 볼트는 `key_file_path`로 등록된 키 파일을 제공하거나(복구 키 로그인 후 등),
+/// `remove_key_file`로 키 파일 요구 해제에 동의해야 합니다.
+///
 /// # 매개변수
 /// * `pin` - 설정할 PIN
 /// * `complexity` - PIN 복잡도 레벨 ("basic", "medium", "high")
+/// * `current_pin` - 현재 PIN (기존 볼트를 PIN 세션에서 재설정할 때 필요)
+/// * `key_file_path` - 등록된 키 파일 경로 (선택)
+/// * `remove_key_file` - 키 파일 없이 재설정하면서 키 파일 요구를 해제할지 여부 (선택)
 /// * `state` - 애플리케이션 상태
 ///
 /// # 반환값
-/// * `Result<(), String>` - 설정 결과
+/// * `Result<(), String>` - 성공 여부
 #[tauri::command]
 pub async fn set_pin_code(
     pin: String,
     complexity: String,
+    current_pin: Option<String>,
+    key_file_path: Option<String>,
+    remove_key_file: Option<bool>,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<(), String> {
     let complexity_level = match complexity.as_str() {
@@ -93,10 +219,66 @@ pub async fn set_pin_code(
         _ => return Err("올바르지 않은 복잡도 레벨입니다.".to_string()),
     };
 
+    let key_file = match key_file_path.as_deref() {
+        Some(path) => Some(read_key_file(path)?),
+        None => None,
+    };
+
+    // 새 볼트는 이 PC에 맞춰 키 유도 비용을 정합니다. 측정에 수 초가 걸리므로 상태 잠금 밖에서 측정
+    let is_new_vault = !state
+        .lock()
+        .map_err(|_| "상태 잠금 실패")?
+        .auth_service
+        .has_pin();
+    let calibration = if is_new_vault {
+        AuthService::measure_kdf(Duration::from_millis(DEFAULT_UNLOCK_TARGET_MS))
+            .map_err(|e| log::warn!("키 유도 비용 보정 실패, 기본 매개변수를 사용합니다: {}", e))
+            .ok()
+    } else {
+        None
+    };
+
     let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    if key_file.is_some() {
+        app_state.auth_service.provide_key_file(key_file.as_deref());
+    }
 
-    match app_state.auth_service.set_pin(&pin, complexity_level) {
+    // 기존 볼트는 인증(PIN 또는 복구 키)으로 얻은 데이터 키를 새 PIN으로 다시 래핑하고,
+    // 새 볼트는 데이터 키를 새로 생성합니다.
+    let remove_key_file = remove_key_file.unwrap_or(false);
+    let (data_key, result) = if app_state.auth_service.has_pin() {
+        let data_key = ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+        let result = app_state.auth_service.reset_pin(
+            current_pin.as_deref(),
+            &pin,
+            complexity_level,
+            &data_key,
+            remove_key_file,
+        );
+        (data_key, result)
+    } else {
+        // 측정한 매개변수를 저장한 뒤 PIN을 해시하고 데이터 키를 래핑
+        if let Some(calibration) = &calibration {
+            if let Err(e) = app_state.auth_service.apply_kdf_calibration(calibration) {
+                log::warn!("키 유도 비용 보정 실패, 기본 매개변수를 사용합니다: {}", e);
+            }
+        }
+        let data_key = Arc::new(CryptoService::generate_data_key());
+        let result = if remove_key_file {
+            app_state
+                .auth_service
+                .set_pin_removing_key_file(&pin, complexity_level, &data_key)
+        } else {
+            app_state
+                .auth_service
+                .set_pin(&pin, complexity_level, &data_key)
+        };
+        (data_key, result)
+    };
+
+    match result {
         Ok(()) => {
+            install_master_key(&mut app_state, data_key)?;
             log::info!("PIN이 성공적으로 설정되었습니다.");
             Ok(())
         }
@@ -109,44 +291,63 @@ pub async fn set_pin_code(
 
 /// 복구 키로 인증합니다.
 ///
+/// 복구 키는 한 번만 쓸 수 있으므로 인증에 성공하면 같은 호출에서 새 복구 키로 교체합니다.
+/// 새 복구 키는 이 반환값으로만 전달되므로 프런트엔드는 바로 사용자에게 보여줘야 합니다.
+///
 /// # 매개변수
-/// * `recovery_key` - 복구 키 (Base64 문자열)
+/// * `recovery_key` - 복구 키 (24단어 또는 기존 Base64 문자열)
+/// * `recovery_shares` - 전체 복구 키 대신 입력한 M개 이상의 복구 키 조각 (선택)
 /// * `state` - 애플리케이션 상태
 ///
 /// # 반환값
-/// * `Result<bool, String>` - 인증 성공 여부
+/// * `Result<Option<String>, String>` - 인증 성공 시 새로 발급된 복구 키, 잘못된 복구 키면 `None`
 #[tauri::command]
 pub async fn authenticate_recovery_key(
-    recovery_key: String,
+    recovery_key: Option<String>,
+    recovery_shares: Option<Vec<String>>,
     state: State<'_, Mutex<AppState>>,
-) -> Result<bool, String> {
+) -> Result<Option<String>, String> {
     let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
 
-    match app_state.auth_service.verify_recovery_key(&recovery_key) {
-        Ok(result) => {
-            use crate::models::RecoveryKeyValidationResult;
-            match result {
-                RecoveryKeyValidationResult::Valid => {
-                    // 암호화 서비스 초기화 (복구 키 기반)
-                    if let Some(_recovery_info) = app_state.auth_service.get_recovery_key_info() {
-                        // TODO: 복구 키로부터 마스터 키 유도 구현 필요
-                        log::info!("복구 키 인증 성공, 암호화 서비스 초기화 필요");
-                    }
-                    Ok(true)
-                }
-                RecoveryKeyValidationResult::Invalid => Ok(false),
-                RecoveryKeyValidationResult::InvalidFormat => Err(
-                    "복구 키 형식이 올바르지 않습니다. Base64 형식의 32바이트 키를 입력해주세요."
-                        .to_string(),
-                ),
-                RecoveryKeyValidationResult::Deactivated => {
-                    Err("복구 키가 비활성화되었습니다.".to_string())
-                }
-            }
+    // 조각이 입력되었으면 복구 키로 복원
+    let recovery_key = app_state
+        .recovery_service
+        .resolve_recovery_input(recovery_key.as_deref(), recovery_shares.as_deref())
+        .map_err(|e| e.to_string())?;
+
+    match app_state.auth_service.login_with_recovery_key(&recovery_key) {
+        Ok(RecoveryLoginResult::Unlocked {
+            data_key,
+            new_recovery_key,
+        }) => {
+            install_master_key(&mut app_state, Arc::new(data_key))?;
+            Ok(Some(new_recovery_key))
         }
+        Ok(RecoveryLoginResult::Rejected(result)) => match result {
+            RecoveryKeyValidationResult::Valid | RecoveryKeyValidationResult::Invalid => Ok(None),
+            RecoveryKeyValidationResult::InvalidFormat => {
+                // 어느 단어가 잘못됐는지 사용자에게 알려줌
+                let reason = crate::utils::mnemonic::decode_recovery_key(&recovery_key)
+                    .err()
+                    .map(|e| e.to_string())
+                    .unwrap_or_else(|| "복구 키 형식이 올바르지 않습니다.".to_string());
+                Err(reason)
+            }
+            RecoveryKeyValidationResult::Deactivated => {
+                Err("복구 키가 비활성화되었습니다.".to_string())
+            }
+            RecoveryKeyValidationResult::Superseded => Err(
+                "이미 사용했거나 새 복구 키가 발급되어 더 이상 사용할 수 없는 복구 키입니다. 가장 최근에 발급받은 복구 키를 입력해주세요."
+                    .to_string(),
+            ),
+        },
         Err(e) => {
+            // 데이터 키를 풀지 못했거나 새 복구 키를 저장하지 못함: 로그인하지 않았고 복구 키도 교체되지 않음
             log::error!("복구 키 인증 오류: {}", e);
-            Err("복구 키 인증 중 오류가 발생했습니다.".to_string())
+            Err(
+                "복구 키로 볼트를 열지 못했습니다. 입력한 복구 키는 사용 처리되지 않았으니 다시 시도하거나, PIN으로 로그인한 뒤 복구 키를 다시 생성해주세요."
+                    .to_string(),
+            )
         }
     }
 }
@@ -157,14 +358,16 @@ pub async fn authenticate_recovery_key(
 /// * `state` - 애플리케이션 상태
 ///
 /// # 반환값
-/// * `Result<String, String>` - 생성된 복구 키 (Base64)
+/// * `Result<String, String>` - 생성된 24단어 복구 키
 #[tauri::command]
 pub async fn generate_new_recovery_key(
     state: State<'_, Mutex<AppState>>,
 ) -> Result<String, String> {
     let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
 
-    match app_state.auth_service.generate_recovery_key() {
+    let data_key = ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    match app_state.auth_service.generate_recovery_key(&data_key) {
         Ok(key) => {
             log::info!("복구 키가 성공적으로 생성되었습니다.");
             Ok(key)
@@ -176,6 +379,90 @@ pub async fn generate_new_recovery_key(
     }
 }
 
+/// 현재 복구 키로 인쇄용 복구 키 시트를 만듭니다.
+///
+/// 시트는 메모리에서만 렌더링해 반환하며 디스크에 기록하지 않습니다.
+///
+/// # 매개변수
+/// * `recovery_key` - `generate_new_recovery_key`로 받은 복구 키
+/// * `format` - 출력 형식 (`svg` 또는 `png`)
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<Vec<u8>, String>` - 렌더링된 시트 바이트
+#[tauri::command]
+pub async fn generate_recovery_sheet(
+    recovery_key: String,
+    format: RecoverySheetFormat,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<u8>, String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let sheet = build_recovery_sheet(&mut app_state, &recovery_key)?;
+
+    sheet
+        .render(format)
+        .map_err(|e| format!("복구 키 시트 생성 실패: {}", e))
+}
+
+/// 복구 키 시트를 사용자가 선택한 경로로 내보냅니다.
+///
+/// # 매개변수
+/// * `recovery_key` - `generate_new_recovery_key`로 받은 복구 키
+/// * `format` - 출력 형식 (`svg` 또는 `png`)
+/// * `destination_path` - 사용자가 저장 대화상자에서 선택한 경로
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<(), String>` - 내보내기 결과
+#[tauri::command]
+pub async fn export_recovery_sheet(
+    recovery_key: String,
+    format: RecoverySheetFormat,
+    destination_path: String,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<(), String> {
+    let mut bytes = {
+        let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+        let sheet = build_recovery_sheet(&mut app_state, &recovery_key)?;
+        sheet
+            .render(format)
+            .map_err(|e| format!("복구 키 시트 생성 실패: {}", e))?
+    };
+
+    let result = std::fs::write(&destination_path, &bytes)
+        .map_err(|e| format!("복구 키 시트 저장 실패: {}", e));
+    bytes.zeroize();
+    result?;
+
+    log::info!("복구 키 시트를 내보냈습니다. ({})", format.mime_type());
+    Ok(())
+}
+
+/// 현재 발급된 복구 키인지 확인하고 볼트 ID·발급일을 채운 시트를 만듭니다.
+fn build_recovery_sheet(
+    app_state: &mut AppState,
+    recovery_key: &str,
+) -> Result<RecoverySheet, String> {
+    ensure_unlocked(app_state).map_err(|e| e.to_string())?;
+
+    // 이전에 발급했거나 오타가 있는 키로 시트를 인쇄하지 않도록 확인
+    if !app_state.auth_service.is_current_recovery_key(recovery_key) {
+        return Err("현재 발급된 복구 키가 아닙니다. 복구 키를 다시 생성해주세요.".to_string());
+    }
+
+    let vault_id = app_state
+        .auth_service
+        .vault_id()
+        .map_err(|e| format!("볼트 ID 조회 실패: {}", e))?;
+    let issued_at = app_state
+        .auth_service
+        .recovery_key_created_at()
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs as i64, 0))
+        .unwrap_or_else(chrono::Utc::now);
+
+    RecoverySheet::new(vault_id, issued_at, recovery_key).map_err(|e| e.to_string())
+}
+
 /// 로그아웃합니다.
 ///
 /// # 매개변수
@@ -187,14 +474,8 @@ pub async fn generate_new_recovery_key(
 pub async fn logout(state: State<'_, Mutex<AppState>>) -> Result<(), String> {
     let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
 
-    // 인증 서비스 로그아웃
-    if let Err(e) = app_state.auth_service.logout() {
-        log::error!("로그아웃 오류: {}", e);
-        return Err("로그아웃 중 오류가 발생했습니다.".to_string());
-    }
-
-    // 암호화 서비스 민감한 데이터 정리
-    app_state.crypto_service.clear_sensitive_data();
+    // 모든 키 복사본 정리 후 세션 종료
+    app_state.lock_vault();
 
     log::info!("로그아웃이 완료되었습니다.");
     Ok(())
@@ -226,6 +507,79 @@ pub async fn has_pin_set(state: State<'_, Mutex<AppState>>) -> Result<bool, Stri
     Ok(app_state.auth_service.has_pin())
 }
 
+/// PIN 입력 방식 정보
+#[derive(serde::Serialize)]
+pub struct PinRequirementInfo {
+    /// 새 PIN에 적용되는 복잡도 요구사항
+    pub requirement: PinComplexityRequirement,
+    /// 요구사항 설명
+    pub description: String,
+    /// 새 PIN에 숫자 외의 문자를 허용하는지 여부
+    pub allows_passphrase: bool,
+    /// 현재 설정된 PIN이 패스프레이즈인지 여부 (로그인 화면 입력 방식)
+    pub is_passphrase_pin: bool,
+}
+
+/// PIN 복잡도 요구사항과 입력 방식을 조회합니다.
+///
+/// # 매개변수
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<PinRequirementInfo, String>` - 요구사항 정보
+#[tauri::command]
+pub async fn get_pin_requirement(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<PinRequirementInfo, String> {
+    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let requirement = app_state.auth_service.get_pin_requirement().clone();
+
+    Ok(PinRequirementInfo {
+        description: requirement.description(),
+        allows_passphrase: requirement.allows_passphrase(),
+        is_passphrase_pin: app_state.auth_service.is_passphrase_pin(),
+        requirement,
+    })
+}
+
+/// PIN 복잡도 요구사항을 변경합니다.
+///
+/// 기존 PIN은 계속 사용할 수 있으며, 요구사항을 충족하지 못하면 로그인 후 변경이 안내됩니다.
+///
+/// # 매개변수
+/// * `requirement` - 새 복잡도 요구사항
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<(), String>` - 변경 결과
+#[tauri::command]
+pub async fn set_pin_requirement(
+    requirement: PinComplexityRequirement,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<(), String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+
+    ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    app_state
+        .auth_service
+        .set_pin_requirement(requirement)
+        .map_err(|e| e.user_friendly_message())
+}
+
+/// 로그인한 PIN을 현재 요구사항에 맞게 변경해야 하는지 확인합니다.
+///
+/// # 매개변수
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<bool, String>` - PIN 변경 필요 여부
+#[tauri::command]
+pub async fn is_pin_upgrade_required(state: State<'_, Mutex<AppState>>) -> Result<bool, String> {
+    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    Ok(app_state.auth_service.is_pin_upgrade_required())
+}
+
 /// 복구 키 설정 여부를 확인합니다.
 ///
 /// # 매개변수
@@ -239,6 +593,23 @@ pub async fn has_recovery_key_set(state: State<'_, Mutex<AppState>>) -> Result<b
     Ok(app_state.auth_service.has_recovery_key())
 }
 
+/// 복구 키 수명 주기 상태(발급일, 마지막 사용 시각, 사용 횟수, 교체 필요 여부)를 조회합니다.
+///
+/// # 매개변수
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<RecoveryKeyStatus, String>` - 복구 키 상태 (키 값 제외)
+#[tauri::command]
+pub async fn get_recovery_key_status(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<RecoveryKeyStatus, String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    Ok(app_state.auth_service.recovery_key_status())
+}
+
 /// 세션 남은 시간을 조회합니다.
 ///
 /// # 매개변수
@@ -256,6 +627,8 @@ pub async fn get_session_remaining_time(
 
 /// PIN을 변경합니다.
 ///
+/// 볼트가 열려 있어야 하며, 현재 세션을 그대로 유지한 채 데이터 키만 새 PIN으로 다시 래핑합니다.
+///
 /// # 매개변수
 /// * `old_pin` - 기존 PIN
 /// * `new_pin` - 새 PIN
@@ -278,7 +651,7 @@ pub async fn change_pin(
         _ => return Err("올바르지 않은 복잡도 레벨입니다.".to_string()),
     };
 
-    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let mut app_state = lock_unlocked(&state)?;
 
     match app_state
         .auth_service
@@ -295,6 +668,404 @@ pub async fn change_pin(
     }
 }
 
+/// 강압(duress) PIN을 설정합니다.
+///
+/// 강압 PIN으로 로그인하면 비어 있는 위장 볼트가 열립니다.
+///
+/// # 매개변수
+/// * `pin` - 강압 PIN (일반 PIN과 달라야 함)
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<(), String>` - 설정 결과
+#[tauri::command]
+pub async fn set_duress_pin(pin: String, state: State<'_, Mutex<AppState>>) -> Result<(), String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+
+    let data_key = ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    let decoy_key = CryptoService::generate_data_key();
+    app_state
+        .auth_service
+        .set_duress_pin(&pin, &decoy_key, &data_key)
+        .map_err(|e| e.user_friendly_message())?;
+
+    // 로그에서 강압 PIN 설정 여부가 드러나지 않도록 일반적인 문구만 기록
+    log::info!("보안 설정이 변경되었습니다.");
+    Ok(())
+}
+
+/// 강압 PIN을 제거합니다.
+///
+/// # 매개변수
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<(), String>` - 제거 결과
+#[tauri::command]
+pub async fn remove_duress_pin(state: State<'_, Mutex<AppState>>) -> Result<(), String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+
+    let data_key = ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    app_state
+        .auth_service
+        .remove_duress_pin(&data_key)
+        .map_err(|e| e.user_friendly_message())
+}
+
+/// 강압 PIN 설정 여부를 확인합니다.
+///
+/// # 매개변수
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<bool, String>` - 강압 PIN 설정 여부
+#[tauri::command]
+pub async fn has_duress_pin(state: State<'_, Mutex<AppState>>) -> Result<bool, String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let data_key = ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+    Ok(app_state.auth_service.has_duress_pin(&data_key))
+}
+
+/// 패닉 PIN을 설정합니다.
+///
+/// 패닉 PIN으로 로그인하면 볼트 키가 파기되어 기존 데이터는 영구히 복호화할 수 없게 되고,
+/// 새로 만든 빈 볼트가 열립니다.
+///
+/// # 매개변수
+/// * `pin` - 패닉 PIN (일반 PIN, 강압 PIN과 달라야 함)
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<(), String>` - 설정 결과
+#[tauri::command]
+pub async fn set_panic_pin(pin: String, state: State<'_, Mutex<AppState>>) -> Result<(), String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+
+    let data_key = ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    app_state
+        .auth_service
+        .set_panic_pin(&pin, &data_key)
+        .map_err(|e| e.user_friendly_message())?;
+
+    log::info!("보안 설정이 변경되었습니다.");
+    Ok(())
+}
+
+/// 패닉 PIN을 제거합니다.
+///
+/// # 매개변수
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<(), String>` - 제거 결과
+#[tauri::command]
+pub async fn remove_panic_pin(state: State<'_, Mutex<AppState>>) -> Result<(), String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+
+    let data_key = ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    app_state
+        .auth_service
+        .remove_panic_pin(&data_key)
+        .map_err(|e| e.user_friendly_message())
+}
+
+/// 패닉 PIN 설정 여부를 확인합니다.
+///
+/// # 매개변수
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<bool, String>` - 패닉 PIN 설정 여부
+#[tauri::command]
+pub async fn has_panic_pin(state: State<'_, Mutex<AppState>>) -> Result<bool, String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let data_key = ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+    Ok(app_state.auth_service.has_panic_pin(&data_key))
+}
+
+/// 새 키 파일을 생성합니다.
+///
+/// 생성만 하며 등록은 `enroll_key_file`로 별도로 해야 합니다.
+/// 기존 파일은 덮어쓰지 않습니다.
+///
+/// # 매개변수
+/// * `path` - 키 파일을 저장할 경로 (예: 별도 USB)
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<(), String>` - 생성 결과
+#[tauri::command]
+pub async fn generate_key_file(
+    path: String,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<(), String> {
+    use std::io::Write;
+
+    // 키 파일을 다 쓸 때까지 상태 잠금을 유지해 그 사이에 볼트가 잠기지 않게 함
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    let contents = AuthService::generate_key_file_contents();
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .map_err(|e| format!("키 파일 생성 실패: {}", e))?;
+    file.write_all(&contents)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("키 파일 저장 실패: {}", e))?;
+
+    log::info!("새 키 파일이 생성되었습니다.");
+    Ok(())
+}
+
+/// 키 파일을 등록합니다. 이후 잠금 해제에는 PIN과 키 파일이 모두 필요합니다.
+///
+/// # 매개변수
+/// * `pin` - 현재 PIN
+/// * `key_file_path` - 등록할 키 파일 경로
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<(), String>` - 등록 결과
+#[tauri::command]
+pub async fn enroll_key_file(
+    pin: String,
+    key_file_path: String,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<(), String> {
+    let contents = read_key_file(&key_file_path)?;
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+
+    let data_key = ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    app_state
+        .auth_service
+        .enroll_key_file(&pin, &contents, &data_key)
+        .map_err(|e| e.user_friendly_message())
+}
+
+/// 등록된 키 파일을 새 키 파일로 교체합니다.
+///
+/// # 매개변수
+/// * `pin` - 현재 PIN
+/// * `key_file_path` - 새 키 파일 경로
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<(), String>` - 교체 결과
+#[tauri::command]
+pub async fn replace_key_file(
+    pin: String,
+    key_file_path: String,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<(), String> {
+    {
+        let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+        if !app_state.auth_service.has_key_file() {
+            return Err("등록된 키 파일이 없습니다.".to_string());
+        }
+    }
+
+    enroll_key_file(pin, key_file_path, state).await
+}
+
+/// 키 파일 등록을 해제합니다. 이후에는 PIN만으로 잠금을 해제합니다.
+///
+/// # 매개변수
+/// * `pin` - 현재 PIN
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<(), String>` - 해제 결과
+#[tauri::command]
+pub async fn remove_key_file(pin: String, state: State<'_, Mutex<AppState>>) -> Result<(), String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+
+    let data_key = ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    app_state
+        .auth_service
+        .remove_key_file(&pin, &data_key)
+        .map_err(|e| e.user_friendly_message())
+}
+
+/// 키 파일 등록 여부를 확인합니다. (로그인 화면에서 키 파일 선택 표시용)
+///
+/// # 매개변수
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<bool, String>` - 키 파일 등록 여부
+#[tauri::command]
+pub async fn has_key_file(state: State<'_, Mutex<AppState>>) -> Result<bool, String> {
+    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    Ok(app_state.auth_service.has_key_file())
+}
+
+/// 자동 잠금 시간을 조회합니다.
+///
+/// # 매개변수
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<u32, String>` - 자동 잠금 시간 (분, 0이면 사용 안 함)
+#[tauri::command]
+pub async fn get_auto_lock_minutes(state: State<'_, Mutex<AppState>>) -> Result<u32, String> {
+    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    Ok(app_state.auth_service.get_security_config().auto_lock_minutes)
+}
+
+/// 자동 잠금 시간을 설정합니다.
+///
+/// 이 시간 동안 커맨드 호출이 없으면 백엔드가 볼트 키를 제거하고 `vault-locked` 이벤트를 보냅니다.
+///
+/// # 매개변수
+/// * `minutes` - 자동 잠금 시간 (분, 0이면 사용 안 함)
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<(), String>` - 설정 결과
+#[tauri::command]
+pub async fn set_auto_lock_minutes(
+    minutes: u32,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<(), String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+
+    ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    app_state
+        .auth_service
+        .set_auto_lock_minutes(minutes)
+        .map_err(|e| e.user_friendly_message())
+}
+
+/// 높은 보안 등급 파일에 접근하기 위해 PIN을 다시 확인합니다.
+///
+/// 성공하면 일정 시간(`STEP_UP_WINDOW_SECONDS`) 동안 높은 보안 등급 파일을 열거나 내보낼 수 있습니다.
+///
+/// # 매개변수
+/// * `pin` - 현재 PIN
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<u64, String>` - 재확인 유효 시간 (초)
+#[tauri::command]
+pub async fn step_up_authentication(
+    pin: String,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<u64, String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+
+    ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    app_state
+        .auth_service
+        .step_up(&pin)
+        .map_err(|e| e.user_friendly_message())?;
+
+    Ok(STEP_UP_WINDOW_SECONDS)
+}
+
+/// 최고 보안 암호를 설정합니다.
+///
+/// 최고 보안 등급 파일은 이 암호로만 열 수 있는 별도 키로 암호화되며,
+/// PIN이나 복구 키로는 복구할 수 없습니다.
+///
+/// # 매개변수
+/// * `pin` - 현재 PIN
+/// * `passphrase` - 최고 보안 암호 (PIN과 달라야 함)
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<(), String>` - 설정 결과
+#[tauri::command]
+pub async fn set_critical_passphrase(
+    pin: String,
+    passphrase: String,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<(), String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+
+    ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    app_state
+        .auth_service
+        .set_critical_passphrase(&pin, &passphrase)
+        .map_err(|e| e.user_friendly_message())
+}
+
+/// 최고 보안 암호를 변경합니다.
+///
+/// # 매개변수
+/// * `old_passphrase` - 현재 최고 보안 암호
+/// * `new_passphrase` - 새 최고 보안 암호
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<(), String>` - 변경 결과
+#[tauri::command]
+pub async fn change_critical_passphrase(
+    old_passphrase: String,
+    new_passphrase: String,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<(), String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+
+    ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    app_state
+        .auth_service
+        .change_critical_passphrase(&old_passphrase, &new_passphrase)
+        .map_err(|e| e.user_friendly_message())
+}
+
+/// 최고 보안 암호로 최고 보안 등급 파일을 잠금 해제합니다.
+/// PIN 재확인(`step_up_authentication`) 후 유효 시간 안에서만 사용할 수 있습니다.
+///
+/// # 매개변수
+/// * `passphrase` - 최고 보안 암호
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<(), String>` - 잠금 해제 결과
+#[tauri::command]
+pub async fn unlock_critical_files(
+    passphrase: String,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<(), String> {
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+
+    ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    if !app_state.auth_service.has_recent_step_up() {
+        return Err(VaultError::StepUpRequired.to_string());
+    }
+
+    app_state
+        .auth_service
+        .unlock_critical_key(&passphrase)
+        .map_err(|e| e.user_friendly_message())
+}
+
+/// 최고 보안 암호 설정 여부를 확인합니다.
+///
+/// # 매개변수
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<bool, String>` - 설정 여부
+#[tauri::command]
+pub async fn has_critical_passphrase(state: State<'_, Mutex<AppState>>) -> Result<bool, String> {
+    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    Ok(app_state.auth_service.has_critical_passphrase())
+}
+
 /// 자동 로그아웃 시간을 조회합니다.
 #[tauri::command]
 pub async fn get_auto_logout_time(state: State<'_, Mutex<AppState>>) -> Result<u64, String> {
@@ -308,9 +1079,59 @@ pub async fn set_auto_logout_time(
     seconds: u64,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<(), String> {
-    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let mut app_state = lock_unlocked(&state)?;
     app_state
         .auth_service
         .set_session_timeout(seconds)
         .map_err(|e| e.to_string())
 }
+
+/// 현재 PC에 맞춰 키 유도 비용을 다시 보정합니다.
+///
+/// 볼트를 더 빠른 PC로 옮긴 뒤 비용을 올릴 때 사용합니다. 현재 PIN의 검증자와 래핑된 키는
+/// 바로 새 매개변수로 교체되고, 다른 PIN과 최고 보안 암호는 다음에 사용될 때 교체됩니다.
+/// 측정한 비용이 현재보다 낮으면 `allow_downgrade`를 지정한 경우에만 적용합니다.
+///
+/// # 매개변수
+/// * `pin` - 현재 PIN
+/// * `target_unlock_ms` - 목표 잠금 해제 시간 (밀리초, 없으면 `DEFAULT_UNLOCK_TARGET_MS`)
+/// * `allow_downgrade` - 현재보다 약한 비용으로 낮추는 것을 허용할지 여부 (선택)
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<KdfCalibration, String>` - 선택된 매개변수와 예상 잠금 해제 시간
+#[tauri::command]
+pub async fn reharden_kdf(
+    pin: String,
+    target_unlock_ms: Option<u64>,
+    allow_downgrade: Option<bool>,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<KdfCalibration, String> {
+    // 측정에 수 초가 걸리므로 잠금 해제 여부만 확인하고 상태 잠금 밖에서 측정
+    {
+        let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+        ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+    }
+    let target_ms = target_unlock_ms
+        .unwrap_or(DEFAULT_UNLOCK_TARGET_MS)
+        .min(MAX_UNLOCK_TARGET_MS);
+    let calibration = AuthService::measure_kdf(Duration::from_millis(target_ms)).map_err(|e| {
+        log::warn!("키 유도 비용 측정 실패: {}", e);
+        e.user_friendly_message()
+    })?;
+
+    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let data_key = ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+    app_state
+        .auth_service
+        .reharden_kdf(
+            &pin,
+            &data_key,
+            calibration,
+            allow_downgrade.unwrap_or(false),
+        )
+        .map_err(|e| {
+            log::warn!("키 유도 비용 재보정 실패: {}", e);
+            e.user_friendly_message()
+        })
+}
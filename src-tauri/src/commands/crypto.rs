@@ -1,48 +1,15 @@
 // 암호화 관련 Tauri Commands
 // 프론트엔드에서 암호화 서비스를 호출할 수 있는 인터페이스를 제공합니다.
 
+use crate::models::EncryptionAlgorithm;
 use crate::services::CryptoService;
+use crate::commands::guard::lock_unlocked;
 use crate::AppState;
 use tauri::State;
 use uuid::Uuid;
 use std::sync::Mutex;
 use base64::{Engine as _, engine::general_purpose};
 
-/// PIN으로부터 마스터 키를 유도합니다.
-/// 
-/// C# EncryptionService.DeriveKeyFromPin()과 동일한 기능을 제공합니다.
-/// PBKDF2-HMAC-SHA256 알고리즘을 사용하여 100,000회 반복합니다.
-/// 
-/// # 매개변수
-/// * `pin` - 사용자 PIN (4-8자리 숫자)
-/// * `salt_hex` - 32바이트 솔트 (16진수 문자열)
-/// * `state` - 애플리케이션 상태
-/// 
-/// # 반환값
-/// * `Result<(), String>` - 키 유도 결과
-#[tauri::command]
-pub async fn derive_master_key_from_pin(
-    pin: String,
-    salt_hex: String,
-    state: State<'_, Mutex<AppState>>
-) -> Result<(), String> {
-    log::info!("PIN으로부터 마스터 키 유도 요청");
-    
-    // 16진수 솔트를 바이트로 변환
-    let salt = hex::decode(&salt_hex)
-        .map_err(|_| "솔트 형식이 올바르지 않습니다.")?;
-    
-    // 앱 상태에서 암호화 서비스 가져오기
-    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
-    
-    // 마스터 키 유도
-    app_state.crypto_service.derive_master_key(&pin, &salt)
-        .map_err(|e| e.to_string())?;
-    
-    log::info!("마스터 키 유도 완료");
-    Ok(())
-}
-
 /// 32바이트 랜덤 솔트를 생성합니다.
 /// 
 /// C# EncryptionService.GenerateSalt()와 동일한 기능을 제공합니다.
@@ -107,7 +74,7 @@ pub async fn encrypt_data_csharp_compatible(
         .map_err(|_| "키 형식이 올바르지 않습니다.")?;
     
     // 앱 상태에서 암호화 서비스 가져오기
-    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let app_state = lock_unlocked(&state)?;
     
     // 데이터 암호화
     let encrypted_data = app_state.crypto_service.encrypt_data_csharp_compatible(&data, &key)
@@ -151,7 +118,7 @@ pub async fn decrypt_data_csharp_compatible(
         .map_err(|_| "키 형식이 올바르지 않습니다.")?;
     
     // 앱 상태에서 암호화 서비스 가져오기
-    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let app_state = lock_unlocked(&state)?;
     
     // 데이터 복호화
     let decrypted_data = app_state.crypto_service.decrypt_data_csharp_compatible(&encrypted_data, &key)
@@ -194,7 +161,7 @@ pub async fn encrypt_file(
         .map_err(|_| "파일 ID 형식이 올바르지 않습니다.")?;
     
     // 앱 상태에서 암호화 서비스 가져오기
-    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let app_state = lock_unlocked(&state)?;
     
     // 파일 암호화
     let encrypted_data = app_state.crypto_service.encrypt_file(&data, &uuid)
@@ -243,7 +210,7 @@ pub async fn decrypt_file(
         .map_err(|_| "파일 ID 형식이 올바르지 않습니다.")?;
     
     // 앱 상태에서 암호화 서비스 가져오기
-    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let app_state = lock_unlocked(&state)?;
     
     // 파일 복호화
     let decrypted_data = app_state.crypto_service.decrypt_file(&encrypted_data, &uuid)
@@ -288,14 +255,16 @@ pub async fn clear_sensitive_data(
 ) -> Result<(), String> {
     log::info!("민감한 데이터 정리 요청");
     
-    let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let mut app_state = lock_unlocked(&state)?;
     app_state.crypto_service.clear_sensitive_data();
     
     log::info!("민감한 데이터 정리 완료");
     Ok(())
 }
 
-/// 현재 사용 중인 암호화 알고리즘을 반환합니다.
+/// 볼트가 새 파일에 사용하는 암호화 알고리즘을 반환합니다.
+/// 
+/// 기존 파일은 각자의 컨테이너 헤더에 기록된 알고리즘으로 복호화됩니다.
 /// 
 /// # 매개변수
 /// * `state` - 애플리케이션 상태
@@ -307,12 +276,36 @@ pub async fn get_encryption_algorithm(
     state: State<'_, Mutex<AppState>>
 ) -> Result<String, String> {
     let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
-    let algorithm = app_state.crypto_service.get_default_algorithm();
+    let file_service = app_state.file_service.lock().map_err(|_| "파일 서비스 잠금 실패")?;
+    
+    Ok(file_service.encryption_algorithm().name().to_string())
+}
+
+/// 볼트가 새 파일에 사용할 암호화 알고리즘을 설정합니다.
+/// 
+/// 설정은 볼트 메타데이터에 저장되며, 이미 암호화된 파일은 다시 암호화하지 않습니다.
+/// 
+/// # 매개변수
+/// * `algorithm` - 알고리즘 이름 ("AES-256-GCM" 또는 "ChaCha20-Poly1305")
+/// * `state` - 애플리케이션 상태
+/// 
+/// # 반환값
+/// * `Result<(), String>` - 설정 결과
+#[tauri::command]
+pub async fn set_encryption_algorithm(
+    algorithm: String,
+    state: State<'_, Mutex<AppState>>
+) -> Result<(), String> {
+    let algorithm = EncryptionAlgorithm::from_name(&algorithm)
+        .ok_or_else(|| format!("지원하지 않는 암호화 알고리즘입니다: {}", algorithm))?;
     
-    let algorithm_name = match algorithm {
-        crate::models::EncryptionAlgorithm::AES256GCM => "AES-256-GCM",
-        crate::models::EncryptionAlgorithm::ChaCha20Poly1305 => "ChaCha20-Poly1305",
-    };
+    let mut app_state = lock_unlocked(&state)?;
+    {
+        let mut file_service = app_state.file_service.lock().map_err(|_| "파일 서비스 잠금 실패")?;
+        file_service.set_encryption_algorithm(algorithm.clone())
+            .map_err(|e| e.to_string())?;
+    }
+    app_state.crypto_service.set_default_algorithm(algorithm);
     
-    Ok(algorithm_name.to_string())
-}
\ No newline at end of file
+    Ok(())
+}
@@ -2,6 +2,8 @@ use crate::models::{
     file::FileEntry,
     folder::FolderEntry,
 };
+use crate::commands::guard::lock_unlocked;
+use crate::services::CryptoService;
 use crate::AppState;
 use tauri::State;
 use uuid::Uuid;
@@ -21,11 +23,16 @@ pub async fn initialize_database(
     vault_path: String,
     state: State<'_, Mutex<AppState>>
 ) -> Result<(), String> {
-    let app_state = state.lock().unwrap();
+    let app_state = lock_unlocked(&state)?;
+    let data_key = app_state
+        .crypto_service
+        .get_master_key()
+        .ok_or_else(|| "볼트가 잠겨 있습니다.".to_string())?;
+    let db_key = CryptoService::derive_database_key(&data_key);
+
     let mut db_service = app_state.database_service.lock().unwrap();
-    
     db_service
-        .initialize(&vault_path)
+        .initialize(&vault_path, &db_key)
         .map_err(|e| format!("데이터베이스 초기화 실패: {}", e))
 }
 
@@ -43,7 +50,7 @@ pub async fn add_file_metadata(
     file_entry: FileEntry,
     state: State<'_, Mutex<AppState>>
 ) -> Result<(), String> {
-    let app_state = state.lock().unwrap();
+    let app_state = lock_unlocked(&state)?;
     let db_service = app_state.database_service.lock().unwrap();
     
     db_service
@@ -65,7 +72,7 @@ pub async fn get_file_metadata(
     file_id: String,
     state: State<'_, Mutex<AppState>>
 ) -> Result<Option<FileEntry>, String> {
-    let app_state = state.lock().unwrap();
+    let app_state = lock_unlocked(&state)?;
     let db_service = app_state.database_service.lock().unwrap();
     
     let file_uuid = Uuid::parse_str(&file_id)
@@ -90,7 +97,7 @@ pub async fn get_files_by_folder(
     folder_id: Option<String>,
     state: State<'_, Mutex<AppState>>
 ) -> Result<Vec<FileEntry>, String> {
-    let app_state = state.lock().unwrap();
+    let app_state = lock_unlocked(&state)?;
     let db_service = app_state.database_service.lock().unwrap();
     
     // 폴더 ID 변환
@@ -120,7 +127,7 @@ pub async fn remove_file_metadata(
     file_id: String,
     state: State<'_, Mutex<AppState>>
 ) -> Result<(), String> {
-    let app_state = state.lock().unwrap();
+    let app_state = lock_unlocked(&state)?;
     let db_service = app_state.database_service.lock().unwrap();
     
     let file_uuid = Uuid::parse_str(&file_id)
@@ -145,7 +152,7 @@ pub async fn add_folder_metadata(
     folder_entry: FolderEntry,
     state: State<'_, Mutex<AppState>>
 ) -> Result<(), String> {
-    let app_state = state.lock().unwrap();
+    let app_state = lock_unlocked(&state)?;
     let db_service = app_state.database_service.lock().unwrap();
     
     db_service
@@ -167,7 +174,7 @@ pub async fn get_folder_metadata(
     folder_id: String,
     state: State<'_, Mutex<AppState>>
 ) -> Result<Option<FolderEntry>, String> {
-    let app_state = state.lock().unwrap();
+    let app_state = lock_unlocked(&state)?;
     let db_service = app_state.database_service.lock().unwrap();
     
     let folder_uuid = Uuid::parse_str(&folder_id)
@@ -190,7 +197,7 @@ pub async fn get_folder_metadata(
 pub async fn get_all_folders_metadata(
     state: State<'_, Mutex<AppState>>
 ) -> Result<Vec<FolderEntry>, String> {
-    let app_state = state.lock().unwrap();
+    let app_state = lock_unlocked(&state)?;
     let db_service = app_state.database_service.lock().unwrap();
     
     db_service
@@ -212,7 +219,7 @@ pub async fn update_folder_metadata(
     folder_entry: FolderEntry,
     state: State<'_, Mutex<AppState>>
 ) -> Result<(), String> {
-    let app_state = state.lock().unwrap();
+    let app_state = lock_unlocked(&state)?;
     let db_service = app_state.database_service.lock().unwrap();
     
     db_service
@@ -234,7 +241,7 @@ pub async fn remove_folder_metadata(
     folder_id: String,
     state: State<'_, Mutex<AppState>>
 ) -> Result<(), String> {
-    let app_state = state.lock().unwrap();
+    let app_state = lock_unlocked(&state)?;
     let db_service = app_state.database_service.lock().unwrap();
     
     let folder_uuid = Uuid::parse_str(&folder_id)
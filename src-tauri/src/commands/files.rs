@@ -1,4 +1,9 @@
-use crate::models::file::FileEntry;
+use crate::models::file::{FileEntry, FileSecurityLevel, LegacyMigrationReport};
+use crate::models::SecretKey;
+use crate::commands::guard::{
+    ensure_file_access, ensure_unlocked, lock_file_access, lock_unlocked,
+};
+use crate::services::crypto::CryptoService;
 use crate::AppState;
 use std::collections::HashMap;
 use std::io::Read;
@@ -57,10 +62,7 @@ pub async fn get_files_in_folder(
         None
     };
 
-    let app_state = state.lock().map_err(|e| {
-        log::error!("상태 잠금 실패: {}", e);
-        format!("상태 잠금 실패: {}", e)
-    })?;
+    let app_state = lock_unlocked(&state)?;
 
     let database_service = app_state.database_service.lock().map_err(|e| {
         log::error!("데이터베이스 서비스 잠금 실패: {}", e);
@@ -97,6 +99,8 @@ pub async fn add_file_to_vault(
     folder_id: Option<String>,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<FileEntry, String> {
+    lock_unlocked(&state)?;
+
     use std::fs;
     use std::path::Path;
 
@@ -150,10 +154,7 @@ pub async fn add_file_to_vault(
     // 따라서 FileService를 복제(Clone)하여 사용합니다. (FileService는 Clone을 derive하고 내부적으로 Arc 등을 사용하여 상태를 공유함)
     // 2. 파일 서비스 복제 (Lock 최소화)
     let file_service_clone = {
-        let app_state = state.lock().map_err(|e| {
-            log::error!("상태 잠금 실패: {}", e);
-            format!("상태 잠금 실패: {}", e)
-        })?;
+        let app_state = lock_unlocked(&state)?;
         let file_service_guard = app_state.file_service.lock().map_err(|e| {
             log::error!("파일 서비스 잠금 실패: {}", e);
             format!("파일 서비스 잠금 실패: {}", e)
@@ -206,10 +207,7 @@ pub async fn delete_file_from_vault(
         }
     };
 
-    let app_state = state.lock().map_err(|e| {
-        log::error!("상태 잠금 실패: {}", e);
-        format!("상태 잠금 실패: {}", e)
-    })?;
+    let app_state = lock_unlocked(&state)?;
 
     let database_service = app_state.database_service.lock().map_err(|e| {
         log::error!("데이터베이스 서비스 잠금 실패: {}", e);
@@ -297,10 +295,7 @@ pub async fn rename_file_in_vault(
         return Err("파일명에 다음 문자는 사용할 수 없습니다: < > : \" | ? * / \\".to_string());
     }
 
-    let app_state = state.lock().map_err(|e| {
-        log::error!("상태 잠금 실패: {}", e);
-        format!("상태 잠금 실패: {}", e)
-    })?;
+    let app_state = lock_unlocked(&state)?;
 
     let database_service = app_state.database_service.lock().map_err(|e| {
         log::error!("데이터베이스 서비스 잠금 실패: {}", e);
@@ -457,8 +452,10 @@ pub async fn rename_file_in_vault(
 #[tauri::command]
 pub async fn extract_file_from_vault(
     _file_id: String,
-    _state: State<'_, Mutex<AppState>>,
+    state: State<'_, Mutex<AppState>>,
 ) -> Result<String, String> {
+    lock_unlocked(&state)?;
+
     // TODO: 파일 서비스 구현 후 활성화
     Err("파일 추출 기능이 아직 구현되지 않았습니다.".to_string())
 }
@@ -500,10 +497,7 @@ pub async fn export_file_from_vault(
 
     // 파일 메타데이터 조회 (스코프 분리)
     let file_entry = {
-        let app_state = state.lock().map_err(|e| {
-            log::error!("상태 잠금 실패: {}", e);
-            format!("상태 잠금 실패: {}", e)
-        })?;
+        let app_state = lock_file_access(&state, &file_id)?;
 
         let database_service = app_state.database_service.lock().map_err(|e| {
             log::error!("데이터베이스 서비스 잠금 실패: {}", e);
@@ -549,10 +543,7 @@ pub async fn export_file_from_vault(
 
     // 파일 서비스를 복사하여 await 포인트에서 사용
     let file_service_clone = {
-        let app_state = state.lock().map_err(|e| {
-            log::error!("상태 잠금 실패: {}", e);
-            format!("상태 잠금 실패: {}", e)
-        })?;
+        let app_state = lock_file_access(&state, &file_id)?;
 
         // 파일 서비스를 복사
         let file_service_guard = app_state.file_service.lock().map_err(|e| {
@@ -595,6 +586,8 @@ pub async fn create_new_file_in_vault(
     content: String,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<FileEntry, String> {
+    lock_unlocked(&state)?;
+
     log::info!(
         "새 파일 생성 요청: file_name={}, folder_id={:?}",
         file_name,
@@ -680,25 +673,20 @@ pub async fn create_new_file_in_vault(
     }
     .to_string();
 
-    // 암호화 수행
-    let (encrypted_data, encrypted_size) = {
-        let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
-
-        // 마스터 키 확인 및 가져오기
-        let master_key = if let Some(key) = app_state.crypto_service.get_master_key() {
-            key
-        } else {
-            return Err("마스터 키가 설정되지 않았습니다. (로그인 필요)".to_string());
-        };
+    // 암호화 수행 (볼트에 설정된 알고리즘과 새 파일 데이터 키 사용) - FileService와 일치시킴
+    let (encrypted_data, encrypted_size, wrapped_key) = {
+        let app_state = lock_unlocked(&state)?;
+        let file_service = app_state
+            .file_service
+            .lock()
+            .map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?;
 
-        // 직접 C# 호환 암호화 (마스터 키 사용) - FileService와 일치시킴
-        let encrypted_bytes = app_state
-            .crypto_service
-            .encrypt_data_csharp_compatible(content.as_bytes(), &master_key)
+        let (encrypted_bytes, wrapped_key) = file_service
+            .encrypt_file_data(content.as_bytes(), &file_id)
             .map_err(|e| format!("파일 암호화 실패: {}", e))?;
 
         let size = encrypted_bytes.len() as u64;
-        (encrypted_bytes, size)
+        (encrypted_bytes, size, wrapped_key)
     };
 
     // 파일 엔트리 생성
@@ -714,9 +702,10 @@ pub async fn create_new_file_in_vault(
         encrypted_size,
     );
     file_entry.id = file_id; // 암호화에 사용된 ID로 설정
+    file_entry.wrapped_key = Some(wrapped_key);
 
     // 볼트 디렉토리 초기화 확인
-    let vault_dir = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+    let vault_dir = crate::vault_root();
     let securevault_dir = vault_dir.join(".securevault");
     let files_dir = securevault_dir.join("files");
 
@@ -737,10 +726,7 @@ pub async fn create_new_file_in_vault(
 
     // 데이터베이스에 메타데이터 저장
     {
-        let app_state = state.lock().map_err(|e| {
-            log::error!("상태 잠금 실패: {}", e);
-            format!("상태 잠금 실패: {}", e)
-        })?;
+        let app_state = lock_unlocked(&state)?;
 
         let database_service = app_state.database_service.lock().map_err(|e| {
             log::error!("데이터베이스 서비스 잠금 실패: {}", e);
@@ -789,6 +775,8 @@ pub async fn create_binary_file_in_vault(
     content: String, // base64 인코딩된 바이너리 데이터
     state: State<'_, Mutex<AppState>>,
 ) -> Result<FileEntry, String> {
+    lock_unlocked(&state)?;
+
     use base64::{engine::general_purpose, Engine as _};
     use std::fs;
     use std::io::Write;
@@ -910,22 +898,20 @@ pub async fn create_binary_file_in_vault(
 
     let file_id = uuid::Uuid::new_v4();
 
-    // 암호화 수행
-    let (encrypted_data, encrypted_size) = {
-        let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
-
-        // 마스터 키 확인
-        if !app_state.crypto_service.has_master_key() {
-            return Err("마스터 키가 설정되지 않았습니다. (로그인 필요)".to_string());
-        }
+    // 암호화 수행 (볼트에 설정된 알고리즘과 새 파일 데이터 키 사용)
+    let (encrypted_data, encrypted_size, wrapped_key) = {
+        let app_state = lock_unlocked(&state)?;
+        let file_service = app_state
+            .file_service
+            .lock()
+            .map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?;
 
-        let encrypted = app_state
-            .crypto_service
-            .encrypt_file(&binary_data, &file_id)
+        let (encrypted, wrapped_key) = file_service
+            .encrypt_file_data(&binary_data, &file_id)
             .map_err(|e| format!("파일 암호화 실패: {}", e))?;
 
-        let size = encrypted.ciphertext.len() as u64;
-        (encrypted.ciphertext, size)
+        let size = encrypted.len() as u64;
+        (encrypted, size, wrapped_key)
     };
 
     // 파일 엔트리 생성
@@ -941,13 +927,11 @@ pub async fn create_binary_file_in_vault(
         encrypted_size,
     );
     file_entry.id = file_id; // 암호화에 사용된 ID로 설정 (중요)
+    file_entry.wrapped_key = Some(wrapped_key);
 
     // 데이터베이스에 파일 메타데이터 저장
     {
-        let app_state = state.lock().map_err(|e| {
-            log::error!("상태 잠금 실패: {}", e);
-            format!("상태 잠금 실패: {}", e)
-        })?;
+        let app_state = lock_unlocked(&state)?;
 
         let database_service = app_state.database_service.lock().map_err(|e| {
             log::error!("데이터베이스 서비스 잠금 실패: {}", e);
@@ -962,7 +946,7 @@ pub async fn create_binary_file_in_vault(
     }
 
     // 볼트 디렉토리 및 파일 저장
-    let vault_dir = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+    let vault_dir = crate::vault_root();
     let securevault_dir = vault_dir.join(".securevault");
     let files_dir = securevault_dir.join("files");
 
@@ -1003,7 +987,7 @@ pub fn get_file_content(
     file_id: String,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<Vec<u8>, String> {
-    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_file_access(&state, &file_id)?;
 
     // 파일 메타데이터 조회
     let db_service = app_state
@@ -1060,8 +1044,10 @@ pub fn get_file_content(
 pub async fn update_file_content(
     _file_id: String,
     _content: Vec<u8>,
-    _state: State<'_, Mutex<AppState>>,
+    state: State<'_, Mutex<AppState>>,
 ) -> Result<(), String> {
+    lock_unlocked(&state)?;
+
     // TODO: 파일 서비스 구현 후 활성화
     Err("파일 내용 업데이트 기능이 아직 구현되지 않았습니다.".to_string())
 }
@@ -1122,11 +1108,14 @@ pub async fn add_folder_to_vault(
         return Err("지정된 경로가 폴더가 아닙니다.".to_string());
     }
 
-    let app_state = state.lock().map_err(|e| {
+    let mut app_state = state.lock().map_err(|e| {
         log::error!("상태 잠금 실패: {}", e);
         format!("상태 잠금 실패: {}", e)
     })?;
 
+    // 볼트가 열려 있어야 하며, 파일 서비스 초기화에도 인증된 키만 사용
+    let data_key = ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
     // 볼트 초기화 확인 및 수행
     let mut file_service = app_state
         .file_service
@@ -1139,11 +1128,9 @@ pub async fn add_folder_to_vault(
         crate::initialize_vault_directory_simple()
             .map_err(|e| format!("볼트 초기화 실패: {}", e))?;
 
-        // 파일 서비스에 기본 볼트 정보 설정
-        let vault_path = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
-        // 임시 마스터 키 - 실제로는 앱 초기화 시 설정됨
-        let temp_master_key = [0u8; 32];
-        file_service.set_vault_info(vault_path.to_str().unwrap_or("."), temp_master_key);
+        // 파일 서비스에 볼트 정보 설정
+        let vault_path = crate::vault_root();
+        file_service.set_vault_info(vault_path.to_str().unwrap_or("."), data_key);
         log::info!("볼트 초기화 완료");
     }
 
@@ -1152,7 +1139,10 @@ pub async fn add_folder_to_vault(
         log::error!("마스터 키를 가져올 수 없습니다.");
         "마스터 키가 설정되지 않았습니다. 로그인이 필요합니다.".to_string()
     })?;
-    log::info!("마스터 키 획득 완료 (Key0={:02X})", master_key[0]);
+    log::info!("마스터 키 획득 완료");
+
+    // 작은 파일들은 파일 서비스 잠금 없이 병렬로 암호화하므로 볼트 알고리즘을 미리 복사
+    let algorithm = file_service.encryption_algorithm().clone();
 
     drop(file_service);
 
@@ -1162,7 +1152,7 @@ pub async fn add_folder_to_vault(
     })?;
 
     // 데이터 디렉토리 미리 생성 (병렬 처리 시 중복 체크 방지)
-    let vault_path = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+    let vault_path = crate::vault_root();
     let data_dir = vault_path.join(".securevault").join("files");
     if !data_dir.exists() {
         fs::create_dir_all(&data_dir).map_err(|e| format!("데이터 디렉토리 생성 실패: {}", e))?;
@@ -1319,6 +1309,7 @@ pub async fn add_folder_to_vault(
                 source_path,
                 &data_dir,
                 &master_key,
+                &algorithm,
             )
         })
         .collect();
@@ -1397,10 +1388,7 @@ pub async fn export_folder_from_vault(
         }
     };
 
-    let app_state = state.lock().map_err(|e| {
-        log::error!("상태 잠금 실패: {}", e);
-        format!("상태 잠금 실패: {}", e)
-    })?;
+    let app_state = lock_unlocked(&state)?;
 
     let database_service = app_state.database_service.lock().map_err(|e| {
         log::error!("데이터베이스 서비스 잠금 실패: {}", e);
@@ -1602,8 +1590,10 @@ pub async fn start_chunked_upload(
     file_name: String,
     file_size: u64,
     folder_id: Option<String>,
-    _state: State<'_, Mutex<AppState>>,
+    state: State<'_, Mutex<AppState>>,
 ) -> Result<String, String> {
+    lock_unlocked(&state)?;
+
     log::info!(
         "청크 업로드 시작: file_name={}, file_size={}, folder_id={:?}",
         file_name,
@@ -1642,7 +1632,7 @@ pub async fn start_chunked_upload(
     let session_id = uuid::Uuid::new_v4().to_string();
 
     // 임시 디렉토리 경로 계산 (볼트 내부 .securevault/tmp 사용)
-    let current_dir = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+    let current_dir = crate::vault_root();
     let mut vault_dir = current_dir.join(".securevault");
     if !vault_dir.exists() {
         if let Some(parent) = current_dir.parent() {
@@ -1710,6 +1700,8 @@ pub async fn upload_file_chunk(
     is_last_chunk: bool,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<bool, String> {
+    lock_unlocked(&state)?;
+
     use base64::{engine::general_purpose, Engine as _};
     use std::io::Write;
 
@@ -1939,10 +1931,7 @@ pub async fn upload_file_chunk(
 
         // 스트리밍 방식으로 파일 암호화 및 저장 구현
         let file_entry = {
-            let app_state = state.lock().map_err(|e| {
-                log::error!("상태 잠금 실패: {}", e);
-                format!("상태 잠금 실패: {}", e)
-            })?;
+            let app_state = lock_unlocked(&state)?;
 
             // 파일 크기 확인 (메모리에 로드하지 않고)
             let original_size = std::fs::metadata(&final_file_path)
@@ -2045,7 +2034,7 @@ pub async fn upload_file_chunk(
                 };
 
             // 스트리밍 암호화 및 저장 (Vault Path도 함께 반환)
-            let (encrypted_file_name, encrypted_size, vault_path_opt) = {
+            let (file_id, encrypted_file_name, encrypted_size, wrapped_key, vault_path_opt) = {
                 let mut file_service = app_state.file_service.lock().map_err(|e| {
                     log::error!("파일 서비스 잠금 실패: {}", e);
                     format!("파일 서비스 잠금 실패: {}", e)
@@ -2059,12 +2048,12 @@ pub async fn upload_file_chunk(
 
                 let current_vault_path = file_service.get_vault_path();
 
-                let encrypted_file_name = format!("encrypted_{}", uuid::Uuid::new_v4());
+                // 컨테이너 헤더에 기록되는 ID와 DB ID를 일치시킴
+                let file_id = uuid::Uuid::new_v4();
+                let encrypted_file_name = format!("{}.enc", file_id);
 
                 // 암호화된 파일을 저장할 경로 설정
-                let vault_path = current_vault_path.clone().unwrap_or_else(|| {
-                    std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."))
-                });
+                let vault_path = current_vault_path.clone().unwrap_or_else(crate::vault_root);
 
                 let data_dir = vault_path.join(".securevault").join("files");
 
@@ -2077,28 +2066,45 @@ pub async fn upload_file_chunk(
 
                 let encrypted_file_path = data_dir.join(&encrypted_file_name);
 
+                // 파일 데이터 키 생성
+                let (file_key, wrapped_key) = file_service
+                    .generate_file_key(FileSecurityLevel::Normal, &file_id)
+                    .map_err(|e| format!("파일 키 생성 실패: {}", e))?;
+
                 // 파일 크기에 따른 최적화된 암호화 방식 선택
-                let encrypted_size = if original_size > 100 * 1024 * 1024 {
+                let encrypted = if original_size > 100 * 1024 * 1024 {
                     // 100MB 이상은 병렬 암호화
                     log::info!(
                         "큰 파일 병렬 암호화 시작: {}MB",
                         original_size / (1024 * 1024)
                     );
                     file_service
-                        .encrypt_file_parallel_streaming(&processed_file_path, &encrypted_file_path)
+                        .encrypt_file_parallel_streaming(
+                            &processed_file_path,
+                            &encrypted_file_path,
+                            &file_id,
+                            &file_key,
+                        )
                         .map_err(|e| {
                             log::error!("병렬 암호화 실패: {}", e);
                             format!("병렬 암호화 실패: {}", e)
-                        })?
+                        })
                 } else {
                     // 작은 파일은 기존 스트리밍 암호화 사용
                     file_service
-                        .encrypt_file_streaming(&processed_file_path, &encrypted_file_path)
+                        .encrypt_file_streaming(
+                            &processed_file_path,
+                            &encrypted_file_path,
+                            &file_id,
+                            &file_key,
+                        )
                         .map_err(|e| {
                             log::error!("스트리밍 암호화 실패: {}", e);
                             format!("스트리밍 암호화 실패: {}", e)
-                        })?
+                        })
                 };
+                drop(file_key);
+                let encrypted_size = encrypted?;
 
                 log::info!(
                     "스트리밍 암호화 완료: {} -> {} bytes",
@@ -2106,7 +2112,13 @@ pub async fn upload_file_chunk(
                     encrypted_size
                 );
 
-                (encrypted_file_name, encrypted_size, current_vault_path)
+                (
+                    file_id,
+                    encrypted_file_name,
+                    encrypted_size,
+                    wrapped_key,
+                    current_vault_path,
+                )
             };
 
             // MIME 타입 추정
@@ -2182,7 +2194,7 @@ pub async fn upload_file_chunk(
             .to_string();
 
             // 압축 정보와 함께 파일 엔트리 생성
-            let file_entry = crate::models::file::FileEntry::new_with_compression(
+            let mut file_entry = crate::models::file::FileEntry::new_with_compression(
                 file_name.clone(),
                 file_name.clone(),
                 original_size,
@@ -2196,6 +2208,8 @@ pub async fn upload_file_chunk(
                 compressed_size,
                 compression_ratio,
             );
+            file_entry.id = file_id;
+            file_entry.wrapped_key = Some(wrapped_key);
 
             // 데이터베이스에 파일 메타데이터 저장
             let mut database_service = app_state.database_service.lock().map_err(|e| {
@@ -2208,12 +2222,15 @@ pub async fn upload_file_chunk(
                 log::warn!(
                     "Global DatabaseService connection lost. Attempting re-initialization..."
                 );
-                let vault_path = vault_path_opt.unwrap_or_else(|| {
-                    std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."))
-                });
+                let vault_path = vault_path_opt.unwrap_or_else(crate::vault_root);
+
+                if let (Some(path_str), Some(data_key)) = (
+                    vault_path.to_str(),
+                    app_state.crypto_service.get_master_key(),
+                ) {
+                    let db_key = CryptoService::derive_database_key(&data_key);
 
-                if let Some(path_str) = vault_path.to_str() {
-                    if let Err(e) = database_service.initialize(path_str) {
+                    if let Err(e) = database_service.initialize(path_str, &db_key) {
                         log::error!("Failed to re-initialize database: {}", e);
                     } else {
                         log::info!("Database re-initialized successfully.");
@@ -2224,8 +2241,7 @@ pub async fn upload_file_chunk(
             if let Err(e) = database_service.add_file(&file_entry) {
                 log::error!("파일 메타데이터 저장 실패: {}", e);
                 // 암호화된 파일 삭제 (롤백)
-                let vault_path =
-                    std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+                let vault_path = crate::vault_root();
                 let data_dir = vault_path.join(".securevault").join("data").join("files");
                 let encrypted_file_path = data_dir.join(&encrypted_file_name);
                 let _ = std::fs::remove_file(&encrypted_file_path);
@@ -2280,8 +2296,10 @@ pub async fn upload_file_chunk(
 #[tauri::command]
 pub async fn cancel_chunked_upload(
     session_id: String,
-    _state: State<'_, Mutex<AppState>>,
+    state: State<'_, Mutex<AppState>>,
 ) -> Result<(), String> {
+    lock_unlocked(&state)?;
+
     log::info!("청크 업로드 취소: session_id={}", session_id);
 
     // 세션 정보 조회 및 제거
@@ -2373,7 +2391,7 @@ fn process_large_file_with_parallel(
         .to_string();
 
     // 임시 파일 경로들 (볼트 내부 .securevault/tmp 사용)
-    let mut vault_dir = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+    let mut vault_dir = crate::vault_root();
     if !vault_dir.join(".securevault").exists() {
         if let Some(parent) = vault_dir.parent() {
             if parent.join(".securevault").exists() {
@@ -2388,6 +2406,9 @@ fn process_large_file_with_parallel(
     let compressed_file_path = temp_dir.join(format!("compressed_{}", uuid::Uuid::new_v4()));
     let encrypted_file_path = temp_dir.join(format!("encrypted_{}", uuid::Uuid::new_v4()));
 
+    // 컨테이너 헤더에 기록되는 ID (DB ID와 일치해야 함)
+    let file_id = uuid::Uuid::new_v4();
+
     // 1. 병렬 압축 수행
     let compression_result = {
         let compression_service = app_state
@@ -2407,16 +2428,28 @@ fn process_large_file_with_parallel(
         compression_result.space_saved_percent()
     );
 
-    // 2. 병렬 암호화 수행
-    let encrypted_size = {
+    // 2. 파일 데이터 키로 병렬 암호화 수행
+    let (encrypted_size, wrapped_key) = {
         let file_service = app_state
             .file_service
             .lock()
             .map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?;
 
-        file_service
-            .encrypt_file_parallel_streaming(&compressed_file_path, &encrypted_file_path)
-            .map_err(|e| format!("병렬 암호화 실패: {}", e))?
+        let (file_key, wrapped_key) = file_service
+            .generate_file_key(FileSecurityLevel::Normal, &file_id)
+            .map_err(|e| format!("파일 키 생성 실패: {}", e))?;
+        let encrypted = file_service.encrypt_file_parallel_streaming(
+            &compressed_file_path,
+            &encrypted_file_path,
+            &file_id,
+            &file_key,
+        );
+        drop(file_key);
+
+        (
+            encrypted.map_err(|e| format!("병렬 암호화 실패: {}", e))?,
+            wrapped_key,
+        )
     };
 
     log::info!(
@@ -2426,7 +2459,7 @@ fn process_large_file_with_parallel(
     );
 
     // 3. 최종 저장 위치로 이동 (FileService와 동일한 경로 사용)
-    let vault_path = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+    let vault_path = crate::vault_root();
     let data_dir = vault_path.join(".securevault").join("files");
 
     if !data_dir.exists() {
@@ -2475,6 +2508,8 @@ fn process_large_file_with_parallel(
         compression_result.compressed_size,
         compression_result.compression_ratio,
     );
+    file_entry.id = file_id;
+    file_entry.wrapped_key = Some(wrapped_key);
 
     // file_entry.id를 사용하여 암호화된 파일명 생성 (ID 일치 보장)
     let encrypted_file_name = format!("{}.enc", file_entry.id);
@@ -2535,7 +2570,8 @@ fn process_small_file_parallel_phase1(
     root_folder_name: &str,
     source_path: &std::path::Path,
     data_dir: &std::path::Path,
-    master_key: &[u8; 32],
+    master_key: &SecretKey,
+    algorithm: &crate::models::EncryptionAlgorithm,
 ) -> Result<crate::models::file::FileEntry, String> {
     use std::fs;
 
@@ -2606,11 +2642,18 @@ fn process_small_file_parallel_phase1(
             (false, original_size, 1.0)
         };
 
-    // 순차 암호화 처리 (전달받은 마스터 키 사용)
-    let crypto_service = crate::services::crypto::CryptoService::new();
-    let encrypted_data = crypto_service
-        .encrypt_data_csharp_compatible(&processed_data, master_key)
-        .map_err(|e| format!("파일 암호화 실패: {}", e))?;
+    // 순차 암호화 처리 (파일 데이터 키를 마스터 키로 래핑, 헤더의 ID와 DB ID를 일치시킴)
+    let file_id = uuid::Uuid::new_v4();
+    let file_key = CryptoService::generate_data_key();
+    let wrapped_key = CryptoService::wrap_file_key(master_key, &file_key, &file_id)
+        .map_err(|e| format!("파일 키 래핑 실패: {}", e))?;
+    let encrypted_data = crate::services::container::encrypt_container(
+        &processed_data,
+        &file_key,
+        &file_id,
+        algorithm.clone(),
+    )
+    .map_err(|e| format!("파일 암호화 실패: {}", e))?;
 
     // 처리된 데이터 메모리 해제 (메모리 사용량 최적화)
     drop(processed_data);
@@ -2644,7 +2687,7 @@ fn process_small_file_parallel_phase1(
     }
     .to_string();
 
-    // 파일 엔트리 먼저 생성 (ID 생성됨)
+    // 파일 엔트리 생성 (암호화에 사용한 ID로 설정)
     let mut file_entry = crate::models::file::FileEntry::new_with_compression(
         file_name.clone(),
         file_name.clone(),
@@ -2659,6 +2702,8 @@ fn process_small_file_parallel_phase1(
         compressed_size,
         compression_ratio,
     );
+    file_entry.id = file_id;
+    file_entry.wrapped_key = Some(wrapped_key);
 
     // file_entry.id를 사용하여 암호화된 파일명 생성 (ID 일치 보장)
     let encrypted_file_name = format!("{}.enc", file_entry.id);
@@ -2711,10 +2756,7 @@ pub async fn export_file(
 
     // 2. 파일 서비스 준비
     let file_service_clone = {
-        let app_state = state.lock().map_err(|e| {
-            log::error!("상태 잠금 실패: {}", e);
-            format!("상태 잠금 실패: {}", e)
-        })?;
+        let app_state = lock_file_access(&state, &file_id)?;
         let file_service_guard = app_state.file_service.lock().map_err(|e| {
             log::error!("파일 서비스 잠금 실패: {}", e);
             format!("파일 서비스 잠금 실패: {}", e)
@@ -2746,7 +2788,7 @@ fn get_unique_filename(
     use std::path::Path;
 
     // DB 서비스 잠금
-    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_unlocked(state)?;
     let db_service = app_state
         .database_service
         .lock()
@@ -2828,7 +2870,7 @@ pub async fn move_file(
         None
     };
 
-    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_unlocked(&state)?;
     let database_service = app_state
         .database_service
         .lock()
@@ -2883,3 +2925,81 @@ pub async fn move_file(
     );
     Ok(())
 }
+
+/// 파일 보안 등급을 변경합니다.
+///
+/// 현재 등급과 새 등급 중 높은 쪽의 추가 인증이 필요하며, 최고 보안 등급으로 바꾸거나
+/// 최고 보안 등급에서 내리면 파일을 새 키로 다시 암호화합니다.
+///
+/// # 매개변수
+/// * `file_id` - 파일 ID
+/// * `security_level` - 새 보안 등급
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<FileEntry, String>` - 갱신된 파일 정보
+#[tauri::command]
+pub async fn set_file_security_level(
+    file_id: String,
+    security_level: FileSecurityLevel,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<FileEntry, String> {
+    let file_uuid = uuid::Uuid::parse_str(&file_id)
+        .map_err(|e| format!("잘못된 파일 ID 형식: {}", e))?;
+
+    let mut app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    let current_level = {
+        let database_service = app_state
+            .database_service
+            .lock()
+            .map_err(|e| format!("데이터베이스 서비스 잠금 실패: {}", e))?;
+
+        match database_service.get_file(&file_uuid) {
+            Ok(Some(file)) => file.security_level,
+            Ok(None) => return Err("파일을 찾을 수 없습니다.".to_string()),
+            Err(e) => return Err(format!("파일 조회 실패: {}", e)),
+        }
+    };
+
+    // 등급을 내리는 경우에도 현재 등급의 인증을 요구하여 등급 변경으로 보호를 우회하지 못하게 함
+    ensure_file_access(&mut app_state, current_level.max(security_level))
+        .map_err(|e| e.to_string())?;
+
+    let mut file_service = app_state
+        .file_service
+        .lock()
+        .map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?;
+
+    file_service
+        .change_security_level(&file_uuid, security_level)
+        .map_err(|e| e.to_string())
+}
+
+/// 이전 형식(헤더 없는 형식, 이전 버전 컨테이너)의 암호화 파일을 현재 컨테이너 형식으로 변환합니다.
+///
+/// 최고 보안 파일은 최고 보안 암호가 입력된 상태에서만 변환되며, 그렇지 않으면
+/// 건너뛴 목록으로 반환됩니다.
+///
+/// # 매개변수
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<LegacyMigrationReport, String>` - 변환 결과
+#[tauri::command]
+pub async fn migrate_legacy_files(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<LegacyMigrationReport, String> {
+    let mut app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    let mut file_service = app_state
+        .file_service
+        .lock()
+        .map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?;
+
+    file_service
+        .migrate_legacy_files()
+        .map_err(|e| e.to_string())
+}
@@ -1,22 +1,10 @@
 use crate::models::folder::FolderEntry;
+use crate::commands::guard::lock_unlocked;
 use crate::AppState;
 use std::sync::Mutex;
 use tauri::State;
 use uuid::Uuid;
 
-/// 테스트용 간단한 폴더 생성 커맨드
-#[tauri::command]
-pub async fn test_create_folder(name: String) -> Result<String, String> {
-    log::info!("테스트 폴더 생성 요청: {}", name);
-
-    if name.trim().is_empty() {
-        return Err("폴더명이 비어있습니다.".to_string());
-    }
-
-    // 간단한 성공 응답
-    Ok(format!("테스트 폴더 '{}' 생성 성공", name))
-}
-
 /// 새 폴더 생성 커맨드 (C# OnCreateFolder 포팅)
 ///
 /// # 매개변수
@@ -35,10 +23,7 @@ pub async fn create_folder(
 ) -> Result<FolderEntry, String> {
     log::info!("폴더 생성 요청: name={}, parent_id={:?}", name, parent_id);
 
-    let app_state = state.lock().map_err(|e| {
-        log::error!("상태 잠금 실패: {}", e);
-        format!("상태 잠금 실패: {}", e)
-    })?;
+    let app_state = lock_unlocked(&state)?;
 
     let folder_service = &app_state.folder_service;
 
@@ -114,10 +99,7 @@ pub async fn delete_folder(
         "올바르지 않은 폴더 ID 형식입니다.".to_string()
     })?;
 
-    let app_state = state.lock().map_err(|e| {
-        log::error!("상태 잠금 실패: {}", e);
-        format!("상태 잠금 실패: {}", e)
-    })?;
+    let app_state = lock_unlocked(&state)?;
 
     // 먼저 데이터베이스에서 폴더 존재 확인
     let database_service = app_state.database_service.lock().map_err(|e| {
@@ -249,10 +231,7 @@ pub async fn rename_folder(
         return Err("폴더명에 다음 문자는 사용할 수 없습니다: < > : \" | ? * / \\".to_string());
     }
 
-    let app_state = state.lock().map_err(|e| {
-        log::error!("상태 잠금 실패: {}", e);
-        format!("상태 잠금 실패: {}", e)
-    })?;
+    let app_state = lock_unlocked(&state)?;
 
     let database_service = app_state.database_service.lock().map_err(|e| {
         log::error!("데이터베이스 서비스 잠금 실패: {}", e);
@@ -324,7 +303,7 @@ pub async fn rename_folder(
 pub async fn get_folder_tree(
     state: State<'_, Mutex<AppState>>,
 ) -> Result<Vec<FolderEntry>, String> {
-    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_unlocked(&state)?;
 
     // 데이터베이스에서 모든 폴더 로드
     let database_service = app_state
@@ -430,7 +409,7 @@ pub async fn get_subfolders(
     parent_id: Option<String>,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<Vec<FolderEntry>, String> {
-    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_unlocked(&state)?;
 
     // 부모 ID 변환
     let parent_uuid = if let Some(id_str) = parent_id {
@@ -489,7 +468,7 @@ pub async fn get_folder(
     folder_id: String,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<Option<FolderEntry>, String> {
-    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_unlocked(&state)?;
     let folder_service = &app_state.folder_service;
 
     let folder_uuid =
@@ -512,7 +491,7 @@ pub async fn get_folder_path(
     folder_id: String,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<String, String> {
-    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_unlocked(&state)?;
     let folder_service = &app_state.folder_service;
 
     let folder_uuid =
@@ -537,7 +516,7 @@ pub async fn get_current_folder_id(
     selected_path: String,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<Option<String>, String> {
-    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_unlocked(&state)?;
     let folder_service = &app_state.folder_service;
 
     let folder_id = folder_service.get_current_folder_id(&selected_path);
@@ -556,7 +535,7 @@ pub async fn get_current_folder_id(
 pub async fn get_all_folders(
     state: State<'_, Mutex<AppState>>,
 ) -> Result<Vec<FolderEntry>, String> {
-    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_unlocked(&state)?;
     let folder_service = &app_state.folder_service;
     Ok(folder_service.get_all_folders())
 }
@@ -579,7 +558,7 @@ pub async fn update_folder_stats(
     size_delta: i64,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<(), String> {
-    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_unlocked(&state)?;
     let folder_service = &app_state.folder_service;
 
     let folder_uuid =
@@ -604,7 +583,7 @@ pub async fn calculate_folder_stats(
     folder_id: Option<String>,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<FolderStats, String> {
-    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_unlocked(&state)?;
     let database_service = &app_state.database_service;
 
     // 폴더 ID 변환
@@ -720,7 +699,7 @@ pub async fn export_folder(
 
     // 1. 초기 폴더 정보 및 구조 로드 (Lock 최소화)
     let (root_folder, all_folders_map) = {
-        let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+        let app_state = lock_unlocked(&state)?;
         let db_service = app_state
             .database_service
             .lock()
@@ -768,7 +747,7 @@ pub async fn export_folder(
         // A. 현재 폴더의 파일들 내보내기
         // 파일 목록 조회 (DB Lock 필요)
         let files = {
-            let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+            let app_state = lock_unlocked(&state)?;
             let db_service = app_state
                 .database_service
                 .lock()
@@ -873,7 +852,7 @@ pub async fn move_folder(
         return Err("폴더를 자기 자신 내부로 이동할 수 없습니다.".to_string());
     }
 
-    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_unlocked(&state)?;
     let database_service = app_state
         .database_service
         .lock()
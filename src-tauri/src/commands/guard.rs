@@ -0,0 +1,143 @@
+// 커맨드 인증 가드
+// 민감한 커맨드가 실행되기 전에 볼트가 열려 있는지 한곳에서 확인합니다.
+
+use crate::models::file::FileSecurityLevel;
+use crate::models::{SecretKey, VaultError};
+use crate::AppState;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tauri::State;
+
+/// 볼트가 열려 있는지 확인하고 현재 볼트 데이터 키를 반환합니다.
+///
+/// 세션이 없거나 만료되었거나, 키가 없거나 모두 0인 경우 남아 있는 키를
+/// 모두 지우고(`AppState::lock_vault`) `VaultError::Locked`를 반환합니다.
+///
+/// # 매개변수
+/// * `app_state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<Arc<SecretKey>, VaultError>` - 볼트 데이터 키 (복사본이 아닌 공유 참조)
+pub fn ensure_unlocked(app_state: &mut AppState) -> Result<Arc<SecretKey>, VaultError> {
+    if !app_state.auth_service.is_session_valid() {
+        if app_state.crypto_service.has_master_key() {
+            app_state.lock_vault();
+        }
+        return Err(VaultError::Locked);
+    }
+
+    let data_key = match app_state.crypto_service.get_master_key() {
+        Some(key) => key,
+        None => return Err(VaultError::Locked),
+    };
+
+    if data_key.is_all_zero() {
+        log::error!("비어 있는 볼트 키가 감지되어 볼트를 잠급니다.");
+        app_state.lock_vault();
+        return Err(VaultError::Locked);
+    }
+
+    Ok(data_key)
+}
+
+/// 커맨드 시작 시 호출하는 인증 가드입니다.
+///
+/// 상태를 잠근 채로 볼트가 열려 있는지 확인하고 그 잠금을 그대로 반환합니다.
+/// 확인과 작업 사이에 볼트가 잠기지 않도록 커맨드는 반환된 상태로 작업해야 합니다.
+///
+/// # 매개변수
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<MutexGuard<AppState>, String>` - 잠겨 있으면 `[VAULT_LOCKED]`로 시작하는 오류 메시지
+pub fn lock_unlocked<'a>(
+    state: &'a State<'_, Mutex<AppState>>,
+) -> Result<MutexGuard<'a, AppState>, String> {
+    let mut app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+    Ok(app_state)
+}
+
+/// 파일 보안 등급에 맞는 추가 인증이 되어 있는지 확인합니다.
+///
+/// * `High` - 최근 `STEP_UP_WINDOW_SECONDS` 안에 PIN을 다시 확인해야 함
+/// * `Critical` - PIN 재확인과 함께 최고 보안 암호로 파일 키를 잠금 해제해야 함
+///
+/// `Critical`이면 잠금 해제된 최고 보안 파일 키를 파일 서비스에 전달하고,
+/// 재확인이 만료되었으면 파일 서비스에 남아 있는 키를 지웁니다.
+///
+/// # 매개변수
+/// * `app_state` - 애플리케이션 상태
+/// * `level` - 접근하려는 파일의 보안 등급
+///
+/// # 반환값
+/// * `Result<(), VaultError>` - 접근 가능 여부
+pub fn ensure_file_access(
+    app_state: &mut AppState,
+    level: FileSecurityLevel,
+) -> Result<(), VaultError> {
+    if level == FileSecurityLevel::Normal {
+        return Ok(());
+    }
+
+    let stepped_up = app_state.auth_service.has_recent_step_up();
+    let critical_key = if stepped_up {
+        app_state.auth_service.critical_key()
+    } else {
+        None
+    };
+
+    let has_critical_key = critical_key.is_some();
+    // 데이터 키 교체 후 재암호화가 끝나지 않은 최고 보안 파일은 이전 키로 열어야 함
+    let previous_critical_key = critical_key
+        .as_ref()
+        .and_then(|key| app_state.auth_service.retired_critical_key(key))
+        .map(Arc::new);
+
+    if let Ok(file_service) = app_state.file_service.lock() {
+        file_service.set_critical_key(critical_key);
+        file_service.set_previous_critical_key(previous_critical_key);
+    }
+
+    if !stepped_up {
+        return Err(VaultError::StepUpRequired);
+    }
+
+    if level == FileSecurityLevel::Critical && !has_critical_key {
+        return Err(VaultError::CriticalKeyRequired);
+    }
+
+    Ok(())
+}
+
+/// 파일을 열거나 내보내는 커맨드 시작 시 호출하는 가드입니다.
+/// 볼트 잠금 여부와 파일 보안 등급에 따른 추가 인증을 같은 상태 잠금 안에서 확인하고
+/// 그 잠금을 그대로 반환합니다.
+///
+/// # 매개변수
+/// * `state` - 애플리케이션 상태
+/// * `file_id` - 파일 ID
+///
+/// # 반환값
+/// * `Result<MutexGuard<AppState>, String>` - 추가 인증이 필요하면 `[STEP_UP_REQUIRED]` 또는
+///   `[CRITICAL_KEY_REQUIRED]`로 시작하는 오류 메시지
+pub fn lock_file_access<'a>(
+    state: &'a State<'_, Mutex<AppState>>,
+    file_id: &str,
+) -> Result<MutexGuard<'a, AppState>, String> {
+    let mut app_state = lock_unlocked(state)?;
+
+    let level = {
+        let database_service = app_state
+            .database_service
+            .lock()
+            .map_err(|e| format!("데이터베이스 서비스 잠금 실패: {}", e))?;
+        database_service
+            .get_file_metadata(file_id)
+            .map_err(|e| e.to_string())?
+            .map(|entry| entry.security_level)
+            .unwrap_or_default()
+    };
+
+    ensure_file_access(&mut app_state, level).map_err(|e| e.to_string())?;
+    Ok(app_state)
+}
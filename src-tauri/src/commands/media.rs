@@ -1,3 +1,4 @@
+use crate::commands::guard::{lock_file_access, lock_unlocked};
 use crate::AppState;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -33,11 +34,10 @@ pub fn get_media_metadata(
     file_id: String,
     app_state: State<'_, Mutex<AppState>>,
 ) -> Result<MediaMetadata, String> {
-    println!("미디어 메타데이터 추출 시작: file_id={}", file_id);
+    let app_state = lock_unlocked(&app_state)?;
+
+    log::debug!("미디어 메타데이터 추출 시작: file_id={}", file_id);
 
-    let app_state = app_state
-        .lock()
-        .map_err(|e| format!("상태 잠금 실패: {}", e))?;
     let database_service = app_state
         .database_service
         .lock()
@@ -54,7 +54,7 @@ pub fn get_media_metadata(
     let media_type = determine_media_type(&extension);
 
     // 실제 파일 경로 계산 (절대 경로)
-    let current_dir = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+    let current_dir = crate::vault_root();
 
     // .securevault 폴더 위치 찾기 (현재 디렉토리 또는 상위 디렉토리)
     let mut vault_dir = current_dir.join(".securevault");
@@ -91,7 +91,7 @@ pub fn get_media_metadata(
         file_path: file_path_str,
     };
 
-    println!("미디어 메타데이터 추출 완료: {:?}", metadata);
+    log::debug!("미디어 메타데이터 추출 완료: file_id={}", file_id);
     Ok(metadata)
 }
 
@@ -102,18 +102,16 @@ pub fn prepare_media_stream(
     file_id: String,
     app_state: State<'_, Mutex<AppState>>,
 ) -> Result<String, String> {
-    println!("미디어 스트리밍 준비 요청: file_id={}", file_id);
+    // 1. AppState 락 획득 (잠금 해제와 파일 접근 권한은 같은 락 안에서 확인)
+    let app_state_guard = lock_file_access(&app_state, &file_id)?;
 
-    // 1. AppState 락 획득
-    let app_state_guard = app_state
-        .lock()
-        .map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    log::debug!("미디어 스트리밍 준비 요청: file_id={}", file_id);
 
     // UUID 파싱
     let _file_uuid = Uuid::from_str(&file_id).map_err(|e| format!("잘못된 파일 ID 형식: {}", e))?;
 
     // 2. 파일 경로 계산
-    let current_dir = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+    let current_dir = crate::vault_root();
     let mut vault_dir = current_dir.join(".securevault");
     if !vault_dir.exists() {
         if let Some(parent) = current_dir.parent() {
@@ -133,37 +131,13 @@ pub fn prepare_media_stream(
         ));
     }
 
-    // 4. 복호화 (청크 스트리밍 우선 시도, 실패 시 전체 파일 복호화 폴백)
-    let decrypted_data = {
-        let file_service = app_state_guard
-            .file_service
-            .lock()
-            .map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?;
-
-        // 먼저 청크 스트리밍 복호화 시도 (개별 파일 업로드 형식)
-        match file_service.decrypt_file_streaming_chunked(&encrypted_file_path) {
-            Ok(data) => {
-                log::info!("청크 스트리밍 복호화 성공");
-                data
-            }
-            Err(e) => {
-                log::info!("청크 복호화 실패, 전체 파일 복호화 시도: {}", e);
-
-                // 폴백: 전체 파일을 읽어서 한 번에 복호화 (폴더 업로드 형식)
-                let encrypted_data = std::fs::read(&encrypted_file_path)
-                    .map_err(|e| format!("암호화된 파일 읽기 실패: {}", e))?;
-
-                let master_key = file_service
-                    .get_master_key()
-                    .ok_or("마스터 키가 설정되지 않았습니다. (로그인 필요)")?;
-
-                let crypto_service = crate::services::crypto::CryptoService::new();
-                crypto_service
-                    .decrypt_data_csharp_compatible(&encrypted_data, &master_key)
-                    .map_err(|e2| format!("복호화 실패. 청크: {}, 전체: {}", e, e2))?
-            }
-        }
-    };
+    // 4. 복호화 (컨테이너 헤더로 형식과 알고리즘을 판별하고, 보안 등급에 맞는 키 사용)
+    let decrypted_data = app_state_guard
+        .file_service
+        .lock()
+        .map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?
+        .get_file_content(&file_id)
+        .map_err(|e| e.to_string())?;
 
     // 5. 파일 메타데이터에서 압축 여부 확인 및 압축 해제
     let final_data = {
@@ -210,7 +184,7 @@ pub fn prepare_media_stream(
         .map_err(|e| format!("임시 파일 쓰기 실패: {}", e))?;
 
     let temp_path_str = temp_file_path.to_string_lossy().to_string();
-    println!("미디어 스트리밍 준비 완료: {}", temp_path_str);
+    log::debug!("미디어 스트리밍 준비 완료: file_id={}", file_id);
     Ok(temp_path_str)
 }
 
@@ -222,14 +196,15 @@ pub fn get_media_stream(
     size: usize,
     app_state: State<'_, Mutex<AppState>>,
 ) -> Result<String, String> {
-    println!(
+    let app_state = lock_file_access(&app_state, &file_id)?;
+
+    log::debug!(
         "미디어 스트림 요청: file_id={}, offset={}, size={}",
-        file_id, offset, size
+        file_id,
+        offset,
+        size
     );
 
-    let app_state = app_state
-        .lock()
-        .map_err(|e| format!("상태 잠금 실패: {}", e))?;
     let mut file_service = app_state
         .file_service
         .lock()
@@ -252,7 +227,7 @@ pub fn get_media_stream(
     use base64::{engine::general_purpose, Engine as _};
     let encoded = general_purpose::STANDARD.encode(chunk);
 
-    println!("미디어 스트림 반환: chunk_size={}", chunk.len());
+    log::debug!("미디어 스트림 반환: chunk_size={}", chunk.len());
     Ok(encoded)
 }
 
@@ -262,11 +237,10 @@ pub fn get_full_media_data(
     file_id: String,
     app_state: State<'_, Mutex<AppState>>,
 ) -> Result<String, String> {
-    println!("전체 미디어 데이터 요청: file_id={}", file_id);
+    let app_state = lock_file_access(&app_state, &file_id)?;
+
+    log::debug!("전체 미디어 데이터 요청: file_id={}", file_id);
 
-    let app_state = app_state
-        .lock()
-        .map_err(|e| format!("상태 잠금 실패: {}", e))?;
     let database_service = app_state
         .database_service
         .lock()
@@ -302,14 +276,23 @@ pub fn get_full_media_data(
     use base64::{engine::general_purpose, Engine as _};
     let encoded = general_purpose::STANDARD.encode(data);
 
-    println!("전체 미디어 데이터 반환: size={}", file_entry.file_size);
+    log::debug!("전체 미디어 데이터 반환: size={}", file_entry.file_size);
     Ok(encoded)
 }
 
 /// 미디어 파일 지원 여부 확인
 #[tauri::command]
-pub fn is_media_file_supported(file_name: String) -> bool {
-    let extension = get_file_extension(&file_name);
+pub fn is_media_file_supported(
+    file_name: String,
+    app_state: State<'_, Mutex<AppState>>,
+) -> Result<bool, String> {
+    lock_unlocked(&app_state)?;
+    Ok(is_supported_media_extension(&file_name))
+}
+
+/// 파일 확장자가 지원하는 미디어 형식인지 확인합니다.
+fn is_supported_media_extension(file_name: &str) -> bool {
+    let extension = get_file_extension(file_name);
 
     const SUPPORTED_EXTENSIONS: &[&str] = &[
         // 오디오 형식
@@ -380,9 +363,9 @@ mod tests {
 
     #[test]
     fn test_media_file_support() {
-        assert!(is_media_file_supported("music.mp3".to_string()));
-        assert!(is_media_file_supported("video.mp4".to_string()));
-        assert!(!is_media_file_supported("document.txt".to_string()));
+        assert!(is_supported_media_extension("music.mp3"));
+        assert!(is_supported_media_extension("video.mp4"));
+        assert!(!is_supported_media_extension("document.txt"));
     }
 
     #[test]
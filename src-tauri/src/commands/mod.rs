@@ -8,8 +8,10 @@ pub mod crypto;
 pub mod database;
 pub mod files;
 pub mod folders;
+pub mod guard;
 pub mod media;
 pub mod recovery;
+pub mod reencryption;
 pub mod search;
 pub mod security;
 pub mod upload;
@@ -1,4 +1,5 @@
-use crate::models::recovery::{RecoveryKeyInfo, RecoveryVerificationResult};
+use crate::models::recovery::RecoveryKeyInfo;
+use crate::commands::guard::lock_unlocked;
 use crate::AppState;
 use std::sync::Mutex;
 use tauri::State;
@@ -7,11 +8,11 @@ use tauri::State;
 /// C# RecoveryKeyDialog에서 호출되는 기능을 Tauri로 포팅
 ///
 /// # 반환값
-/// * `Ok(String)` - Base64로 인코딩된 256비트 복구 키
+/// * `Ok(String)` - 256비트 복구 키를 인코딩한 24단어
 /// * `Err(String)` - 오류 메시지 (한국어)
 #[tauri::command]
 pub async fn generate_recovery_key(state: State<'_, Mutex<AppState>>) -> Result<String, String> {
-    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let app_state = lock_unlocked(&state)?;
     let recovery_service = &app_state.recovery_service;
 
     recovery_service
@@ -19,11 +20,37 @@ pub async fn generate_recovery_key(state: State<'_, Mutex<AppState>>) -> Result<
         .map_err(|e| format!("복구 키 생성 실패: {}", e))
 }
 
+/// 복구 키 분할 커맨드
+/// 복구 키를 N개의 조각으로 나눠 M명 이상이 모여야 볼트를 복구할 수 있게 함
+///
+/// # 매개변수
+/// * `recovery_key` - 나눌 복구 키
+/// * `threshold` - 복원에 필요한 조각 수 (M)
+/// * `share_count` - 전체 조각 수 (N)
+///
+/// # 반환값
+/// * `Ok(Vec<String>)` - 번호와 체크섬이 포함된 조각 목록
+/// * `Err(String)` - 오류 메시지 (한국어)
+#[tauri::command]
+pub async fn split_recovery_key(
+    recovery_key: String,
+    threshold: u8,
+    share_count: u8,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<String>, String> {
+    let app_state = lock_unlocked(&state)?;
+
+    app_state
+        .recovery_service
+        .split_recovery_key(&recovery_key, threshold, share_count)
+        .map_err(|e| format!("복구 키 분할 실패: {}", e))
+}
+
 /// 복구 키 해시 생성 커맨드
 /// 복구 키를 저장하기 위한 SHA-256 해시 생성
 ///
 /// # 매개변수
-/// * `recovery_key` - Base64 형식의 복구 키
+/// * `recovery_key` - 24단어 또는 기존 Base64 형식의 복구 키
 ///
 /// # 반환값
 /// * `Ok(String)` - Base64로 인코딩된 SHA-256 해시값
@@ -33,7 +60,7 @@ pub async fn hash_recovery_key(
     recovery_key: String,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<String, String> {
-    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let app_state = lock_unlocked(&state)?;
     let recovery_service = &app_state.recovery_service;
 
     recovery_service
@@ -57,7 +84,7 @@ pub async fn verify_recovery_key(
     stored_hash: String,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<bool, String> {
-    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let app_state = lock_unlocked(&state)?;
     let recovery_service = &app_state.recovery_service;
 
     recovery_service
@@ -65,90 +92,6 @@ pub async fn verify_recovery_key(
         .map_err(|e| format!("복구 키 검증 실패: {}", e))
 }
 
-/// 복구 키로 마스터 키 유도 커맨드
-/// 복구 키를 사용하여 볼트 암호화에 사용할 마스터 키 생성
-///
-/// # 매개변수
-/// * `recovery_key` - Base64 형식의 복구 키
-/// * `salt` - Base64로 인코딩된 32바이트 솔트
-/// * `iterations` - PBKDF2 반복 횟수 (선택사항, 기본값: 100,000)
-///
-/// # 반환값
-/// * `Ok(Vec<u8>)` - 32바이트 마스터 키
-/// * `Err(String)` - 오류 메시지 (한국어)
-#[tauri::command]
-pub async fn derive_key_from_recovery_key(
-    recovery_key: String,
-    salt: String,
-    iterations: Option<u32>,
-    state: State<'_, Mutex<AppState>>,
-) -> Result<Vec<u8>, String> {
-    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
-    let recovery_service = &app_state.recovery_service;
-
-    // Base64 솔트 디코딩
-    let salt_bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &salt)
-        .map_err(|_| "올바르지 않은 솔트 형식입니다.".to_string())?;
-
-    if salt_bytes.len() != 32 {
-        return Err("솔트는 32바이트여야 합니다.".to_string());
-    }
-
-    let salt_array: [u8; 32] = salt_bytes
-        .try_into()
-        .map_err(|_| "솔트 변환 오류".to_string())?;
-
-    recovery_service
-        .derive_key_from_recovery_key(&recovery_key, &salt_array, iterations)
-        .map(|key| key.to_vec())
-        .map_err(|e| format!("마스터 키 유도 실패: {}", e))
-}
-
-/// 복구 키 검증 및 마스터 키 유도 통합 커맨드
-/// 복구 키 검증과 마스터 키 유도를 한 번에 수행
-///
-/// # 매개변수
-/// * `input_recovery_key` - 사용자가 입력한 복구 키
-/// * `stored_hash` - 저장된 복구 키 해시값
-/// * `salt` - Base64로 인코딩된 32바이트 솔트
-/// * `iterations` - PBKDF2 반복 횟수 (선택사항)
-///
-/// # 반환값
-/// * `Ok(RecoveryVerificationResult)` - 검증 결과 및 마스터 키
-/// * `Err(String)` - 오류 메시지 (한국어)
-#[tauri::command]
-pub async fn verify_and_derive_key(
-    input_recovery_key: String,
-    stored_hash: String,
-    salt: String,
-    iterations: Option<u32>,
-    state: State<'_, Mutex<AppState>>,
-) -> Result<RecoveryVerificationResult, String> {
-    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
-    let recovery_service = &app_state.recovery_service;
-
-    // Base64 솔트 디코딩
-    let salt_bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &salt)
-        .map_err(|_| "올바르지 않은 솔트 형식입니다.".to_string())?;
-
-    if salt_bytes.len() != 32 {
-        return Err("솔트는 32바이트여야 합니다.".to_string());
-    }
-
-    let salt_array: [u8; 32] = salt_bytes
-        .try_into()
-        .map_err(|_| "솔트 변환 오류".to_string())?;
-
-    let result = recovery_service.verify_and_derive_key(
-        &input_recovery_key,
-        &stored_hash,
-        &salt_array,
-        iterations,
-    );
-
-    Ok(result)
-}
-
 /// 복구 키 형식 검증 커맨드
 /// 사용자가 입력한 복구 키의 형식이 올바른지 확인
 ///
@@ -193,96 +136,3 @@ pub async fn get_recovery_key_info(
 
     Ok(info)
 }
-
-/// 복구 키 사용 표시 커맨드
-/// 복구 키를 사용했음을 표시 (한 번만 사용 가능)
-///
-/// # 반환값
-/// * `Ok(())` - 성공
-/// * `Err(String)` - 오류 메시지 (한국어)
-#[tauri::command]
-pub async fn mark_recovery_key_used(state: State<'_, Mutex<AppState>>) -> Result<(), String> {
-    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
-    let recovery_service = &app_state.recovery_service;
-
-    recovery_service
-        .mark_recovery_key_used()
-        .map_err(|e| format!("복구 키 사용 표시 실패: {}", e))
-}
-
-/// 복구 키 정보 초기화 커맨드
-/// 보안을 위해 메모리에서 복구 키 정보 삭제
-///
-/// # 반환값
-/// * `Ok(())` - 성공
-#[tauri::command]
-pub async fn clear_recovery_key(state: State<'_, Mutex<AppState>>) -> Result<(), String> {
-    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
-    let recovery_service = &app_state.recovery_service;
-    recovery_service.clear_recovery_key();
-    Ok(())
-}
-
-/// 복구 키 기반 인증 커맨드
-/// 복구 키를 사용하여 볼트에 인증하고 새로운 PIN 설정 가능
-///
-/// # 매개변수
-/// * `recovery_key` - 사용자가 입력한 복구 키
-/// * `stored_hash` - 저장된 복구 키 해시
-/// * `salt` - 볼트 솔트
-///
-/// # 반환값
-/// * `Ok(Vec<u8>)` - 인증 성공 시 마스터 키
-/// * `Err(String)` - 인증 실패 메시지 (한국어)
-#[tauri::command]
-pub async fn authenticate_with_recovery_key(
-    recovery_key: String,
-    stored_hash: String,
-    salt: String,
-    state: State<'_, Mutex<AppState>>,
-) -> Result<Vec<u8>, String> {
-    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
-    let recovery_service = &app_state.recovery_service;
-
-    // 복구 키 형식 검증
-    recovery_service
-        .validate_recovery_key_format(&recovery_key)
-        .map_err(|e| format!("복구 키 형식 오류: {}", e))?;
-
-    // Base64 솔트 디코딩
-    let salt_bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &salt)
-        .map_err(|_| "올바르지 않은 솔트 형식입니다.".to_string())?;
-
-    if salt_bytes.len() != 32 {
-        return Err("솔트는 32바이트여야 합니다.".to_string());
-    }
-
-    let salt_array: [u8; 32] = salt_bytes
-        .try_into()
-        .map_err(|_| "솔트 변환 오류".to_string())?;
-
-    // 복구 키 검증 및 마스터 키 유도
-    let result = recovery_service.verify_and_derive_key(
-        &recovery_key,
-        &stored_hash,
-        &salt_array,
-        Some(100_000), // C# 버전과 동일한 반복 횟수
-    );
-
-    if result.is_valid {
-        if let Some(master_key) = result.master_key {
-            // 복구 키 사용 표시
-            recovery_service
-                .mark_recovery_key_used()
-                .map_err(|e| format!("복구 키 사용 표시 실패: {}", e))?;
-
-            Ok(master_key)
-        } else {
-            Err("마스터 키 유도에 실패했습니다.".to_string())
-        }
-    } else {
-        Err(result
-            .error_message
-            .unwrap_or_else(|| "복구 키 인증에 실패했습니다.".to_string()))
-    }
-}
@@ -0,0 +1,320 @@
+// 재암호화 관련 Tauri 명령어
+// 볼트 전체 재암호화 작업의 시작, 일시 중지, 재개, 상태 조회 기능을 제공합니다.
+
+use crate::commands::auth::install_master_key;
+use crate::commands::guard::{ensure_unlocked, lock_unlocked};
+use crate::models::{EncryptionAlgorithm, VaultError};
+use crate::services::file::FileService;
+use crate::services::reencryption::{
+    self, ReencryptionCheckpoint, ReencryptionJob, ReencryptionStatus, REENCRYPTION_PROGRESS_EVENT,
+};
+use crate::AppState;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// 재암호화 시작 결과
+#[derive(serde::Serialize)]
+pub struct ReencryptionStart {
+    /// 시작된 작업 정보
+    pub job: ReencryptionJob,
+    /// 데이터 키 교체로 새로 발급된 복구 키 (이전 복구 키는 더 이상 쓸 수 없음)
+    pub recovery_key: Option<String>,
+}
+
+/// 볼트 데이터 키를 교체하고 모든 파일을 새 키로 다시 암호화하는 작업을 시작합니다 (백그라운드 처리).
+///
+/// 새 데이터 키로 PIN/강압/복구 키/최고 보안 슬롯을 모두 다시 만들고 메타데이터 DB 키도 바꾼 뒤,
+/// 파일을 백그라운드에서 다시 암호화합니다. 이전 키는 모든 파일이 끝날 때까지 읽기용으로만 보관합니다.
+/// 이전 교체의 재암호화가 끝나지 않았으면 키를 다시 바꾸지 않고 남은 파일만 처리합니다.
+///
+/// 알고리즘을 지정하면 볼트의 암호화 알고리즘 설정도 함께 바뀌어 이후 새 파일에도 적용됩니다.
+/// 일시 중지되었거나 중단된 작업이 있으면 새로 시작하지 않고 `resume_reencryption`으로 이어서 진행해야 합니다.
+///
+/// # 매개변수
+/// * `algorithm` - 대상 알고리즘 이름 (None이면 현재 볼트 설정)
+/// * `pin` - 현재 PIN
+/// * `critical_passphrase` - 최고 보안 암호 (설정되어 있으면 필수)
+/// * `app_handle` - 진행률 이벤트 발송용 핸들
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<ReencryptionStart, String>` - 시작된 작업 정보와 새 복구 키
+#[tauri::command]
+pub async fn start_reencryption(
+    algorithm: Option<String>,
+    pin: String,
+    critical_passphrase: Option<String>,
+    app_handle: AppHandle,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<ReencryptionStart, String> {
+    let mut app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let data_key = ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    if app_state.reencryption_manager.is_running() {
+        return Err("이미 재암호화 작업이 진행 중입니다.".to_string());
+    }
+
+    {
+        let mut file_service = app_state
+            .file_service
+            .lock()
+            .map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?;
+
+        if let Some(existing) = file_service
+            .load_reencryption_checkpoint()
+            .map_err(|e| e.to_string())?
+        {
+            if existing.job.is_resumable() {
+                return Err(
+                    "완료되지 않은 재암호화 작업이 있습니다. 이어서 진행해주세요.".to_string(),
+                );
+            }
+        }
+    }
+
+    let mut recovery_key = None;
+    if app_state.auth_service.retired_data_key(&data_key).is_some() {
+        // 이전 교체에서 건너뛰었거나 실패한 파일만 남았으므로 키는 그대로 두고 다시 확인만 함
+        app_state
+            .auth_service
+            .step_up(&pin)
+            .map_err(|e| e.user_friendly_message())?;
+        if let Some(passphrase) = critical_passphrase.as_deref() {
+            app_state
+                .auth_service
+                .unlock_critical_key(passphrase)
+                .map_err(|e| e.user_friendly_message())?;
+        }
+    } else {
+        let rotation = app_state
+            .auth_service
+            .rotate_data_key(&pin, critical_passphrase.as_deref(), &data_key)
+            .map_err(|e| e.user_friendly_message())?;
+
+        // keys.json은 이미 새 키로 저장되었으므로, 여기서 실패해도 다음 로그인 때 이전 키로 DB를 열어 교체함
+        app_state
+            .file_service
+            .lock()
+            .map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?
+            .rekey_database(&rotation.data_key)
+            .map_err(|e| e.to_string())?;
+        install_master_key(&mut app_state, Arc::clone(&rotation.data_key))?;
+        recovery_key = rotation.recovery_key;
+    }
+
+    // 아직 다시 암호화되지 않은 최고 보안 파일은 이전 최고 보안 키로 읽음
+    let critical_key = app_state.auth_service.critical_key();
+    let previous_critical_key = critical_key
+        .as_ref()
+        .and_then(|key| app_state.auth_service.retired_critical_key(key))
+        .map(Arc::new);
+
+    let (checkpoint, worker) = {
+        let mut file_service = app_state
+            .file_service
+            .lock()
+            .map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?;
+        file_service.set_critical_key(critical_key);
+        file_service.set_previous_critical_key(previous_critical_key);
+
+        let algorithm = match algorithm {
+            Some(name) => EncryptionAlgorithm::from_name(&name)
+                .ok_or_else(|| format!("지원하지 않는 암호화 알고리즘입니다: {}", name))?,
+            None => file_service.encryption_algorithm().clone(),
+        };
+        file_service
+            .set_encryption_algorithm(algorithm.clone())
+            .map_err(|e| e.to_string())?;
+
+        let checkpoint = ReencryptionCheckpoint::new(algorithm);
+        file_service
+            .save_reencryption_checkpoint(&checkpoint)
+            .map_err(|e| e.to_string())?;
+
+        (checkpoint, file_service.clone())
+    };
+    app_state
+        .crypto_service
+        .set_default_algorithm(checkpoint.job.algorithm.clone());
+
+    let job = checkpoint.job.clone();
+    spawn_reencryption(&app_state, app_handle, worker, checkpoint)?;
+    Ok(ReencryptionStart { job, recovery_key })
+}
+
+/// 일시 중지되었거나 중단된 재암호화 작업을 체크포인트부터 이어서 진행합니다.
+///
+/// # 매개변수
+/// * `app_handle` - 진행률 이벤트 발송용 핸들
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<ReencryptionJob, String>` - 재개된 작업 정보
+#[tauri::command]
+pub async fn resume_reencryption(
+    app_handle: AppHandle,
+    state: State<'_, Mutex<AppState>>,
+) -> Result<ReencryptionJob, String> {
+    let mut app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
+    if app_state.reencryption_manager.is_running() {
+        return Err("이미 재암호화 작업이 진행 중입니다.".to_string());
+    }
+
+    let (checkpoint, worker) = {
+        let mut file_service = app_state
+            .file_service
+            .lock()
+            .map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?;
+
+        let mut checkpoint = file_service
+            .load_reencryption_checkpoint()
+            .map_err(|e| e.to_string())?
+            .filter(|checkpoint| checkpoint.job.is_resumable())
+            .ok_or_else(|| "이어서 진행할 재암호화 작업이 없습니다.".to_string())?;
+
+        // 교체 도중 중단되었다면 먼저 일관된 상태로 맞춤
+        file_service
+            .recover_reencryption(&mut checkpoint)
+            .map_err(|e| e.to_string())?;
+
+        (checkpoint, file_service.clone())
+    };
+
+    let job = checkpoint.job.clone();
+    spawn_reencryption(&app_state, app_handle, worker, checkpoint)?;
+    Ok(job)
+}
+
+/// 실행 중인 재암호화 작업을 일시 중지합니다.
+///
+/// 처리 중인 파일까지만 마치고 체크포인트를 저장한 뒤 멈춥니다.
+///
+/// # 반환값
+/// * `Result<bool, String>` - 실행 중인 작업이 있었는지 여부
+#[tauri::command]
+pub async fn pause_reencryption(state: State<'_, Mutex<AppState>>) -> Result<bool, String> {
+    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    Ok(app_state.reencryption_manager.pause())
+}
+
+/// 재암호화 작업 상태를 조회합니다.
+///
+/// 이 세션에서 실행한 작업이 없으면 볼트에 저장된 체크포인트를 반환합니다.
+///
+/// # 반환값
+/// * `Result<Option<ReencryptionJob>, String>` - 작업 정보 (시작한 적이 없으면 None)
+#[tauri::command]
+pub async fn get_reencryption_status(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Option<ReencryptionJob>, String> {
+    let app_state = lock_unlocked(&state)?;
+    if let Some(job) = app_state.reencryption_manager.get_job() {
+        return Ok(Some(job));
+    }
+
+    let mut file_service = app_state
+        .file_service
+        .lock()
+        .map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?;
+    let job = file_service
+        .load_reencryption_checkpoint()
+        .map_err(|e| e.to_string())?
+        .map(|checkpoint| {
+            let mut job = checkpoint.job;
+            // 비정상 종료로 남은 실행 상태는 일시 중지로 보고
+            if job.status == ReencryptionStatus::Running {
+                job.status = ReencryptionStatus::Paused;
+            }
+            job
+        });
+    Ok(job)
+}
+
+/// 백그라운드 스레드에서 재암호화 작업을 실행합니다.
+///
+/// 파일마다 `worker`에서 잠금 없이 다시 암호화하고, 교체만 앱 상태의 파일 서비스 잠금 아래에서
+/// 수행하므로 작업 중에도 볼트를 계속 사용할 수 있습니다.
+fn spawn_reencryption(
+    app_state: &AppState,
+    app_handle: AppHandle,
+    mut worker: FileService,
+    mut checkpoint: ReencryptionCheckpoint,
+) -> Result<(), String> {
+    let manager = app_state.reencryption_manager.clone();
+    let cancellation_token = manager
+        .mark_started(&checkpoint.job)
+        .ok_or_else(|| "이미 재암호화 작업이 진행 중입니다.".to_string())?;
+
+    std::thread::spawn(move || {
+        let state = app_handle.state::<Mutex<AppState>>();
+
+        let result = reencryption::run_reencryption(
+            &mut worker,
+            &mut checkpoint,
+            &cancellation_token,
+            |prepared, checkpoint| {
+                let app_state = state
+                    .lock()
+                    .map_err(|e| VaultError::DatabaseError(format!("상태 잠금 실패: {}", e)))?;
+                let mut file_service = app_state.file_service.lock().map_err(|e| {
+                    VaultError::DatabaseError(format!("파일 서비스 잠금 실패: {}", e))
+                })?;
+                file_service.commit_reencryption(prepared, checkpoint)
+            },
+            |job| {
+                manager.update(job);
+                let _ = app_handle.emit(REENCRYPTION_PROGRESS_EVENT, job);
+            },
+        );
+
+        if let Err(e) = result {
+            log::error!("재암호화 작업 실패: {} - {}", checkpoint.job.id, e);
+            checkpoint.job.status = ReencryptionStatus::Failed;
+            checkpoint.job.error = Some(e.to_string());
+            if let Err(e) = worker.save_reencryption_checkpoint(&checkpoint) {
+                log::warn!("재암호화 실패 상태 저장 실패: {}", e);
+            }
+        }
+
+        // 모든 파일이 새 키로 바뀌었으면 이전 키를 더 이상 보관하지 않음
+        if checkpoint.job.status == ReencryptionStatus::Completed
+            && checkpoint.job.failed.is_empty()
+            && checkpoint.job.skipped.is_empty()
+        {
+            discard_retired_keys(&state);
+        }
+
+        manager.mark_finished(&checkpoint.job);
+        let event = match checkpoint.job.status {
+            ReencryptionStatus::Completed => "reencrypt://complete",
+            ReencryptionStatus::Paused => "reencrypt://paused",
+            _ => "reencrypt://error",
+        };
+        let _ = app_handle.emit(event, &checkpoint.job);
+    });
+
+    Ok(())
+}
+
+/// 재암호화가 끝난 볼트의 이전 데이터 키 기록과 메모리의 이전 키를 삭제합니다.
+///
+/// 작업 중에 볼트가 잠겼으면 아무것도 하지 않으며, 다음 재암호화가 끝날 때 삭제됩니다.
+fn discard_retired_keys(state: &Mutex<AppState>) {
+    let Ok(mut app_state) = state.lock() else {
+        return;
+    };
+    let Some(data_key) = app_state.crypto_service.get_master_key() else {
+        return;
+    };
+
+    if let Err(e) = app_state.auth_service.discard_retired_keys(&data_key) {
+        log::warn!("이전 데이터 키 삭제 실패: {}", e);
+        return;
+    }
+    if let Ok(file_service) = app_state.file_service.lock() {
+        file_service.set_previous_master_key(None);
+        file_service.set_previous_critical_key(None);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::models::{file::FileEntry, folder::FolderEntry};
+use crate::commands::guard::lock_unlocked;
 use crate::AppState;
 use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
@@ -25,7 +26,7 @@ pub async fn search_files(
     _folder_id: Option<String>,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<SearchResult, String> {
-    let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+    let app_state = lock_unlocked(&state)?;
     let database_service = app_state
         .database_service
         .lock()
@@ -55,8 +56,10 @@ pub async fn search_files(
 pub async fn search_content(
     query: String,
     file_types: Vec<String>,
-    _state: State<'_, Mutex<AppState>>,
+    state: State<'_, Mutex<AppState>>,
 ) -> Result<Vec<String>, String> {
+    lock_unlocked(&state)?;
+
     // TODO: 파일 내용 검색 구현
     log::debug!(
         "내용 검색 요청: query={}, file_types={:?}",
@@ -1,13 +1,15 @@
 // 보안 관련 Tauri 커맨드
-// 네트워크 접근 차단 및 보안 상태 확인 기능을 제공합니다.
+// 네트워크 접근 차단, 보안 상태 확인, 로그 조회 기능을 제공합니다.
 
+use crate::commands::guard::ensure_unlocked;
+use crate::services::{log_sink, CryptoService};
 use crate::AppState;
 use tauri::State;
 use std::sync::Mutex;
 
 /// 보안 상태를 조회합니다.
 /// 
-/// 네트워크 접근 차단 상태, 인증 상태 등 전반적인 보안 상태를 확인합니다.
+/// 네트워크 접근 차단 상태, 인증 상태, 프로세스 보호 상태 등 전반적인 보안 상태를 확인합니다.
 /// 
 /// # 매개변수
 /// * `state` - 애플리케이션 상태
@@ -44,6 +46,12 @@ pub async fn get_security_status(
         let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
         app_state.auth_service.is_session_valid()
     };
+
+    let process_hardening = {
+        let app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+        app_state.process_hardening.clone()
+    };
+    let overall_status = if process_hardening.is_degraded() { "degraded" } else { "secure" };
     
     // 보안 상태 정보 구성
     let security_status = serde_json::json!({
@@ -59,7 +67,8 @@ pub async fn get_security_status(
             "has_recovery_key": has_recovery_key,
             "session_valid": session_valid
         },
-        "overall_status": "secure"
+        "process_hardening": process_hardening,
+        "overall_status": overall_status
     });
     
     match serde_json::to_string_pretty(&security_status) {
@@ -96,4 +105,30 @@ pub async fn check_network_access(
     );
     
     Ok(network_report.blocking_enabled)
-}
\ No newline at end of file
+}
+/// 현재 볼트의 로그를 복호화하여 조회합니다.
+///
+/// `.securevault/logs`의 순환 로그 파일을 오래된 순서로 읽으며, 기록된 로그는 이미 가림 처리되어 있습니다.
+///
+/// # 매개변수
+/// * `state` - 애플리케이션 상태
+///
+/// # 반환값
+/// * `Result<Vec<String>, String>` - 로그 줄 목록
+#[tauri::command]
+pub async fn get_app_logs(
+    state: State<'_, Mutex<AppState>>
+) -> Result<Vec<String>, String> {
+    let data_key = {
+        let mut app_state = state.lock().map_err(|_| "상태 잠금 실패")?;
+        ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?
+    };
+
+    let log_key = CryptoService::derive_log_key(&data_key);
+    drop(data_key);
+
+    log_sink::read_logs(&log_sink::log_directory(&crate::vault_root()), &log_key).map_err(|e| {
+        log::error!("로그 조회 실패: {}", e);
+        "로그를 읽는 중 오류가 발생했습니다.".to_string()
+    })
+}
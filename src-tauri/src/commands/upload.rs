@@ -2,6 +2,7 @@
 // 백그라운드 파일 업로드, 진행률 조회, 작업 취소 기능을 제공합니다.
 
 use crate::services::upload_manager::UploadJob;
+use crate::commands::guard::lock_unlocked;
 use crate::AppState;
 use std::sync::Mutex;
 use tauri::{AppHandle, Emitter, State};
@@ -19,6 +20,8 @@ pub async fn start_file_upload(
     app_handle: AppHandle,
     state: State<'_, Mutex<AppState>>,
 ) -> Result<String, String> {
+    lock_unlocked(&state)?;
+
     use std::path::Path;
 
     log::info!("백그라운드 업로드 시작 요청: {}", file_path);
@@ -55,7 +58,7 @@ pub async fn start_file_upload(
 
     // UploadManager에서 작업 ID 가져오기 및 필요한 서비스 복제
     let (job_id, upload_manager, file_service) = {
-        let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+        let app_state = lock_unlocked(&state)?;
         let job_id = app_state.upload_manager.add_job(
             file_path.clone(),
             actual_file_name.clone(),
@@ -199,7 +202,7 @@ pub async fn get_upload_status(
     let job_uuid =
         Uuid::parse_str(&job_id).map_err(|_| "잘못된 작업 ID 형식입니다.".to_string())?;
 
-    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_unlocked(&state)?;
 
     // 진행률 업데이트
     app_state.upload_manager.update_job_progress(&job_uuid);
@@ -213,6 +216,6 @@ pub async fn get_upload_status(
 /// 모든 업로드 작업 목록을 조회합니다.
 #[tauri::command]
 pub async fn get_all_uploads(state: State<'_, Mutex<AppState>>) -> Result<Vec<UploadJob>, String> {
-    let app_state = state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_unlocked(&state)?;
     Ok(app_state.upload_manager.get_all_jobs())
 }
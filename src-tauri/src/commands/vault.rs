@@ -1,6 +1,7 @@
 // 볼트 관련 Tauri 커맨드 (기본 구조)
 // 프론트엔드에서 호출할 수 있는 볼트 관리 함수들을 정의합니다.
 
+use crate::commands::guard::ensure_unlocked;
 use crate::AppState;
 use tauri::State;
 
@@ -49,7 +50,7 @@ pub async fn update_vault_config(
 pub async fn initialize_vault(
     vault_name: Option<String>,
     vault_path: Option<String>,
-    _state: State<'_, std::sync::Mutex<crate::AppState>>,
+    state: State<'_, std::sync::Mutex<crate::AppState>>,
 ) -> Result<(), String> {
     log::info!(
         "볼트 초기화 요청: name={:?}, path={:?}",
@@ -57,6 +58,14 @@ pub async fn initialize_vault(
         vault_path
     );
 
+    let mut app_state = state.lock().map_err(|e| {
+        log::error!("상태 잠금 실패: {}", e);
+        format!("상태 잠금 실패: {}", e)
+    })?;
+
+    // 인증된 볼트 키 없이 초기화하지 않음 (모두 0인 임시 키 사용 금지)
+    let data_key = ensure_unlocked(&mut app_state).map_err(|e| e.to_string())?;
+
     // 볼트 디렉토리 초기화 수행
     crate::initialize_vault_directory_simple().map_err(|e| {
         log::error!("볼트 초기화 실패: {}", e);
@@ -64,21 +73,14 @@ pub async fn initialize_vault(
     })?;
 
     // 파일 서비스 초기화
-    let app_state = _state.lock().map_err(|e| {
-        log::error!("상태 잠금 실패: {}", e);
-        format!("상태 잠금 실패: {}", e)
-    })?;
-
     let mut file_service = app_state.file_service.lock().map_err(|e| {
         log::error!("파일 서비스 잠금 실패: {}", e);
         format!("파일 서비스 잠금 실패: {}", e)
     })?;
 
     if !file_service.is_initialized() {
-        let vault_root = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
-        // TODO: 실제 마스터 키 생성 및 관리 로직 필요
-        let master_key = [0u8; 32];
-        file_service.set_vault_info(vault_root.to_string_lossy().as_ref(), master_key);
+        let vault_root = crate::vault_root();
+        file_service.set_vault_info(vault_root.to_string_lossy().as_ref(), data_key);
         log::info!("파일 서비스 초기화 완료");
     }
 
@@ -1,4 +1,5 @@
 use tauri::State;
+use crate::commands::guard::{lock_file_access, lock_unlocked};
 use crate::AppState;
 use base64::{Engine as _, engine::general_purpose};
 use std::sync::Mutex;
@@ -16,7 +17,7 @@ pub fn get_text_file_content(
     file_id: String,
     app_state: State<'_, Mutex<AppState>>,
 ) -> Result<String, String> {
-    let app_state = app_state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_file_access(&app_state, &file_id)?;
     let mut file_service = app_state.file_service.lock().map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?;
     
     // 동기적으로 파일 내용 읽기 (임시 구현)
@@ -48,7 +49,7 @@ pub fn get_binary_file_content(
     file_id: String,
     app_state: State<'_, Mutex<AppState>>,
 ) -> Result<String, String> {
-    let app_state = app_state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_file_access(&app_state, &file_id)?;
     let mut file_service = app_state.file_service.lock().map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?;
     
     let data = file_service.get_file_content(&file_id).map_err(|e| e.to_string())?;
@@ -72,35 +73,26 @@ pub fn save_text_file(
     content: String,
     app_state: State<'_, Mutex<AppState>>,
 ) -> Result<(), String> {
-    println!("save_text_file 명령어 호출됨: file_id={}, content_length={}", file_id, content.len());
+    let app_state = lock_file_access(&app_state, &file_id)?;
+
     log::info!("텍스트 파일 저장 요청: file_id={}, content_length={}", file_id, content.len());
     
-    let app_state = app_state.lock().map_err(|e| {
-        let error_msg = format!("상태 잠금 실패: {}", e);
-        println!("에러: {}", error_msg);
-        error_msg
-    })?;
-    
     let mut file_service = app_state.file_service.lock().map_err(|e| {
         let error_msg = format!("파일 서비스 잠금 실패: {}", e);
-        println!("에러: {}", error_msg);
+        log::error!("{}", error_msg);
         error_msg
     })?;
     
     let data = content.as_bytes().to_vec();
     
     // 실제 파일 저장 구현
-    println!("파일 저장 처리 중...");
-    
     match file_service.update_file_content(&file_id, data) {
         Ok(_) => {
-            println!("파일 저장 완료");
             log::info!("파일 저장 완료: file_id={}", file_id);
             Ok(())
         }
         Err(e) => {
             let error_msg = format!("파일 저장 실패: {}", e);
-            println!("에러: {}", error_msg);
             log::error!("파일 저장 실패: file_id={}, error={}", file_id, e);
             Err(error_msg)
         }
@@ -125,7 +117,7 @@ pub fn detect_file_mime_type(
     // 파일 데이터의 일부만 읽어서 MIME 타입 감지 (성능 최적화)
     const SAMPLE_SIZE: usize = 1024; // 첫 1KB만 읽기
     
-    let app_state = app_state.lock().map_err(|e| format!("상태 잠금 실패: {}", e))?;
+    let app_state = lock_unlocked(&app_state)?;
     let mut file_service = app_state.file_service.lock().map_err(|e| format!("파일 서비스 잠금 실패: {}", e))?;
     let viewer_service = app_state.viewer_service.lock().map_err(|e| format!("뷰어 서비스 잠금 실패: {}", e))?;
     
@@ -153,15 +145,23 @@ pub fn detect_file_mime_type(
 /// # 매개변수
 /// * `file_name` - 파일명
 /// * `mime_type` - MIME 타입 (선택사항)
+/// * `app_state` - 애플리케이션 상태
 /// 
 /// # 반환값
-/// * `String` - 뷰어 타입 ("text", "image", "media", "unsupported")
+/// * `Result<String, String>` - 뷰어 타입 ("text", "image", "media", "unsupported")
 #[tauri::command]
 pub fn get_file_viewer_type(
     file_name: String,
     mime_type: Option<String>,
-) -> String {
-    let ext = get_file_extension(&file_name);
+    app_state: State<'_, Mutex<AppState>>,
+) -> Result<String, String> {
+    lock_unlocked(&app_state)?;
+    Ok(detect_viewer_type(&file_name, mime_type.as_deref()))
+}
+
+/// 파일명과 MIME 타입으로 뷰어 타입을 판단합니다.
+fn detect_viewer_type(file_name: &str, mime_type: Option<&str>) -> String {
+    let ext = get_file_extension(file_name);
     
     // 텍스트 파일 확장자
     const TEXT_EXTENSIONS: &[&str] = &[
@@ -209,7 +209,7 @@ pub fn get_file_viewer_type(
         }
         
         // 특별한 MIME 타입들
-        match mime.as_str() {
+        match mime {
             "application/json" | "application/xml" | "application/javascript" => {
                 return "text".to_string();
             }
@@ -224,12 +224,22 @@ pub fn get_file_viewer_type(
 /// 
 /// # 매개변수
 /// * `file_name` - 파일명
+/// * `app_state` - 애플리케이션 상태
 /// 
 /// # 반환값
-/// * `String` - 구문 강조 언어 ("javascript", "python", "rust", etc.)
+/// * `Result<String, String>` - 구문 강조 언어 ("javascript", "python", "rust", etc.)
 #[tauri::command]
-pub fn get_syntax_language(file_name: String) -> String {
-    let ext = get_file_extension(&file_name);
+pub fn get_syntax_language(
+    file_name: String,
+    app_state: State<'_, Mutex<AppState>>,
+) -> Result<String, String> {
+    lock_unlocked(&app_state)?;
+    Ok(detect_syntax_language(&file_name))
+}
+
+/// 파일명으로 구문 강조 언어를 판단합니다.
+fn detect_syntax_language(file_name: &str) -> String {
+    let ext = get_file_extension(file_name);
     let lower_name = file_name.to_lowercase();
     
     // 특수 파일명 처리
@@ -318,17 +328,17 @@ mod tests {
     
     #[test]
     fn test_viewer_type_detection() {
-        assert_eq!(get_file_viewer_type("test.txt".to_string(), None), "text");
-        assert_eq!(get_file_viewer_type("image.jpg".to_string(), None), "image");
-        assert_eq!(get_file_viewer_type("music.mp3".to_string(), None), "media");
-        assert_eq!(get_file_viewer_type("unknown.xyz".to_string(), None), "unsupported");
+        assert_eq!(detect_viewer_type("test.txt", None), "text");
+        assert_eq!(detect_viewer_type("image.jpg", None), "image");
+        assert_eq!(detect_viewer_type("music.mp3", None), "media");
+        assert_eq!(detect_viewer_type("unknown.xyz", None), "unsupported");
     }
     
     #[test]
     fn test_syntax_language_detection() {
-        assert_eq!(get_syntax_language("script.js".to_string()), "javascript");
-        assert_eq!(get_syntax_language("main.rs".to_string()), "rust");
-        assert_eq!(get_syntax_language("Dockerfile".to_string()), "dockerfile");
-        assert_eq!(get_syntax_language("config.yaml".to_string()), "yaml");
+        assert_eq!(detect_syntax_language("script.js"), "javascript");
+        assert_eq!(detect_syntax_language("main.rs"), "rust");
+        assert_eq!(detect_syntax_language("Dockerfile"), "dockerfile");
+        assert_eq!(detect_syntax_language("config.yaml"), "yaml");
     }
 }
\ No newline at end of file
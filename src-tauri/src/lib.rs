@@ -1,8 +1,9 @@
 // SecureVault Tauri 애플리케이션 메인 라이브러리
 // USB 포터블 보안 파일 매니저의 핵심 로직을 담당합니다.
 
-use std::sync::Mutex;
-use tauri::Manager;
+use std::path::PathBuf;
+use std::sync::{Mutex, RwLock};
+use tauri::{Emitter, Manager};
 
 // 모듈 선언
 pub mod commands;
@@ -28,6 +29,36 @@ pub use services::{
 /// 모든 볼트 작업의 표준 반환 타입으로 사용됩니다.
 pub type SecureVaultResult<T> = Result<T, VaultError>;
 
+/// 현재 열려 있는 볼트의 루트 디렉토리 (로그인 시 설정)
+static ACTIVE_VAULT_ROOT: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// 현재 볼트 루트 디렉토리를 반환합니다.
+///
+/// 로그인 시 인증된 PIN에 해당하는 볼트 루트가 설정되며, 그 전에는 현재 작업 디렉토리를 사용합니다.
+/// 파일/메타데이터 경로(`<루트>/.securevault/...`)는 모두 이 값을 기준으로 계산해야 합니다.
+///
+/// # 반환값
+/// * `PathBuf` - 볼트 루트 디렉토리
+pub fn vault_root() -> PathBuf {
+    if let Ok(root) = ACTIVE_VAULT_ROOT.read() {
+        if let Some(root) = root.as_ref() {
+            return root.clone();
+        }
+    }
+
+    std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
+}
+
+/// 현재 볼트 루트 디렉토리를 설정합니다.
+///
+/// # 매개변수
+/// * `root` - 볼트 루트 (None이면 현재 작업 디렉토리)
+pub fn set_vault_root(root: Option<PathBuf>) {
+    if let Ok(mut active_root) = ACTIVE_VAULT_ROOT.write() {
+        *active_root = root;
+    }
+}
+
 /// 애플리케이션 전역 상태
 /// 모든 서비스들을 관리하고 스레드 안전성을 보장합니다.
 #[derive(Debug)]
@@ -46,12 +77,16 @@ pub struct AppState {
     pub database_service: Mutex<services::database::DatabaseService>,
     /// 네트워크 가드 - 모든 네트워크 접근 차단
     pub network_guard: NetworkGuard,
+    /// 프로세스 보호 결과 - 코어 덤프/ptrace 차단, 메모리 고정 여부
+    pub process_hardening: services::process_guard::ProcessHardeningReport,
     /// 압축 서비스 - 파일 압축/해제 담당
     pub compression_service: Mutex<services::compression::CompressionService>,
     /// 뷰어 서비스 - 파일 뷰어 기능 담당
     pub viewer_service: Mutex<services::viewer::ViewerService>,
     /// 업로드 관리자 - 백그라운드 파일 업로드 관리
     pub upload_manager: services::upload_manager::UploadManager,
+    /// 재암호화 관리자 - 볼트 전체 재암호화 작업 관리
+    pub reencryption_manager: services::reencryption::ReencryptionManager,
 }
 
 impl AppState {
@@ -60,32 +95,22 @@ impl AppState {
     /// # 반환값
     /// * `Self` - 초기화된 애플리케이션 상태
     pub fn new() -> Self {
+        // 키를 다루기 전에 코어 덤프와 디버거 연결을 막음 (디버거가 연결되어 있으면 여기서 종료될 수 있음)
+        let process_hardening = services::process_guard::initialize_process_guard();
+
         // 네트워크 가드를 가장 먼저 초기화하여 모든 네트워크 접근 차단
         let network_guard = services::network_guard::initialize_network_guard();
 
-        // 데이터베이스 서비스 초기화
-        let mut database_service = services::database::DatabaseService::new();
-
-        // 현재 디렉토리를 볼트 경로로 사용
-        let vault_path = std::env::current_dir()
-            .unwrap_or_else(|_| std::path::PathBuf::from("."))
-            .to_string_lossy()
-            .to_string();
-
-        if let Err(e) = database_service.initialize(&vault_path) {
-            log::error!("데이터베이스 초기화 실패: {}", e);
-        } else {
-            log::info!("데이터베이스 초기화 완료");
-        }
-
         Self {
             auth_service: AuthService::new(),
             crypto_service: CryptoService::new(),
             recovery_service: services::recovery::RecoveryService::new(),
             folder_service: services::folder::FolderService::new(),
             file_service: Mutex::new(services::file::FileService::new()),
-            database_service: Mutex::new(database_service),
+            // 메타데이터 DB는 암호화되어 있으므로 잠금 해제 후 볼트 키로 엶
+            database_service: Mutex::new(services::database::DatabaseService::new()),
             network_guard,
+            process_hardening,
             compression_service: Mutex::new(
                 services::compression::CompressionService::new_with_defaults(),
             ),
@@ -93,17 +118,108 @@ impl AppState {
                 services::file::FileService::new(),
             )),
             upload_manager: services::upload_manager::UploadManager::new(),
+            reencryption_manager: services::reencryption::ReencryptionManager::new(),
+        }
+    }
+
+    /// 볼트를 잠그고 메모리에 있는 볼트 키를 모두 제거합니다.
+    ///
+    /// 암호화 서비스와 파일 서비스(업로드 스레드의 복제본 포함)의 키를 0으로 덮어쓰고
+    /// 메타데이터 DB 연결을 닫습니다. 진행 중인 업로드를 취소하고 재암호화 작업을 일시 중지한 뒤 세션을 종료하며,
+    /// 로그 파일 기록을 멈추고 로그 키를 제거합니다.
+    pub fn lock_vault(&mut self) {
+        let cancelled = self.upload_manager.cancel_all_jobs();
+        if cancelled > 0 {
+            log::info!("볼트 잠금으로 업로드 작업 {}개를 취소했습니다.", cancelled);
+        }
+        if self.reencryption_manager.pause() {
+            log::info!("볼트 잠금으로 재암호화 작업을 일시 중지했습니다.");
+        }
+
+        self.crypto_service.clear_sensitive_data();
+        if let Ok(mut file_service) = self.file_service.lock() {
+            file_service.clear_master_key();
+            file_service.close_database();
         }
+        if let Ok(mut database_service) = self.database_service.lock() {
+            database_service.close();
+        }
+
+        if let Err(e) = self.auth_service.logout() {
+            log::error!("로그아웃 오류: {}", e);
+        }
+        set_vault_root(None);
+
+        log::info!("볼트가 잠겼습니다.");
+        services::log_sink::detach();
+    }
+
+    /// 유휴 시간과 세션 만료를 확인하여 필요하면 볼트를 잠급니다.
+    ///
+    /// 최근 사용자 활동이 있었다면 세션 활동 시각을 갱신합니다.
+    ///
+    /// # 반환값
+    /// * `Option<LockReason>` - 잠금을 수행했다면 그 사유
+    pub fn check_auto_lock(&mut self) -> Option<services::idle_lock::LockReason> {
+        use services::idle_lock::{self, LockReason};
+
+        if !self.crypto_service.has_master_key() {
+            return None;
+        }
+
+        let reason = if idle_lock::is_idle(self.auth_service.auto_lock_seconds()) {
+            LockReason::Idle
+        } else if self.auth_service.is_session_expired() {
+            LockReason::SessionExpired
+        } else {
+            if idle_lock::idle_seconds() <= idle_lock::IDLE_CHECK_INTERVAL.as_secs() {
+                self.auth_service.refresh_activity();
+            }
+
+            // PIN 재확인 시간이 지나면 파일 서비스의 최고 보안 파일 키도 제거
+            if !self.auth_service.has_recent_step_up() {
+                if let Ok(file_service) = self.file_service.lock() {
+                    file_service.set_critical_key(None);
+                }
+            }
+            return None;
+        };
+
+        self.lock_vault();
+        Some(reason)
     }
 }
 
+/// 유휴 잠금 감시 스레드를 시작합니다.
+///
+/// 주기적으로 `AppState::check_auto_lock`을 호출하고, 잠금이 일어나면
+/// 프론트엔드에 `vault-locked` 이벤트를 보냅니다.
+fn spawn_idle_lock_watcher(app_handle: tauri::AppHandle) {
+    use services::idle_lock::{VaultLockedPayload, IDLE_CHECK_INTERVAL, VAULT_LOCKED_EVENT};
+
+    std::thread::spawn(move || loop {
+        std::thread::sleep(IDLE_CHECK_INTERVAL);
+
+        let state = app_handle.state::<Mutex<AppState>>();
+        let reason = match state.lock() {
+            Ok(mut app_state) => app_state.check_auto_lock(),
+            Err(_) => None,
+        };
+
+        if let Some(reason) = reason {
+            log::info!("자동 잠금: {:?}", reason);
+            let _ = app_handle.emit(VAULT_LOCKED_EVENT, VaultLockedPayload { reason });
+        }
+    });
+}
+
 /// Tauri 애플리케이션 실행 함수
 ///
 /// 애플리케이션을 초기화하고 모든 서비스와 커맨드를 등록합니다.
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    // 로깅 초기화
-    env_logger::init();
+    // 로깅 초기화 (가림 처리 후 볼트가 열리면 `.securevault/logs`에 암호화하여 기록)
+    services::log_sink::init();
 
     // 볼트 디렉토리 초기화 (애플리케이션 시작 전)
     if let Err(e) = initialize_vault_directory_simple() {
@@ -212,10 +328,13 @@ pub fn run() {
                 log::error!("메인 윈도우를 찾을 수 없습니다.");
             }
 
+            // 유휴 잠금 감시 시작
+            spawn_idle_lock_watcher(app.handle().clone());
+
             Ok(())
         })
-        // Tauri 커맨드 등록
-        .invoke_handler(tauri::generate_handler![
+        // Tauri 커맨드 등록 (모든 커맨드 호출을 사용자 활동으로 기록)
+        .invoke_handler(with_activity_tracking(tauri::generate_handler![
             // 기본 테스트 커맨드
             greet,
             // 인증 관련 커맨드
@@ -228,20 +347,41 @@ pub fn run() {
             commands::auth::get_session_remaining_time,
             commands::auth::change_pin,
             commands::auth::generate_new_recovery_key,
+            commands::auth::generate_recovery_sheet,
+            commands::auth::export_recovery_sheet,
+            commands::auth::get_recovery_key_status,
             commands::auth::authenticate_recovery_key,
             commands::auth::get_auto_logout_time,
             commands::auth::set_auto_logout_time,
+            commands::auth::get_auto_lock_minutes,
+            commands::auth::set_auto_lock_minutes,
+            commands::auth::step_up_authentication,
+            commands::auth::set_critical_passphrase,
+            commands::auth::change_critical_passphrase,
+            commands::auth::unlock_critical_files,
+            commands::auth::has_critical_passphrase,
+            commands::auth::set_duress_pin,
+            commands::auth::remove_duress_pin,
+            commands::auth::has_duress_pin,
+            commands::auth::set_panic_pin,
+            commands::auth::remove_panic_pin,
+            commands::auth::has_panic_pin,
+            commands::auth::get_pin_requirement,
+            commands::auth::set_pin_requirement,
+            commands::auth::is_pin_upgrade_required,
+            commands::auth::generate_key_file,
+            commands::auth::enroll_key_file,
+            commands::auth::replace_key_file,
+            commands::auth::remove_key_file,
+            commands::auth::has_key_file,
+            commands::auth::reharden_kdf,
             // 복구 키 관련 커맨드 (C# SecurityService 포팅)
             commands::recovery::generate_recovery_key,
+            commands::recovery::split_recovery_key,
             commands::recovery::hash_recovery_key,
             commands::recovery::verify_recovery_key,
-            commands::recovery::derive_key_from_recovery_key,
-            commands::recovery::verify_and_derive_key,
             commands::recovery::validate_recovery_key_format,
             commands::recovery::get_recovery_key_info,
-            commands::recovery::mark_recovery_key_used,
-            commands::recovery::clear_recovery_key,
-            commands::recovery::authenticate_with_recovery_key,
             // 파일 관리 관련 커맨드 (C# FileManagerService 포팅)
             commands::files::get_files_in_folder,
             commands::files::add_file_to_vault,
@@ -249,6 +389,8 @@ pub fn run() {
             commands::files::delete_file_from_vault,
             commands::files::rename_file_in_vault,
             commands::files::move_file,
+            commands::files::set_file_security_level,
+            commands::files::migrate_legacy_files,
             commands::files::extract_file_from_vault,
             commands::files::export_file_from_vault,
             commands::files::export_file,
@@ -262,7 +404,6 @@ pub fn run() {
             commands::files::upload_file_chunk,
             commands::files::cancel_chunked_upload,
             // 폴더 관리 관련 커맨드 (C# FolderManager + MainForm 포팅)
-            commands::folders::test_create_folder,
             commands::folders::create_folder,
             commands::folders::delete_folder,
             commands::folders::rename_folder,
@@ -288,7 +429,6 @@ pub fn run() {
             commands::database::update_folder_metadata,
             commands::database::remove_folder_metadata,
             // 암호화 관련 커맨드
-            commands::crypto::derive_master_key_from_pin,
             commands::crypto::generate_salt,
             commands::crypto::generate_crypto_recovery_key,
             commands::crypto::encrypt_data_csharp_compatible,
@@ -298,9 +438,11 @@ pub fn run() {
             commands::crypto::has_master_key,
             commands::crypto::clear_sensitive_data,
             commands::crypto::get_encryption_algorithm,
+            commands::crypto::set_encryption_algorithm,
             // 보안 관련 커맨드
             commands::security::get_security_status,
             commands::security::check_network_access,
+            commands::security::get_app_logs,
             // 볼트 관련 커맨드
             commands::vault::initialize_vault,
             commands::vault::get_vault_config,
@@ -329,11 +471,28 @@ pub fn run() {
             commands::upload::cancel_upload,
             commands::upload::get_upload_status,
             commands::upload::get_all_uploads,
-        ])
+            commands::reencryption::start_reencryption,
+            commands::reencryption::resume_reencryption,
+            commands::reencryption::pause_reencryption,
+            commands::reencryption::get_reencryption_status,
+        ]))
         .run(tauri::generate_context!())
         .expect("SecureVault 애플리케이션 실행 중 오류가 발생했습니다.");
 }
 
+/// 커맨드 핸들러를 감싸 호출될 때마다 사용자 활동을 기록합니다.
+///
+/// 세션 활동 시각은 유휴 잠금 감시 스레드가 다음 확인 때 반영합니다.
+fn with_activity_tracking<F>(handler: F) -> impl Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static
+where
+    F: Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static,
+{
+    move |invoke| {
+        services::idle_lock::record_command(invoke.message.command());
+        handler(invoke)
+    }
+}
+
 /// 볼트 디렉토리를 초기화합니다 (간단한 버전).
 ///
 /// 애플리케이션 시작 시 필요한 디렉토리 구조를 생성합니다.
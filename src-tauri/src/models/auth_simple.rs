@@ -1,6 +1,10 @@
 // 간단한 인증 모델 테스트
 
+use super::encryption::KdfParams;
+use super::secret::SecretKey;
+use super::vault::BruteForceConfig;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use uuid::Uuid;
 
 /// PIN 복잡도 레벨
@@ -27,6 +31,10 @@ pub enum PinValidationResult {
     AccountLocked(u64),
     /// PIN 만료
     Expired,
+    /// 키 파일이 등록되어 있으나 제공되지 않음
+    KeyFileRequired,
+    /// 제공된 키 파일이 등록된 키 파일과 다름
+    KeyFileMismatch,
 }
 
 /// 복구 키 검증 결과
@@ -40,6 +48,31 @@ pub enum RecoveryKeyValidationResult {
     InvalidFormat,
     /// 복구 키 비활성화 상태
     Deactivated,
+    /// 새 복구 키 발급으로 교체된 이전 복구 키
+    Superseded,
+}
+
+/// 복구 키 로그인 결과
+#[derive(Debug)]
+pub enum RecoveryLoginResult {
+    /// 로그인 성공. 사용한 복구 키는 이미 `new_recovery_key`로 교체되어 더 이상 쓸 수 없음
+    Unlocked {
+        /// 볼트 데이터 키
+        data_key: SecretKey,
+        /// 새로 발급한 24단어 복구 키
+        new_recovery_key: String,
+    },
+    /// 로그인 거부
+    Rejected(RecoveryKeyValidationResult),
+}
+
+/// 볼트 데이터 키 교체 결과
+#[derive(Debug)]
+pub struct DataKeyRotation {
+    /// 새 볼트 데이터 키
+    pub data_key: Arc<SecretKey>,
+    /// 새로 발급한 24단어 복구 키 (복구 키가 있던 경우, 이전 복구 키는 더 이상 쓸 수 없음)
+    pub recovery_key: Option<String>,
 }
 
 /// 인증 방법
@@ -71,6 +104,33 @@ pub enum AuthState {
 pub struct PinInfo {
     pub hash: String,
     pub salt: Vec<u8>,
+    /// PIN 검증자 해시 매개변수 (필드가 없는 기존 데이터는 SHA-256)
+    #[serde(default = "KdfParams::legacy_pin_hash")]
+    pub hash_params: KdfParams,
+    /// 마스터 키 유도 매개변수 (keys.json이 없는 기존 볼트 이전용, 필드가 없으면 PBKDF2 100,000회)
+    #[serde(default = "KdfParams::legacy_master_key")]
+    pub key_params: KdfParams,
+    /// 숫자 외의 문자가 포함된 패스프레이즈인지 여부 (필드가 없는 기존 데이터는 숫자 PIN)
+    #[serde(default)]
+    pub is_passphrase: bool,
+    /// 검증자 계산에 키 파일이 함께 사용되었는지 여부 (필드가 없는 기존 데이터는 PIN만 사용)
+    #[serde(default)]
+    pub key_file: bool,
+}
+
+/// KEK로 래핑된 볼트 데이터 키
+/// KEK는 PIN 또는 복구 키에서 `params`/`salt`로 유도됩니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrappedKey {
+    /// KEK 유도용 32바이트 솔트
+    pub salt: Vec<u8>,
+    /// KEK 유도 매개변수
+    pub params: KdfParams,
+    /// 래핑된 데이터 키 (Base64, IV + 암호문 + 태그)
+    pub wrapped_key: String,
+    /// KEK 유도에 키 파일이 함께 사용되었는지 여부
+    #[serde(default)]
+    pub key_file: bool,
 }
 
 /// 간단한 복구 키 정보 (auth_simple 전용)
@@ -78,6 +138,55 @@ pub struct PinInfo {
 pub struct SimpleRecoveryKeyInfo {
     pub hash: String,
     pub is_active: bool,
+    /// 발급 시각 (Unix 초, 이전 버전에서 만든 복구 키는 없음)
+    #[serde(default)]
+    pub created_at: Option<u64>,
+    /// 마지막 사용 시각 (Unix 초)
+    #[serde(default)]
+    pub last_used_at: Option<u64>,
+    /// 사용 횟수
+    #[serde(default)]
+    pub use_count: u32,
+    /// 사용된 뒤 아직 새 복구 키로 교체되지 않았는지 여부
+    #[serde(default)]
+    pub rotation_required: bool,
+}
+
+/// 교체되어 더 이상 사용할 수 없는 복구 키 기록
+///
+/// 래핑된 데이터 키는 교체 시 덮어쓰거나 삭제하므로 이 키로는 볼트를 열 수 없습니다.
+/// 해시만 남겨 두어 이전 키를 입력하면 교체되었음을 알려줍니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupersededRecoveryKey {
+    /// 교체된 복구 키의 SHA-256 해시 (Base64)
+    pub hash: String,
+    /// 발급 시각 (Unix 초)
+    pub created_at: Option<u64>,
+    /// 교체 시각 (Unix 초)
+    pub superseded_at: u64,
+    /// 교체 전까지의 사용 횟수
+    pub use_count: u32,
+}
+
+/// 복구 키 수명 주기 상태 (키 값 제외)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryKeyStatus {
+    /// 복구 키 발급 여부
+    pub has_key: bool,
+    /// 활성 상태 여부
+    pub is_active: bool,
+    /// 발급 시각 (Unix 초)
+    pub created_at: Option<u64>,
+    /// 마지막 사용 시각 (Unix 초)
+    pub last_used_at: Option<u64>,
+    /// 사용 횟수
+    pub use_count: u32,
+    /// 사용 후 교체가 필요한지 여부
+    pub rotation_required: bool,
+    /// 지금까지 교체된 복구 키 수 (최근 기록만 보관)
+    pub superseded_count: usize,
+    /// 마지막으로 교체된 시각 (Unix 초)
+    pub last_superseded_at: Option<u64>,
 }
 
 /// 간단한 인증 세션
@@ -100,12 +209,23 @@ pub struct BruteForceProtection {
     pub lockout_until: Option<u64>,
     pub max_attempts: u32,
     pub base_lockout_seconds: u64,
+    /// 한도 초과 후 실패할 때마다 잠금 시간을 두 배로 늘릴지 여부
+    pub exponential_backoff: bool,
+    /// 잠금 시간 상한 (초)
+    pub max_lockout_seconds: u64,
 }
 
 // 간단한 구현들
 impl PinInfo {
     pub fn new(hash: String, salt: Vec<u8>, _complexity: PinComplexity) -> Self {
-        Self { hash, salt }
+        Self {
+            hash,
+            salt,
+            hash_params: KdfParams::default(),
+            key_params: KdfParams::default(),
+            is_passphrase: false,
+            key_file: false,
+        }
     }
 
     pub fn is_expired(&self) -> bool {
@@ -115,19 +235,51 @@ impl PinInfo {
 
 impl SimpleRecoveryKeyInfo {
     pub fn new(hash: String) -> Self {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
         Self {
             hash,
             is_active: true,
+            created_at: Some(now),
+            last_used_at: None,
+            use_count: 0,
+            rotation_required: false,
         }
     }
 
+    /// 사용 기록을 남기고 교체가 필요함을 표시합니다.
     pub fn record_usage(&mut self) {
-        // 간단한 구현: 아무것도 하지 않음
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        self.use_count = self.use_count.saturating_add(1);
+        self.last_used_at = Some(now);
+        self.rotation_required = true;
     }
 
     pub fn deactivate(&mut self) {
         self.is_active = false;
     }
+
+    /// 교체 기록으로 변환합니다.
+    pub fn supersede(&self) -> SupersededRecoveryKey {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        SupersededRecoveryKey {
+            hash: self.hash.clone(),
+            created_at: self.created_at,
+            superseded_at: now,
+            use_count: self.use_count,
+        }
+    }
 }
 
 impl AuthSession {
@@ -194,18 +346,44 @@ impl AuthSession {
 
 impl BruteForceProtection {
     pub fn new() -> Self {
+        Self::from_config(&BruteForceConfig::default())
+    }
+
+    /// 볼트 보안 설정의 브루트포스 정책으로 생성합니다.
+    pub fn from_config(config: &BruteForceConfig) -> Self {
         Self {
             failed_attempts: 0,
             last_failure_time: None,
             is_locked: false,
             lockout_until: None,
-            max_attempts: 5,
-            base_lockout_seconds: 1800,
+            max_attempts: config.max_attempts.max(1),
+            base_lockout_seconds: config.base_lockout_seconds,
+            exponential_backoff: config.exponential_backoff,
+            max_lockout_seconds: config.max_lockout_seconds,
+        }
+    }
+
+    /// 현재 실패 횟수에 해당하는 잠금 시간(초)을 계산합니다.
+    ///
+    /// 지수적 백오프가 켜져 있으면 한도를 넘긴 실패마다 기본 잠금 시간이 두 배가 되며,
+    /// 어떤 경우에도 `max_lockout_seconds`를 넘지 않습니다.
+    pub fn lockout_duration_seconds(&self) -> u64 {
+        if self.failed_attempts < self.max_attempts {
+            return 0;
         }
+
+        let duration = if self.exponential_backoff {
+            let exponent = (self.failed_attempts - self.max_attempts).min(32);
+            self.base_lockout_seconds.saturating_mul(1u64 << exponent)
+        } else {
+            self.base_lockout_seconds
+        };
+
+        duration.min(self.max_lockout_seconds)
     }
 
     pub fn record_failure(&mut self) {
-        self.failed_attempts += 1;
+        self.failed_attempts = self.failed_attempts.saturating_add(1);
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -214,10 +392,22 @@ impl BruteForceProtection {
 
         if self.failed_attempts >= self.max_attempts {
             self.is_locked = true;
-            self.lockout_until = Some(now + self.base_lockout_seconds);
+            self.lockout_until = Some(now + self.lockout_duration_seconds());
         }
     }
 
+    /// 저장된 기록이 변조되거나 삭제된 경우 최대 잠금 상태로 전환합니다.
+    pub fn lock_for_tampering(&mut self) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        self.failed_attempts = self.failed_attempts.max(self.max_attempts);
+        self.last_failure_time = Some(now);
+        self.is_locked = true;
+        self.lockout_until = Some(now + self.max_lockout_seconds);
+    }
+
     pub fn record_success(&mut self) {
         self.failed_attempts = 0;
         self.last_failure_time = None;
@@ -55,6 +55,58 @@ impl EncryptionAlgorithm {
             Self::ChaCha20Poly1305 => 16, // 128 bits
         }
     }
+
+    /// 암호화 파일 컨테이너 헤더에 기록되는 알고리즘 ID를 반환합니다.
+    ///
+    /// # 반환값
+    /// * `u8` - 알고리즘 ID (값은 파일 형식의 일부이므로 바꾸면 안 됨)
+    pub fn container_id(&self) -> u8 {
+        match self {
+            Self::AES256GCM => 1,
+            Self::ChaCha20Poly1305 => 2,
+        }
+    }
+
+    /// 컨테이너 헤더의 알고리즘 ID로부터 알고리즘을 찾습니다.
+    ///
+    /// # 매개변수
+    /// * `id` - 알고리즘 ID
+    ///
+    /// # 반환값
+    /// * `Option<Self>` - 알 수 없는 ID면 None
+    pub fn from_container_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Self::AES256GCM),
+            2 => Some(Self::ChaCha20Poly1305),
+            _ => None,
+        }
+    }
+
+    /// 프론트엔드에 표시하고 주고받는 알고리즘 이름을 반환합니다.
+    ///
+    /// # 반환값
+    /// * `&str` - 알고리즘 이름
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::AES256GCM => "AES-256-GCM",
+            Self::ChaCha20Poly1305 => "ChaCha20-Poly1305",
+        }
+    }
+
+    /// 알고리즘 이름으로부터 알고리즘을 찾습니다.
+    ///
+    /// # 매개변수
+    /// * `name` - 알고리즘 이름 (`name`의 반환값)
+    ///
+    /// # 반환값
+    /// * `Option<Self>` - 알 수 없는 이름이면 None
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "AES-256-GCM" => Some(Self::AES256GCM),
+            "ChaCha20-Poly1305" => Some(Self::ChaCha20Poly1305),
+            _ => None,
+        }
+    }
 }
 
 /// 암호화 메타데이터
@@ -223,6 +275,8 @@ impl EncryptedData {
 
 /// 키 유도 매개변수
 /// PBKDF2 등의 키 유도 함수에서 사용되는 매개변수입니다.
+/// 
+/// PIN/암호에서 키를 유도할 때는 볼트별로 보정되어 저장되는 `KdfParams`를 사용합니다.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyDerivationParams {
     /// 솔트 값
@@ -254,44 +308,162 @@ impl KeyDerivationParams {
             hash_algorithm: "SHA256".to_string(),
         }
     }
+}
+
+/// 키 유도 함수 알고리즘
+/// 저장된 PIN 검증자와 마스터 키가 어떤 방식으로 만들어졌는지 기록합니다.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum KdfAlgorithm {
+    /// SHA-256 단일 해시 (레거시 PIN 검증자, 검증 전용)
+    Sha256,
     
-    /// 고성능 키 유도 매개변수를 생성합니다.
-    /// 
-    /// 성능이 중요한 상황에서 사용하며, 보안 수준을 약간 낮춥니다.
+    /// PBKDF2-HMAC-SHA256 (레거시 마스터 키 유도, 검증 전용)
+    Pbkdf2Sha256,
+    
+    /// Argon2id (기본값)
+    /// GPU/ASIC 무차별 대입에 강한 메모리 하드 함수
+    Argon2id,
+}
+
+/// 버전이 기록되는 키 유도 매개변수
+/// 
+/// 결과값과 함께 저장되므로, 나중에 비용을 올리거나 알고리즘을 바꿔도
+/// 기존 값을 그대로 검증한 뒤 다음 로그인에서 새 매개변수로 다시 만들 수 있습니다.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KdfParams {
+    /// 키 유도 알고리즘
+    pub algorithm: KdfAlgorithm,
+    
+    /// 메모리 비용 (KiB, Argon2id 전용)
+    pub memory_kib: u32,
+    
+    /// 시간 비용 (Argon2id 패스 수 또는 PBKDF2 반복 횟수)
+    pub iterations: u32,
+    
+    /// 병렬도 (Argon2id 전용)
+    pub parallelism: u32,
+}
+
+impl KdfParams {
+    /// Argon2id 기본 메모리 비용 (64MiB)
+    pub const ARGON2_MEMORY_KIB: u32 = 64 * 1024;
+    
+    /// Argon2id 기본 시간 비용
+    pub const ARGON2_ITERATIONS: u32 = 3;
+    
+    /// Argon2id 기본 병렬도
+    pub const ARGON2_PARALLELISM: u32 = 1;
+    
+    /// 보정 시 사용하는 Argon2id 최대 메모리 비용 (256MiB, 사양이 낮은 PC에서도 열 수 있도록 제한)
+    pub const ARGON2_MAX_MEMORY_KIB: u32 = 256 * 1024;
+    
+    /// 보정 시 사용하는 Argon2id 최대 시간 비용
+    pub const ARGON2_MAX_ITERATIONS: u32 = 32;
+    
+    /// Argon2id 매개변수를 생성합니다.
     /// 
     /// # 매개변수
-    /// * `salt` - 솔트 값
+    /// * `memory_kib` - 메모리 비용 (KiB)
+    /// * `iterations` - 시간 비용
+    /// * `parallelism` - 병렬도
     /// 
     /// # 반환값
-    /// * `Self` - 고성능 키 유도 매개변수
-    pub fn fast_with_salt(salt: Vec<u8>) -> Self {
+    /// * `Self` - Argon2id 매개변수
+    pub fn argon2id(memory_kib: u32, iterations: u32, parallelism: u32) -> Self {
         Self {
-            salt,
-            iterations: 50_000,       // 성능 우선
-            key_length: 32,
-            hash_algorithm: "SHA256".to_string(),
+            algorithm: KdfAlgorithm::Argon2id,
+            memory_kib,
+            iterations,
+            parallelism,
         }
     }
     
-    /// 고보안 키 유도 매개변수를 생성합니다.
+    /// 레거시 PIN 검증자 매개변수 (SHA-256(PIN + 솔트))
     /// 
-    /// 보안이 최우선인 상황에서 사용하며, 성능을 희생합니다.
+    /// 매개변수 필드가 없는 기존 auth.json을 읽을 때 사용됩니다.
+    pub fn legacy_pin_hash() -> Self {
+        Self {
+            algorithm: KdfAlgorithm::Sha256,
+            memory_kib: 0,
+            iterations: 1,
+            parallelism: 0,
+        }
+    }
+    
+    /// 레거시 마스터 키 매개변수 (PBKDF2-HMAC-SHA256, 100,000회)
+    /// 
+    /// 매개변수 필드가 없는 기존 auth.json을 읽을 때 사용됩니다.
+    pub fn legacy_master_key() -> Self {
+        Self {
+            algorithm: KdfAlgorithm::Pbkdf2Sha256,
+            memory_kib: 0,
+            iterations: 100_000,
+            parallelism: 0,
+        }
+    }
+    
+    /// 현재 기본값보다 약한 매개변수인지 확인합니다.
+    /// 
+    /// # 반환값
+    /// * `bool` - 재해시가 필요하면 true
+    pub fn needs_upgrade(&self) -> bool {
+        self.algorithm != KdfAlgorithm::Argon2id
+            || self.memory_kib < Self::ARGON2_MEMORY_KIB
+            || self.iterations < Self::ARGON2_ITERATIONS
+    }
+    
+    /// 다른 매개변수보다 무차별 대입 비용이 낮은지 확인합니다.
+    /// 
+    /// Argon2id가 아니거나, 메모리 비용 또는 전체 비용(메모리 × 반복 횟수)이 더 작으면
+    /// 약한 것으로 봅니다.
     /// 
     /// # 매개변수
-    /// * `salt` - 솔트 값
+    /// * `other` - 비교할 매개변수
     /// 
     /// # 반환값
-    /// * `Self` - 고보안 키 유도 매개변수
-    pub fn secure_with_salt(salt: Vec<u8>) -> Self {
-        Self {
-            salt,
-            iterations: 200_000,      // 보안 우선
-            key_length: 32,
-            hash_algorithm: "SHA256".to_string(),
+    /// * `bool` - `other`보다 약하면 true
+    pub fn is_weaker_than(&self, other: &KdfParams) -> bool {
+        if self.algorithm != KdfAlgorithm::Argon2id {
+            return other.algorithm == KdfAlgorithm::Argon2id;
         }
+        if other.algorithm != KdfAlgorithm::Argon2id {
+            return false;
+        }
+        
+        let cost = u64::from(self.memory_kib) * u64::from(self.iterations);
+        let other_cost = u64::from(other.memory_kib) * u64::from(other.iterations);
+        self.memory_kib < other.memory_kib || cost < other_cost
+    }
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self::argon2id(
+            Self::ARGON2_MEMORY_KIB,
+            Self::ARGON2_ITERATIONS,
+            Self::ARGON2_PARALLELISM,
+        )
     }
 }
 
+/// 키 유도 비용 보정 결과
+/// 
+/// 현재 PC에서 측정한 시간을 기준으로 고른 매개변수와 예상 잠금 해제 시간을 담습니다.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KdfCalibration {
+    /// 선택된 키 유도 매개변수 (기본값보다 약해지지 않음)
+    pub params: KdfParams,
+    
+    /// 선택된 매개변수로 키를 한 번 유도하는 데 걸린 시간 (밀리초)
+    pub derivation_ms: u64,
+    
+    /// 목표 잠금 해제 시간 (밀리초)
+    pub target_unlock_ms: u64,
+    
+    /// 예상 잠금 해제 시간 (밀리초, 로그인 한 번에 필요한 키 유도 횟수 반영)
+    pub estimated_unlock_ms: u64,
+}
+
 /// 메모리 보안 유틸리티
 /// 민감한 데이터를 메모리에서 안전하게 제거하는 기능을 제공합니다.
 pub struct SecureMemory;
@@ -36,6 +36,12 @@ pub enum AuthError {
 
     #[error("잘못된 입력입니다: {0}")]
     InvalidInput(String),
+
+    #[error("키 파일이 필요합니다. 등록된 키 파일을 선택해주세요.")]
+    KeyFileRequired,
+
+    #[error("키 파일이 올바르지 않습니다. 등록된 키 파일을 선택해주세요.")]
+    KeyFileMismatch,
 }
 
 /// 암호화 관련 에러
@@ -74,6 +80,21 @@ pub enum CryptoError {
 
     #[error("{0}")]
     InvalidKey(String),
+
+    #[error("암호화 파일 형식이 올바르지 않습니다: {0}")]
+    InvalidContainer(String),
+
+    #[error("지원하지 않는 암호화 파일 버전입니다: {0}")]
+    UnsupportedContainerVersion(u16),
+
+    #[error("암호화된 파일이 요청한 파일과 일치하지 않습니다.")]
+    FileIdMismatch,
+
+    #[error("파일이 다른 키로 암호화되어 있습니다.")]
+    KeyIdMismatch,
+
+    #[error("파일 키 커밋 검증에 실패했습니다. 다른 키로 암호화되었거나 헤더가 변조되었습니다.")]
+    KeyCommitmentMismatch,
 }
 
 /// 파일 관리 관련 에러
@@ -179,8 +200,30 @@ pub enum VaultError {
 
     #[error("작업이 취소되었습니다.")]
     OperationCancelled,
+
+    /// 볼트가 잠겨 있음 (메시지는 `VAULT_LOCKED_CODE`로 시작)
+    #[error("[VAULT_LOCKED] 볼트가 잠겨 있습니다. 다시 로그인해주세요.")]
+    Locked,
+
+    /// 높은 보안 등급 파일에 접근하려면 PIN 재확인이 필요함
+    #[error("[STEP_UP_REQUIRED] 이 파일을 열려면 PIN을 다시 입력해야 합니다.")]
+    StepUpRequired,
+
+    /// 최고 보안 등급 파일에 접근하려면 별도 보안 암호가 필요함
+    #[error("[CRITICAL_KEY_REQUIRED] 이 파일을 열려면 최고 보안 암호를 입력해야 합니다.")]
+    CriticalKeyRequired,
 }
 
+/// 볼트 잠금 오류 문자열의 식별 코드
+/// 프론트엔드는 커맨드 오류가 이 코드로 시작하면 로그인 화면으로 이동합니다.
+pub const VAULT_LOCKED_CODE: &str = "[VAULT_LOCKED]";
+
+/// PIN 재확인 필요 오류 문자열의 식별 코드
+pub const STEP_UP_REQUIRED_CODE: &str = "[STEP_UP_REQUIRED]";
+
+/// 최고 보안 암호 필요 오류 문자열의 식별 코드
+pub const CRITICAL_KEY_REQUIRED_CODE: &str = "[CRITICAL_KEY_REQUIRED]";
+
 /// 데이터베이스 관련 에러
 #[derive(Error, Debug)]
 pub enum DatabaseError {
@@ -284,6 +327,9 @@ impl SecureVaultError {
                 format!("보안을 위해 {}초 후 다시 시도해주세요.", duration.as_secs())
             }
             SecureVaultError::Auth(AuthError::InvalidInput(msg)) => msg.clone(),
+            SecureVaultError::Auth(
+                err @ (AuthError::KeyFileRequired | AuthError::KeyFileMismatch),
+            ) => err.to_string(),
             SecureVaultError::File(FileError::FileNotFound(_)) => {
                 "파일을 찾을 수 없습니다.".to_string()
             }
@@ -407,6 +453,9 @@ impl VaultError {
             VaultError::NotInitialized => {
                 "볼트가 초기화되지 않았습니다. 먼저 볼트를 설정해주세요.".to_string()
             }
+            VaultError::Locked | VaultError::StepUpRequired | VaultError::CriticalKeyRequired => {
+                self.to_string()
+            }
             VaultError::AlreadyInitialized => "볼트가 이미 초기화되어 있습니다.".to_string(),
             VaultError::InvalidConfiguration => {
                 "볼트 설정이 올바르지 않습니다. 설정을 확인해주세요.".to_string()
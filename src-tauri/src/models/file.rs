@@ -85,6 +85,10 @@ pub struct FileEntry {
     
     /// 파일 보안 등급 (C# SecurityLevel 호환)
     pub security_level: FileSecurityLevel,
+
+    /// 보안 등급 키로 래핑된 파일 데이터 키 (None이면 보안 등급 키로 직접 암호화된 이전 파일)
+    #[serde(default, skip_serializing)]
+    pub wrapped_key: Option<Vec<u8>>,
 }
 
 impl FileEntry {
@@ -142,6 +146,7 @@ impl FileEntry {
             custom_properties: HashMap::new(),
             access_count: 0,
             security_level: FileSecurityLevel::Normal,
+            wrapped_key: None,
         }
     }
 
@@ -205,6 +210,7 @@ impl FileEntry {
             custom_properties: HashMap::new(),
             access_count: 0,
             security_level: FileSecurityLevel::Normal,
+            wrapped_key: None,
         }
     }
     
@@ -270,13 +276,14 @@ impl FileEntry {
 }
 
 /// 파일 보안 등급 (C# FileSecurityLevel 포팅)
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+/// 값이 클수록 높은 등급이며, 비교 연산으로 더 높은 등급을 구할 수 있습니다.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum FileSecurityLevel {
     /// 일반 보안 등급
     Normal = 0,
-    /// 높은 보안 등급 (추가 인증 필요)
+    /// 높은 보안 등급 (열거나 내보낼 때 PIN 재확인 필요)
     High = 1,
-    /// 최고 보안 등급 (특별 권한 필요)
+    /// 최고 보안 등급 (PIN 재확인과 별도 최고 보안 암호로 유도한 키 필요)
     Critical = 2,
 }
 
@@ -482,6 +489,22 @@ impl Default for FileFilter {
     }
 }
 
+/// 이전 형식 파일 변환 결과
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LegacyMigrationReport {
+    /// 현재 컨테이너 형식으로 변환한 파일 수
+    pub migrated: u32,
+
+    /// 이미 현재 컨테이너 형식이던 파일 수
+    pub already_current: u32,
+
+    /// 키가 없어 건너뛴 파일 (최고 보안 키 미입력 등)
+    pub skipped: Vec<Uuid>,
+
+    /// 변환에 실패한 파일
+    pub failed: Vec<Uuid>,
+}
+
 /// 파일 크기를 사람이 읽기 쉬운 형태로 변환합니다.
 /// 
 /// # 매개변수
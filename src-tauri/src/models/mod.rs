@@ -10,13 +10,15 @@ pub mod auth_simple;
 pub mod encryption;
 pub mod recovery;
 pub mod compression;
+pub mod secret;
 
 // 모델들을 재내보내기 (모호한 재내보내기 방지)
-pub use error::{VaultError, DatabaseError, CryptoError, FileError, AuthError};
+pub use error::{VaultError, DatabaseError, CryptoError, FileError, AuthError, VAULT_LOCKED_CODE};
 pub use vault::*;
 pub use file::*;
 pub use folder::{FolderEntry, FolderTree, FolderStatus};
 pub use auth_simple::*;
 pub use encryption::*;
-pub use recovery::{RecoveryError, RecoveryKeyInfo, RecoveryVerificationResult};
-pub use compression::{CompressionLevel, CompressionResult};
\ No newline at end of file
+pub use recovery::{RecoveryError, RecoveryKeyInfo, RecoverySheetFormat, RecoveryVerificationResult};
+pub use compression::{CompressionLevel, CompressionResult};
+pub use secret::SecretKey;
\ No newline at end of file
@@ -84,4 +84,23 @@ impl RecoveryVerificationResult {
             master_key: None,
         }
     }
-}
\ No newline at end of file
+}
+/// 복구 키 시트 출력 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecoverySheetFormat {
+    /// A4 크기 SVG 문서 (한국어 안내문 포함)
+    Svg,
+    /// A4 150dpi 흑백 PNG 이미지 (내장 비트맵 글꼴 사용)
+    Png,
+}
+
+impl RecoverySheetFormat {
+    /// 형식에 맞는 MIME 타입
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Svg => "image/svg+xml",
+            Self::Png => "image/png",
+        }
+    }
+}
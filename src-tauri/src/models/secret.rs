@@ -0,0 +1,174 @@
+// 비밀 키 타입
+// 볼트 키, 파일 키 등 모든 256비트 키를 메모리에서 안전하게 다루기 위한 타입을 정의합니다.
+
+use super::SecureRandom;
+use zeroize::Zeroize;
+
+/// 키 길이 (바이트)
+pub const SECRET_KEY_LEN: usize = 32;
+
+/// 키 바이트를 담는 힙 영역
+///
+/// 한 페이지(4 KiB) 단위로 정렬하여 키마다 별도 페이지를 차지하게 합니다.
+/// `munlock`은 페이지 단위로 동작하므로, 다른 키와 페이지를 공유하면
+/// 한 키를 해제할 때 아직 사용 중인 키의 잠금까지 풀리기 때문입니다.
+#[repr(C, align(4096))]
+struct KeyPage {
+    bytes: [u8; SECRET_KEY_LEN],
+}
+
+/// 256비트 비밀 키
+///
+/// - `Clone`/`Copy`를 구현하지 않으므로 키를 복사하지 않고 빌려서 사용해야 합니다.
+///   여러 곳에서 같은 키를 가져야 하면 `Arc<SecretKey>`로 공유합니다.
+/// - 키 바이트는 힙에 있어 값을 이동해도 복사본이 남지 않고, 소멸 시 0으로 덮어씁니다.
+/// - Linux에서는 `mlock`으로 스왑되지 않도록 고정합니다.
+/// - `Debug` 출력에는 키 내용이 나타나지 않습니다.
+pub struct SecretKey {
+    page: Box<KeyPage>,
+}
+
+impl SecretKey {
+    /// 0으로 채워진 키를 만듭니다. 키 유도 함수의 출력 버퍼로 사용합니다.
+    pub fn zeroed() -> Self {
+        let page = Box::new(KeyPage {
+            bytes: [0u8; SECRET_KEY_LEN],
+        });
+        lock_memory(&page.bytes);
+        Self { page }
+    }
+
+    /// 새 랜덤 키를 생성합니다.
+    pub fn generate() -> Self {
+        let mut key = Self::zeroed();
+        SecureRandom::fill_bytes(key.expose_mut());
+        key
+    }
+
+    /// 바이트 배열을 키로 옮깁니다. 원본 배열은 0으로 덮어씁니다.
+    ///
+    /// # 매개변수
+    /// * `bytes` - 키 바이트
+    pub fn from_bytes(bytes: &mut [u8; SECRET_KEY_LEN]) -> Self {
+        let mut key = Self::zeroed();
+        key.expose_mut().copy_from_slice(bytes);
+        bytes.zeroize();
+        key
+    }
+
+    /// 바이트 슬라이스를 키로 복사합니다.
+    ///
+    /// # 반환값
+    /// * `Option<Self>` - 길이가 32바이트가 아니면 None
+    pub fn from_slice(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != SECRET_KEY_LEN {
+            return None;
+        }
+        let mut key = Self::zeroed();
+        key.expose_mut().copy_from_slice(bytes);
+        Some(key)
+    }
+
+    /// 키 바이트를 빌려줍니다. 반환된 참조를 복사해 보관하지 말아야 합니다.
+    pub fn expose(&self) -> &[u8; SECRET_KEY_LEN] {
+        &self.page.bytes
+    }
+
+    /// 키 바이트를 가변으로 빌려줍니다.
+    pub fn expose_mut(&mut self) -> &mut [u8; SECRET_KEY_LEN] {
+        &mut self.page.bytes
+    }
+
+    /// 키가 모두 0인지 확인합니다.
+    pub fn is_all_zero(&self) -> bool {
+        self.page.bytes.iter().fold(0u8, |acc, &b| acc | b) == 0
+    }
+}
+
+impl PartialEq for SecretKey {
+    /// 실행 시간이 키 내용에 따라 달라지지 않도록 모든 바이트를 비교합니다.
+    fn eq(&self, other: &Self) -> bool {
+        self.page
+            .bytes
+            .iter()
+            .zip(other.page.bytes.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+    }
+}
+
+impl Eq for SecretKey {}
+
+impl std::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretKey([REDACTED])")
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.page.bytes.zeroize();
+        unlock_memory(&self.page.bytes);
+    }
+}
+
+/// 키가 있는 메모리 페이지를 스왑되지 않도록 고정합니다.
+///
+/// `RLIMIT_MEMLOCK` 한도를 넘으면 실패할 수 있으며, 이 경우 고정 없이 계속 사용합니다.
+#[cfg(target_os = "linux")]
+fn lock_memory(bytes: &[u8; SECRET_KEY_LEN]) {
+    // SAFETY: 유효한 힙 메모리 범위를 전달하며, mlock은 메모리 내용을 변경하지 않음
+    let result = unsafe { libc::mlock(bytes.as_ptr().cast(), bytes.len()) };
+    if result != 0 {
+        log::debug!("키 메모리 고정 실패: {}", std::io::Error::last_os_error());
+    }
+}
+
+/// 키 메모리 페이지 고정을 해제합니다.
+#[cfg(target_os = "linux")]
+fn unlock_memory(bytes: &[u8; SECRET_KEY_LEN]) {
+    // SAFETY: lock_memory에 전달했던 것과 같은 범위이며, 고정되지 않은 범위여도 안전함
+    unsafe {
+        libc::munlock(bytes.as_ptr().cast(), bytes.len());
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn lock_memory(_bytes: &[u8; SECRET_KEY_LEN]) {}
+
+#[cfg(not(target_os = "linux"))]
+fn unlock_memory(_bytes: &[u8; SECRET_KEY_LEN]) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bytes_wipes_source() {
+        let mut bytes = [7u8; SECRET_KEY_LEN];
+        let key = SecretKey::from_bytes(&mut bytes);
+
+        assert_eq!(bytes, [0u8; SECRET_KEY_LEN]);
+        assert_eq!(key.expose(), &[7u8; SECRET_KEY_LEN]);
+    }
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let key = SecretKey::from_bytes(&mut [0xAB; SECRET_KEY_LEN]);
+        let output = format!("{:?}", key);
+
+        assert_eq!(output, "SecretKey([REDACTED])");
+    }
+
+    #[test]
+    fn test_keys_do_not_share_pages() {
+        let first = SecretKey::generate();
+        let second = SecretKey::generate();
+
+        assert_eq!(first.expose().as_ptr() as usize % 4096, 0);
+        assert_eq!(second.expose().as_ptr() as usize % 4096, 0);
+        assert_ne!(first, second);
+        assert!(!first.is_all_zero());
+        assert!(SecretKey::zeroed().is_all_zero());
+    }
+}
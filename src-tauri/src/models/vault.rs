@@ -316,13 +316,15 @@ pub struct SecurityConfig {
     /// 복구 키 활성화 여부
     pub recovery_key_enabled: bool,
     
-    /// 이중 인증 활성화 여부 (향후 확장용)
+    /// 이중 인증(키 파일) 활성화 여부
+    /// 키 파일 등록/제거 시 인증 서비스가 관리하며 직접 변경할 수 없습니다.
     pub two_factor_enabled: bool,
     
     /// 메모리 보안 강화 여부
     pub enhanced_memory_security: bool,
     
     /// 로그 암호화 여부
+    /// 끄면 가림 처리만 한 평문으로 기록하며, 다음 잠금 해제부터 적용됩니다.
     pub encrypt_logs: bool,
     
     /// 안전한 삭제 활성화 여부
@@ -345,14 +347,17 @@ impl Default for SecurityConfig {
     }
 }
 
+/// PIN/패스프레이즈 최대 길이 (정규화 후 문자 수)
+pub const MAX_PIN_LENGTH: usize = 128;
+
 /// PIN 복잡도 요구사항
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum PinComplexityRequirement {
-    /// 낮음 (4자리 숫자)
+    /// 낮음 (4-8자리 숫자)
     Low,
-    /// 중간 (6자리 숫자)
+    /// 중간 (6-8자리 숫자)
     Medium,
-    /// 높음 (8자리 이상, 특수문자 허용)
+    /// 높음 (8자 이상 패스프레이즈, 문자 포함, 특수문자 허용)
     High,
     /// 사용자 정의
     Custom {
@@ -371,9 +376,9 @@ impl PinComplexityRequirement {
     /// * `String` - 요구사항 설명
     pub fn description(&self) -> String {
         match self {
-            Self::Low => "4자리 숫자".to_string(),
-            Self::Medium => "6자리 숫자".to_string(),
-            Self::High => "8자리 이상 (특수문자 허용)".to_string(),
+            Self::Low => "4-8자리 숫자".to_string(),
+            Self::Medium => "6-8자리 숫자".to_string(),
+            Self::High => "8자 이상 (문자 포함, 특수문자 허용)".to_string(),
             Self::Custom { min_length, max_length, require_numbers, require_letters, require_special_chars } => {
                 let mut desc = format!("{}-{}자리", min_length, max_length);
                 let mut requirements = Vec::new();
@@ -390,6 +395,63 @@ impl PinComplexityRequirement {
             }
         }
     }
+
+    /// 숫자 외의 문자(패스프레이즈)를 허용하는지 확인합니다.
+    pub fn allows_passphrase(&self) -> bool {
+        match self {
+            Self::Low | Self::Medium => false,
+            Self::High => true,
+            Self::Custom { require_letters, require_special_chars, .. } => {
+                *require_letters || *require_special_chars
+            }
+        }
+    }
+
+    /// PIN이 요구사항을 충족하는지 검증합니다.
+    ///
+    /// 길이는 바이트가 아닌 문자 수로 계산하므로 한글 음절은 한 글자로 셉니다.
+    /// 입력은 호출 전에 유니코드 정규화(NFKC)되어 있어야 합니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - 정규화된 PIN 또는 패스프레이즈
+    ///
+    /// # 반환값
+    /// * `Result<(), String>` - 충족하지 않으면 요구사항 설명
+    pub fn validate(&self, pin: &str) -> Result<(), String> {
+        let length = pin.chars().count();
+        let all_digits = !pin.is_empty() && pin.chars().all(|c| c.is_ascii_digit());
+        let has_digit = pin.chars().any(|c| c.is_numeric());
+        let has_letter = pin.chars().any(|c| c.is_alphabetic());
+        let has_special = pin
+            .chars()
+            .any(|c| !c.is_alphanumeric() && !c.is_whitespace());
+
+        let valid = match self {
+            Self::Low => all_digits && (4..=8).contains(&length),
+            Self::Medium => all_digits && (6..=8).contains(&length),
+            Self::High => (8..=MAX_PIN_LENGTH).contains(&length) && has_letter,
+            Self::Custom {
+                min_length,
+                max_length,
+                require_numbers,
+                require_letters,
+                require_special_chars,
+            } => {
+                let max = (*max_length as usize).min(MAX_PIN_LENGTH);
+                (*min_length as usize..=max).contains(&length)
+                    && (!*require_numbers || has_digit)
+                    && (!*require_letters || has_letter)
+                    && (!*require_special_chars || has_special)
+                    && (self.allows_passphrase() || all_digits)
+            }
+        };
+
+        if valid && !pin.chars().any(|c| c.is_control()) {
+            Ok(())
+        } else {
+            Err(self.description())
+        }
+    }
 }
 
 /// 브루트포스 방지 설정
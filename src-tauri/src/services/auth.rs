@@ -3,19 +3,89 @@
 // C# SecurityService.cs를 완전히 포팅한 버전입니다.
 
 use crate::models::{
-    AuthError, AuthMethod, AuthSession, AuthState, BruteForceProtection, PinComplexity, PinInfo,
-    PinValidationResult, RecoveryKeyValidationResult, SimpleRecoveryKeyInfo,
+    AuthError, AuthMethod, AuthSession, AuthState, BruteForceProtection, DataKeyRotation, KdfAlgorithm,
+    KdfCalibration, KdfParams, PinComplexity, PinComplexityRequirement, PinInfo, PinValidationResult,
+    RecoveryKeyStatus, RecoveryKeyValidationResult, RecoveryLoginResult, SecurityConfig, SimpleRecoveryKeyInfo,
+    SecretKey, SupersededRecoveryKey, WrappedKey, MAX_PIN_LENGTH,
 };
+use crate::services::CryptoService;
+use crate::utils::constant_time_compare;
+use crate::utils::mnemonic::{decode_recovery_key, encode_recovery_mnemonic, is_valid_recovery_key_format};
 use crate::SecureVaultResult;
 use base64::{engine::general_purpose, Engine as _};
+use hmac::{Hmac, Mac};
 use pbkdf2::pbkdf2_hmac;
 use rand::{rngs::OsRng, RngCore};
-use regex::Regex;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use unicode_normalization::UnicodeNormalization;
 use uuid::Uuid;
+use zeroize::Zeroize;
+
+/// PIN 검증자 도메인 분리 문자열
+/// 검증자가 같은 PIN/솔트로 유도되는 마스터 키와 같아지지 않도록 합니다.
+const PIN_VERIFIER_CONTEXT: &[u8] = b"SecureVault PIN verifier v1";
+
+/// 브루트포스 기록 MAC 도메인 분리 문자열
+const LOCKOUT_MAC_CONTEXT: &[u8] = b"SecureVault lockout record v1";
+
+/// 강압/패닉 PIN에 쓰는 익명 슬롯 개수
+///
+/// 설정 여부와 관계없이 auth.json과 keys.json에 항상 이 개수만큼 기록하고, 사용하지 않는
+/// 슬롯은 같은 형식의 임의 데이터로 채워 파일만으로는 어떤 PIN이 설정되었는지 알 수 없게 합니다.
+const ALTERNATE_PIN_SLOTS: usize = 2;
+
+/// 강압 PIN이 사용하는 익명 슬롯 위치
+const DURESS_SLOT: usize = 0;
+
+/// 패닉 PIN이 사용하는 익명 슬롯 위치
+const PANIC_SLOT: usize = 1;
+
+/// 익명 슬롯에 봉인하는 정보의 고정 크기 (바이트, 내용과 관계없이 같은 길이로 채움)
+const SEALED_SLOT_DATA_SIZE: usize = 512;
+
+/// 위장 볼트와 자리 표시용 빈 볼트가 위치하는 디렉토리 (.securevault 기준)
+///
+/// 강압 PIN 설정 여부와 관계없이 임의 이름의 디렉토리가 항상 하나 있습니다.
+const DECOY_VAULTS_DIR: &str = "data/vaults";
+
+/// 파기할 파일을 옮겨 두는 디렉토리 (`.securevault` 기준, 백그라운드에서 덮어쓴 뒤 삭제)
+const ERASE_DIR: &str = "erase";
+
+/// 키 파일 내용 해시 도메인 분리 문자열
+const KEY_FILE_CONTEXT: &[u8] = b"SecureVault key file v1";
+
+/// 키 파일 검증자 도메인 분리 문자열
+const KEY_FILE_VERIFIER_CONTEXT: &[u8] = b"SecureVault key file verifier v1";
+
+/// 새로 생성하는 키 파일 크기 (바이트)
+const KEY_FILE_SIZE: usize = 64;
+
+/// 최고 보안 암호 KEK 유도 도메인 분리 문자열
+const CRITICAL_PASSPHRASE_CONTEXT: &[u8] = b"SecureVault critical passphrase v1";
+
+/// 최고 보안 암호 최소 길이 (문자 수)
+const MIN_CRITICAL_PASSPHRASE_LENGTH: usize = 8;
+
+/// PIN 재확인(step-up) 후 높은 보안 등급 파일에 접근할 수 있는 시간 (초)
+pub const STEP_UP_WINDOW_SECONDS: u64 = 120;
+
+/// 키 유도 비용 보정 시 기본 목표 잠금 해제 시간 (밀리초)
+pub const DEFAULT_UNLOCK_TARGET_MS: u64 = 1000;
+
+/// 키 유도 비용 보정 시 허용하는 최대 목표 잠금 해제 시간 (밀리초)
+pub const MAX_UNLOCK_TARGET_MS: u64 = 10_000;
+
+/// PIN 로그인 한 번에 계산하는 키 유도 횟수 (일반/강압/패닉 PIN 검증자와 KEK)
+const KDF_DERIVATIONS_PER_UNLOCK: u32 = 4;
+
+/// 보관할 교체된 복구 키 기록의 최대 개수
+const MAX_SUPERSEDED_RECOVERY_KEYS: usize = 16;
 
 /// 인증 데이터 영구 저장용 구조체
 #[derive(Serialize, Deserialize)]
@@ -24,12 +94,144 @@ struct AuthData {
     recovery_key_info: Option<SimpleRecoveryKeyInfo>,
     #[serde(default = "default_session_timeout")]
     session_timeout_seconds: u64,
+    #[serde(default)]
+    security_config: SecurityConfig,
+    /// 강압/패닉 PIN 익명 슬롯의 검증자 (사용하지 않는 슬롯은 임의 데이터)
+    #[serde(default)]
+    pin_slots: Vec<PinInfo>,
+    #[serde(default)]
+    key_file_verifier: Option<String>,
+    #[serde(default)]
+    superseded_recovery_keys: Vec<SupersededRecoveryKey>,
+    /// 브루트포스 기록(lockout.json) MAC 키 (Base64)
+    ///
+    /// 값이 있으면 lockout.json도 있어야 하며, 없거나 MAC이 맞지 않으면 변조로 간주합니다.
+    #[serde(default)]
+    lockout_key: Option<String>,
 }
 
 fn default_session_timeout() -> u64 {
     3600
 }
 
+/// 래핑된 볼트 데이터 키 저장용 구조체 (keys.json)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct KeyStore {
+    /// PIN에서 유도한 KEK로 래핑된 데이터 키
+    pin: Option<WrappedKey>,
+    /// 복구 키에서 유도한 KEK로 래핑된 데이터 키
+    recovery: Option<WrappedKey>,
+    /// 강압/패닉 PIN 익명 슬롯 (사용하지 않는 슬롯은 임의 데이터)
+    #[serde(default)]
+    slots: Vec<KeySlot>,
+    /// 익명 슬롯 사용 여부 (일반 볼트 데이터 키로 봉인, Base64)
+    #[serde(default)]
+    slot_usage: Option<String>,
+    /// 최고 보안 암호에서 유도한 KEK로 래핑된 최고 보안 파일 키
+    #[serde(default)]
+    critical: Option<WrappedKey>,
+    /// 볼트 ID (복구 키 시트 등에 표시, 이전 버전 볼트는 처음 조회할 때 생성)
+    #[serde(default)]
+    vault_id: Option<Uuid>,
+    /// PIN/암호를 새로 해시하거나 래핑할 때 사용하는 보정된 키 유도 매개변수 (없으면 기본값)
+    #[serde(default)]
+    kdf_params: Option<KdfParams>,
+    /// 데이터 키를 교체한 뒤 재암호화가 끝날 때까지 보관하는 이전 키
+    #[serde(default)]
+    retired: Vec<RetiredKeys>,
+}
+
+/// 데이터 키 교체 후 아직 다시 암호화되지 않은 파일을 열기 위한 이전 키
+///
+/// 이전 데이터 키는 새 데이터 키로, 이전 최고 보안 파일 키는 새 최고 보안 파일 키로 래핑되어
+/// 있으므로 어느 볼트의 기록인지는 새 키로 풀어 봐야만 알 수 있습니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RetiredKeys {
+    /// 새 데이터 키로 래핑된 이전 데이터 키 (Base64)
+    data_key: String,
+    /// 새 최고 보안 파일 키로 래핑된 이전 최고 보안 파일 키 (Base64)
+    #[serde(default)]
+    critical_key: Option<String>,
+}
+
+impl RetiredKeys {
+    /// 래핑된 키를 KEK로 언래핑합니다. 다른 볼트나 다른 키의 기록이면 None을 반환합니다.
+    fn unwrap(wrapped: &str, kek: &SecretKey) -> Option<SecretKey> {
+        let wrapped = general_purpose::STANDARD.decode(wrapped).ok()?;
+        CryptoService::unwrap_key(kek, &wrapped).ok()
+    }
+
+    /// 키를 KEK로 래핑하여 Base64로 반환합니다.
+    fn wrap(key: &SecretKey, kek: &SecretKey) -> SecureVaultResult<String> {
+        Ok(general_purpose::STANDARD.encode(CryptoService::wrap_key(kek, key)?))
+    }
+}
+
+/// 강압/패닉 PIN 익명 슬롯의 래핑된 키
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KeySlot {
+    /// 슬롯 PIN에서 유도한 KEK로 래핑된 데이터 키 (강압 PIN은 위장 볼트, 패닉 PIN은 새 빈 볼트)
+    key: WrappedKey,
+    /// 슬롯 데이터 키로 봉인한 정보 (Base64, 고정 크기, 강압 PIN은 위장 볼트 정보,
+    /// 패닉 PIN은 새 빈 볼트의 슬롯 사용 정보)
+    sealed: String,
+}
+
+/// 익명 슬롯 사용 여부 (일반 볼트 세션에서만 열 수 있음)
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+struct SlotUsage {
+    duress: bool,
+    panic: bool,
+}
+
+/// 위장 볼트 세션 전용 정보 (강압 PIN 슬롯의 위장 볼트 데이터 키로 봉인)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DecoyState {
+    /// `DECOY_VAULTS_DIR` 아래의 위장 볼트 디렉토리 이름
+    vault_dir: String,
+    /// 위장 볼트의 최고 보안 파일 키
+    #[serde(default)]
+    critical: Option<WrappedKey>,
+}
+
+/// 인증에 사용된 PIN 슬롯
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PinSlot {
+    /// 실제 볼트 PIN
+    Primary,
+    /// 위장 볼트를 여는 강압(duress) PIN
+    Duress,
+}
+
+/// 브루트포스 방지 상태 저장용 구조체 (lockout.json)
+///
+/// 앱을 재시작해도 실패 횟수와 잠금 시각이 유지되도록 합니다.
+/// auth.json의 `lockout_key`로 계산한 MAC을 함께 기록해, 실패 횟수나 잠금 시각을 고치거나
+/// 파일을 지우면 최대 잠금으로 처리합니다. auth.json까지 예전 사본으로 되돌리는 경우는
+/// 막지 못하며, 파일을 복사한 오프라인 공격에 대한 방어는 PIN 검증자와 KEK의 Argon2id
+/// 비용이 담당합니다.
+#[derive(Serialize, Deserialize)]
+struct BruteForceRecord {
+    failed_attempts: u32,
+    last_failure_time: Option<u64>,
+    lockout_until: Option<u64>,
+    /// 위 필드에 대한 HMAC-SHA256 (Base64)
+    #[serde(default)]
+    mac: String,
+}
+
+impl BruteForceRecord {
+    /// 기록 필드에 대한 MAC을 계산합니다.
+    fn compute_mac(&self, key: &[u8]) -> Vec<u8> {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC은 모든 키 길이를 허용합니다");
+        mac.update(LOCKOUT_MAC_CONTEXT);
+        mac.update(&self.failed_attempts.to_le_bytes());
+        mac.update(&self.last_failure_time.unwrap_or(0).to_le_bytes());
+        mac.update(&self.lockout_until.unwrap_or(0).to_le_bytes());
+        mac.finalize().into_bytes().to_vec()
+    }
+}
+
 /// 인증 서비스
 /// C# SecurityService를 완전히 포팅한 Rust 버전
 /// 사용자 인증과 세션 관리를 담당합니다.
@@ -41,6 +243,9 @@ pub struct AuthService {
     /// 복구 키 정보
     recovery_key_info: Option<SimpleRecoveryKeyInfo>,
 
+    /// 교체되어 더 이상 사용할 수 없는 복구 키 기록
+    superseded_recovery_keys: Vec<SupersededRecoveryKey>,
+
     /// 현재 세션
     current_session: Option<AuthSession>,
 
@@ -52,6 +257,48 @@ pub struct AuthService {
 
     /// 인증 상태
     auth_state: AuthState,
+
+    /// 래핑된 볼트 데이터 키
+    key_store: KeyStore,
+
+    /// 볼트 보안 설정
+    security_config: SecurityConfig,
+
+    /// 강압/패닉 PIN 익명 슬롯의 검증자
+    pin_slots: Vec<PinInfo>,
+
+    /// 위장 볼트 세션 정보 (강압 PIN으로 잠금 해제한 동안만 보관)
+    decoy_state: Option<DecoyState>,
+
+    /// 위장 볼트 정보 봉인 키 (강압 PIN으로 잠금 해제한 동안만 보관)
+    decoy_seal_key: Option<SecretKey>,
+
+    /// 현재 세션이 인증된 PIN 슬롯
+    active_slot: PinSlot,
+
+    /// 마지막으로 검증된 PIN이 현재 복잡도 요구사항을 충족하지 못하는지 여부
+    pin_upgrade_required: bool,
+
+    /// 등록된 키 파일 검증자 (Base64)
+    key_file_verifier: Option<String>,
+
+    /// 현재 제공된 키 파일 해시 (메모리에만 보관)
+    key_file_secret: Option<SecretKey>,
+
+    /// 브루트포스 기록 MAC 키 (Base64)
+    lockout_key: Option<String>,
+
+    /// 마지막 PIN 재확인 시각
+    step_up_at: Option<Instant>,
+
+    /// 잠금 해제된 최고 보안 파일 키 (PIN 재확인 유효 시간 동안만 보관)
+    critical_key: Option<Arc<SecretKey>>,
+
+    /// 위장 볼트 세션에서 마지막으로 생성한 복구 키의 검증자 (디스크에 저장하지 않음)
+    decoy_recovery_key_hash: Option<String>,
+
+    /// 인증 데이터 저장 디렉토리 (.securevault/config)
+    config_dir: PathBuf,
 }
 
 impl AuthService {
@@ -60,17 +307,52 @@ impl AuthService {
     /// # 반환값
     /// * `Self` - 초기화된 인증 서비스
     pub fn new() -> Self {
+        let config_dir = std::env::current_dir()
+            .unwrap_or_else(|_| PathBuf::from("."))
+            .join(".securevault")
+            .join("config");
+
+        Self::with_config_dir(config_dir)
+    }
+
+    /// 지정한 설정 디렉토리를 사용하는 인증 서비스를 생성합니다.
+    ///
+    /// # 매개변수
+    /// * `config_dir` - auth.json이 저장될 디렉토리
+    ///
+    /// # 반환값
+    /// * `Self` - 초기화된 인증 서비스
+    pub fn with_config_dir<P: Into<PathBuf>>(config_dir: P) -> Self {
         let mut service = Self {
             pin_info: None,
             recovery_key_info: None,
+            superseded_recovery_keys: Vec::new(),
             current_session: None,
             session_timeout_seconds: 3600, // 기본 1시간
             brute_force_protection: BruteForceProtection::new(),
             auth_state: AuthState::Unauthenticated,
+            key_store: KeyStore::default(),
+            security_config: SecurityConfig::default(),
+            pin_slots: Vec::new(),
+            decoy_state: None,
+            decoy_seal_key: None,
+            active_slot: PinSlot::Primary,
+            pin_upgrade_required: false,
+            key_file_verifier: None,
+            key_file_secret: None,
+            lockout_key: None,
+            step_up_at: None,
+            critical_key: None,
+            decoy_recovery_key_hash: None,
+            config_dir: config_dir.into(),
         };
 
         // 디스크에서 인증 데이터 로드
-        service.load_auth_data();
+        service.load_credentials();
+        service.load_brute_force_state();
+
+        // 이전 실행에서 끝나지 않은 파기를 이어서 진행
+        service.erase_discarded_in_background();
 
         // 테스트용 PIN 1234 설정 (배포 시 제거됨)
         // service.initialize_test_pin();
@@ -88,16 +370,47 @@ impl AuthService {
             0x1D, 0x1E, 0x1F, 0x20,
         ];
 
-        if let Ok(hashed_pin) = self.hash_pin("1234", &salt) {
+        let hash_params = KdfParams::legacy_pin_hash();
+        if let Ok(hashed_pin) = self.hash_pin("1234", &salt, &hash_params) {
             self.pin_info = Some(PinInfo {
                 hash: hashed_pin,
                 salt: salt.to_vec(),
+                hash_params,
+                key_params: KdfParams::legacy_master_key(),
+                is_passphrase: false,
+                key_file: false,
             });
         }
     }
 
     /// PIN을 해시화합니다.
-    pub fn hash_pin(&self, pin: &str, salt: &[u8]) -> SecureVaultResult<String> {
+    ///
+    /// 레거시(SHA-256) 매개변수는 기존 형식을 그대로 재현하고, 그 외에는
+    /// 유도된 키를 도메인 분리 문자열과 함께 한 번 더 해시하여 검증자로 사용합니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - PIN
+    /// * `salt` - 32바이트 솔트
+    /// * `params` - 검증자 해시 매개변수
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<String>` - Base64 인코딩된 검증자
+    pub fn hash_pin(&self, pin: &str, salt: &[u8], params: &KdfParams) -> SecureVaultResult<String> {
+        self.hash_pin_with_key_file(pin, None, salt, params)
+    }
+
+    /// PIN과 키 파일 해시로 검증자를 계산합니다.
+    ///
+    /// 키 파일이 등록된 볼트는 일반 PIN 검증자에도 키 파일 해시를 함께 사용하므로,
+    /// auth.json만 가지고는 키 파일 없이 PIN을 오프라인으로 추측할 수 없습니다.
+    /// 키 파일이 없으면 `hash_pin`과 같은 값을 반환합니다.
+    fn hash_pin_with_key_file(
+        &self,
+        pin: &str,
+        key_file: Option<&SecretKey>,
+        salt: &[u8],
+        params: &KdfParams,
+    ) -> SecureVaultResult<String> {
         if pin.is_empty() {
             return Err(AuthError::InvalidPinFormat.into());
         }
@@ -106,67 +419,277 @@ impl AuthService {
             return Err(AuthError::InvalidSalt.into());
         }
 
-        let mut hasher = Sha256::new();
+        let pin = Self::normalize_pin(pin);
+        let mut material = Self::pin_key_material(&pin, key_file);
+        let derived = CryptoService::derive_key_with_params(&material, salt, params);
+        material.zeroize();
+        let derived = derived.map_err(|_| AuthError::HashingFailed)?;
 
-        // PIN + 솔트 결합
-        hasher.update(pin.as_bytes());
-        hasher.update(salt);
+        if params.algorithm == KdfAlgorithm::Sha256 {
+            // 레거시 형식: SHA-256(PIN + 솔트)
+            return Ok(general_purpose::STANDARD.encode(derived.expose()));
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(PIN_VERIFIER_CONTEXT);
+        hasher.update(derived.expose());
 
         let hash = hasher.finalize();
         Ok(general_purpose::STANDARD.encode(&hash))
     }
 
-    /// PIN 형식을 검증합니다.
+    /// PIN/패스프레이즈를 유니코드 NFKC로 정규화합니다.
+    ///
+    /// 한글 IME나 운영체제에 따라 같은 문자열이 완성형(NFC) 또는 조합형(NFD) 자모,
+    /// 호환 자모, 전각 숫자 등으로 입력될 수 있으므로 해시와 키 유도 전에 항상 정규화합니다.
+    /// ASCII 숫자 PIN은 그대로이므로 기존 검증자와 래핑된 키에 영향을 주지 않습니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - 입력된 PIN 또는 패스프레이즈
+    ///
+    /// # 반환값
+    /// * `String` - 정규화된 문자열
+    pub fn normalize_pin(pin: &str) -> String {
+        pin.nfkc().collect()
+    }
+
+    /// 새로 설정할 PIN이 볼트의 복잡도 요구사항을 충족하는지 검증합니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - 정규화된 PIN 또는 패스프레이즈
     pub fn validate_pin_format(&self, pin: &str) -> SecureVaultResult<()> {
         if pin.is_empty() {
             return Err(AuthError::InvalidPinFormat.into());
         }
 
-        // 4-8자리 숫자
-        let pin_regex = Regex::new(r"^\d{4,8}$").unwrap();
-        if !pin_regex.is_match(pin) {
+        self.security_config
+            .pin_complexity
+            .validate(pin)
+            .map_err(|requirement| {
+                AuthError::InvalidInput(format!("PIN은 {} 조건을 충족해야 합니다.", requirement))
+                    .into()
+            })
+    }
+
+    /// 인증 시 입력된 PIN의 기본 형식만 검증합니다.
+    ///
+    /// 복잡도 요구사항이 강화되어도 기존 PIN으로 로그인한 뒤 변경할 수 있도록
+    /// 요구사항은 확인하지 않습니다.
+    fn validate_pin_input(pin: &str) -> SecureVaultResult<()> {
+        if pin.is_empty()
+            || pin.chars().count() > MAX_PIN_LENGTH
+            || pin.chars().any(|c| c.is_control())
+        {
             return Err(AuthError::InvalidPinFormat.into());
         }
 
         Ok(())
     }
 
-    /// PIN을 설정합니다.
-    pub fn set_pin(&mut self, pin: &str, complexity: PinComplexity) -> SecureVaultResult<()> {
+    /// PIN을 설정하고 볼트 데이터 키를 새 PIN으로 래핑합니다.
+    ///
+    /// 처음 설정하는 경우 `data_key`는 새로 생성한 키여야 하며, 이전 복구 키는 무효화됩니다.
+    /// 이미 PIN이 있는 경우(변경/재설정) `data_key`는 현재 볼트의 키여야 하며,
+    /// 복구 키 래핑은 그대로 유지됩니다.
+    ///
+    /// 키 파일이 등록된 볼트는 등록된 키 파일이 제공되어 있어야 합니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - 새 PIN
+    /// * `complexity` - PIN 복잡도
+    /// * `data_key` - 볼트 데이터 키
+    ///
+    /// # 오류
+    /// * `AuthError::KeyFileRequired` / `AuthError::KeyFileMismatch` - 등록된 키 파일이 제공되지 않음
+    pub fn set_pin(
+        &mut self,
+        pin: &str,
+        complexity: PinComplexity,
+        data_key: &SecretKey,
+    ) -> SecureVaultResult<()> {
+        self.set_pin_inner(pin, complexity, data_key, false)
+    }
+
+    /// 키 파일 요구를 해제하면서 PIN을 재설정합니다.
+    ///
+    /// 키 파일을 잃어버려 복구 키로 로그인한 경우처럼, 사용자가 키 파일 해제에
+    /// 명시적으로 동의했을 때만 호출해야 합니다. 등록된 키 파일이 제공되어 있으면
+    /// 키 파일 요구를 유지한 채 `set_pin`과 같이 동작합니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - 새 PIN
+    /// * `complexity` - PIN 복잡도
+    /// * `data_key` - 볼트 데이터 키
+    pub fn set_pin_removing_key_file(
+        &mut self,
+        pin: &str,
+        complexity: PinComplexity,
+        data_key: &SecretKey,
+    ) -> SecureVaultResult<()> {
+        self.set_pin_inner(pin, complexity, data_key, true)
+    }
+
+    /// 로그인된 세션에서 기존 볼트의 PIN을 재설정합니다.
+    ///
+    /// PIN으로 로그인한 세션은 현재 PIN을 다시 확인해야 합니다. 자리를 비운 사이 열린 세션을
+    /// 쓰는 사람이 PIN을 바꾸지 못하게 하기 위함이며, PIN을 잊어 복구 키로 로그인한 세션만
+    /// 현재 PIN 없이 재설정할 수 있습니다.
+    ///
+    /// # 매개변수
+    /// * `current_pin` - 현재 PIN (복구 키 세션이면 생략 가능)
+    /// * `pin` - 새 PIN
+    /// * `complexity` - PIN 복잡도
+    /// * `data_key` - 현재 볼트 데이터 키
+    /// * `remove_key_file` - 등록된 키 파일 없이 재설정하면서 키 파일 요구를 해제할지 여부
+    ///
+    /// # 오류
+    /// * `AuthError::AuthenticationFailed` - 현재 PIN이 없거나 일치하지 않음
+    pub fn reset_pin(
+        &mut self,
+        current_pin: Option<&str>,
+        pin: &str,
+        complexity: PinComplexity,
+        data_key: &SecretKey,
+        remove_key_file: bool,
+    ) -> SecureVaultResult<()> {
+        let recovery_session = self
+            .current_session
+            .as_ref()
+            .is_some_and(|session| session.auth_method == AuthMethod::RecoveryKey);
+
+        if !recovery_session {
+            let current_pin = current_pin.ok_or(AuthError::AuthenticationFailed)?;
+            let current_pin = &Self::normalize_pin(current_pin);
+            self.ensure_not_locked_out()?;
+            self.confirm_primary_pin(current_pin)?;
+        }
+
+        self.set_pin_inner(pin, complexity, data_key, remove_key_file)
+    }
+
+    fn set_pin_inner(
+        &mut self,
+        pin: &str,
+        complexity: PinComplexity,
+        data_key: &SecretKey,
+        remove_key_file: bool,
+    ) -> SecureVaultResult<()> {
+        let pin = &Self::normalize_pin(pin);
+
         // PIN 형식 검증
         self.validate_pin_format(pin)?;
 
+        // 위장 볼트 세션에서는 강압 PIN을 변경
+        if self.is_decoy_session() {
+            self.store_duress_pin(pin, data_key)?;
+            self.save_credentials()?;
+            return Ok(());
+        }
+
+        if self.matches_duress_pin(pin) || self.matches_panic_pin(pin) {
+            return Err(AuthError::InvalidInput("강압/패닉 PIN과 다른 PIN을 사용해야 합니다.".to_string()).into());
+        }
+
+        // 키 파일 없이 재설정하면 키 파일 요구가 빠지므로 사용자가 동의한 경우에만 허용
+        if self.key_file_verifier.is_some() && self.verified_key_file().is_none() {
+            if !remove_key_file {
+                return Err(if self.key_file_secret.is_some() {
+                    AuthError::KeyFileMismatch
+                } else {
+                    AuthError::KeyFileRequired
+                }
+                .into());
+            }
+            self.provide_key_file(None);
+            self.key_file_verifier = None;
+            self.security_config.two_factor_enabled = false;
+            log::warn!("사용자 확인에 따라 키 파일 요구를 해제하고 PIN을 재설정합니다.");
+        }
+
+        let is_new_vault = self.pin_info.is_none();
+        self.store_primary_pin(pin, complexity, data_key)?;
+
+        // 새 볼트를 만든 사용자는 방금 PIN을 정했으므로 바로 인증된 세션을 시작
+        if is_new_vault {
+            self.auth_state = AuthState::Authenticated(AuthMethod::Pin);
+            self.create_session(AuthMethod::Pin, self.session_timeout_seconds)?;
+        }
+
+        log::info!("PIN이 성공적으로 설정되었습니다.");
+        Ok(())
+    }
+
+    /// 일반 PIN 슬롯을 기록하고 데이터 키를 래핑합니다.
+    fn store_primary_pin(
+        &mut self,
+        pin: &str,
+        complexity: PinComplexity,
+        data_key: &SecretKey,
+    ) -> SecureVaultResult<()> {
         // 32바이트 솔트 생성
         let mut salt = [0u8; 32];
         OsRng.fill_bytes(&mut salt);
 
-        // PIN 해시 생성
-        let hash = self.hash_pin(pin, &salt)?;
+        // PIN 해시 생성 (이 볼트의 키 유도 매개변수, 등록된 키 파일 포함)
+        let params = self.kdf_params();
+        let key_file = self.verified_key_file();
+        let uses_key_file = key_file.is_some();
+        let hash = self.hash_pin_with_key_file(pin, key_file, &salt, &params)?;
+
+        // 데이터 키를 새 PIN(과 키 파일)으로 래핑
+        let wrapped = self.wrap_with_pin(pin, data_key)?;
+
+        if self.pin_info.is_none() {
+            // 새 볼트: 이전 데이터 키에 묶인 복구 키는 더 이상 쓸 수 없음
+            self.recovery_key_info = None;
+            self.key_store.recovery = None;
+            self.superseded_recovery_keys.clear();
+
+            // 익명 슬롯은 모두 임의 데이터로 채우고 자리 표시용 위장 볼트 디렉토리를 만듦
+            self.pin_slots.clear();
+            self.key_store.slots.clear();
+            self.seal_slot_usage(data_key, SlotUsage::default())?;
+            self.replace_decoy_vault(&Self::random_vault_dir_name());
+        }
 
         // PIN 정보 저장
-        self.pin_info = Some(PinInfo::new(hash, salt.to_vec(), complexity));
+        let mut pin_info = PinInfo::new(hash, salt.to_vec(), complexity);
+        pin_info.hash_params = params;
+        pin_info.is_passphrase = !pin.chars().all(|c| c.is_ascii_digit());
+        pin_info.key_file = uses_key_file;
+        self.pin_info = Some(pin_info);
+        self.key_store.pin = Some(wrapped);
+        self.pin_upgrade_required = false;
+        self.pad_pin_slots();
 
         // 변경 사항 저장
-        self.save_auth_data();
-
-        log::info!("PIN이 성공적으로 설정되었습니다.");
+        self.save_credentials()?;
+        self.save_brute_force_state();
         Ok(())
     }
 
     /// PIN이 일치하는지 검증합니다.
-    pub fn verify_pin_hash(&self, input_pin: &str, stored_hash: &str, salt: &[u8]) -> bool {
+    pub fn verify_pin_hash(
+        &self,
+        input_pin: &str,
+        stored_hash: &str,
+        salt: &[u8],
+        params: &KdfParams,
+    ) -> bool {
         if input_pin.is_empty() || stored_hash.is_empty() {
             return false;
         }
 
-        match self.hash_pin(input_pin, salt) {
-            Ok(input_hash) => input_hash == stored_hash,
+        match self.hash_pin(input_pin, salt, params) {
+            Ok(input_hash) => constant_time_compare(input_hash.as_bytes(), stored_hash.as_bytes()),
             Err(_) => false,
         }
     }
 
     /// PIN을 검증합니다.
     pub fn verify_pin(&mut self, pin: &str) -> SecureVaultResult<PinValidationResult> {
+        let pin = &Self::normalize_pin(pin);
+
         // 브루트포스 방지 체크
         if self.brute_force_protection.is_currently_locked() {
             if let Some(remaining) = self.brute_force_protection.remaining_lockout_seconds() {
@@ -174,6 +697,18 @@ impl AuthService {
             }
         }
 
+        // 키 파일 확인 (PIN보다 먼저 확인하므로 PIN 일치 여부가 드러나지 않음)
+        if self.key_file_verifier.is_some() {
+            if self.key_file_secret.is_none() {
+                return Ok(PinValidationResult::KeyFileRequired);
+            }
+            if self.verified_key_file().is_none() {
+                self.provide_key_file(None);
+                log::warn!("등록되지 않은 키 파일이 제공되었습니다.");
+                return Ok(PinValidationResult::KeyFileMismatch);
+            }
+        }
+
         // PIN 정보 확인
         let pin_info = self.pin_info.as_ref().ok_or(AuthError::NoPinSet)?;
 
@@ -183,14 +718,40 @@ impl AuthService {
         }
 
         // PIN 형식 검증
-        if let Err(_) = self.validate_pin_format(pin) {
+        if Self::validate_pin_input(pin).is_err() {
             return Ok(PinValidationResult::InvalidFormat);
         }
 
         // 해시 검증
-        if self.verify_pin_hash(pin, &pin_info.hash, &pin_info.salt) {
+        // 어떤 PIN이 맞았는지 드러나지 않도록 모든 슬롯을 항상 계산
+        let primary_match = self.matches_primary_pin(pin);
+        let duress_match = self.matches_duress_pin(pin);
+        let panic_match = self.matches_panic_pin(pin);
+
+        if panic_match & !primary_match & !duress_match {
+            // 패닉 PIN: 기존 볼트를 파기하고 미리 만들어 둔 빈 볼트로 정상 로그인처럼 처리
+            self.open_panic_vault(pin)?;
+        }
+
+        if primary_match | duress_match | panic_match {
             // 인증 성공
             self.brute_force_protection.record_success();
+            self.save_brute_force_state();
+
+            if primary_match {
+                self.active_slot = PinSlot::Primary;
+
+                // 요구사항이 강화되기 전의 PIN(예: 숫자 PIN)이면 변경을 안내
+                self.pin_upgrade_required = self.validate_pin_format(pin).is_err();
+
+                // 검증자가 오래된 매개변수로 만들어졌다면 현재 매개변수로 재해시
+                self.upgrade_pin_hash_if_needed(PinSlot::Primary, pin);
+            } else if duress_match {
+                self.active_slot = PinSlot::Duress;
+                self.upgrade_pin_hash_if_needed(PinSlot::Duress, pin);
+            } else {
+                self.active_slot = PinSlot::Primary;
+            }
             self.auth_state = AuthState::Authenticated(AuthMethod::Pin);
 
             // 세션 생성
@@ -201,91 +762,1675 @@ impl AuthService {
         } else {
             // 인증 실패
             self.brute_force_protection.record_failure();
+            self.save_brute_force_state();
             log::warn!("PIN 인증이 실패했습니다.");
             Ok(PinValidationResult::Invalid)
         }
     }
 
-    /// PIN으로부터 마스터 키를 유도합니다.
-    pub fn derive_key_from_pin(
-        &self,
-        pin: &str,
-        salt: &[u8],
-        iterations: u32,
-    ) -> SecureVaultResult<Vec<u8>> {
-        if pin.is_empty() {
-            return Err(AuthError::InvalidPinFormat.into());
+    /// 이 볼트의 키 유도 매개변수와 다른 매개변수로 저장된 PIN 검증자를 다시 만듭니다.
+    ///
+    /// 검증에 성공한 직후에만 호출되며, 솔트와 마스터 키 매개변수는 유지하므로
+    /// 기존 파일의 복호화에는 영향을 주지 않습니다.
+    fn upgrade_pin_hash_if_needed(&mut self, slot: PinSlot, pin: &str) {
+        let params = self.kdf_params();
+
+        // 일반 PIN 검증자는 키 파일이 등록되어 있으면 키 파일도 함께 사용
+        let key_file = match slot {
+            PinSlot::Primary => self.verified_key_file(),
+            PinSlot::Duress => None,
+        };
+        if slot == PinSlot::Primary && self.key_file_verifier.is_some() && key_file.is_none() {
+            return;
         }
+        let uses_key_file = key_file.is_some();
 
-        if salt.len() != 32 {
-            return Err(AuthError::InvalidSalt.into());
+        let salt = match self.pin_slot_info(slot) {
+            Some(info) if info.hash_params != params || info.key_file != uses_key_file => {
+                info.salt.clone()
+            }
+            _ => return,
+        };
+
+        match self.hash_pin_with_key_file(pin, key_file, &salt, &params) {
+            Ok(hash) => {
+                if let Some(info) = self.pin_slot_info_mut(slot) {
+                    info.hash = hash;
+                    info.hash_params = params;
+                    info.key_file = uses_key_file;
+                }
+                self.save_auth_data();
+                log::info!("PIN 검증자를 현재 키 유도 매개변수로 다시 만들었습니다.");
+            }
+            Err(e) => log::warn!("PIN 검증자 업그레이드 실패: {}", e),
         }
+    }
 
-        let mut key = [0u8; 32]; // 256비트 키
-        pbkdf2_hmac::<Sha256>(pin.as_bytes(), salt, iterations, &mut key);
+    /// PIN 슬롯의 검증자 정보
+    fn pin_slot_info(&self, slot: PinSlot) -> Option<&PinInfo> {
+        match slot {
+            PinSlot::Primary => self.pin_info.as_ref(),
+            PinSlot::Duress => self.pin_slots.get(DURESS_SLOT),
+        }
+    }
 
-        Ok(key.to_vec())
+    fn pin_slot_info_mut(&mut self, slot: PinSlot) -> Option<&mut PinInfo> {
+        match slot {
+            PinSlot::Primary => self.pin_info.as_mut(),
+            PinSlot::Duress => self.pin_slots.get_mut(DURESS_SLOT),
+        }
     }
 
-    /// 복구 키를 해시화합니다.
-    pub fn hash_recovery_key(&self, recovery_key: &str) -> SecureVaultResult<String> {
-        if recovery_key.is_empty() {
-            return Err(AuthError::InvalidRecoveryKey.into());
+    /// PIN 슬롯의 래핑된 데이터 키가 이 볼트의 키 유도 매개변수와 다르면 같은 PIN으로 다시 래핑합니다.
+    ///
+    /// 키 파일로 래핑된 슬롯은 키 파일 요구가 빠지지 않도록 확인된 키 파일이 있을 때만 다시 래핑합니다.
+    fn rewrap_pin_slot_if_needed(
+        &mut self,
+        slot: PinSlot,
+        pin: &str,
+        data_key: &SecretKey,
+    ) -> SecureVaultResult<()> {
+        let params = self.kdf_params();
+        let wrapped = match slot {
+            PinSlot::Primary => self.key_store.pin.as_ref(),
+            PinSlot::Duress => self.key_store.slots.get(DURESS_SLOT).map(|slot| &slot.key),
+        };
+        let Some(wrapped) = wrapped else {
+            return Ok(());
+        };
+        if wrapped.params == params || (wrapped.key_file && self.verified_key_file().is_none()) {
+            return Ok(());
         }
 
-        // Base64 디코딩
-        let key_bytes = general_purpose::STANDARD
-            .decode(recovery_key)
-            .map_err(|_| AuthError::InvalidRecoveryKey)?;
+        match slot {
+            PinSlot::Primary => self.key_store.pin = Some(self.wrap_with_pin(pin, data_key)?),
+            PinSlot::Duress => {
+                self.key_store.slots[DURESS_SLOT].key =
+                    Self::wrap_data_key(pin.as_bytes(), data_key, &params)?
+            }
+        }
+        self.save_key_store()
+    }
 
-        if key_bytes.len() != 32 {
-            return Err(AuthError::InvalidRecoveryKey.into());
+    /// 검증된 PIN으로 볼트 데이터 키를 언래핑합니다.
+    ///
+    /// 래핑된 키가 없는 기존 볼트는 PIN에서 직접 유도하던 마스터 키를 데이터 키로 삼고,
+    /// 이를 새 PIN KEK로 래핑하여 저장합니다. 따라서 기존 파일은 그대로 복호화됩니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - `verify_pin`으로 검증된 PIN
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<SecretKey>` - 볼트 데이터 키
+    pub fn unlock_with_pin(&mut self, pin: &str) -> SecureVaultResult<SecretKey> {
+        let pin = &Self::normalize_pin(pin);
+        let pin_info = self.pin_info.as_ref().ok_or(AuthError::NoPinSet)?;
+
+        if self.active_slot == PinSlot::Duress || self.key_store.pin.is_some() {
+            let data_key = match self.active_slot {
+                PinSlot::Duress => {
+                    let slot = self
+                        .key_store
+                        .slots
+                        .get(DURESS_SLOT)
+                        .ok_or(AuthError::NoPinSet)?;
+                    let data_key = Self::unwrap_data_key(pin.as_bytes(), &slot.key)?;
+                    self.open_decoy_state(&data_key)?;
+                    data_key
+                }
+                PinSlot::Primary => {
+                    let wrapped = self.key_store.pin.as_ref().ok_or(AuthError::NoPinSet)?;
+                    let data_key = self.unwrap_with_pin(pin, wrapped)?;
+                    self.seal_slot_usage_if_missing(&data_key);
+                    data_key
+                }
+            };
+
+            // 키 유도 비용을 다시 보정한 뒤 처음 사용된 슬롯은 새 매개변수로 다시 래핑
+            if let Err(e) = self.rewrap_pin_slot_if_needed(self.active_slot, pin, &data_key) {
+                log::warn!("래핑된 키 업그레이드 실패: {}", e);
+            }
+            return Ok(data_key);
         }
 
-        // SHA-256 해시
-        let mut hasher = Sha256::new();
-        hasher.update(&key_bytes);
-        let hash = hasher.finalize();
+        // 기존 볼트 마이그레이션
+        let data_key =
+            CryptoService::derive_key_with_params(pin.as_bytes(), &pin_info.salt, &pin_info.key_params)?;
+        self.key_store.pin = Some(self.wrap_with_pin(pin, &data_key)?);
+        self.save_key_store()?;
+        self.seal_slot_usage_if_missing(&data_key);
 
-        Ok(general_purpose::STANDARD.encode(&hash))
+        log::info!("기존 볼트의 마스터 키를 PIN 래핑 키로 이전했습니다.");
+        Ok(data_key)
     }
 
-    /// 복구 키가 일치하는지 검증합니다.
-    pub fn verify_recovery_key_hash(&self, input_recovery_key: &str, stored_hash: &str) -> bool {
-        if input_recovery_key.is_empty() || stored_hash.is_empty() {
+    /// 입력한 PIN이 일반 PIN과 일치하는지 확인합니다.
+    ///
+    /// 검증자에 키 파일이 포함되어 있으면 등록된 키 파일이 제공되어 있어야 일치할 수 있습니다.
+    fn matches_primary_pin(&self, pin: &str) -> bool {
+        let Some(info) = self.pin_info.as_ref() else {
             return false;
-        }
+        };
 
-        match self.hash_recovery_key(input_recovery_key) {
-            Ok(input_hash) => input_hash == stored_hash,
+        let key_file = if info.key_file {
+            match self.verified_key_file() {
+                Some(key_file) => Some(key_file),
+                None => return false,
+            }
+        } else {
+            None
+        };
+
+        match self.hash_pin_with_key_file(pin, key_file, &info.salt, &info.hash_params) {
+            Ok(hash) => constant_time_compare(hash.as_bytes(), info.hash.as_bytes()),
             Err(_) => false,
         }
     }
 
-    /// 복구 키를 생성합니다.
-    pub fn generate_recovery_key(&mut self) -> SecureVaultResult<String> {
-        // 32바이트 랜덤 키 생성
-        let mut key_bytes = [0u8; 32];
-        OsRng.fill_bytes(&mut key_bytes);
+    /// 입력한 PIN이 강압 PIN과 일치하는지 확인합니다.
+    ///
+    /// 슬롯은 항상 채워져 있으므로 강압 PIN 설정 여부와 관계없이 같은 비용의 해시를 계산합니다.
+    fn matches_duress_pin(&self, pin: &str) -> bool {
+        self.matches_slot_pin(DURESS_SLOT, pin)
+    }
 
-        let recovery_key = general_purpose::STANDARD.encode(&key_bytes);
+    /// 입력한 PIN이 패닉 PIN과 일치하는지 확인합니다.
+    fn matches_panic_pin(&self, pin: &str) -> bool {
+        self.matches_slot_pin(PANIC_SLOT, pin)
+    }
 
-        // 복구 키 해시 생성
-        let hash = self.hash_recovery_key(&recovery_key)?;
+    /// 익명 슬롯의 검증자와 비교합니다. 임의 데이터로 채운 슬롯은 어떤 PIN과도 일치하지 않습니다.
+    fn matches_slot_pin(&self, index: usize, pin: &str) -> bool {
+        self.pin_slots.get(index).is_some_and(|info| {
+            self.verify_pin_hash(pin, &info.hash, &info.salt, &info.hash_params)
+        })
+    }
 
-        // 복구 키 정보 저장
-        self.recovery_key_info = Some(SimpleRecoveryKeyInfo::new(hash));
+    /// 익명 슬롯을 설정된 PIN 개수와 관계없이 항상 `ALTERNATE_PIN_SLOTS`개로 채웁니다.
+    ///
+    /// 이 기능 이전에 만든 볼트를 처음 읽을 때와 새 볼트를 만들 때 호출됩니다.
+    fn pad_pin_slots(&mut self) {
+        if self.pin_info.is_none() {
+            return;
+        }
+
+        let params = self.kdf_params();
+        while self.pin_slots.len() < ALTERNATE_PIN_SLOTS {
+            self.pin_slots.push(Self::filler_pin_info(&params));
+        }
+        while self.key_store.slots.len() < ALTERNATE_PIN_SLOTS {
+            self.key_store.slots.push(Self::filler_key_slot(&params));
+        }
+    }
+
+    /// 사용하지 않는 슬롯에 기록할 검증자 (실제 검증자와 같은 형식의 임의 데이터)
+    fn filler_pin_info(params: &KdfParams) -> PinInfo {
+        let mut salt = [0u8; 32];
+        let mut hash = [0u8; 32];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut hash);
+
+        let mut pin_info = PinInfo::new(
+            general_purpose::STANDARD.encode(hash),
+            salt.to_vec(),
+            PinComplexity::Basic,
+        );
+        pin_info.hash_params = params.clone();
+        pin_info
+    }
+
+    /// 사용하지 않는 슬롯에 기록할 래핑된 키와 봉인 정보 (실제 슬롯과 같은 형식과 길이의 임의 데이터)
+    fn filler_key_slot(params: &KdfParams) -> KeySlot {
+        let mut salt = [0u8; 32];
+        let mut wrapped = [0u8; 12 + 32 + 16];
+        let mut sealed = vec![0u8; 12 + SEALED_SLOT_DATA_SIZE + 16];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut wrapped);
+        OsRng.fill_bytes(&mut sealed);
+
+        KeySlot {
+            key: WrappedKey {
+                salt: salt.to_vec(),
+                params: params.clone(),
+                wrapped_key: general_purpose::STANDARD.encode(wrapped),
+                key_file: false,
+            },
+            sealed: general_purpose::STANDARD.encode(sealed),
+        }
+    }
+
+    /// 값을 고정 크기로 채운 뒤 데이터 키에서 유도한 봉인 키로 암호화합니다.
+    fn seal_slot_data<T: Serialize>(seal_key: &SecretKey, value: &T) -> SecureVaultResult<String> {
+        let mut data = serde_json::to_vec(value)?;
+        if data.len() > SEALED_SLOT_DATA_SIZE {
+            data.zeroize();
+            return Err(
+                AuthError::InvalidInput("슬롯에 저장할 정보가 너무 큽니다.".to_string()).into(),
+            );
+        }
+        // JSON 뒤의 공백은 읽을 때 무시됨
+        data.resize(SEALED_SLOT_DATA_SIZE, b' ');
+
+        let sealed = CryptoService::seal_slot_data(seal_key, &data);
+        data.zeroize();
+        Ok(general_purpose::STANDARD.encode(sealed?))
+    }
+
+    /// `seal_slot_data`로 봉인한 값을 엽니다. 다른 키로 봉인했거나 임의 데이터면 None을 반환합니다.
+    fn open_slot_data<T: serde::de::DeserializeOwned>(
+        seal_key: &SecretKey,
+        sealed: &str,
+    ) -> Option<T> {
+        let sealed = general_purpose::STANDARD.decode(sealed).ok()?;
+        let mut data = CryptoService::open_slot_data(seal_key, &sealed).ok()?;
+        let value = serde_json::from_slice(&data).ok();
+        data.zeroize();
+        value
+    }
+
+    /// 일반 볼트 데이터 키로 봉인된 익명 슬롯 사용 여부를 읽습니다.
+    fn slot_usage(&self, data_key: &SecretKey) -> SlotUsage {
+        let seal_key = CryptoService::derive_slot_seal_key(data_key);
+        self.key_store
+            .slot_usage
+            .as_deref()
+            .and_then(|sealed| Self::open_slot_data(&seal_key, sealed))
+            .unwrap_or_default()
+    }
+
+    /// 익명 슬롯 사용 여부를 일반 볼트 데이터 키로 봉인합니다. (저장은 호출자가 담당)
+    fn seal_slot_usage(&mut self, data_key: &SecretKey, usage: SlotUsage) -> SecureVaultResult<()> {
+        let seal_key = CryptoService::derive_slot_seal_key(data_key);
+        self.key_store.slot_usage = Some(Self::seal_slot_data(&seal_key, &usage)?);
+        Ok(())
+    }
+
+    /// 이 기능 이전에 만든 볼트는 일반 PIN으로 처음 잠금 해제할 때 익명 슬롯을 저장합니다.
+    fn seal_slot_usage_if_missing(&mut self, data_key: &SecretKey) {
+        if self.key_store.slot_usage.is_some() {
+            return;
+        }
+
+        let result = self
+            .seal_slot_usage(data_key, SlotUsage::default())
+            .and_then(|_| self.save_credentials());
+        match result {
+            Ok(()) => {
+                self.replace_decoy_vault(&Self::random_vault_dir_name());
+            }
+            Err(e) => log::warn!("PIN 슬롯 저장 실패: {}", e),
+        }
+    }
+
+    /// 강압 PIN 슬롯에 봉인된 위장 볼트 정보를 엽니다.
+    ///
+    /// 봉인 정보를 읽을 수 없으면 새 위장 볼트 디렉토리로 다시 시작합니다.
+    fn open_decoy_state(&mut self, decoy_data_key: &SecretKey) -> SecureVaultResult<()> {
+        let seal_key = CryptoService::derive_slot_seal_key(decoy_data_key);
+        let state = self
+            .key_store
+            .slots
+            .get(DURESS_SLOT)
+            .and_then(|slot| Self::open_slot_data::<DecoyState>(&seal_key, &slot.sealed));
+
+        self.decoy_seal_key = Some(seal_key);
+        match state {
+            Some(state) => self.decoy_state = Some(state),
+            None => {
+                let state = Self::new_decoy_state();
+                self.replace_decoy_vault(&state.vault_dir);
+                self.decoy_state = Some(state);
+                self.reseal_decoy_state()?;
+                self.save_key_store()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 위장 볼트 세션의 정보를 강압 PIN 슬롯에 다시 봉인합니다. (저장은 호출자가 담당)
+    fn reseal_decoy_state(&mut self) -> SecureVaultResult<()> {
+        if let (Some(seal_key), Some(state)) = (&self.decoy_seal_key, &self.decoy_state) {
+            let sealed = Self::seal_slot_data(seal_key, state)?;
+            self.key_store.slots[DURESS_SLOT].sealed = sealed;
+        }
+        Ok(())
+    }
+
+    fn new_decoy_state() -> DecoyState {
+        DecoyState {
+            vault_dir: Self::random_vault_dir_name(),
+            critical: None,
+        }
+    }
+
+    /// 위장 볼트 디렉토리 이름 (임의 값이므로 이름으로는 자리 표시용 볼트와 구분할 수 없음)
+    fn random_vault_dir_name() -> String {
+        let mut name = [0u8; 16];
+        OsRng.fill_bytes(&mut name);
+        hex::encode(name)
+    }
+
+    /// `DECOY_VAULTS_DIR`의 기존 위장 볼트를 모두 지우고 주어진 이름의 빈 디렉토리 하나만 남깁니다.
+    ///
+    /// 이전 위장 볼트는 데이터 키가 버려져 복호화할 수 없습니다. 로그를 남기지 않습니다.
+    fn replace_decoy_vault(&self, vault_dir: &str) {
+        let decoy_vaults_dir = self.securevault_dir().join(DECOY_VAULTS_DIR);
+        if let Ok(entries) = fs::read_dir(&decoy_vaults_dir) {
+            for entry in entries.flatten() {
+                self.discard_path(&entry.path());
+            }
+        }
+        let _ = fs::create_dir_all(decoy_vaults_dir.join(vault_dir));
+        self.erase_discarded_in_background();
+    }
+
+    /// 파기할 파일이나 디렉토리를 `ERASE_DIR`로 옮깁니다. 옮기지 못하면 그 자리에서 파기합니다.
+    fn discard_path(&self, path: &Path) {
+        if fs::symlink_metadata(path).is_err() {
+            return;
+        }
+
+        let erase_dir = self.securevault_dir().join(ERASE_DIR);
+        let target = erase_dir.join(Self::random_vault_dir_name());
+        if fs::create_dir_all(&erase_dir)
+            .and_then(|_| fs::rename(path, &target))
+            .is_err()
+        {
+            Self::erase_tree(path);
+        }
+    }
+
+    /// `ERASE_DIR`에 옮겨 둔 파일을 백그라운드 스레드에서 덮어쓰고 삭제합니다.
+    ///
+    /// 키가 이미 파기되어 복호화할 수 없는 파일이므로 기다리지 않습니다. 볼트 크기에 따라
+    /// 로그인 시간이 달라지지 않게 하기 위함이며, 앱이 도중에 종료되면 다음 실행 때 이어서 파기합니다.
+    fn erase_discarded_in_background(&self) {
+        let erase_dir = self.securevault_dir().join(ERASE_DIR);
+        if erase_dir.exists() {
+            std::thread::spawn(move || Self::erase_tree(&erase_dir));
+        }
+    }
+
+    /// 파일은 `overwrite_and_remove_file`로 덮어쓴 뒤 삭제하고, 디렉토리는 내용을 모두 파기한 뒤
+    /// 삭제합니다. 심볼릭 링크는 대상을 건드리지 않고 링크만 삭제합니다.
+    fn erase_tree(path: &Path) {
+        let Ok(metadata) = fs::symlink_metadata(path) else {
+            return;
+        };
+
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(path) {
+                for entry in entries.flatten() {
+                    Self::erase_tree(&entry.path());
+                }
+            }
+            let _ = fs::remove_dir(path);
+        } else if metadata.file_type().is_symlink()
+            || crate::utils::overwrite_and_remove_file(path).is_err()
+        {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    /// 강압 PIN 슬롯을 기록하고 위장 볼트 데이터 키를 래핑합니다.
+    ///
+    /// 슬롯 검증자에는 PIN 종류나 키 파일 사용 여부를 기록하지 않습니다.
+    fn store_duress_pin(&mut self, pin: &str, data_key: &SecretKey) -> SecureVaultResult<()> {
+        let mut salt = [0u8; 32];
+        OsRng.fill_bytes(&mut salt);
+
+        let params = self.kdf_params();
+        let hash = self.hash_pin(pin, &salt, &params)?;
+        let wrapped = Self::wrap_data_key(pin.as_bytes(), data_key, &params)?;
+
+        let mut pin_info = PinInfo::new(hash, salt.to_vec(), PinComplexity::Basic);
+        pin_info.hash_params = params;
+        self.pin_slots[DURESS_SLOT] = pin_info;
+        self.key_store.slots[DURESS_SLOT].key = wrapped;
+        Ok(())
+    }
+
+    /// 강압(duress) PIN을 설정합니다.
+    ///
+    /// 강압 PIN으로 로그인하면 별도의 위장 볼트(메타데이터 DB와 파일 디렉토리)가 열리며,
+    /// 로그와 처리 시간은 일반 로그인과 같습니다. 강압 PIN은 익명 슬롯에 기록되므로
+    /// auth.json과 keys.json만으로는 설정 여부를 알 수 없습니다. 위장 볼트 세션에서 호출하면
+    /// 아무것도 변경하지 않고 성공한 것처럼 반환합니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - 강압 PIN (일반 PIN, 패닉 PIN과 같을 수 없음)
+    /// * `decoy_data_key` - 새 위장 볼트 데이터 키
+    /// * `data_key` - 현재 볼트 데이터 키 (슬롯 사용 여부 봉인용)
+    pub fn set_duress_pin(
+        &mut self,
+        pin: &str,
+        decoy_data_key: &SecretKey,
+        data_key: &SecretKey,
+    ) -> SecureVaultResult<()> {
+        let pin = &Self::normalize_pin(pin);
+        self.validate_pin_format(pin)?;
+
+        if self.is_decoy_session() {
+            return Ok(());
+        }
+
+        if self.pin_info.is_none() {
+            return Err(AuthError::NoPinSet.into());
+        }
+        if self.matches_primary_pin(pin) || self.matches_panic_pin(pin) {
+            return Err(AuthError::InvalidInput(
+                "강압 PIN은 다른 PIN과 달라야 합니다.".to_string(),
+            )
+            .into());
+        }
+
+        self.store_duress_pin(pin, decoy_data_key)?;
+
+        // 새 위장 볼트의 디렉토리 이름은 위장 볼트 데이터 키로 봉인
+        let state = Self::new_decoy_state();
+        let seal_key = CryptoService::derive_slot_seal_key(decoy_data_key);
+        self.key_store.slots[DURESS_SLOT].sealed = Self::seal_slot_data(&seal_key, &state)?;
+
+        let mut usage = self.slot_usage(data_key);
+        usage.duress = true;
+        self.seal_slot_usage(data_key, usage)?;
+
+        self.save_credentials()?;
+        self.replace_decoy_vault(&state.vault_dir);
+        Ok(())
+    }
+
+    /// 패닉 PIN을 설정합니다.
+    ///
+    /// 패닉 PIN으로 로그인하면 래핑된 키와 인증 기록이 파기되어 볼트를 영구히 복호화할 수
+    /// 없게 되고, 설정할 때 미리 만들어 둔 빈 볼트가 열립니다. 위장 볼트 세션에서 호출하면
+    /// 아무것도 변경하지 않고 성공한 것처럼 반환합니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - 패닉 PIN (일반 PIN, 강압 PIN과 같을 수 없음)
+    /// * `data_key` - 현재 볼트 데이터 키 (슬롯 사용 여부 봉인용)
+    pub fn set_panic_pin(&mut self, pin: &str, data_key: &SecretKey) -> SecureVaultResult<()> {
+        let pin = &Self::normalize_pin(pin);
+        self.validate_pin_format(pin)?;
+
+        if self.is_decoy_session() {
+            return Ok(());
+        }
+
+        if self.pin_info.is_none() {
+            return Err(AuthError::NoPinSet.into());
+        }
+        if self.matches_primary_pin(pin) || self.matches_duress_pin(pin) {
+            return Err(AuthError::InvalidInput(
+                "패닉 PIN은 다른 PIN과 달라야 합니다.".to_string(),
+            )
+            .into());
+        }
+
+        let mut salt = [0u8; 32];
+        OsRng.fill_bytes(&mut salt);
+        let params = self.kdf_params();
+        let hash = self.hash_pin(pin, &salt, &params)?;
+
+        // 패닉 PIN으로 열릴 빈 볼트의 래핑된 키와 슬롯 사용 정보를 미리 만들어 둠
+        let panic_data_key = CryptoService::generate_data_key();
+        let key = Self::wrap_data_key(pin.as_bytes(), &panic_data_key, &params)?;
+        let seal_key = CryptoService::derive_slot_seal_key(&panic_data_key);
+        let sealed = Self::seal_slot_data(&seal_key, &SlotUsage::default())?;
+
+        let mut pin_info = PinInfo::new(hash, salt.to_vec(), PinComplexity::Basic);
+        pin_info.hash_params = params;
+        self.pin_slots[PANIC_SLOT] = pin_info;
+        self.key_store.slots[PANIC_SLOT] = KeySlot { key, sealed };
+
+        let mut usage = self.slot_usage(data_key);
+        usage.panic = true;
+        self.seal_slot_usage(data_key, usage)?;
+
+        self.save_credentials()?;
+        Ok(())
+    }
+
+    /// 패닉 PIN을 제거합니다. 위장 볼트 세션에서는 아무것도 하지 않습니다.
+    ///
+    /// # 매개변수
+    /// * `data_key` - 현재 볼트 데이터 키 (슬롯 사용 여부 봉인용)
+    pub fn remove_panic_pin(&mut self, data_key: &SecretKey) -> SecureVaultResult<()> {
+        if self.is_decoy_session() {
+            return Ok(());
+        }
+
+        let params = self.kdf_params();
+        self.pin_slots[PANIC_SLOT] = Self::filler_pin_info(&params);
+        self.key_store.slots[PANIC_SLOT] = Self::filler_key_slot(&params);
+
+        let mut usage = self.slot_usage(data_key);
+        usage.panic = false;
+        self.seal_slot_usage(data_key, usage)?;
+
+        self.save_credentials()?;
+        Ok(())
+    }
+
+    /// 패닉 PIN 설정 여부를 반환합니다. 위장 볼트 세션에서는 항상 false입니다.
+    ///
+    /// # 매개변수
+    /// * `data_key` - 현재 볼트 데이터 키
+    pub fn has_panic_pin(&self, data_key: &SecretKey) -> bool {
+        !self.is_decoy_session() && self.slot_usage(data_key).panic
+    }
+
+    /// 볼트를 암호학적으로 파기합니다.
+    ///
+    /// 파일 자체를 덮어쓰지 않고, 데이터 키를 열 수 있는 유일한 재료인 래핑된 키와
+    /// 인증 기록, 평문 메타데이터 DB, 로그(메모리에 보관 중인 기록 포함)를 제거합니다.
+    /// 볼트 크기와 관계없이 즉시 끝나며, 이후 `.securevault/files`의 암호문은 PIN이나
+    /// 복구 키로도 복호화할 수 없습니다. 로그를 남기지 않습니다.
+    fn crypto_erase(&mut self) {
+        let securevault_dir = self.securevault_dir();
+
+        // 래핑된 키는 삭제 전에 랜덤 데이터로 덮어씀
+        for name in ["keys.json", "keys.json.tmp"] {
+            let path = self.config_dir.join(name);
+            if let Ok(metadata) = fs::metadata(&path) {
+                let mut noise = vec![0u8; metadata.len() as usize];
+                OsRng.fill_bytes(&mut noise);
+                let _ = fs::write(&path, &noise);
+                let _ = fs::remove_file(&path);
+            }
+        }
+
+        for name in ["auth.json", "auth.json.tmp", "lockout.json", "lockout.json.tmp"] {
+            let _ = fs::remove_file(self.config_dir.join(name));
+        }
+
+        // 암호화된 파일, 메타데이터 DB, 업로드 임시 파일, 로그, 위장 볼트는 파기 디렉토리로 옮긴 뒤
+        // 백그라운드에서 덮어쓰고 삭제
+        crate::services::log_sink::detach();
+        for name in [
+            "files",
+            "tmp",
+            "logs",
+            "metadata.db",
+            "metadata.db-wal",
+            "metadata.db-shm",
+            "metadata.db-journal",
+            DECOY_VAULTS_DIR,
+        ] {
+            self.discard_path(&securevault_dir.join(name));
+        }
+        self.erase_discarded_in_background();
+
+        // 메모리 상태 초기화
+        self.pin_info = None;
+        self.recovery_key_info = None;
+        self.superseded_recovery_keys.clear();
+        self.pin_slots.clear();
+        self.decoy_state = None;
+        self.decoy_seal_key = None;
+        self.key_store = KeyStore::default();
+        self.key_file_verifier = None;
+        self.provide_key_file(None);
+        self.end_step_up();
+        self.security_config = SecurityConfig::default();
+        self.brute_force_protection = BruteForceProtection::new();
+        self.lockout_key = None;
+        self.current_session = None;
+        self.active_slot = PinSlot::Primary;
+    }
+
+    /// 기존 볼트를 파기하고 패닉 PIN 슬롯에 미리 만들어 둔 빈 볼트로 바꿉니다.
+    ///
+    /// 검증자와 래핑된 키는 패닉 PIN을 설정할 때 계산해 두었으므로 여기서는 키 유도를 하지
+    /// 않습니다. 잠금 해제까지의 키 유도 횟수가 일반 로그인과 같아 처리 시간으로 구분할 수 없습니다.
+    fn open_panic_vault(&mut self, pin: &str) -> SecureVaultResult<()> {
+        let mut pin_info = self.pin_slots[PANIC_SLOT].clone();
+        let slot = self.key_store.slots[PANIC_SLOT].clone();
+        self.crypto_erase();
+
+        pin_info.is_passphrase = !pin.chars().all(|c| c.is_ascii_digit());
+        self.key_store.kdf_params = Some(slot.key.params.clone());
+        self.key_store.pin = Some(slot.key);
+        self.key_store.slot_usage = Some(slot.sealed);
+        self.pin_info = Some(pin_info);
+        self.pin_upgrade_required = false;
+        self.pad_pin_slots();
+        self.replace_decoy_vault(&Self::random_vault_dir_name());
+
+        self.save_credentials()?;
+        self.save_brute_force_state();
+        Ok(())
+    }
+
+    /// 강압 PIN을 제거합니다. 위장 볼트 세션에서는 아무것도 하지 않습니다.
+    ///
+    /// 위장 볼트는 삭제되고 자리 표시용 빈 디렉토리로 바뀝니다.
+    ///
+    /// # 매개변수
+    /// * `data_key` - 현재 볼트 데이터 키 (슬롯 사용 여부 봉인용)
+    pub fn remove_duress_pin(&mut self, data_key: &SecretKey) -> SecureVaultResult<()> {
+        if self.is_decoy_session() {
+            return Ok(());
+        }
+
+        let params = self.kdf_params();
+        self.pin_slots[DURESS_SLOT] = Self::filler_pin_info(&params);
+        self.key_store.slots[DURESS_SLOT] = Self::filler_key_slot(&params);
+
+        let mut usage = self.slot_usage(data_key);
+        usage.duress = false;
+        self.seal_slot_usage(data_key, usage)?;
+
+        self.save_credentials()?;
+        self.replace_decoy_vault(&Self::random_vault_dir_name());
+        Ok(())
+    }
+
+    /// 강압 PIN 설정 여부를 반환합니다. 위장 볼트 세션에서는 항상 false입니다.
+    ///
+    /// # 매개변수
+    /// * `data_key` - 현재 볼트 데이터 키
+    pub fn has_duress_pin(&self, data_key: &SecretKey) -> bool {
+        !self.is_decoy_session() && self.slot_usage(data_key).duress
+    }
+
+    /// 현재 세션이 강압 PIN으로 열린 위장 볼트인지 확인합니다.
+    pub fn is_decoy_session(&self) -> bool {
+        self.active_slot == PinSlot::Duress
+    }
+
+    /// 볼트 설정 디렉토리의 상위 디렉토리 (.securevault)
+    fn securevault_dir(&self) -> PathBuf {
+        self.config_dir
+            .parent()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(".securevault"))
+    }
+
+    /// 현재 세션에서 사용할 볼트 루트 디렉토리를 반환합니다.
+    ///
+    /// 일반 세션은 `.securevault`의 상위 디렉토리를, 위장 볼트 세션은 강압 PIN 슬롯에
+    /// 봉인된 이름의 `.securevault/data/vaults` 하위 디렉토리를 루트로 사용합니다.
+    pub fn active_vault_root(&self) -> PathBuf {
+        let securevault_dir = self.securevault_dir();
+
+        if !self.is_decoy_session() {
+            return securevault_dir
+                .parent()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from("."));
+        }
+
+        // 위장 볼트 위치는 강압 PIN으로 잠금 해제한 뒤에만 알 수 있음
+        let decoy_vaults_dir = securevault_dir.join(DECOY_VAULTS_DIR);
+        match &self.decoy_state {
+            Some(state) => decoy_vaults_dir.join(&state.vault_dir),
+            None => decoy_vaults_dir,
+        }
+    }
+
+    /// 복구 키로 볼트 데이터 키를 언래핑합니다.
+    ///
+    /// # 매개변수
+    /// * `recovery_key` - `verify_recovery_key`로 검증된 복구 키 (24단어 또는 기존 Base64)
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<SecretKey>` - 볼트 데이터 키
+    ///
+    /// # 오류
+    /// * `AuthError::InvalidRecoveryKey` - 복구 키로 래핑된 데이터 키가 없음
+    pub fn unlock_with_recovery_key(&self, recovery_key: &str) -> SecureVaultResult<SecretKey> {
+        let wrapped = self
+            .key_store
+            .recovery
+            .as_ref()
+            .ok_or(AuthError::InvalidRecoveryKey)?;
+
+        let mut key_bytes =
+            decode_recovery_key(recovery_key).map_err(|_| AuthError::InvalidRecoveryKey)?;
+        let result = Self::unwrap_data_key(&key_bytes, wrapped);
+        key_bytes.zeroize();
+
+        result
+    }
+
+    /// 복구 키로 래핑된 데이터 키가 있는지 확인합니다.
+    pub fn has_recovery_wrapped_key(&self) -> bool {
+        self.key_store.recovery.is_some()
+    }
+
+    /// PIN과 (등록되어 있다면) 키 파일로 일반 PIN 슬롯의 데이터 키를 래핑합니다.
+    fn wrap_with_pin(&self, pin: &str, data_key: &SecretKey) -> SecureVaultResult<WrappedKey> {
+        let key_file = self.verified_key_file();
+        let mut material = Self::pin_key_material(pin, key_file);
+        let wrapped = Self::wrap_data_key(&material, data_key, &self.kdf_params());
+        material.zeroize();
+
+        let mut wrapped = wrapped?;
+        wrapped.key_file = key_file.is_some();
+        Ok(wrapped)
+    }
+
+    /// 일반 PIN 슬롯의 데이터 키를 언래핑합니다. 키 파일로 래핑된 경우 키 파일이 필요합니다.
+    fn unwrap_with_pin(&self, pin: &str, wrapped: &WrappedKey) -> SecureVaultResult<SecretKey> {
+        let key_file = if wrapped.key_file {
+            Some(self.key_file_secret.as_ref().ok_or(AuthError::KeyFileRequired)?)
+        } else {
+            None
+        };
+
+        let mut material = Self::pin_key_material(pin, key_file);
+        let result = Self::unwrap_data_key(&material, wrapped);
+        material.zeroize();
+
+        result
+    }
+
+    /// KEK 유도에 사용할 비밀값을 만듭니다. 키 파일 해시는 PIN 뒤에 구분자와 함께 붙입니다.
+    fn pin_key_material(pin: &str, key_file: Option<&SecretKey>) -> Vec<u8> {
+        let mut material = pin.as_bytes().to_vec();
+        if let Some(digest) = key_file {
+            // PIN에는 제어 문자가 없으므로 NUL 구분자로 경계가 모호해지지 않음
+            material.push(0);
+            material.extend_from_slice(digest.expose());
+        }
+        material
+    }
+
+    /// 새 키 파일 내용(랜덤 바이트)을 생성합니다.
+    pub fn generate_key_file_contents() -> Vec<u8> {
+        let mut contents = vec![0u8; KEY_FILE_SIZE];
+        OsRng.fill_bytes(&mut contents);
+        contents
+    }
+
+    /// 키 파일 내용을 해시합니다. 어떤 파일이든 키 파일로 사용할 수 있습니다.
+    fn key_file_digest(contents: &[u8]) -> SecretKey {
+        let mut hasher = Sha256::new();
+        hasher.update(KEY_FILE_CONTEXT);
+        hasher.update(contents);
+        SecretKey::from_bytes(&mut hasher.finalize().into())
+    }
+
+    /// 키 파일 해시로부터 저장용 검증자를 만듭니다.
+    fn key_file_verifier_for(digest: &SecretKey) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(KEY_FILE_VERIFIER_CONTEXT);
+        hasher.update(digest.expose());
+        general_purpose::STANDARD.encode(hasher.finalize())
+    }
+
+    /// 제공된 키 파일이 등록된 키 파일과 일치하면 그 해시를 반환합니다.
+    fn verified_key_file(&self) -> Option<&SecretKey> {
+        let verifier = self.key_file_verifier.as_ref()?;
+        let digest = self.key_file_secret.as_ref()?;
+        let candidate = Self::key_file_verifier_for(digest);
+
+        if constant_time_compare(candidate.as_bytes(), verifier.as_bytes()) {
+            Some(digest)
+        } else {
+            None
+        }
+    }
+
+    /// 로그인에 사용할 키 파일을 제공하거나(Some) 메모리에서 지웁니다(None).
+    ///
+    /// # 매개변수
+    /// * `contents` - 키 파일 내용
+    pub fn provide_key_file(&mut self, contents: Option<&[u8]>) {
+        self.key_file_secret = contents.map(Self::key_file_digest);
+    }
+
+    /// 키 파일이 등록되어 있는지 확인합니다.
+    pub fn has_key_file(&self) -> bool {
+        self.key_file_verifier.is_some()
+    }
+
+    /// 키 파일을 등록하거나 교체합니다.
+    ///
+    /// 데이터 키를 PIN과 키 파일을 함께 사용한 KEK로 다시 래핑하므로, 이후에는 두 가지가
+    /// 모두 있어야 볼트를 열 수 있습니다. 복구 키 래핑은 그대로 유지됩니다.
+    /// 위장 볼트 세션에서는 아무것도 변경하지 않고 성공한 것처럼 반환합니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - 현재 PIN
+    /// * `contents` - 새 키 파일 내용
+    /// * `data_key` - 현재 볼트 데이터 키
+    pub fn enroll_key_file(
+        &mut self,
+        pin: &str,
+        contents: &[u8],
+        data_key: &SecretKey,
+    ) -> SecureVaultResult<()> {
+        if contents.is_empty() {
+            return Err(AuthError::InvalidInput("키 파일이 비어 있습니다.".to_string()).into());
+        }
+
+        let pin = &Self::normalize_pin(pin);
+        self.confirm_primary_pin(pin)?;
+
+        if self.is_decoy_session() {
+            return Ok(());
+        }
+
+        self.provide_key_file(Some(contents));
+        self.key_file_verifier = self.key_file_secret.as_ref().map(Self::key_file_verifier_for);
+        self.security_config.two_factor_enabled = true;
+
+        self.rehash_primary_pin(pin)?;
+        self.key_store.pin = Some(self.wrap_with_pin(pin, data_key)?);
+        self.save_credentials()?;
+
+        log::info!("키 파일이 등록되었습니다.");
+        Ok(())
+    }
+
+    /// 키 파일 등록을 해제하고 데이터 키를 PIN만으로 다시 래핑합니다.
+    /// 위장 볼트 세션에서는 아무것도 변경하지 않고 성공한 것처럼 반환합니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - 현재 PIN
+    /// * `data_key` - 현재 볼트 데이터 키
+    pub fn remove_key_file(&mut self, pin: &str, data_key: &SecretKey) -> SecureVaultResult<()> {
+        let pin = &Self::normalize_pin(pin);
+        self.confirm_primary_pin(pin)?;
+
+        if self.is_decoy_session() {
+            return Ok(());
+        }
+
+        self.provide_key_file(None);
+        self.key_file_verifier = None;
+        self.security_config.two_factor_enabled = false;
+
+        self.rehash_primary_pin(pin)?;
+        self.key_store.pin = Some(self.wrap_with_pin(pin, data_key)?);
+        self.save_credentials()?;
+
+        log::info!("키 파일 등록이 해제되었습니다.");
+        Ok(())
+    }
+
+    /// 일반 PIN 검증자를 현재 키 파일 등록 상태에 맞춰 다시 만듭니다.
+    fn rehash_primary_pin(&mut self, pin: &str) -> SecureVaultResult<()> {
+        let params = self.kdf_params();
+        let salt = match self.pin_info.as_ref() {
+            Some(info) => info.salt.clone(),
+            None => return Err(AuthError::NoPinSet.into()),
+        };
+        let key_file = self.verified_key_file();
+        let uses_key_file = key_file.is_some();
+        let hash = self.hash_pin_with_key_file(pin, key_file, &salt, &params)?;
+
+        if let Some(info) = self.pin_info.as_mut() {
+            info.hash = hash;
+            info.hash_params = params;
+            info.key_file = uses_key_file;
+        }
+        Ok(())
+    }
+
+    /// 민감한 설정 변경 전에 현재 세션의 PIN을 다시 확인합니다. 실패는 브루트포스 기록에 포함됩니다.
+    fn confirm_primary_pin(&mut self, pin: &str) -> SecureVaultResult<()> {
+        let matches = if self.is_decoy_session() {
+            self.matches_duress_pin(pin)
+        } else {
+            if self.pin_info.is_none() {
+                return Err(AuthError::NoPinSet.into());
+            }
+            self.matches_primary_pin(pin)
+        };
+
+        if !matches {
+            self.brute_force_protection.record_failure();
+            self.save_brute_force_state();
+            return Err(AuthError::AuthenticationFailed.into());
+        }
+
+        Ok(())
+    }
+
+    /// 높은 보안 등급 파일에 접근하기 위해 PIN을 다시 확인합니다.
+    ///
+    /// 성공하면 `STEP_UP_WINDOW_SECONDS` 동안 재확인 상태가 유지됩니다.
+    /// 실패는 로그인 실패와 같이 브루트포스 기록에 포함됩니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - 현재 PIN
+    pub fn step_up(&mut self, pin: &str) -> SecureVaultResult<()> {
+        let pin = &Self::normalize_pin(pin);
+        self.ensure_not_locked_out()?;
+        Self::validate_pin_input(pin)?;
+        self.confirm_primary_pin(pin)?;
+
+        self.step_up_at = Some(Instant::now());
+        log::info!("PIN 재확인이 완료되었습니다.");
+        Ok(())
+    }
+
+    /// PIN 재확인이 유효 시간 안에 있는지 확인합니다.
+    /// 만료되었으면 메모리의 최고 보안 파일 키도 함께 지웁니다.
+    pub fn has_recent_step_up(&mut self) -> bool {
+        let active = self
+            .step_up_at
+            .is_some_and(|at| at.elapsed() < Duration::from_secs(STEP_UP_WINDOW_SECONDS));
+
+        if !active {
+            self.end_step_up();
+        }
+        active
+    }
+
+    /// PIN 재확인 상태를 끝내고 최고 보안 파일 키를 메모리에서 지웁니다.
+    pub fn end_step_up(&mut self) {
+        self.step_up_at = None;
+        self.critical_key = None;
+    }
+
+    /// 최고 보안 암호가 설정되어 있는지 확인합니다.
+    pub fn has_critical_passphrase(&self) -> bool {
+        self.critical_slot().is_some()
+    }
+
+    /// 최고 보안 암호를 처음 설정하고 최고 보안 파일 키를 생성합니다.
+    ///
+    /// 최고 보안 파일 키는 볼트 데이터 키와 별개의 랜덤 키이며, 이 암호로만 열 수 있습니다.
+    /// PIN이나 복구 키로는 복구할 수 없으므로 암호를 잊으면 최고 보안 파일도 열 수 없습니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - 현재 PIN
+    /// * `passphrase` - 새 최고 보안 암호 (PIN과 달라야 함)
+    pub fn set_critical_passphrase(&mut self, pin: &str, passphrase: &str) -> SecureVaultResult<()> {
+        if self.has_critical_passphrase() {
+            return Err(AuthError::InvalidInput("최고 보안 암호가 이미 설정되어 있습니다.".to_string()).into());
+        }
+
+        let pin = &Self::normalize_pin(pin);
+        let passphrase = &Self::normalize_pin(passphrase);
+        self.ensure_not_locked_out()?;
+        self.confirm_primary_pin(pin)?;
+        self.validate_critical_passphrase(passphrase)?;
+
+        let critical_key = CryptoService::generate_data_key();
+        self.set_critical_slot(Self::wrap_critical_key(
+            passphrase,
+            &critical_key,
+            &self.kdf_params(),
+        )?)?;
+        self.save_key_store()?;
+
+        // PIN을 방금 확인했으므로 바로 최고 보안 파일에 접근 가능
+        self.end_step_up();
+        self.step_up_at = Some(Instant::now());
+        self.critical_key = Some(Arc::new(critical_key));
+
+        log::info!("최고 보안 암호가 설정되었습니다.");
+        Ok(())
+    }
+
+    /// 최고 보안 암호를 변경합니다. 최고 보안 파일 키는 그대로이므로 파일을 다시 암호화하지 않습니다.
+    ///
+    /// # 매개변수
+    /// * `old_passphrase` - 현재 최고 보안 암호
+    /// * `new_passphrase` - 새 최고 보안 암호
+    pub fn change_critical_passphrase(
+        &mut self,
+        old_passphrase: &str,
+        new_passphrase: &str,
+    ) -> SecureVaultResult<()> {
+        let old_passphrase = &Self::normalize_pin(old_passphrase);
+        let new_passphrase = &Self::normalize_pin(new_passphrase);
+
+        self.validate_critical_passphrase(new_passphrase)?;
+
+        let critical_key = self.unwrap_critical_key(old_passphrase)?;
+        self.set_critical_slot(Self::wrap_critical_key(
+            new_passphrase,
+            &critical_key,
+            &self.kdf_params(),
+        )?)?;
+        self.save_key_store()?;
+
+        log::info!("최고 보안 암호가 변경되었습니다.");
+        Ok(())
+    }
+
+    /// 최고 보안 암호로 최고 보안 파일 키를 잠금 해제합니다.
+    /// PIN 재확인 유효 시간 동안만 메모리에 보관됩니다.
+    ///
+    /// # 매개변수
+    /// * `passphrase` - 최고 보안 암호
+    pub fn unlock_critical_key(&mut self, passphrase: &str) -> SecureVaultResult<()> {
+        if !self.has_recent_step_up() {
+            return Err(AuthError::InvalidInput("먼저 PIN을 다시 확인해야 합니다.".to_string()).into());
+        }
+
+        let passphrase = &Self::normalize_pin(passphrase);
+        let critical_key = self.unwrap_critical_key(passphrase)?;
+
+        // 키 유도 비용을 다시 보정한 뒤 처음 사용되었다면 새 매개변수로 다시 래핑
+        let params = self.kdf_params();
+        if self
+            .critical_slot()
+            .is_some_and(|wrapped| wrapped.params != params)
+        {
+            let upgraded = Self::wrap_critical_key(passphrase, &critical_key, &params)
+                .and_then(|wrapped| self.set_critical_slot(wrapped))
+                .and_then(|_| self.save_key_store());
+            if let Err(e) = upgraded {
+                log::warn!("최고 보안 키 업그레이드 실패: {}", e);
+            }
+        }
+
+        self.critical_key = Some(Arc::new(critical_key));
+        Ok(())
+    }
+
+    /// PIN 재확인 유효 시간 안이면 잠금 해제된 최고 보안 파일 키를 반환합니다.
+    pub fn critical_key(&mut self) -> Option<Arc<SecretKey>> {
+        if self.has_recent_step_up() {
+            self.critical_key.clone()
+        } else {
+            None
+        }
+    }
+
+    /// 현재 세션(일반/위장 볼트)의 최고 보안 파일 키 슬롯
+    fn critical_slot(&self) -> Option<&WrappedKey> {
+        if self.is_decoy_session() {
+            self.decoy_state.as_ref().and_then(|state| state.critical.as_ref())
+        } else {
+            self.key_store.critical.as_ref()
+        }
+    }
+
+    /// 현재 세션의 최고 보안 파일 키 슬롯을 교체합니다. (저장은 호출자가 담당)
+    ///
+    /// 위장 볼트의 슬롯은 강압 PIN 슬롯에 봉인되므로 keys.json에 따로 드러나지 않습니다.
+    fn set_critical_slot(&mut self, wrapped: WrappedKey) -> SecureVaultResult<()> {
+        if !self.is_decoy_session() {
+            self.key_store.critical = Some(wrapped);
+            return Ok(());
+        }
+
+        let state = self.decoy_state.as_mut().ok_or(AuthError::NoPinSet)?;
+        state.critical = Some(wrapped);
+        self.reseal_decoy_state()
+    }
+
+    /// 최고 보안 암호로 래핑된 키를 언래핑합니다. 실패는 브루트포스 기록에 포함됩니다.
+    fn unwrap_critical_key(&mut self, passphrase: &str) -> SecureVaultResult<SecretKey> {
+        self.ensure_not_locked_out()?;
+        let wrapped = self
+            .critical_slot()
+            .ok_or_else(|| AuthError::InvalidInput("최고 보안 암호가 설정되지 않았습니다.".to_string()))?;
+
+        let mut material = Self::critical_key_material(passphrase);
+        let result = Self::unwrap_data_key(&material, wrapped);
+        material.zeroize();
+
+        if result.is_err() {
+            self.brute_force_protection.record_failure();
+            self.save_brute_force_state();
+            return Err(AuthError::AuthenticationFailed.into());
+        }
+        result
+    }
+
+    fn wrap_critical_key(
+        passphrase: &str,
+        critical_key: &SecretKey,
+        params: &KdfParams,
+    ) -> SecureVaultResult<WrappedKey> {
+        let mut material = Self::critical_key_material(passphrase);
+        let wrapped = Self::wrap_data_key(&material, critical_key, params);
+        material.zeroize();
+        wrapped
+    }
+
+    /// 최고 보안 암호 KEK 유도에 사용할 비밀값 (PIN 슬롯과 도메인 분리)
+    fn critical_key_material(passphrase: &str) -> Vec<u8> {
+        let mut material = CRITICAL_PASSPHRASE_CONTEXT.to_vec();
+        material.push(0);
+        material.extend_from_slice(passphrase.as_bytes());
+        material
+    }
+
+    /// 최고 보안 암호 형식을 검증합니다. 현재 세션의 PIN과 같은 암호는 허용하지 않습니다.
+    fn validate_critical_passphrase(&self, passphrase: &str) -> SecureVaultResult<()> {
+        let length = passphrase.chars().count();
+        if length < MIN_CRITICAL_PASSPHRASE_LENGTH
+            || length > MAX_PIN_LENGTH
+            || passphrase.chars().any(|c| c.is_control())
+        {
+            return Err(AuthError::InvalidInput(format!(
+                "최고 보안 암호는 {}자 이상이어야 합니다.",
+                MIN_CRITICAL_PASSPHRASE_LENGTH
+            ))
+            .into());
+        }
+
+        let same_as_pin = if self.is_decoy_session() {
+            self.matches_duress_pin(passphrase)
+        } else {
+            self.matches_primary_pin(passphrase)
+        };
+
+        if same_as_pin {
+            return Err(AuthError::InvalidInput("최고 보안 암호는 PIN과 달라야 합니다.".to_string()).into());
+        }
+
+        Ok(())
+    }
+
+    /// 브루트포스 잠금 중이면 남은 시간과 함께 오류를 반환합니다.
+    fn ensure_not_locked_out(&self) -> SecureVaultResult<()> {
+        match self.brute_force_protection.remaining_lockout_seconds() {
+            Some(remaining) => {
+                Err(AuthError::BruteForceProtection(Duration::from_secs(remaining)).into())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// 비밀값에서 KEK를 유도하여 데이터 키를 래핑합니다.
+    fn wrap_data_key(
+        secret: &[u8],
+        data_key: &SecretKey,
+        params: &KdfParams,
+    ) -> SecureVaultResult<WrappedKey> {
+        let mut salt = [0u8; 32];
+        OsRng.fill_bytes(&mut salt);
+
+        let kek = CryptoService::derive_key_with_params(secret, &salt, params)?;
+        let wrapped = CryptoService::wrap_key(&kek, data_key);
+
+        Ok(WrappedKey {
+            salt: salt.to_vec(),
+            params: params.clone(),
+            wrapped_key: general_purpose::STANDARD.encode(wrapped?),
+            key_file: false,
+        })
+    }
+
+    /// 비밀값에서 KEK를 유도하여 데이터 키를 언래핑합니다.
+    fn unwrap_data_key(secret: &[u8], wrapped: &WrappedKey) -> SecureVaultResult<SecretKey> {
+        let wrapped_bytes = general_purpose::STANDARD
+            .decode(&wrapped.wrapped_key)
+            .map_err(|_| AuthError::InvalidHash)?;
+
+        let kek = CryptoService::derive_key_with_params(secret, &wrapped.salt, &wrapped.params)?;
+        CryptoService::unwrap_key(&kek, &wrapped_bytes)
+    }
+
+    /// 이 볼트에서 PIN/암호를 새로 해시하거나 래핑할 때 사용하는 키 유도 매개변수를 반환합니다.
+    ///
+    /// # 반환값
+    /// * `KdfParams` - 보정된 매개변수 (보정한 적이 없으면 기본값)
+    pub fn kdf_params(&self) -> KdfParams {
+        self.key_store.kdf_params.clone().unwrap_or_default()
+    }
+
+    /// 현재 PC를 측정하여 목표 잠금 해제 시간에 맞는 키 유도 매개변수를 정합니다.
+    ///
+    /// 측정에는 수 초가 걸릴 수 있으므로 앱 상태 잠금을 잡기 전에 호출하고, 결과는
+    /// `apply_kdf_calibration`으로 저장합니다.
+    ///
+    /// # 매개변수
+    /// * `target_unlock` - 목표 잠금 해제 시간 (로그인 한 번의 모든 키 유도 포함)
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<KdfCalibration>` - 보정 결과
+    pub fn measure_kdf(target_unlock: Duration) -> SecureVaultResult<KdfCalibration> {
+        let (params, derivation) =
+            CryptoService::calibrate_kdf(target_unlock / KDF_DERIVATIONS_PER_UNLOCK)?;
+
+        let derivation_ms = derivation.as_millis() as u64;
+        Ok(KdfCalibration {
+            params,
+            derivation_ms,
+            target_unlock_ms: target_unlock.as_millis() as u64,
+            estimated_unlock_ms: derivation_ms * u64::from(KDF_DERIVATIONS_PER_UNLOCK),
+        })
+    }
+
+    /// 측정한 키 유도 매개변수를 이 볼트의 기본값으로 저장합니다.
+    ///
+    /// 이미 저장된 검증자와 래핑된 키는 그대로 두며, 각 PIN/암호가 다음에 사용될 때
+    /// 새 매개변수로 다시 만들어집니다.
+    ///
+    /// # 매개변수
+    /// * `calibration` - `measure_kdf`의 보정 결과
+    pub fn apply_kdf_calibration(&mut self, calibration: &KdfCalibration) -> SecureVaultResult<()> {
+        self.key_store.kdf_params = Some(calibration.params.clone());
+        self.save_key_store()
+    }
+
+    /// 볼트 데이터 키를 새로 만들고 현재 볼트의 모든 슬롯을 새 키로 다시 래핑합니다.
+    ///
+    /// 현재 세션의 PIN 슬롯(위장 볼트 세션이면 강압 PIN 슬롯)과 복구 키 슬롯은 새 데이터 키를,
+    /// 최고 보안 암호 슬롯은 새 최고 보안 파일 키를 래핑하며 복구 키는 새로 발급합니다.
+    /// 이전 키는 재암호화가 끝날 때까지 새 키로 래핑해 보관합니다. 모든 변경은 keys.json을 한 번
+    /// 교체하여 저장하므로, 도중에 실패하면 이전 키로, 저장된 뒤에는 새 키로 볼트를 열 수 있습니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - 현재 세션의 PIN
+    /// * `critical_passphrase` - 최고 보안 암호 (설정되어 있으면 필수)
+    /// * `data_key` - 현재 볼트 데이터 키
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<DataKeyRotation>` - 새 키와 새 복구 키
+    ///
+    /// # 오류
+    /// * `AuthError::AuthenticationFailed` - PIN 또는 최고 보안 암호가 일치하지 않음 (브루트포스 기록에 포함)
+    /// * `AuthError::InvalidInput` - 이전 교체가 끝나지 않았거나 최고 보안 암호를 입력하지 않음
+    pub fn rotate_data_key(
+        &mut self,
+        pin: &str,
+        critical_passphrase: Option<&str>,
+        data_key: &SecretKey,
+    ) -> SecureVaultResult<DataKeyRotation> {
+        let pin = &Self::normalize_pin(pin);
+        self.ensure_not_locked_out()?;
+        Self::validate_pin_input(pin)?;
+        self.confirm_primary_pin(pin)?;
+
+        if self.retired_data_key(data_key).is_some() {
+            return Err(AuthError::InvalidInput(
+                "이전 데이터 키 교체의 재암호화가 아직 끝나지 않았습니다.".to_string(),
+            )
+            .into());
+        }
+
+        let params = self.kdf_params();
+        let new_data_key = CryptoService::generate_data_key();
+        let mut retired = RetiredKeys {
+            data_key: RetiredKeys::wrap(data_key, &new_data_key)?,
+            critical_key: None,
+        };
+
+        // 최고 보안 파일 키도 새로 만들어 같은 암호로 래핑
+        let mut critical = None;
+        if self.critical_slot().is_some() {
+            let passphrase = critical_passphrase
+                .map(Self::normalize_pin)
+                .ok_or_else(|| {
+                    AuthError::InvalidInput("최고 보안 암호를 입력해야 합니다.".to_string())
+                })?;
+            let previous = self.unwrap_critical_key(&passphrase)?;
+            let new_critical_key = CryptoService::generate_data_key();
+            let wrapped = Self::wrap_critical_key(&passphrase, &new_critical_key, &params)?;
+            retired.critical_key = Some(RetiredKeys::wrap(&previous, &new_critical_key)?);
+            critical = Some((wrapped, new_critical_key));
+        }
+
+        let pin_slot = match self.active_slot {
+            PinSlot::Primary => self.wrap_with_pin(pin, &new_data_key)?,
+            PinSlot::Duress => Self::wrap_data_key(pin.as_bytes(), &new_data_key, &params)?,
+        };
+
+        // 메모리에 모두 반영한 뒤 keys.json 한 번으로 저장
+        let staged = self.stage_rotated_slots(pin_slot, critical.as_ref(), data_key, &new_data_key);
+        self.key_store.retired.push(retired);
+
+        let has_recovery_key = if self.is_decoy_session() {
+            self.decoy_recovery_key_hash.is_some()
+        } else {
+            self.key_store.recovery.is_some()
+        };
+        let saved = staged.and_then(|_| {
+            if has_recovery_key {
+                // 이전 복구 키는 이전 데이터 키를 래핑하므로 새 복구 키로 교체 (keys.json과 함께 저장)
+                self.generate_recovery_key(&new_data_key).map(Some)
+            } else {
+                Ok(None)
+            }
+        });
+        let recovery_key = match saved.and_then(|key| self.save_key_store().map(|_| key)) {
+            Ok(key) => key,
+            Err(e) => {
+                // 디스크에 남은 상태로 되돌려 이전 키로 계속 사용
+                self.load_credentials();
+                if self.is_decoy_session() {
+                    self.open_decoy_state(data_key)?;
+                }
+                return Err(e);
+            }
+        };
+
+        // PIN과 최고 보안 암호를 방금 확인했으므로 재암호화 중 최고 보안 파일에도 접근 가능
+        if let Some((_, new_critical_key)) = critical {
+            self.critical_key = Some(Arc::new(new_critical_key));
+        }
+        self.step_up_at = Some(Instant::now());
+
+        log::info!("볼트 데이터 키를 교체했습니다.");
+        Ok(DataKeyRotation {
+            data_key: Arc::new(new_data_key),
+            recovery_key,
+        })
+    }
+
+    /// 데이터 키 교체 결과를 현재 세션의 PIN 슬롯과 봉인된 정보에 반영합니다. (저장은 호출자가 담당)
+    fn stage_rotated_slots(
+        &mut self,
+        pin_slot: WrappedKey,
+        critical: Option<&(WrappedKey, SecretKey)>,
+        data_key: &SecretKey,
+        new_data_key: &SecretKey,
+    ) -> SecureVaultResult<()> {
+        match self.active_slot {
+            PinSlot::Primary => {
+                // 익명 슬롯 사용 여부는 새 데이터 키로 다시 봉인
+                let usage = self.slot_usage(data_key);
+                self.seal_slot_usage(new_data_key, usage)?;
+                self.key_store.pin = Some(pin_slot);
+            }
+            PinSlot::Duress => {
+                // 위장 볼트 정보는 새 위장 볼트 데이터 키로 다시 봉인
+                self.decoy_seal_key = Some(CryptoService::derive_slot_seal_key(new_data_key));
+                self.key_store.slots[DURESS_SLOT].key = pin_slot;
+            }
+        }
+
+        match critical {
+            Some((wrapped, _)) => self.set_critical_slot(wrapped.clone()),
+            None => self.reseal_decoy_state(),
+        }
+    }
+
+    /// 데이터 키를 교체하는 중이면 이전 데이터 키를 반환합니다.
+    ///
+    /// # 매개변수
+    /// * `data_key` - 현재 볼트 데이터 키
+    pub fn retired_data_key(&self, data_key: &SecretKey) -> Option<SecretKey> {
+        self.key_store
+            .retired
+            .iter()
+            .find_map(|retired| RetiredKeys::unwrap(&retired.data_key, data_key))
+    }
+
+    /// 데이터 키를 교체하는 중이면 이전 최고 보안 파일 키를 반환합니다.
+    ///
+    /// # 매개변수
+    /// * `critical_key` - 현재 최고 보안 파일 키
+    pub fn retired_critical_key(&self, critical_key: &SecretKey) -> Option<SecretKey> {
+        self.key_store.retired.iter().find_map(|retired| {
+            retired
+                .critical_key
+                .as_deref()
+                .and_then(|wrapped| RetiredKeys::unwrap(wrapped, critical_key))
+        })
+    }
+
+    /// 재암호화가 끝난 뒤 현재 볼트의 이전 키 기록을 삭제합니다.
+    ///
+    /// # 매개변수
+    /// * `data_key` - 현재 볼트 데이터 키
+    pub fn discard_retired_keys(&mut self, data_key: &SecretKey) -> SecureVaultResult<()> {
+        let before = self.key_store.retired.len();
+        self.key_store
+            .retired
+            .retain(|retired| RetiredKeys::unwrap(&retired.data_key, data_key).is_none());
+
+        if self.key_store.retired.len() != before {
+            self.save_key_store()?;
+            log::info!("재암호화가 끝나 이전 데이터 키를 삭제했습니다.");
+        }
+        Ok(())
+    }
+
+    /// 측정한 키 유도 비용을 적용하고 현재 세션의 PIN 슬롯을 바로 새 매개변수로 다시 만듭니다.
+    ///
+    /// 볼트를 더 빠른 PC로 옮긴 뒤 비용을 올릴 때 사용합니다. 현재 PIN의 검증자와 래핑된
+    /// 데이터 키는 즉시 교체되고, 다른 PIN과 최고 보안 암호는 다음에 사용될 때 교체됩니다.
+    /// 현재 비용보다 약한 매개변수는 `allow_downgrade`를 명시한 경우에만 적용합니다.
+    /// 위장 볼트 세션에서는 실제 볼트의 keys.json을 바꾸지 않도록 아무것도 저장하지 않고
+    /// 보정 결과만 돌려줍니다.
+    ///
+    /// # 매개변수
+    /// * `pin` - 현재 세션의 PIN
+    /// * `data_key` - 현재 볼트 데이터 키
+    /// * `calibration` - `measure_kdf`의 보정 결과
+    /// * `allow_downgrade` - 현재보다 약한 비용으로 낮추는 것을 허용할지 여부
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<KdfCalibration>` - 보정 결과
+    ///
+    /// # 오류
+    /// * `AuthError::AuthenticationFailed` - PIN이 일치하지 않음 (브루트포스 기록에 포함)
+    /// * `AuthError::InvalidInput` - 현재보다 약한 비용이고 낮추기를 허용하지 않음
+    pub fn reharden_kdf(
+        &mut self,
+        pin: &str,
+        data_key: &SecretKey,
+        calibration: KdfCalibration,
+        allow_downgrade: bool,
+    ) -> SecureVaultResult<KdfCalibration> {
+        let pin = &Self::normalize_pin(pin);
+        self.ensure_not_locked_out()?;
+        self.confirm_primary_pin(pin)?;
+
+        if calibration.params.is_weaker_than(&self.kdf_params()) && !allow_downgrade {
+            return Err(AuthError::InvalidInput(
+                "측정된 키 유도 비용이 현재 설정보다 낮습니다. 낮추려면 명시적으로 허용해야 합니다."
+                    .to_string(),
+            )
+            .into());
+        }
+
+        if self.is_decoy_session() {
+            return Ok(calibration);
+        }
+
+        self.apply_kdf_calibration(&calibration)?;
+        let slot = self.active_slot;
+        self.upgrade_pin_hash_if_needed(slot, pin);
+        self.rewrap_pin_slot_if_needed(slot, pin, data_key)?;
+
+        log::info!("키 유도 비용을 다시 보정했습니다.");
+        Ok(calibration)
+    }
+
+    /// PIN으로부터 마스터 키를 유도합니다.
+    pub fn derive_key_from_pin(
+        &self,
+        pin: &str,
+        salt: &[u8],
+        iterations: u32,
+    ) -> SecureVaultResult<Vec<u8>> {
+        if pin.is_empty() {
+            return Err(AuthError::InvalidPinFormat.into());
+        }
+
+        if salt.len() != 32 {
+            return Err(AuthError::InvalidSalt.into());
+        }
+
+        let mut key = [0u8; 32]; // 256비트 키
+        pbkdf2_hmac::<Sha256>(pin.as_bytes(), salt, iterations, &mut key);
+
+        Ok(key.to_vec())
+    }
+
+    /// 복구 키를 해시화합니다.
+    pub fn hash_recovery_key(&self, recovery_key: &str) -> SecureVaultResult<String> {
+        if recovery_key.is_empty() {
+            return Err(AuthError::InvalidRecoveryKey.into());
+        }
+
+        // 24단어 또는 기존 Base64 디코딩 (같은 키 바이트면 해시도 같음)
+        let mut key_bytes =
+            decode_recovery_key(recovery_key).map_err(|_| AuthError::InvalidRecoveryKey)?;
+
+        // SHA-256 해시
+        let mut hasher = Sha256::new();
+        hasher.update(key_bytes);
+        let hash = hasher.finalize();
+        key_bytes.zeroize();
+
+        Ok(general_purpose::STANDARD.encode(&hash))
+    }
+
+    /// 복구 키가 일치하는지 검증합니다.
+    pub fn verify_recovery_key_hash(&self, input_recovery_key: &str, stored_hash: &str) -> bool {
+        if input_recovery_key.is_empty() || stored_hash.is_empty() {
+            return false;
+        }
+
+        match self.hash_recovery_key(input_recovery_key) {
+            Ok(input_hash) => input_hash == stored_hash,
+            Err(_) => false,
+        }
+    }
+
+    /// 복구 키를 생성하고 볼트 데이터 키를 복구 키로 래핑합니다.
+    ///
+    /// 이전 복구 키의 래핑은 덮어쓰므로 더 이상 볼트를 열 수 없습니다.
+    ///
+    /// # 매개변수
+    /// * `data_key` - 현재 볼트 데이터 키
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<String>` - 공백으로 구분된 24단어 복구 키
+    pub fn generate_recovery_key(&mut self, data_key: &SecretKey) -> SecureVaultResult<String> {
+        // 32바이트 랜덤 키 생성
+        let mut key_bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut key_bytes);
+
+        let recovery_key = encode_recovery_mnemonic(&key_bytes);
+
+        // 복구 키 해시 생성
+        let hash = self.hash_recovery_key(&recovery_key)?;
+
+        // 위장 볼트 세션: 실제 복구 키를 덮어쓰지 않도록 세션 동안 메모리에만 기록
+        if self.is_decoy_session() {
+            key_bytes.zeroize();
+            self.decoy_recovery_key_hash = Some(hash);
+            log::info!("복구 키가 생성되었습니다.");
+            return Ok(recovery_key);
+        }
+
+        // 데이터 키를 복구 키로 래핑 (이전 복구 키의 래핑은 덮어써 더 이상 풀 수 없음)
+        // 복구 키는 256비트 무작위 값이라 보정된 비용이 필요 없으므로 기본 매개변수 사용
+        let wrapped = Self::wrap_data_key(&key_bytes, data_key, &KdfParams::default());
+        key_bytes.zeroize();
+        self.key_store.recovery = Some(wrapped?);
+
+        // 이전 복구 키는 교체 기록으로 옮기고 새 복구 키 정보 저장
+        self.supersede_recovery_key();
+        self.recovery_key_info = Some(SimpleRecoveryKeyInfo::new(hash));
+
+        // 변경 사항 저장
+        self.save_credentials()?;
+
+        log::info!("복구 키가 생성되었습니다.");
+        Ok(recovery_key)
+    }
+
+    /// 입력한 복구 키가 현재 발급된 복구 키인지 확인합니다.
+    ///
+    /// 위장 볼트 세션에서는 복구 키를 저장하지 않으므로 그 세션에서 생성한 복구 키와 비교합니다.
+    pub fn is_current_recovery_key(&self, recovery_key: &str) -> bool {
+        let hash = if self.is_decoy_session() {
+            self.decoy_recovery_key_hash.as_ref()
+        } else {
+            self.recovery_key_info
+                .as_ref()
+                .filter(|info| info.is_active)
+                .map(|info| &info.hash)
+        };
+
+        hash.is_some_and(|hash| self.verify_recovery_key_hash(recovery_key, hash))
+    }
+
+    /// 현재 복구 키의 발급 시각(Unix 초)을 반환합니다.
+    pub fn recovery_key_created_at(&self) -> Option<u64> {
+        if self.is_decoy_session() {
+            return None;
+        }
+        self.recovery_key_info.as_ref().and_then(|info| info.created_at)
+    }
+
+    /// 볼트 ID를 반환합니다. 아직 없으면 새로 만들어 저장합니다.
+    pub fn vault_id(&mut self) -> SecureVaultResult<Uuid> {
+        if let Some(id) = self.key_store.vault_id {
+            return Ok(id);
+        }
+
+        let id = Uuid::new_v4();
+        self.key_store.vault_id = Some(id);
+        self.save_key_store()?;
+        Ok(id)
+    }
+
+    /// 복구 키로 로그인합니다. 성공하면 같은 호출에서 사용한 복구 키를 새 복구 키로 교체합니다.
+    ///
+    /// 복구 키는 한 번만 쓸 수 있습니다. 데이터 키를 풀지 못하거나 새 복구 키를 저장하지 못하면
+    /// 세션을 만들지 않고 오류를 반환하므로, 사용한 복구 키가 교체되지 않은 채 볼트가 열리지 않습니다.
+    ///
+    /// # 매개변수
+    /// * `recovery_key` - 복구 키 (24단어 또는 기존 Base64)
+    ///
+    /// # 반환값
+    /// * `RecoveryLoginResult::Unlocked` - 데이터 키와 새로 발급한 복구 키
+    /// * `RecoveryLoginResult::Rejected` - 거부 사유
+    ///
+    /// # 오류
+    /// * `AuthError::InvalidRecoveryKey` - 복구 키로 래핑된 데이터 키를 풀 수 없음
+    pub fn login_with_recovery_key(
+        &mut self,
+        recovery_key: &str,
+    ) -> SecureVaultResult<RecoveryLoginResult> {
+        let result = self.check_recovery_key(recovery_key)?;
+
+        match result {
+            RecoveryKeyValidationResult::Valid => {}
+            RecoveryKeyValidationResult::Superseded => {
+                log::warn!("교체된 이전 복구 키로 인증을 시도했습니다.");
+                return Ok(RecoveryLoginResult::Rejected(result));
+            }
+            RecoveryKeyValidationResult::Invalid => {
+                log::warn!("복구 키 인증이 실패했습니다.");
+                return Ok(RecoveryLoginResult::Rejected(result));
+            }
+            _ => return Ok(RecoveryLoginResult::Rejected(result)),
+        }
+
+        let data_key = self.unlock_with_recovery_key(recovery_key)?;
+
+        // 사용 기록을 남긴 뒤 곧바로 교체 (사용 기록은 교체 기록과 함께 저장됨)
+        if let Some(info) = self.recovery_key_info.as_mut() {
+            info.record_usage();
+        }
+        self.active_slot = PinSlot::Primary;
+        let new_recovery_key = match self.generate_recovery_key(&data_key) {
+            Ok(key) => key,
+            Err(e) => {
+                // 디스크에 남은 상태로 되돌려 이전 복구 키를 다시 쓸 수 있게 함
+                self.load_credentials();
+                return Err(e);
+            }
+        };
 
-        // 변경 사항 저장
-        self.save_auth_data();
+        self.auth_state = AuthState::Authenticated(AuthMethod::RecoveryKey);
+        self.create_session(AuthMethod::RecoveryKey, 1800)?; // 30분 세션
 
-        log::info!("복구 키가 생성되었습니다.");
-        Ok(recovery_key)
+        log::info!("복구 키 인증이 성공했고 사용한 복구 키를 새 복구 키로 교체했습니다.");
+        Ok(RecoveryLoginResult::Unlocked {
+            data_key,
+            new_recovery_key,
+        })
     }
 
-    /// 복구 키를 검증합니다.
-    pub fn verify_recovery_key(
-        &mut self,
+    /// 복구 키의 상태를 확인합니다. 인증 상태나 사용 기록은 바꾸지 않습니다.
+    ///
+    /// # 반환값
+    /// * `Valid` - 현재 활성 복구 키
+    /// * `Superseded` - 새 복구 키 발급으로 교체된 이전 키
+    /// * `Deactivated` / `InvalidFormat` / `Invalid` - 그 밖의 실패
+    pub fn check_recovery_key(
+        &self,
         recovery_key: &str,
     ) -> SecureVaultResult<RecoveryKeyValidationResult> {
         // 복구 키 정보 확인
@@ -299,30 +2444,61 @@ impl AuthService {
             return Ok(RecoveryKeyValidationResult::Deactivated);
         }
 
-        // 복구 키 형식 검증
-        if let Err(_) = general_purpose::STANDARD.decode(recovery_key) {
+        // 복구 키 형식 검증 (24단어 체크섬 또는 기존 Base64)
+        if !is_valid_recovery_key_format(recovery_key) {
             return Ok(RecoveryKeyValidationResult::InvalidFormat);
         }
 
-        // 해시 값을 미리 복사
-        let stored_hash = recovery_info.hash.clone();
+        let input_hash = self.hash_recovery_key(recovery_key)?;
+        if constant_time_compare(input_hash.as_bytes(), recovery_info.hash.as_bytes()) {
+            return Ok(RecoveryKeyValidationResult::Valid);
+        }
 
-        // 해시 검증
-        if self.verify_recovery_key_hash(recovery_key, &stored_hash) {
-            // 인증 성공
-            if let Some(recovery_info_mut) = self.recovery_key_info.as_mut() {
-                recovery_info_mut.record_usage();
-            }
-            self.auth_state = AuthState::Authenticated(AuthMethod::RecoveryKey);
+        if self
+            .superseded_recovery_keys
+            .iter()
+            .any(|old| constant_time_compare(input_hash.as_bytes(), old.hash.as_bytes()))
+        {
+            return Ok(RecoveryKeyValidationResult::Superseded);
+        }
 
-            // 세션 생성
-            self.create_session(AuthMethod::RecoveryKey, 1800)?; // 30분 세션
+        Ok(RecoveryKeyValidationResult::Invalid)
+    }
 
-            log::info!("복구 키 인증이 성공했습니다.");
-            Ok(RecoveryKeyValidationResult::Valid)
-        } else {
-            log::warn!("복구 키 인증이 실패했습니다.");
-            Ok(RecoveryKeyValidationResult::Invalid)
+    /// 사용된 복구 키를 아직 새 키로 교체하지 않았는지 확인합니다.
+    pub fn recovery_rotation_required(&self) -> bool {
+        !self.is_decoy_session()
+            && self
+                .recovery_key_info
+                .as_ref()
+                .is_some_and(|info| info.is_active && info.rotation_required)
+    }
+
+    /// 복구 키 수명 주기 상태를 반환합니다. (키 값과 해시는 포함하지 않음)
+    pub fn recovery_key_status(&self) -> RecoveryKeyStatus {
+        let info = self.recovery_key_info.as_ref();
+        RecoveryKeyStatus {
+            has_key: info.is_some(),
+            is_active: info.is_some_and(|i| i.is_active),
+            created_at: info.and_then(|i| i.created_at),
+            last_used_at: info.and_then(|i| i.last_used_at),
+            use_count: info.map_or(0, |i| i.use_count),
+            rotation_required: self.recovery_rotation_required(),
+            superseded_count: self.superseded_recovery_keys.len(),
+            last_superseded_at: self.superseded_recovery_keys.last().map(|s| s.superseded_at),
+        }
+    }
+
+    /// 현재 복구 키를 교체 기록으로 옮깁니다. 래핑된 데이터 키는 호출자가 덮어쓰거나 삭제합니다.
+    fn supersede_recovery_key(&mut self) {
+        if let Some(info) = self.recovery_key_info.take() {
+            self.superseded_recovery_keys.push(info.supersede());
+
+            let excess = self
+                .superseded_recovery_keys
+                .len()
+                .saturating_sub(MAX_SUPERSEDED_RECOVERY_KEYS);
+            self.superseded_recovery_keys.drain(..excess);
         }
     }
 
@@ -341,16 +2517,12 @@ impl AuthService {
             return Err(AuthError::InvalidSalt.into());
         }
 
-        let key_bytes = general_purpose::STANDARD
-            .decode(recovery_key)
-            .map_err(|_| AuthError::InvalidRecoveryKey)?;
-
-        if key_bytes.len() != 32 {
-            return Err(AuthError::InvalidRecoveryKey.into());
-        }
+        let mut key_bytes =
+            decode_recovery_key(recovery_key).map_err(|_| AuthError::InvalidRecoveryKey)?;
 
         let mut master_key = [0u8; 32];
         pbkdf2_hmac::<Sha256>(&key_bytes, salt, iterations, &mut master_key);
+        key_bytes.zeroize();
 
         Ok(master_key.to_vec())
     }
@@ -396,6 +2568,13 @@ impl AuthService {
 
         self.current_session = None;
         self.auth_state = AuthState::Unauthenticated;
+        self.active_slot = PinSlot::Primary;
+        self.pin_upgrade_required = false;
+        self.decoy_recovery_key_hash = None;
+        self.decoy_state = None;
+        self.decoy_seal_key = None;
+        self.provide_key_file(None);
+        self.end_step_up();
 
         log::info!("로그아웃이 완료되었습니다.");
         Ok(())
@@ -414,6 +2593,37 @@ impl AuthService {
         self.pin_info.is_some()
     }
 
+    /// 설정된 PIN이 패스프레이즈인지 확인합니다. (로그인 화면 입력 방식 결정용)
+    pub fn is_passphrase_pin(&self) -> bool {
+        self.pin_info.as_ref().is_some_and(|info| info.is_passphrase)
+    }
+
+    /// 로그인한 PIN이 현재 복잡도 요구사항을 충족하지 못해 변경이 필요한지 반환합니다.
+    pub fn is_pin_upgrade_required(&self) -> bool {
+        self.pin_upgrade_required && !self.is_decoy_session()
+    }
+
+    /// 새 PIN에 적용되는 복잡도 요구사항을 반환합니다.
+    pub fn get_pin_requirement(&self) -> &PinComplexityRequirement {
+        &self.security_config.pin_complexity
+    }
+
+    /// PIN 복잡도 요구사항을 변경합니다.
+    ///
+    /// 기존 PIN은 그대로 유효하며, 요구사항을 충족하지 못하면 다음 로그인부터
+    /// `is_pin_upgrade_required`가 true가 되어 PIN 변경을 안내합니다.
+    pub fn set_pin_requirement(&mut self, requirement: PinComplexityRequirement) -> SecureVaultResult<()> {
+        if let PinComplexityRequirement::Custom { min_length, max_length, .. } = &requirement {
+            if *min_length < 4 || min_length > max_length || *max_length as usize > MAX_PIN_LENGTH {
+                return Err(AuthError::InvalidInput("PIN 길이 범위가 올바르지 않습니다.".to_string()).into());
+            }
+        }
+
+        let mut config = self.security_config.clone();
+        config.pin_complexity = requirement;
+        self.set_security_config(config)
+    }
+
     pub fn has_recovery_key(&self) -> bool {
         self.recovery_key_info.is_some()
     }
@@ -441,17 +2651,47 @@ impl AuthService {
         self.recovery_key_info.as_ref()
     }
 
+    /// 현재 세션의 마지막 활동 시각을 갱신합니다.
+    pub fn refresh_activity(&mut self) {
+        if let Some(session) = self.current_session.as_mut() {
+            session.refresh_activity();
+        }
+    }
+
+    /// 세션이 있지만 만료되었는지 확인합니다. (상태를 변경하지 않음)
+    pub fn is_session_expired(&self) -> bool {
+        self.current_session
+            .as_ref()
+            .is_some_and(|session| session.is_expired())
+    }
+
+    /// 자동 잠금 시간(초)을 반환합니다. 0이면 자동 잠금을 사용하지 않습니다.
+    pub fn auto_lock_seconds(&self) -> u64 {
+        self.security_config.auto_lock_minutes as u64 * 60
+    }
+
+    /// 자동 잠금 시간을 설정합니다.
+    ///
+    /// # 매개변수
+    /// * `minutes` - 자동 잠금 시간 (분, 0이면 자동 잠금 없음)
+    pub fn set_auto_lock_minutes(&mut self, minutes: u32) -> SecureVaultResult<()> {
+        let mut config = self.security_config.clone();
+        config.auto_lock_minutes = minutes;
+        self.set_security_config(config)
+    }
+
     pub fn get_session_remaining_time(&self) -> Option<u64> {
         self.current_session
             .as_ref()
             .map(|s| s.remaining_time_seconds())
     }
 
-    /// 복구 키를 비활성화합니다.
+    /// 복구 키를 비활성화합니다. 래핑된 데이터 키도 삭제하므로 다시 활성화할 수 없습니다.
     pub fn deactivate_recovery_key(&mut self) -> SecureVaultResult<()> {
         if let Some(recovery_info) = &mut self.recovery_key_info {
             recovery_info.deactivate();
-            self.save_auth_data(); // 변경 사항 저장
+            self.key_store.recovery = None;
+            self.save_credentials()?; // 변경 사항 저장
             log::info!("복구 키가 비활성화되었습니다.");
             Ok(())
         } else {
@@ -460,22 +2700,34 @@ impl AuthService {
     }
 
     /// PIN을 변경합니다.
+    ///
+    /// 로그인된 세션에서만 변경할 수 있으며, 그 세션과 같은 슬롯의 PIN(위장 볼트 세션이면 강압 PIN)을
+    /// 현재 PIN으로 확인합니다. 새 세션을 만들지 않으므로 볼트 키 없이 세션만 열리는 일이 없습니다.
+    ///
+    /// # 오류
+    /// * `AuthError::SessionExpired` - 로그인된 세션이 없음
+    /// * `AuthError::AuthenticationFailed` - 현재 PIN이 일치하지 않음
     pub fn change_pin(
         &mut self,
         old_pin: &str,
         new_pin: &str,
         complexity: PinComplexity,
     ) -> SecureVaultResult<()> {
-        // 기존 PIN 검증
-        match self.verify_pin(old_pin)? {
-            PinValidationResult::Valid => {
-                // 새 PIN 설정 (내부적으로 save_auth_data 호출됨)
-                self.set_pin(new_pin, complexity)?;
-                log::info!("PIN이 성공적으로 변경되었습니다.");
-                Ok(())
-            }
-            _ => Err(AuthError::AuthenticationFailed.into()),
+        if !self.is_session_valid() {
+            return Err(AuthError::SessionExpired.into());
         }
+
+        let old_pin = &Self::normalize_pin(old_pin);
+        self.ensure_not_locked_out()?;
+        Self::validate_pin_input(old_pin)?;
+        self.confirm_primary_pin(old_pin)?;
+
+        // 데이터 키를 새 PIN으로 다시 래핑 (파일은 재암호화하지 않음)
+        let data_key = self.unlock_with_pin(old_pin)?;
+        self.set_pin(new_pin, complexity, &data_key)?;
+
+        log::info!("PIN이 성공적으로 변경되었습니다.");
+        Ok(())
     }
 
     /// 세션 타임아웃을 설정합니다.
@@ -503,46 +2755,269 @@ impl AuthService {
         self.session_timeout_seconds
     }
 
-    /// 인증 데이터를 디스크에 저장합니다.
-    fn save_auth_data(&self) {
-        let auth_data = AuthData {
+    /// 볼트 보안 설정을 반환합니다.
+    pub fn get_security_config(&self) -> &SecurityConfig {
+        &self.security_config
+    }
+
+    /// 볼트 보안 설정을 변경합니다.
+    ///
+    /// 브루트포스 정책은 즉시 반영되며, 현재 실패 횟수와 잠금 시각은 유지됩니다.
+    pub fn set_security_config(&mut self, mut config: SecurityConfig) -> SecureVaultResult<()> {
+        // 키 파일 사용 여부는 래핑된 키와 일치해야 하므로 등록/해제로만 변경
+        config.two_factor_enabled = self.key_file_verifier.is_some();
+
+        let mut protection = BruteForceProtection::from_config(&config.brute_force_protection);
+        protection.failed_attempts = self.brute_force_protection.failed_attempts;
+        protection.last_failure_time = self.brute_force_protection.last_failure_time;
+        protection.is_locked = self.brute_force_protection.is_locked;
+        protection.lockout_until = self.brute_force_protection.lockout_until;
+
+        self.brute_force_protection = protection;
+        self.security_config = config;
+
+        self.save_auth_data();
+        log::info!("보안 설정이 변경되었습니다.");
+        Ok(())
+    }
+
+    /// 디스크에 저장할 인증 데이터를 만듭니다.
+    fn auth_data(&self) -> AuthData {
+        AuthData {
             pin_info: self.pin_info.clone(),
             recovery_key_info: self.recovery_key_info.clone(),
             session_timeout_seconds: self.session_timeout_seconds,
+            security_config: self.security_config.clone(),
+            pin_slots: self.pin_slots.clone(),
+            key_file_verifier: self.key_file_verifier.clone(),
+            superseded_recovery_keys: self.superseded_recovery_keys.clone(),
+            lockout_key: self.lockout_key.clone(),
+        }
+    }
+
+    /// 인증 데이터를 디스크에 저장합니다.
+    ///
+    /// 래핑된 키와 함께 바뀐 인증 데이터는 `save_credentials`로 저장해야 합니다.
+    fn save_auth_data(&self) {
+        if let Ok(json) = serde_json::to_string_pretty(&self.auth_data()) {
+            if !self.config_dir.exists() {
+                let _ = fs::create_dir_all(&self.config_dir);
+            }
+            if let Err(e) = self.write_config_file("auth.json", json.as_bytes()) {
+                log::error!("인증 데이터 저장 실패: {}", e);
+            }
+        }
+    }
+
+    /// 래핑된 데이터 키를 디스크에 저장합니다.
+    fn save_key_store(&self) -> SecureVaultResult<()> {
+        let json = serde_json::to_string_pretty(&self.key_store)?;
+
+        if !self.config_dir.exists() {
+            fs::create_dir_all(&self.config_dir)?;
+        }
+
+        // 부분 기록으로 키를 잃지 않도록 임시 파일에 쓴 뒤 교체
+        self.write_config_file("keys.json", json.as_bytes())?;
+        Ok(())
+    }
+
+    /// 래핑된 데이터 키와 인증 데이터를 함께 저장합니다.
+    ///
+    /// PIN 검증값과 그 PIN으로 래핑한 키가 서로 다른 시점의 것으로 남지 않도록, 두 임시 파일을
+    /// 모두 기록한 뒤 `keys.json`을 교체하는 시점을 커밋으로 삼습니다. 그 뒤에 중단되면 다음
+    /// 로드에서 남은 `auth.json.tmp`를 마저 교체하고, 그 전에 중단되면 임시 파일을 버립니다.
+    fn save_credentials(&self) -> SecureVaultResult<()> {
+        let keys_json = serde_json::to_string_pretty(&self.key_store)?;
+        let auth_json = serde_json::to_string_pretty(&self.auth_data())?;
+
+        if !self.config_dir.exists() {
+            fs::create_dir_all(&self.config_dir)?;
+        }
+
+        let keys_temp = self.write_config_temp_file("keys.json", keys_json.as_bytes())?;
+        let auth_temp = self.write_config_temp_file("auth.json", auth_json.as_bytes())?;
+        fs::rename(keys_temp, self.config_dir.join("keys.json"))?;
+        fs::rename(auth_temp, self.config_dir.join("auth.json"))?;
+        Ok(())
+    }
+
+    /// 설정 디렉토리의 파일을 임시 파일에 기록한 뒤 교체합니다.
+    ///
+    /// 기록 도중 중단되어도 이전 내용 또는 새 내용 중 하나만 남습니다.
+    fn write_config_file(&self, name: &str, contents: &[u8]) -> std::io::Result<()> {
+        let temp_path = self.write_config_temp_file(name, contents)?;
+        fs::rename(temp_path, self.config_dir.join(name))
+    }
+
+    /// 설정 디렉토리에 `<name>.tmp` 임시 파일을 기록하고 디스크에 반영한 뒤 경로를 반환합니다.
+    fn write_config_temp_file(&self, name: &str, contents: &[u8]) -> std::io::Result<PathBuf> {
+        let temp_path = self.config_dir.join(format!("{}.tmp", name));
+
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        Ok(temp_path)
+    }
+
+    /// 래핑된 데이터 키와 인증 데이터를 디스크에서 로드합니다.
+    ///
+    /// `save_credentials`가 중단되어 남은 임시 파일을 먼저 정리합니다.
+    fn load_credentials(&mut self) {
+        self.finish_pending_credentials();
+        self.load_key_store();
+        self.load_auth_data();
+    }
+
+    /// 중단된 저장의 임시 파일을 마저 교체하거나 버립니다.
+    ///
+    /// `keys.json.tmp`가 남아 있으면 커밋 전에 중단된 것이므로 두 임시 파일을 모두 버립니다.
+    /// `auth.json.tmp`만 남아 있으면 `keys.json`은 이미 교체되었으므로, 온전히 기록된 경우에 한해
+    /// `auth.json`으로 교체합니다.
+    fn finish_pending_credentials(&self) {
+        let keys_temp = self.config_dir.join("keys.json.tmp");
+        let auth_temp = self.config_dir.join("auth.json.tmp");
+
+        if keys_temp.exists() {
+            let _ = fs::remove_file(&keys_temp);
+            let _ = fs::remove_file(&auth_temp);
+            return;
+        }
+
+        let complete = fs::read_to_string(&auth_temp)
+            .is_ok_and(|json| serde_json::from_str::<AuthData>(&json).is_ok());
+        if complete {
+            if let Err(e) = fs::rename(&auth_temp, self.config_dir.join("auth.json")) {
+                log::error!("중단된 인증 데이터 저장을 마치지 못했습니다: {}", e);
+            }
+        } else {
+            let _ = fs::remove_file(&auth_temp);
+        }
+    }
+
+    /// 래핑된 데이터 키를 디스크에서 로드합니다.
+    fn load_key_store(&mut self) {
+        let key_file = self.config_dir.join("keys.json");
+        if let Ok(json) = fs::read_to_string(key_file) {
+            match serde_json::from_str::<KeyStore>(&json) {
+                Ok(key_store) => self.key_store = key_store,
+                Err(e) => log::error!("키 저장소 로드 실패: {}", e),
+            }
+        }
+        self.pad_pin_slots();
+    }
+
+    /// 브루트포스 방지 상태를 디스크에 저장합니다.
+    ///
+    /// MAC 키가 아직 없으면 새로 만들어 auth.json에 먼저 기록합니다.
+    fn save_brute_force_state(&mut self) {
+        if self.lockout_key.is_none() {
+            let mut key = [0u8; 32];
+            OsRng.fill_bytes(&mut key);
+            self.lockout_key = Some(general_purpose::STANDARD.encode(key));
+            key.zeroize();
+            self.save_auth_data();
+        }
+        let Some(key) = self.lockout_key.as_ref().and_then(|k| general_purpose::STANDARD.decode(k).ok()) else {
+            log::error!("브루트포스 기록 MAC 키를 읽을 수 없습니다.");
+            return;
         };
 
-        if let Ok(json) = serde_json::to_string_pretty(&auth_data) {
-            if let Ok(current_dir) = std::env::current_dir() {
-                let config_dir = current_dir.join(".securevault").join("config");
-                if !config_dir.exists() {
-                    let _ = fs::create_dir_all(&config_dir);
-                }
-                let auth_file = config_dir.join("auth.json");
-                if let Err(e) = fs::write(auth_file, json) {
-                    log::error!("인증 데이터 저장 실패: {}", e);
-                }
+        let protection = &self.brute_force_protection;
+        let mut record = BruteForceRecord {
+            failed_attempts: protection.failed_attempts,
+            last_failure_time: protection.last_failure_time,
+            lockout_until: protection.lockout_until,
+            mac: String::new(),
+        };
+        record.mac = general_purpose::STANDARD.encode(record.compute_mac(&key));
+
+        if let Ok(json) = serde_json::to_string_pretty(&record) {
+            if !self.config_dir.exists() {
+                let _ = fs::create_dir_all(&self.config_dir);
+            }
+            if let Err(e) = self.write_config_file("lockout.json", json.as_bytes()) {
+                log::error!("브루트포스 기록 저장 실패: {}", e);
+            }
+        }
+    }
+
+    /// 브루트포스 방지 상태를 디스크에서 로드합니다.
+    ///
+    /// 기록이 있어야 하는데 없거나, 읽을 수 없거나, MAC이 맞지 않으면 변조로 보고 최대 잠금을
+    /// 적용합니다. MAC 키가 없는 이전 버전 볼트에서 기록이 없으면 실패 기록이 없는 것으로 봅니다.
+    fn load_brute_force_state(&mut self) {
+        if self.pin_info.is_none() {
+            return;
+        }
+
+        let json = match fs::read_to_string(self.config_dir.join("lockout.json")) {
+            Ok(json) => Some(json),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                log::warn!("브루트포스 기록을 읽을 수 없습니다: {}", e);
+                return self.apply_tampered_lockout();
+            }
+        };
+        let key = self.lockout_key.as_ref().and_then(|k| general_purpose::STANDARD.decode(k).ok());
+
+        let (json, key) = match (json, key) {
+            (None, None) if self.lockout_key.is_none() => return,
+            (Some(json), Some(key)) => (json, key),
+            _ => {
+                log::warn!("브루트포스 기록 또는 MAC 키가 없습니다.");
+                return self.apply_tampered_lockout();
+            }
+        };
+
+        let record = match serde_json::from_str::<BruteForceRecord>(&json) {
+            Ok(record) => record,
+            Err(e) => {
+                log::warn!("브루트포스 기록을 읽을 수 없습니다: {}", e);
+                return self.apply_tampered_lockout();
             }
+        };
+        let stored_mac = general_purpose::STANDARD.decode(&record.mac).unwrap_or_default();
+        if !constant_time_compare(&stored_mac, &record.compute_mac(&key)) {
+            log::warn!("브루트포스 기록의 MAC이 일치하지 않습니다.");
+            return self.apply_tampered_lockout();
         }
+
+        let protection = &mut self.brute_force_protection;
+        protection.failed_attempts = record.failed_attempts;
+        protection.last_failure_time = record.last_failure_time;
+        protection.lockout_until = record.lockout_until;
+        protection.is_locked = record.failed_attempts >= protection.max_attempts;
+    }
+
+    /// 변조된 브루트포스 기록 대신 최대 잠금을 적용하고 새 기록을 저장합니다.
+    fn apply_tampered_lockout(&mut self) {
+        self.brute_force_protection.lock_for_tampering();
+        self.save_brute_force_state();
     }
 
     /// 인증 데이터를 디스크에서 로드합니다.
     fn load_auth_data(&mut self) {
-        if let Ok(current_dir) = std::env::current_dir() {
-            let auth_file = current_dir
-                .join(".securevault")
-                .join("config")
-                .join("auth.json");
-            if auth_file.exists() {
-                if let Ok(json) = fs::read_to_string(auth_file) {
-                    if let Ok(auth_data) = serde_json::from_str::<AuthData>(&json) {
-                        self.pin_info = auth_data.pin_info;
-                        self.recovery_key_info = auth_data.recovery_key_info;
-                        self.session_timeout_seconds = auth_data.session_timeout_seconds;
-                        log::info!("인증 데이터가 로드되었습니다.");
-                    }
+        let auth_file = self.config_dir.join("auth.json");
+        if auth_file.exists() {
+            if let Ok(json) = fs::read_to_string(auth_file) {
+                if let Ok(auth_data) = serde_json::from_str::<AuthData>(&json) {
+                    self.pin_info = auth_data.pin_info;
+                    self.recovery_key_info = auth_data.recovery_key_info;
+                    self.session_timeout_seconds = auth_data.session_timeout_seconds;
+                    self.brute_force_protection = BruteForceProtection::from_config(
+                        &auth_data.security_config.brute_force_protection,
+                    );
+                    self.security_config = auth_data.security_config;
+                    self.pin_slots = auth_data.pin_slots;
+                    self.key_file_verifier = auth_data.key_file_verifier;
+                    self.superseded_recovery_keys = auth_data.superseded_recovery_keys;
+                    self.lockout_key = auth_data.lockout_key;
+                    log::info!("인증 데이터가 로드되었습니다.");
                 }
             }
         }
+        self.pad_pin_slots();
     }
 }
 
@@ -551,3 +3026,1031 @@ impl Default for AuthService {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// 복구 키 로그인이 성공해야 하는 테스트용 도우미. 데이터 키와 새 복구 키를 반환합니다.
+    fn login_with_recovery(service: &mut AuthService, recovery_key: &str) -> (SecretKey, String) {
+        match service.login_with_recovery_key(recovery_key).unwrap() {
+            RecoveryLoginResult::Unlocked {
+                data_key,
+                new_recovery_key,
+            } => (data_key, new_recovery_key),
+            RecoveryLoginResult::Rejected(result) => panic!("복구 키 로그인 거부: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_set_and_verify_pin() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+
+        service
+            .set_pin("482913", PinComplexity::Basic, &CryptoService::generate_data_key())
+            .unwrap();
+        let pin_info = service.get_pin_info().unwrap();
+        assert_eq!(pin_info.hash_params.algorithm, KdfAlgorithm::Argon2id);
+        assert_eq!(pin_info.key_params.algorithm, KdfAlgorithm::Argon2id);
+
+        assert_eq!(service.verify_pin("000000").unwrap(), PinValidationResult::Invalid);
+        assert_eq!(service.verify_pin("482913").unwrap(), PinValidationResult::Valid);
+    }
+
+    #[test]
+    fn test_verifier_differs_from_master_key() {
+        let service = AuthService::with_config_dir(TempDir::new().unwrap().path());
+        let salt = [7u8; 32];
+        let params = KdfParams::default();
+
+        let verifier = service.hash_pin("482913", &salt, &params).unwrap();
+        let master_key = CryptoService::derive_key_with_params(b"482913", &salt, &params).unwrap();
+
+        assert_ne!(verifier, general_purpose::STANDARD.encode(master_key.expose()));
+    }
+
+    #[test]
+    fn test_legacy_pin_hash_is_upgraded() {
+        let temp_dir = TempDir::new().unwrap();
+        let salt = [3u8; 32];
+
+        // 매개변수 필드가 없는 기존 auth.json
+        let mut hasher = Sha256::new();
+        hasher.update(b"1234");
+        hasher.update(salt);
+        let legacy_hash = general_purpose::STANDARD.encode(hasher.finalize());
+        let json = serde_json::json!({
+            "pin_info": { "hash": legacy_hash, "salt": salt.to_vec() },
+            "recovery_key_info": null,
+        });
+        fs::create_dir_all(temp_dir.path().join("config")).unwrap();
+        fs::write(temp_dir.path().join("config").join("auth.json"), json.to_string()).unwrap();
+
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        let pin_info = service.get_pin_info().unwrap();
+        assert_eq!(pin_info.hash_params, KdfParams::legacy_pin_hash());
+        assert_eq!(pin_info.key_params, KdfParams::legacy_master_key());
+
+        assert_eq!(service.verify_pin("1234").unwrap(), PinValidationResult::Valid);
+
+        // 재시작 후에도 업그레이드된 검증자가 유지되고, 마스터 키 매개변수는 그대로여야 함
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        let pin_info = reloaded.get_pin_info().unwrap();
+        assert_eq!(pin_info.hash_params, KdfParams::default());
+        assert_eq!(pin_info.key_params, KdfParams::legacy_master_key());
+        assert_ne!(pin_info.hash, legacy_hash);
+        assert_eq!(reloaded.verify_pin("1234").unwrap(), PinValidationResult::Valid);
+    }
+
+    #[test]
+    fn test_calibrate_kdf_never_weaker_than_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+
+        let calibration = AuthService::measure_kdf(Duration::from_millis(1)).unwrap();
+        assert_eq!(calibration.params, KdfParams::default());
+        service.apply_kdf_calibration(&calibration).unwrap();
+        assert_eq!(
+            calibration.estimated_unlock_ms,
+            calibration.derivation_ms * u64::from(KDF_DERIVATIONS_PER_UNLOCK)
+        );
+        let reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(reloaded.kdf_params(), KdfParams::default());
+    }
+
+    #[test]
+    fn test_recalibrated_params_upgrade_pin_slot_on_use() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_key = CryptoService::generate_data_key();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+
+        // 더 빠른 PC에서 보정한 것처럼 볼트의 매개변수만 변경
+        let stronger = KdfParams::argon2id(
+            KdfParams::ARGON2_MEMORY_KIB,
+            KdfParams::ARGON2_ITERATIONS + 1,
+            KdfParams::ARGON2_PARALLELISM,
+        );
+        service.key_store.kdf_params = Some(stronger.clone());
+        service.save_key_store().unwrap();
+
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(reloaded.get_pin_info().unwrap().hash_params, KdfParams::default());
+        assert_eq!(reloaded.verify_pin("111111").unwrap(), PinValidationResult::Valid);
+        assert_eq!(reloaded.unlock_with_pin("111111").unwrap(), data_key);
+
+        // 다음 시작 시에도 새 매개변수로 만든 검증자와 래핑된 키로 같은 데이터 키가 열려야 함
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(reloaded.get_pin_info().unwrap().hash_params, stronger);
+        assert_eq!(reloaded.key_store.pin.as_ref().unwrap().params, stronger);
+        assert_eq!(reloaded.verify_pin("111111").unwrap(), PinValidationResult::Valid);
+        assert_eq!(reloaded.unlock_with_pin("111111").unwrap(), data_key);
+    }
+
+    #[test]
+    fn test_reharden_kdf_requires_current_pin() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_key = CryptoService::generate_data_key();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+        assert_eq!(service.verify_pin("111111").unwrap(), PinValidationResult::Valid);
+
+        let calibration = AuthService::measure_kdf(Duration::from_millis(1)).unwrap();
+        assert!(service
+            .reharden_kdf("222222", &data_key, calibration.clone(), false)
+            .is_err());
+        let calibration = service
+            .reharden_kdf("111111", &data_key, calibration, false)
+            .unwrap();
+        assert_eq!(calibration.params, service.kdf_params());
+
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(reloaded.verify_pin("111111").unwrap(), PinValidationResult::Valid);
+        assert_eq!(reloaded.unlock_with_pin("111111").unwrap(), data_key);
+    }
+
+    #[test]
+    fn test_reharden_kdf_refuses_weaker_params_unless_allowed() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_key = CryptoService::generate_data_key();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+        assert_eq!(service.verify_pin("111111").unwrap(), PinValidationResult::Valid);
+
+        let stronger = KdfParams::argon2id(
+            KdfParams::ARGON2_MEMORY_KIB,
+            KdfParams::ARGON2_ITERATIONS + 1,
+            KdfParams::ARGON2_PARALLELISM,
+        );
+        service.key_store.kdf_params = Some(stronger.clone());
+        service.save_key_store().unwrap();
+
+        // 더 느린 PC에서 측정한 것처럼 기본값으로 낮아지는 보정
+        let calibration = AuthService::measure_kdf(Duration::from_millis(1)).unwrap();
+        assert!(calibration.params.is_weaker_than(&stronger));
+        assert!(service
+            .reharden_kdf("111111", &data_key, calibration.clone(), false)
+            .is_err());
+        let reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(reloaded.kdf_params(), stronger);
+
+        service.reharden_kdf("111111", &data_key, calibration, true).unwrap();
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(reloaded.kdf_params(), KdfParams::default());
+        assert_eq!(reloaded.verify_pin("111111").unwrap(), PinValidationResult::Valid);
+        assert_eq!(reloaded.unlock_with_pin("111111").unwrap(), data_key);
+    }
+
+    #[test]
+    fn test_reharden_kdf_in_decoy_session_saves_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_key = CryptoService::generate_data_key();
+        let decoy_key = CryptoService::generate_data_key();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+        service.set_duress_pin("999999", &decoy_key, &data_key).unwrap();
+        service.logout().unwrap();
+        let keys_before = fs::read(temp_dir.path().join("config").join("keys.json")).unwrap();
+
+        assert_eq!(service.verify_pin("999999").unwrap(), PinValidationResult::Valid);
+        let stronger = KdfCalibration {
+            params: KdfParams::argon2id(
+                KdfParams::ARGON2_MEMORY_KIB,
+                KdfParams::ARGON2_ITERATIONS + 1,
+                KdfParams::ARGON2_PARALLELISM,
+            ),
+            ..AuthService::measure_kdf(Duration::from_millis(1)).unwrap()
+        };
+        let calibration = service
+            .reharden_kdf("999999", &decoy_key, stronger.clone(), false)
+            .unwrap();
+        assert_eq!(calibration, stronger);
+        let keys_after = fs::read(temp_dir.path().join("config").join("keys.json")).unwrap();
+        assert_eq!(keys_after, keys_before);
+    }
+
+    #[test]
+    fn test_change_pin_keeps_data_key() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_key = CryptoService::generate_data_key();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+
+        service.change_pin("111111", "222222", PinComplexity::Basic).unwrap();
+
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(reloaded.verify_pin("111111").unwrap(), PinValidationResult::Invalid);
+        assert_eq!(reloaded.verify_pin("222222").unwrap(), PinValidationResult::Valid);
+        assert_eq!(reloaded.unlock_with_pin("222222").unwrap(), data_key);
+    }
+
+    #[test]
+    fn test_change_pin_requires_session_and_does_not_start_one() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_key = CryptoService::generate_data_key();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+        service.logout().unwrap();
+
+        // 잠긴 상태에서는 올바른 PIN으로도 변경할 수 없고 세션도 열리지 않음
+        assert!(service.change_pin("111111", "222222", PinComplexity::Basic).is_err());
+        assert!(!service.is_session_valid());
+
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(reloaded.verify_pin("111111").unwrap(), PinValidationResult::Valid);
+        assert!(reloaded.change_pin("999999", "222222", PinComplexity::Basic).is_err());
+        reloaded.change_pin("111111", "222222", PinComplexity::Basic).unwrap();
+        assert!(reloaded.is_session_valid());
+        assert_eq!(reloaded.unlock_with_pin("222222").unwrap(), data_key);
+    }
+
+    #[test]
+    fn test_reset_pin_requires_current_pin_outside_recovery_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_key = CryptoService::generate_data_key();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+        let recovery_key = service.generate_recovery_key(&data_key).unwrap();
+
+        // PIN 세션에서는 현재 PIN 없이 또는 틀린 PIN으로 재설정할 수 없음
+        assert!(service
+            .reset_pin(None, "222222", PinComplexity::Basic, &data_key, false)
+            .is_err());
+        assert!(service
+            .reset_pin(Some("999999"), "222222", PinComplexity::Basic, &data_key, false)
+            .is_err());
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(reloaded.verify_pin("111111").unwrap(), PinValidationResult::Valid);
+
+        service
+            .reset_pin(Some("111111"), "222222", PinComplexity::Basic, &data_key, false)
+            .unwrap();
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(reloaded.unlock_with_pin("222222").unwrap(), data_key);
+
+        // 복구 키 세션은 현재 PIN 없이 재설정 가능
+        let (recovered, _) = login_with_recovery(&mut reloaded, &recovery_key);
+        reloaded
+            .reset_pin(None, "333333", PinComplexity::Basic, &recovered, false)
+            .unwrap();
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(reloaded.unlock_with_pin("333333").unwrap(), data_key);
+    }
+
+    #[test]
+    fn test_interrupted_credential_save_is_finished_or_discarded() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        let data_key = CryptoService::generate_data_key();
+        let mut service = AuthService::with_config_dir(&config_dir);
+        service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+        let old_auth = fs::read(config_dir.join("auth.json")).unwrap();
+        let old_keys = fs::read(config_dir.join("keys.json")).unwrap();
+
+        service.change_pin("111111", "222222", PinComplexity::Basic).unwrap();
+        let new_auth = fs::read(config_dir.join("auth.json")).unwrap();
+        let new_keys = fs::read(config_dir.join("keys.json")).unwrap();
+
+        // keys.json 교체 전에 중단: 두 임시 파일을 버리고 이전 PIN 유지
+        fs::write(config_dir.join("auth.json"), &old_auth).unwrap();
+        fs::write(config_dir.join("keys.json"), &old_keys).unwrap();
+        fs::write(config_dir.join("auth.json.tmp"), &new_auth).unwrap();
+        fs::write(config_dir.join("keys.json.tmp"), &new_keys).unwrap();
+        let mut reloaded = AuthService::with_config_dir(&config_dir);
+        assert_eq!(reloaded.unlock_with_pin("111111").unwrap(), data_key);
+        assert!(!config_dir.join("auth.json.tmp").exists());
+        assert!(!config_dir.join("keys.json.tmp").exists());
+
+        // keys.json 교체 후 중단: 남은 auth.json.tmp를 마저 교체해 새 PIN 사용
+        fs::write(config_dir.join("keys.json"), &new_keys).unwrap();
+        fs::write(config_dir.join("auth.json.tmp"), &new_auth).unwrap();
+        let mut reloaded = AuthService::with_config_dir(&config_dir);
+        assert_eq!(reloaded.verify_pin("222222").unwrap(), PinValidationResult::Valid);
+        assert_eq!(reloaded.unlock_with_pin("222222").unwrap(), data_key);
+        assert!(!config_dir.join("auth.json.tmp").exists());
+
+        // 일부만 기록된 auth.json.tmp는 버림
+        fs::write(config_dir.join("auth.json.tmp"), &new_auth[..new_auth.len() / 2]).unwrap();
+        let mut reloaded = AuthService::with_config_dir(&config_dir);
+        assert_eq!(reloaded.unlock_with_pin("222222").unwrap(), data_key);
+        assert!(!config_dir.join("auth.json.tmp").exists());
+    }
+
+    #[test]
+    fn test_recovery_key_unwraps_data_key() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_key = CryptoService::generate_data_key();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+        let recovery_key = service.generate_recovery_key(&data_key).unwrap();
+
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        let (recovered, new_recovery_key) = login_with_recovery(&mut reloaded, &recovery_key);
+        assert_eq!(recovered, data_key);
+
+        // 복구 후 PIN 재설정 시에도 같은 데이터 키가 유지되어야 함
+        reloaded.set_pin("333333", PinComplexity::Basic, &recovered).unwrap();
+        assert_eq!(reloaded.unlock_with_pin("333333").unwrap(), data_key);
+        assert_eq!(reloaded.unlock_with_recovery_key(&new_recovery_key).unwrap(), data_key);
+    }
+
+    #[test]
+    fn test_used_recovery_key_is_recorded_and_superseded() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_key = CryptoService::generate_data_key();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+        let old_key = service.generate_recovery_key(&data_key).unwrap();
+        assert!(!service.recovery_rotation_required());
+
+        // 로그인하는 호출에서 바로 새 복구 키로 교체되고 사용 기록은 교체 기록에 남음
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        let (recovered, new_key) = login_with_recovery(&mut reloaded, &old_key);
+        assert_eq!(recovered, data_key);
+        assert!(!reloaded.recovery_rotation_required());
+
+        // 사용한 키는 재시작 후에도 다시 로그인할 수 없음
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert!(matches!(
+            reloaded.login_with_recovery_key(&old_key).unwrap(),
+            RecoveryLoginResult::Rejected(RecoveryKeyValidationResult::Superseded)
+        ));
+        assert!(!reloaded.is_session_valid());
+        assert!(reloaded.unlock_with_recovery_key(&old_key).is_err());
+        assert_eq!(reloaded.unlock_with_recovery_key(&new_key).unwrap(), data_key);
+
+        let status = reloaded.recovery_key_status();
+        assert_eq!(status.use_count, 0);
+        assert!(!status.rotation_required);
+        assert_eq!(status.superseded_count, 1);
+        assert_eq!(reloaded.superseded_recovery_keys[0].use_count, 1);
+    }
+
+    #[test]
+    fn test_rotate_data_key_rewraps_every_slot() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_key = CryptoService::generate_data_key();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        service.set_pin("482913", PinComplexity::Basic, &data_key).unwrap();
+        let old_recovery_key = service.generate_recovery_key(&data_key).unwrap();
+        service.step_up("482913").unwrap();
+        service
+            .set_critical_passphrase("482913", "correct horse battery")
+            .unwrap();
+        let old_critical_key = service.critical_key().unwrap();
+
+        assert!(service.rotate_data_key("000000", None, &data_key).is_err());
+        assert!(service.rotate_data_key("482913", None, &data_key).is_err());
+        let rotation = service
+            .rotate_data_key("482913", Some("correct horse battery"), &data_key)
+            .unwrap();
+        assert_ne!(*rotation.data_key, data_key);
+        let new_critical_key = service.critical_key().unwrap();
+        assert_ne!(*new_critical_key, *old_critical_key);
+
+        // 교체가 끝나지 않은 동안 다시 교체할 수 없음
+        assert!(service
+            .rotate_data_key("482913", Some("correct horse battery"), &rotation.data_key)
+            .is_err());
+
+        // 모든 슬롯이 새 키를 열고, 이전 키는 새 키로만 꺼낼 수 있음
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(
+            reloaded.unlock_with_pin("482913").unwrap(),
+            *rotation.data_key
+        );
+        assert!(reloaded
+            .unlock_with_recovery_key(&old_recovery_key)
+            .is_err());
+        assert_eq!(
+            reloaded
+                .unlock_with_recovery_key(rotation.recovery_key.as_deref().unwrap())
+                .unwrap(),
+            *rotation.data_key
+        );
+        reloaded.step_up("482913").unwrap();
+        reloaded.unlock_critical_key("correct horse battery").unwrap();
+        assert_eq!(reloaded.critical_key().as_deref(), Some(&*new_critical_key));
+        assert_eq!(reloaded.retired_data_key(&rotation.data_key).unwrap(), data_key);
+        assert!(reloaded.retired_data_key(&data_key).is_none());
+        assert_eq!(
+            reloaded.retired_critical_key(&new_critical_key).unwrap(),
+            *old_critical_key
+        );
+
+        // 재암호화가 끝나면 이전 키 기록을 삭제
+        reloaded.discard_retired_keys(&rotation.data_key).unwrap();
+        let reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert!(reloaded.retired_data_key(&rotation.data_key).is_none());
+    }
+
+    #[test]
+    fn test_legacy_vault_migrates_to_wrapped_key() {
+        let temp_dir = TempDir::new().unwrap();
+        let salt = [5u8; 32];
+        let legacy_hash = AuthService::with_config_dir(temp_dir.path().join("config"))
+            .hash_pin("1234", &salt, &KdfParams::legacy_pin_hash())
+            .unwrap();
+        let json = serde_json::json!({
+            "pin_info": { "hash": legacy_hash, "salt": salt.to_vec() },
+            "recovery_key_info": null,
+        });
+        fs::create_dir_all(temp_dir.path().join("config")).unwrap();
+        fs::write(temp_dir.path().join("config").join("auth.json"), json.to_string()).unwrap();
+
+        // 기존 볼트의 파일은 PBKDF2로 유도한 키로 암호화되어 있음
+        let legacy_key =
+            CryptoService::derive_key_with_params(b"1234", &salt, &KdfParams::legacy_master_key())
+                .unwrap();
+
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(service.verify_pin("1234").unwrap(), PinValidationResult::Valid);
+        assert_eq!(service.unlock_with_pin("1234").unwrap(), legacy_key);
+        assert!(temp_dir.path().join("config").join("keys.json").exists());
+
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(reloaded.unlock_with_pin("1234").unwrap(), legacy_key);
+    }
+
+    /// 실패 2회 후 잠기는 PIN 설정 서비스를 만듭니다.
+    fn service_with_lockout(temp_dir: &TempDir) -> AuthService {
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        service
+            .set_pin("111111", PinComplexity::Basic, &CryptoService::generate_data_key())
+            .unwrap();
+
+        let mut config = SecurityConfig::default();
+        config.brute_force_protection.max_attempts = 2;
+        config.brute_force_protection.base_lockout_seconds = 10;
+        config.brute_force_protection.max_lockout_seconds = 25;
+        service.set_security_config(config).unwrap();
+        service
+    }
+
+    #[test]
+    fn test_lockout_persists_across_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut service = service_with_lockout(&temp_dir);
+
+        assert_eq!(service.verify_pin("000111").unwrap(), PinValidationResult::Invalid);
+        assert_eq!(service.verify_pin("000111").unwrap(), PinValidationResult::Invalid);
+
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(reloaded.get_brute_force_protection().failed_attempts, 2);
+        assert!(matches!(
+            reloaded.verify_pin("111111").unwrap(),
+            PinValidationResult::AccountLocked(_)
+        ));
+    }
+
+    #[test]
+    fn test_lockout_exponential_backoff_is_capped() {
+        let mut protection = BruteForceProtection::from_config(&crate::models::BruteForceConfig {
+            max_attempts: 2,
+            base_lockout_seconds: 10,
+            exponential_backoff: true,
+            max_lockout_seconds: 25,
+            ip_blocking_enabled: false,
+        });
+
+        protection.record_failure();
+        assert_eq!(protection.lockout_duration_seconds(), 0);
+        protection.record_failure();
+        assert_eq!(protection.lockout_duration_seconds(), 10);
+        protection.record_failure();
+        assert_eq!(protection.lockout_duration_seconds(), 20);
+        protection.record_failure();
+        assert_eq!(protection.lockout_duration_seconds(), 25);
+
+        protection.exponential_backoff = false;
+        assert_eq!(protection.lockout_duration_seconds(), 10);
+    }
+
+    #[test]
+    fn test_tampered_lockout_record_applies_max_lockout() {
+        let temp_dir = TempDir::new().unwrap();
+        let lockout_file = temp_dir.path().join("config").join("lockout.json");
+        let mut service = service_with_lockout(&temp_dir);
+        assert_eq!(service.verify_pin("000111").unwrap(), PinValidationResult::Invalid);
+        assert!(!temp_dir.path().join("config").join("lockout.json.tmp").exists());
+
+        let record = fs::read_to_string(&lockout_file).unwrap();
+        let reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert!(reloaded.can_attempt_login());
+        assert_eq!(reloaded.get_brute_force_protection().failed_attempts, 1);
+
+        // 실패 횟수를 고친 기록, 잘린 기록, 삭제된 기록은 모두 최대 잠금으로 처리
+        let edited = record.replace("\"failed_attempts\": 1", "\"failed_attempts\": 0");
+        assert_ne!(edited, record);
+        for contents in [Some(edited), Some(record[..record.len() / 2].to_string()), None] {
+            match contents {
+                Some(contents) => fs::write(&lockout_file, contents).unwrap(),
+                None => fs::remove_file(&lockout_file).unwrap(),
+            }
+
+            let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+            assert!(!reloaded.can_attempt_login());
+            assert!(reloaded.get_brute_force_protection().remaining_lockout_seconds().unwrap() > 10);
+            assert!(matches!(
+                reloaded.verify_pin("111111").unwrap(),
+                PinValidationResult::AccountLocked(_)
+            ));
+
+            // 최대 잠금 기록이 새 MAC과 함께 다시 저장됨
+            let relocked = AuthService::with_config_dir(temp_dir.path().join("config"));
+            assert!(!relocked.can_attempt_login());
+            fs::write(&lockout_file, &record).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_duress_pin_opens_decoy_vault() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join(".securevault").join("config");
+        let data_key = CryptoService::generate_data_key();
+        let decoy_key = CryptoService::generate_data_key();
+
+        let decoy_vaults_dir = temp_dir.path().join(".securevault").join(DECOY_VAULTS_DIR);
+
+        let mut service = AuthService::with_config_dir(&config_dir);
+        service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+        assert!(!service.has_duress_pin(&data_key));
+        assert!(service.set_duress_pin("111111", &decoy_key, &data_key).is_err());
+        service.set_duress_pin("999999", &decoy_key, &data_key).unwrap();
+        assert!(service.has_duress_pin(&data_key));
+
+        let mut reloaded = AuthService::with_config_dir(&config_dir);
+        assert_eq!(reloaded.verify_pin("999999").unwrap(), PinValidationResult::Valid);
+        assert!(reloaded.is_decoy_session());
+        assert!(!reloaded.has_duress_pin(&decoy_key));
+        assert_eq!(reloaded.unlock_with_pin("999999").unwrap(), decoy_key);
+
+        // 위장 볼트는 자리 표시용 볼트를 대신한 유일한 디렉토리
+        let decoy_root = reloaded.active_vault_root();
+        assert_eq!(decoy_root.parent().unwrap(), decoy_vaults_dir);
+        assert!(decoy_root.is_dir());
+        assert_eq!(fs::read_dir(&decoy_vaults_dir).unwrap().count(), 1);
+
+        reloaded.logout().unwrap();
+        assert_eq!(reloaded.verify_pin("111111").unwrap(), PinValidationResult::Valid);
+        assert!(!reloaded.is_decoy_session());
+        assert_eq!(reloaded.unlock_with_pin("111111").unwrap(), data_key);
+        assert_eq!(reloaded.active_vault_root(), temp_dir.path());
+    }
+
+    #[test]
+    fn test_decoy_session_cannot_touch_real_vault() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_key = CryptoService::generate_data_key();
+        let decoy_key = CryptoService::generate_data_key();
+
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+        let recovery_key = service.generate_recovery_key(&data_key).unwrap();
+        service.set_duress_pin("999999", &decoy_key, &data_key).unwrap();
+        service.logout().unwrap();
+
+        // 위장 볼트 세션에서의 PIN 변경, 복구 키 생성, 강압 PIN 제거
+        assert_eq!(service.verify_pin("999999").unwrap(), PinValidationResult::Valid);
+        service.change_pin("999999", "888888", PinComplexity::Basic).unwrap();
+        assert!(!service.is_current_recovery_key(&recovery_key));
+        let decoy_recovery_key = service.generate_recovery_key(&decoy_key).unwrap();
+        assert!(service.is_current_recovery_key(&decoy_recovery_key));
+        assert!(!service.is_current_recovery_key(&recovery_key));
+        service.remove_duress_pin(&decoy_key).unwrap();
+
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(reloaded.verify_pin("888888").unwrap(), PinValidationResult::Valid);
+        assert_eq!(reloaded.unlock_with_pin("888888").unwrap(), decoy_key);
+        reloaded.logout().unwrap();
+        assert_eq!(reloaded.verify_pin("111111").unwrap(), PinValidationResult::Valid);
+        assert_eq!(reloaded.unlock_with_pin("111111").unwrap(), data_key);
+        assert_eq!(reloaded.unlock_with_recovery_key(&recovery_key).unwrap(), data_key);
+    }
+
+    /// 값은 지우고 필드 구성과 문자열/배열 길이만 남깁니다.
+    fn json_shape(value: &serde_json::Value) -> serde_json::Value {
+        use serde_json::Value;
+        match value {
+            Value::String(text) => Value::from(text.len()),
+            Value::Number(_) | Value::Bool(_) => Value::Null,
+            Value::Array(items) => Value::Array(items.iter().map(json_shape).collect()),
+            Value::Object(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(name, field)| (name.clone(), json_shape(field)))
+                    .collect(),
+            ),
+            Value::Null => Value::Null,
+        }
+    }
+
+    #[test]
+    fn test_alternate_pin_slots_do_not_reveal_which_pins_are_set() {
+        let shapes: Vec<_> = [false, true]
+            .into_iter()
+            .map(|with_alternate_pins| {
+                let temp_dir = TempDir::new().unwrap();
+                let securevault_dir = temp_dir.path().join(".securevault");
+                let config_dir = securevault_dir.join("config");
+                let data_key = CryptoService::generate_data_key();
+
+                let mut service = AuthService::with_config_dir(&config_dir);
+                service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+                if with_alternate_pins {
+                    let decoy_key = CryptoService::generate_data_key();
+                    service.set_duress_pin("999999", &decoy_key, &data_key).unwrap();
+                    service.set_panic_pin("777777", &data_key).unwrap();
+                }
+
+                let read = |name: &str| -> serde_json::Value {
+                    let json = fs::read_to_string(config_dir.join(name)).unwrap();
+                    serde_json::from_str(&json).unwrap()
+                };
+                let decoy_vaults: Vec<_> = fs::read_dir(securevault_dir.join(DECOY_VAULTS_DIR))
+                    .unwrap()
+                    .map(|entry| entry.unwrap().file_name().len())
+                    .collect();
+                (json_shape(&read("auth.json")), json_shape(&read("keys.json")), decoy_vaults)
+            })
+            .collect();
+
+        assert_eq!(shapes[0], shapes[1]);
+        assert_eq!(shapes[0].2.len(), 1);
+    }
+
+    #[test]
+    fn test_remove_duress_pin_replaces_decoy_vault() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join(".securevault").join("config");
+        let decoy_vaults_dir = temp_dir.path().join(".securevault").join(DECOY_VAULTS_DIR);
+        let data_key = CryptoService::generate_data_key();
+        let decoy_key = CryptoService::generate_data_key();
+
+        let mut service = AuthService::with_config_dir(&config_dir);
+        service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+        service.set_duress_pin("999999", &decoy_key, &data_key).unwrap();
+        service.logout().unwrap();
+
+        assert_eq!(service.verify_pin("999999").unwrap(), PinValidationResult::Valid);
+        service.unlock_with_pin("999999").unwrap();
+        let decoy_root = service.active_vault_root();
+        fs::create_dir_all(decoy_root.join(".securevault")).unwrap();
+        fs::write(decoy_root.join(".securevault").join("metadata.db"), b"decoy").unwrap();
+        service.logout().unwrap();
+
+        assert_eq!(service.verify_pin("111111").unwrap(), PinValidationResult::Valid);
+        service.remove_duress_pin(&data_key).unwrap();
+        assert!(!service.has_duress_pin(&data_key));
+        assert!(!decoy_root.exists());
+        assert_eq!(fs::read_dir(&decoy_vaults_dir).unwrap().count(), 1);
+        assert_eq!(service.verify_pin("999999").unwrap(), PinValidationResult::Invalid);
+    }
+
+    #[test]
+    fn test_panic_pin_crypto_erases_vault() {
+        let temp_dir = TempDir::new().unwrap();
+        let securevault_dir = temp_dir.path().join(".securevault");
+        let config_dir = securevault_dir.join("config");
+        let data_key = CryptoService::generate_data_key();
+
+        let mut service = AuthService::with_config_dir(&config_dir);
+        service.set_pin("111111", PinComplexity::Basic, &data_key).unwrap();
+        let recovery_key = service.generate_recovery_key(&data_key).unwrap();
+        service
+            .set_duress_pin("999999", &CryptoService::generate_data_key(), &data_key)
+            .unwrap();
+        assert!(service.set_panic_pin("111111", &data_key).is_err());
+        assert!(service.set_panic_pin("999999", &data_key).is_err());
+        service.set_panic_pin("777777", &data_key).unwrap();
+        assert!(service.has_panic_pin(&data_key));
+
+        // 볼트 데이터 키로 암호화된 파일과 평문 메타데이터 DB
+        let crypto = CryptoService::new();
+        let encrypted = crypto
+            .encrypt_data_csharp_compatible(b"secret file contents", data_key.expose())
+            .unwrap();
+        fs::write(securevault_dir.join("metadata.db"), b"file names").unwrap();
+        fs::create_dir_all(securevault_dir.join("logs")).unwrap();
+        fs::write(securevault_dir.join("logs").join("vault.log"), b"log").unwrap();
+        fs::create_dir_all(securevault_dir.join("files")).unwrap();
+        fs::write(securevault_dir.join("files").join("blob.enc"), &encrypted).unwrap();
+
+        let old_key_store: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(config_dir.join("keys.json")).unwrap())
+                .unwrap();
+        // 패닉 PIN 슬롯의 키는 새 빈 볼트의 키이므로 제외
+        let old_wrapped_keys: Vec<String> = ["pin", "recovery"]
+            .iter()
+            .map(|slot| &old_key_store[slot])
+            .chain([&old_key_store["slots"][DURESS_SLOT]["key"]])
+            .map(|wrapped| wrapped["wrapped_key"].as_str().unwrap().to_string())
+            .collect();
+
+        let old_auth_data: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(config_dir.join("auth.json")).unwrap())
+                .unwrap();
+        let panic_slot_hash = old_auth_data["pin_slots"][PANIC_SLOT]["hash"].clone();
+        let panic_slot_key = old_key_store["slots"][PANIC_SLOT]["key"]["wrapped_key"].clone();
+
+        service.logout().unwrap();
+
+        // 패닉 PIN은 정상 로그인처럼 보이며 빈 볼트가 열림
+        assert_eq!(service.verify_pin("777777").unwrap(), PinValidationResult::Valid);
+        assert!(!service.is_decoy_session());
+        let new_key = service.unlock_with_pin("777777").unwrap();
+        assert_ne!(new_key, data_key);
+        assert!(crypto.decrypt_data_csharp_compatible(&encrypted, new_key.expose()).is_err());
+        assert!(!securevault_dir.join("metadata.db").exists());
+        assert!(!securevault_dir.join("logs").exists());
+        assert!(!securevault_dir.join("files").join("blob.enc").exists());
+        assert_eq!(fs::read_dir(securevault_dir.join(DECOY_VAULTS_DIR)).unwrap().count(), 1);
+
+        // 새 빈 볼트는 설정할 때 미리 계산한 검증자와 래핑된 키를 그대로 사용 (추가 키 유도 없음)
+        let new_key_store: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(config_dir.join("keys.json")).unwrap())
+                .unwrap();
+        let new_auth_data: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(config_dir.join("auth.json")).unwrap())
+                .unwrap();
+        assert_eq!(new_key_store["pin"]["wrapped_key"], panic_slot_key);
+        assert_eq!(new_auth_data["pin_info"]["hash"], panic_slot_hash);
+
+        // 기존 PIN, 강압 PIN, 복구 키 모두 더 이상 동작하지 않음
+        let mut reloaded = AuthService::with_config_dir(&config_dir);
+        assert_eq!(reloaded.verify_pin("111111").unwrap(), PinValidationResult::Invalid);
+        assert_eq!(reloaded.verify_pin("999999").unwrap(), PinValidationResult::Invalid);
+        assert!(!reloaded.has_recovery_key());
+        assert!(!reloaded.has_panic_pin(&new_key));
+        assert!(reloaded.unlock_with_recovery_key(&recovery_key).is_err());
+
+        // 기존 래핑된 키가 설정 디렉토리 어디에도 남아 있지 않음
+        for entry in fs::read_dir(&config_dir).unwrap() {
+            let contents = fs::read(entry.unwrap().path()).unwrap();
+            let contents = String::from_utf8_lossy(&contents);
+            for wrapped in &old_wrapped_keys {
+                assert!(!contents.contains(wrapped.as_str()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_erase_tree_overwrites_files_but_not_link_targets() {
+        let temp_dir = TempDir::new().unwrap();
+        let outside = temp_dir.path().join("outside.txt");
+        fs::write(&outside, b"keep").unwrap();
+
+        let tree = temp_dir.path().join("erase").join("vault");
+        fs::create_dir_all(tree.join("files")).unwrap();
+        fs::write(tree.join("files").join("blob.enc"), b"blob").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&outside, tree.join("link")).unwrap();
+
+        AuthService::erase_tree(&temp_dir.path().join("erase"));
+        assert!(!temp_dir.path().join("erase").exists());
+        assert_eq!(fs::read(&outside).unwrap(), b"keep");
+    }
+
+    #[test]
+    fn test_korean_passphrase_is_normalized() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        service
+            .set_pin_requirement(PinComplexityRequirement::High)
+            .unwrap();
+
+        // 완성형(NFC)으로 설정하고 조합형(NFD) 자모로 입력
+        let composed = "비밀번호 금고 2024";
+        let decomposed: String = composed.nfd().collect();
+        assert_ne!(composed, decomposed);
+
+        let data_key = CryptoService::generate_data_key();
+        service.set_pin(composed, PinComplexity::High, &data_key).unwrap();
+        assert!(service.is_passphrase_pin());
+
+        assert_eq!(service.verify_pin(&decomposed).unwrap(), PinValidationResult::Valid);
+        assert_eq!(service.unlock_with_pin(&decomposed).unwrap(), data_key);
+        assert_eq!(service.verify_pin("비밀번호 금고 2025").unwrap(), PinValidationResult::Invalid);
+    }
+
+    #[test]
+    fn test_pin_requirement_is_enforced() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        let data_key = CryptoService::generate_data_key();
+
+        // 기본 요구사항은 6-8자리 숫자
+        assert!(service.set_pin("1234", PinComplexity::Basic, &data_key).is_err());
+        assert!(service.set_pin("12ab56", PinComplexity::Basic, &data_key).is_err());
+        service.set_pin("123456", PinComplexity::Basic, &data_key).unwrap();
+
+        service
+            .set_pin_requirement(PinComplexityRequirement::Custom {
+                min_length: 10,
+                max_length: 64,
+                require_numbers: true,
+                require_letters: true,
+                require_special_chars: true,
+            })
+            .unwrap();
+        assert!(service.validate_pin_format("correcthorse").is_err());
+        assert!(service.validate_pin_format("correct-horse-9").is_ok());
+
+        assert!(service
+            .set_pin_requirement(PinComplexityRequirement::Custom {
+                min_length: 2,
+                max_length: 8,
+                require_numbers: true,
+                require_letters: false,
+                require_special_chars: false,
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_numeric_pin_migrates_to_passphrase() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        let data_key = CryptoService::generate_data_key();
+        service.set_pin("482901", PinComplexity::Basic, &data_key).unwrap();
+
+        // 요구사항을 강화해도 기존 숫자 PIN으로 로그인할 수 있고 변경이 안내됨
+        service
+            .set_pin_requirement(PinComplexityRequirement::High)
+            .unwrap();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(service.verify_pin("482901").unwrap(), PinValidationResult::Valid);
+        assert!(service.is_pin_upgrade_required());
+
+        assert!(service.change_pin("482901", "48291234", PinComplexity::High).is_err());
+        service
+            .change_pin("482901", "금고 열쇠 4829", PinComplexity::High)
+            .unwrap();
+        assert!(!service.is_pin_upgrade_required());
+        assert!(service.is_passphrase_pin());
+
+        assert_eq!(service.verify_pin("482901").unwrap(), PinValidationResult::Invalid);
+        assert_eq!(service.verify_pin("금고 열쇠 4829").unwrap(), PinValidationResult::Valid);
+        assert_eq!(service.unlock_with_pin("금고 열쇠 4829").unwrap(), data_key);
+    }
+
+    #[test]
+    fn test_key_file_is_required_after_enrollment() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        let data_key = CryptoService::generate_data_key();
+        service.set_pin("482901", PinComplexity::Basic, &data_key).unwrap();
+
+        let key_file = AuthService::generate_key_file_contents();
+        assert!(service.enroll_key_file("000111", &key_file, &data_key).is_err());
+        service.enroll_key_file("482901", &key_file, &data_key).unwrap();
+        assert!(service.get_security_config().two_factor_enabled);
+
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert!(service.has_key_file());
+
+        // auth.json의 검증자만으로는 키 파일 없이 PIN을 추측할 수 없음
+        let pin_info = service.get_pin_info().unwrap().clone();
+        assert!(pin_info.key_file);
+        assert_ne!(
+            service.hash_pin("482901", &pin_info.salt, &pin_info.hash_params).unwrap(),
+            pin_info.hash
+        );
+
+        // 키 파일 없음 / 다른 키 파일은 PIN 확인 전에 거부되고 실패 횟수에 포함되지 않음
+        let failed_attempts = service.get_brute_force_protection().failed_attempts;
+        assert_eq!(service.verify_pin("482901").unwrap(), PinValidationResult::KeyFileRequired);
+        service.provide_key_file(Some(b"not the key file"));
+        assert_eq!(service.verify_pin("482901").unwrap(), PinValidationResult::KeyFileMismatch);
+        assert_eq!(service.get_brute_force_protection().failed_attempts, failed_attempts);
+
+        // 키 파일만으로는 열리지 않음
+        service.provide_key_file(Some(&key_file));
+        assert_eq!(service.verify_pin("000111").unwrap(), PinValidationResult::Invalid);
+
+        assert_eq!(service.verify_pin("482901").unwrap(), PinValidationResult::Valid);
+        assert_eq!(service.unlock_with_pin("482901").unwrap(), data_key);
+
+        // 래핑된 키는 PIN만으로는 풀리지 않음
+        let wrapped = service.key_store.pin.clone().unwrap();
+        assert!(wrapped.key_file);
+        assert!(AuthService::unwrap_data_key(b"482901", &wrapped).is_err());
+    }
+
+    #[test]
+    fn test_key_file_replace_and_remove() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        let data_key = CryptoService::generate_data_key();
+        service.set_pin("482901", PinComplexity::Basic, &data_key).unwrap();
+
+        let old_file = AuthService::generate_key_file_contents();
+        let new_file = AuthService::generate_key_file_contents();
+        service.enroll_key_file("482901", &old_file, &data_key).unwrap();
+        service.enroll_key_file("482901", &new_file, &data_key).unwrap();
+
+        // PIN 변경 후에도 키 파일 요구가 유지됨
+        service.change_pin("482901", "739105", PinComplexity::Basic).unwrap();
+
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        reloaded.provide_key_file(Some(&old_file));
+        assert_eq!(reloaded.verify_pin("739105").unwrap(), PinValidationResult::KeyFileMismatch);
+        reloaded.provide_key_file(Some(&new_file));
+        assert_eq!(reloaded.verify_pin("739105").unwrap(), PinValidationResult::Valid);
+        assert_eq!(reloaded.unlock_with_pin("739105").unwrap(), data_key);
+
+        reloaded.remove_key_file("739105", &data_key).unwrap();
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert!(!reloaded.has_key_file());
+        assert!(!reloaded.get_security_config().two_factor_enabled);
+        assert_eq!(reloaded.verify_pin("739105").unwrap(), PinValidationResult::Valid);
+        assert_eq!(reloaded.unlock_with_pin("739105").unwrap(), data_key);
+    }
+
+    #[test]
+    fn test_pin_reset_without_key_file_requires_confirmation() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        let data_key = CryptoService::generate_data_key();
+        service.set_pin("482901", PinComplexity::Basic, &data_key).unwrap();
+        service
+            .enroll_key_file("482901", &AuthService::generate_key_file_contents(), &data_key)
+            .unwrap();
+        let recovery_key = service.generate_recovery_key(&data_key).unwrap();
+
+        // 키 파일을 잃어버려 복구 키로 로그인
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        let (recovered, _) = login_with_recovery(&mut reloaded, &recovery_key);
+
+        // 동의 없이 재설정하면 키 파일 요구가 조용히 빠지지 않음
+        assert!(reloaded.set_pin("739105", PinComplexity::Basic, &recovered).is_err());
+        assert!(AuthService::with_config_dir(temp_dir.path().join("config")).has_key_file());
+
+        reloaded
+            .set_pin_removing_key_file("739105", PinComplexity::Basic, &recovered)
+            .unwrap();
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert!(!reloaded.has_key_file());
+        assert!(!reloaded.get_pin_info().unwrap().key_file);
+        assert_eq!(reloaded.verify_pin("739105").unwrap(), PinValidationResult::Valid);
+        assert_eq!(reloaded.unlock_with_pin("739105").unwrap(), data_key);
+    }
+
+    #[test]
+    fn test_new_vault_setup_starts_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert!(!service.is_session_valid());
+
+        service
+            .set_pin("482913", PinComplexity::Basic, &CryptoService::generate_data_key())
+            .unwrap();
+        assert!(service.is_session_valid());
+
+        service.logout().unwrap();
+        assert!(!service.is_session_valid());
+    }
+
+    #[test]
+    fn test_step_up_and_critical_passphrase() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut service = AuthService::with_config_dir(temp_dir.path().join("config"));
+        service
+            .set_pin("482913", PinComplexity::Basic, &CryptoService::generate_data_key())
+            .unwrap();
+
+        assert!(!service.has_recent_step_up());
+        assert!(service.step_up("000000").is_err());
+        service.step_up("482913").unwrap();
+        assert!(service.has_recent_step_up());
+
+        // PIN과 같은 암호는 사용할 수 없음
+        assert!(service.set_critical_passphrase("482913", "482913").is_err());
+        service
+            .set_critical_passphrase("482913", "correct horse battery")
+            .unwrap();
+        let critical_key = service.critical_key().unwrap();
+
+        // 로그아웃하면 재확인 상태와 최고 보안 파일 키가 사라짐
+        service.logout().unwrap();
+        assert!(service.critical_key().is_none());
+
+        let mut reloaded = AuthService::with_config_dir(temp_dir.path().join("config"));
+        assert!(reloaded.has_critical_passphrase());
+        reloaded.step_up("482913").unwrap();
+        assert!(reloaded.unlock_critical_key("wrong passphrase").is_err());
+        reloaded.unlock_critical_key("correct horse battery").unwrap();
+        assert_eq!(reloaded.critical_key().as_deref(), Some(&*critical_key));
+
+        // 암호를 바꿔도 최고 보안 파일 키는 그대로
+        reloaded
+            .change_critical_passphrase("correct horse battery", "new critical phrase")
+            .unwrap();
+        reloaded.end_step_up();
+        reloaded.step_up("482913").unwrap();
+        reloaded.unlock_critical_key("new critical phrase").unwrap();
+        assert_eq!(reloaded.critical_key().as_deref(), Some(&*critical_key));
+    }
+}
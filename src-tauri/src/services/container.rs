@@ -0,0 +1,1062 @@
+// 암호화 파일 컨테이너
+// `.enc` 파일의 자기 기술형 헤더와 청크 프레이밍을 정의합니다.
+//
+// 형식 (헤더의 정수는 little-endian):
+//   헤더   : 매직(6) + 버전(2) + 헤더 길이(2) + 알고리즘 ID(1) + 플래그(1)
+//            + 청크 크기(4) + 파일 ID(16) + 키 ID(8) + 논스 접두사(7) + 키 커밋(32)
+//   본문   : [암호문 + 태그(16)] 반복 (마지막 청크를 제외한 모든 청크는 청크 크기만큼의 평문)
+//
+// 청크 논스는 STREAM 구성(논스 접두사(7) + 청크 번호(4, big-endian) + 마지막 플래그(1))으로
+// 만들고 파일에는 저장하지 않습니다. 파일은 항상 마지막 플래그가 붙은 청크(빈 청크일 수 있음)로
+// 끝나므로, 청크를 빼거나 중복하거나 순서를 바꾸거나 청크 경계에서 잘라내면 복호화가 실패합니다.
+//
+// 헤더 전체가 모든 청크의 AEAD 추가 인증 데이터로 사용되므로, 헤더의 어느 필드를
+// 바꾸더라도 복호화가 실패합니다. 특히 파일 ID가 인증되므로 다른 파일의 `.enc`로 바꿔치기하면
+// 열리지 않습니다. 매직이 없는 파일은 이전(헤더 없는) 형식이며 `migrate_legacy_files`로 변환합니다.
+//
+// AES-GCM과 ChaCha20-Poly1305는 키에 커밋하지 않아 하나의 암호문이 서로 다른 두 키로 모두
+// 복호화되도록 만들 수 있습니다. 헤더의 키 커밋(HMAC-SHA256(커밋 키, 앞부분 헤더))을 청크를 열기
+// 전에 검증하므로 컨테이너는 그것을 만든 키로만 열립니다. 파일 키는 직접 쓰지 않고, 청크 키와
+// 커밋 키를 각각의 컨텍스트로 유도해 AEAD와 키 커밋이 같은 키를 공유하지 않습니다.
+
+use crate::models::{CryptoError, EncryptionAlgorithm, SecretKey, SecureMemory, SecureRandom};
+use crate::SecureVaultResult;
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use chacha20poly1305::{ChaCha20Poly1305, Key as ChaChaKey, Nonce as ChaChaNonce};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use std::sync::Arc;
+use uuid::Uuid;
+use zeroize::{Zeroize, Zeroizing};
+
+/// 컨테이너 매직 바이트
+pub const CONTAINER_MAGIC: [u8; 6] = *b"SVENC\0";
+
+/// 컨테이너 형식 버전
+pub const CONTAINER_VERSION: u16 = 1;
+
+/// 헤더 길이 (바이트)
+pub const CONTAINER_HEADER_LEN: usize = 79;
+
+/// 헤더에서 키 커밋이 시작하는 위치 (키 커밋이 덮는 앞부분의 길이)
+const KEY_COMMITMENT_OFFSET: usize = CONTAINER_HEADER_LEN - KEY_COMMITMENT_LEN;
+
+/// 기본 평문 청크 크기 (4MB)
+pub const DEFAULT_CHUNK_SIZE: u32 = 4 * 1024 * 1024;
+
+/// 허용하는 최대 평문 청크 크기 (64MB)
+pub const MAX_CHUNK_SIZE: u32 = 64 * 1024 * 1024;
+
+/// 키 ID 길이 (바이트)
+pub const KEY_ID_LEN: usize = 8;
+
+/// 청크 논스 접두사 길이 (논스 12바이트 = 접두사 7 + 청크 번호 4 + 마지막 플래그 1)
+pub const NONCE_PREFIX_LEN: usize = 7;
+
+/// 키 커밋 길이 (바이트)
+pub const KEY_COMMITMENT_LEN: usize = 32;
+
+/// 청크 논스 크기
+const NONCE_SIZE: usize = 12;
+
+/// 청크 인증 태그 크기
+const TAG_SIZE: usize = 16;
+
+/// 키 ID 계산 시 사용하는 도메인 구분 문자열
+const KEY_ID_CONTEXT: &[u8] = b"SecureVault key id v1";
+
+/// 청크 키 유도 시 사용하는 도메인 구분 문자열
+const CHUNK_KEY_CONTEXT: &[u8] = b"SecureVault container chunk key v1";
+
+/// 키 커밋 키 유도 시 사용하는 도메인 구분 문자열
+const KEY_COMMITMENT_CONTEXT: &[u8] = b"SecureVault key commitment v1";
+
+/// 키 ID를 계산합니다.
+///
+/// 키 자체를 노출하지 않고 어떤 키로 암호화됐는지 구분하기 위한 값입니다.
+///
+/// # 매개변수
+/// * `key` - 파일 암호화 키
+///
+/// # 반환값
+/// * `[u8; 8]` - SHA-256(컨텍스트 || 키)의 앞 8바이트
+pub fn key_id(key: &SecretKey) -> [u8; KEY_ID_LEN] {
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_ID_CONTEXT);
+    hasher.update(key.expose());
+    let digest = hasher.finalize();
+
+    let mut id = [0u8; KEY_ID_LEN];
+    id.copy_from_slice(&digest[..KEY_ID_LEN]);
+    id
+}
+
+/// 파일 암호화 키와 용도별 컨텍스트로 HMAC-SHA256 하위 키를 유도합니다.
+fn derive_subkey(key: &SecretKey, context: &[u8]) -> SecretKey {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key.expose())
+        .expect("HMAC은 모든 키 길이를 허용합니다");
+    mac.update(context);
+
+    let mut subkey = SecretKey::zeroed();
+    subkey
+        .expose_mut()
+        .copy_from_slice(&mac.finalize().into_bytes());
+    subkey
+}
+
+/// 키 커밋을 계산합니다.
+///
+/// 키 ID(8바이트)는 키를 구분하는 용도일 뿐 충돌을 찾을 수 있으므로, 키 커밋은
+/// 전체 32바이트 HMAC으로 키와 헤더 앞부분에 함께 커밋합니다. HMAC 키는 청크 키와 별도로
+/// 유도한 커밋 키입니다.
+///
+/// # 매개변수
+/// * `key` - 파일 암호화 키
+/// * `header_prefix` - 키 커밋 앞까지의 헤더 바이트
+fn key_commitment(key: &SecretKey, header_prefix: &[u8]) -> Hmac<Sha256> {
+    let commitment_key = derive_subkey(key, KEY_COMMITMENT_CONTEXT);
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(commitment_key.expose())
+        .expect("HMAC은 모든 키 길이를 허용합니다");
+    mac.update(header_prefix);
+    mac
+}
+
+/// 데이터가 컨테이너 형식인지 매직 바이트로 확인합니다.
+///
+/// 이전 형식은 임의의 IV(단일 블록) 또는 100MB 이하의 청크 길이로 시작하므로
+/// 매직과 겹치지 않습니다.
+pub fn is_container(data: &[u8]) -> bool {
+    data.len() >= CONTAINER_MAGIC.len() && data[..CONTAINER_MAGIC.len()] == CONTAINER_MAGIC
+}
+
+/// 컨테이너의 형식 버전을 반환합니다.
+///
+/// # 반환값
+/// * `Option<u16>` - 형식 버전 (컨테이너가 아니면 None)
+pub fn container_version(data: &[u8]) -> Option<u16> {
+    if is_container(data) && data.len() >= 8 {
+        Some(u16::from_le_bytes([data[6], data[7]]))
+    } else {
+        None
+    }
+}
+
+/// 형식 버전을 확인합니다.
+///
+/// # 오류
+/// * `CryptoError::UnsupportedContainerVersion` - 알 수 없는 형식 버전
+fn check_version(version: u16) -> SecureVaultResult<()> {
+    if version == CONTAINER_VERSION {
+        Ok(())
+    } else {
+        Err(CryptoError::UnsupportedContainerVersion(version).into())
+    }
+}
+
+/// 컨테이너 헤더
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerHeader {
+    /// 형식 버전
+    pub version: u16,
+    /// 암호화 알고리즘
+    pub algorithm: EncryptionAlgorithm,
+    /// 평문 청크 크기
+    pub chunk_size: u32,
+    /// 파일 ID (`files` 테이블의 ID와 같음)
+    pub file_id: Uuid,
+    /// 암호화에 사용한 키의 ID
+    pub key_id: [u8; KEY_ID_LEN],
+    /// 청크 논스 접두사
+    pub nonce_prefix: [u8; NONCE_PREFIX_LEN],
+    /// 키 커밋
+    pub key_commitment: [u8; KEY_COMMITMENT_LEN],
+}
+
+impl ContainerHeader {
+    /// 새 헤더를 생성합니다.
+    ///
+    /// 논스 접두사는 파일마다 무작위로 만듭니다.
+    ///
+    /// # 매개변수
+    /// * `algorithm` - 암호화 알고리즘
+    /// * `chunk_size` - 평문 청크 크기
+    /// * `file_id` - 파일 ID
+    /// * `key` - 파일 암호화 키 (키 ID와 키 커밋 계산용)
+    pub fn new(
+        algorithm: EncryptionAlgorithm,
+        chunk_size: u32,
+        file_id: Uuid,
+        key: &SecretKey,
+    ) -> Self {
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_LEN];
+        SecureRandom::fill_bytes(&mut nonce_prefix);
+
+        let mut header = Self {
+            version: CONTAINER_VERSION,
+            algorithm,
+            chunk_size,
+            file_id,
+            key_id: key_id(key),
+            nonce_prefix,
+            key_commitment: [0u8; KEY_COMMITMENT_LEN],
+        };
+        let commitment = key_commitment(key, &header.commitment_prefix()).finalize();
+        header
+            .key_commitment
+            .copy_from_slice(&commitment.into_bytes());
+        header
+    }
+
+    /// 키 커밋이 덮는 헤더 앞부분(키 커밋 앞까지)을 반환합니다.
+    fn commitment_prefix(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes();
+        bytes.truncate(KEY_COMMITMENT_OFFSET);
+        bytes
+    }
+
+    /// 헤더의 키 커밋을 검증합니다.
+    ///
+    /// # 매개변수
+    /// * `key` - 파일 암호화 키
+    ///
+    /// # 오류
+    /// * `CryptoError::KeyCommitmentMismatch` - 다른 키로 만들었거나 헤더가 변조됨
+    pub fn verify_key_commitment(&self, key: &SecretKey) -> SecureVaultResult<()> {
+        key_commitment(key, &self.commitment_prefix())
+            .verify_slice(&self.key_commitment)
+            .map_err(|_| CryptoError::KeyCommitmentMismatch.into())
+    }
+
+    /// 헤더를 바이트로 직렬화합니다.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(CONTAINER_HEADER_LEN);
+        bytes.extend_from_slice(&CONTAINER_MAGIC);
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+        bytes.extend_from_slice(&(CONTAINER_HEADER_LEN as u16).to_le_bytes());
+        bytes.push(self.algorithm.container_id());
+        bytes.push(0); // 플래그 (예약)
+        bytes.extend_from_slice(&self.chunk_size.to_le_bytes());
+        bytes.extend_from_slice(self.file_id.as_bytes());
+        bytes.extend_from_slice(&self.key_id);
+        bytes.extend_from_slice(&self.nonce_prefix);
+        bytes.extend_from_slice(&self.key_commitment);
+        bytes
+    }
+
+    /// 바이트에서 헤더를 파싱합니다.
+    ///
+    /// # 매개변수
+    /// * `bytes` - 헤더 바이트 (정확히 헤더 길이만큼)
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<Self>` - 파싱된 헤더
+    ///
+    /// # 오류
+    /// * `CryptoError::InvalidContainer` - 매직, 길이, 필드 값이 올바르지 않음
+    /// * `CryptoError::UnsupportedContainerVersion` - 알 수 없는 형식 버전
+    pub fn parse(bytes: &[u8]) -> SecureVaultResult<Self> {
+        if !is_container(bytes) {
+            return Err(
+                CryptoError::InvalidContainer("매직 바이트가 없습니다.".to_string()).into(),
+            );
+        }
+        if bytes.len() < 10 {
+            return Err(CryptoError::InvalidContainer("헤더가 잘렸습니다.".to_string()).into());
+        }
+
+        let version = u16::from_le_bytes([bytes[6], bytes[7]]);
+        check_version(version)?;
+
+        let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        if header_len != CONTAINER_HEADER_LEN || bytes.len() != header_len {
+            return Err(CryptoError::InvalidContainer(format!(
+                "헤더 길이가 올바르지 않습니다: {}",
+                header_len
+            ))
+            .into());
+        }
+
+        let algorithm = EncryptionAlgorithm::from_container_id(bytes[10])
+            .ok_or_else(|| CryptoError::InvalidAlgorithm(format!("알고리즘 ID {}", bytes[10])))?;
+
+        if bytes[11] != 0 {
+            return Err(CryptoError::InvalidContainer(format!(
+                "알 수 없는 플래그: {:#04x}",
+                bytes[11]
+            ))
+            .into());
+        }
+
+        let chunk_size = u32::from_le_bytes([bytes[12], bytes[13], bytes[14], bytes[15]]);
+        if chunk_size == 0 || chunk_size > MAX_CHUNK_SIZE {
+            return Err(CryptoError::InvalidContainer(format!(
+                "청크 크기가 올바르지 않습니다: {}",
+                chunk_size
+            ))
+            .into());
+        }
+
+        let mut file_id = [0u8; 16];
+        file_id.copy_from_slice(&bytes[16..32]);
+        let mut key_id = [0u8; KEY_ID_LEN];
+        key_id.copy_from_slice(&bytes[32..40]);
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_LEN];
+        nonce_prefix.copy_from_slice(&bytes[40..KEY_COMMITMENT_OFFSET]);
+        let mut key_commitment = [0u8; KEY_COMMITMENT_LEN];
+        key_commitment.copy_from_slice(&bytes[KEY_COMMITMENT_OFFSET..]);
+
+        Ok(Self {
+            version,
+            algorithm,
+            chunk_size,
+            file_id: Uuid::from_bytes(file_id),
+            key_id,
+            nonce_prefix,
+            key_commitment,
+        })
+    }
+
+    /// 스트림에서 헤더를 읽습니다.
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<(Self, Vec<u8>)>` - (헤더, 인증 데이터로 쓰일 원본 헤더 바이트)
+    pub fn read_from<R: Read>(reader: &mut R) -> SecureVaultResult<(Self, Vec<u8>)> {
+        let mut prefix = [0u8; 10];
+        reader
+            .read_exact(&mut prefix)
+            .map_err(|_| CryptoError::InvalidContainer("헤더를 읽을 수 없습니다.".to_string()))?;
+        if !is_container(&prefix) {
+            return Err(
+                CryptoError::InvalidContainer("매직 바이트가 없습니다.".to_string()).into(),
+            );
+        }
+
+        let version = u16::from_le_bytes([prefix[6], prefix[7]]);
+        check_version(version)?;
+
+        let header_len = u16::from_le_bytes([prefix[8], prefix[9]]) as usize;
+        if header_len != CONTAINER_HEADER_LEN {
+            return Err(CryptoError::InvalidContainer(format!(
+                "헤더 길이가 올바르지 않습니다: {}",
+                header_len
+            ))
+            .into());
+        }
+
+        let mut bytes = prefix.to_vec();
+        bytes.resize(header_len, 0);
+        reader
+            .read_exact(&mut bytes[prefix.len()..])
+            .map_err(|_| CryptoError::InvalidContainer("헤더가 잘렸습니다.".to_string()))?;
+
+        let header = Self::parse(&bytes)?;
+        Ok((header, bytes))
+    }
+}
+
+/// 청크 암호화기
+///
+/// 헤더 바이트를 추가 인증 데이터로, 청크 번호와 마지막 여부를 논스로 묶어 청크를 봉인하거나 엽니다.
+/// 청크 키는 복사하지 않고 공유하므로, 병렬 암호화에서는 복제하지 않고 참조로 스레드에 넘깁니다.
+pub struct ChunkCipher {
+    key: Arc<SecretKey>,
+    algorithm: EncryptionAlgorithm,
+    header_bytes: Vec<u8>,
+    nonce_prefix: [u8; NONCE_PREFIX_LEN],
+}
+
+impl ChunkCipher {
+    /// 새 청크 암호화기를 생성합니다.
+    ///
+    /// # 매개변수
+    /// * `key` - 파일 암호화 키
+    /// * `header` - 컨테이너 헤더
+    pub fn new(key: &SecretKey, header: &ContainerHeader) -> Self {
+        Self::with_header_bytes(Self::chunk_key(key), header, header.to_bytes())
+    }
+
+    /// 파일 암호화 키에서 청크 키를 유도해 여러 청크에서 공유할 키로 만듭니다.
+    fn chunk_key(key: &SecretKey) -> Arc<SecretKey> {
+        Arc::new(derive_subkey(key, CHUNK_KEY_CONTEXT))
+    }
+
+    /// 이미 읽은 헤더 바이트로 청크 암호화기를 생성합니다.
+    fn with_header_bytes(
+        key: Arc<SecretKey>,
+        header: &ContainerHeader,
+        header_bytes: Vec<u8>,
+    ) -> Self {
+        Self {
+            key,
+            algorithm: header.algorithm.clone(),
+            header_bytes,
+            nonce_prefix: header.nonce_prefix,
+        }
+    }
+
+    /// 헤더 바이트를 반환합니다.
+    pub fn header_bytes(&self) -> &[u8] {
+        &self.header_bytes
+    }
+
+    /// 청크 번호와 마지막 여부로 논스를 만듭니다.
+    fn chunk_nonce(&self, index: u32, last: bool) -> [u8; NONCE_SIZE] {
+        let mut nonce = [0u8; NONCE_SIZE];
+        nonce[..NONCE_PREFIX_LEN].copy_from_slice(&self.nonce_prefix);
+        nonce[NONCE_PREFIX_LEN..NONCE_SIZE - 1].copy_from_slice(&index.to_be_bytes());
+        nonce[NONCE_SIZE - 1] = last as u8;
+        nonce
+    }
+
+    /// 평문 청크를 봉인합니다.
+    ///
+    /// # 매개변수
+    /// * `index` - 0부터 시작하는 청크 번호
+    /// * `last` - 파일의 마지막 청크인지 여부
+    /// * `plaintext` - 평문 청크
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<Vec<u8>>` - 암호문 + 태그
+    pub fn seal_chunk(
+        &self,
+        index: u32,
+        last: bool,
+        plaintext: &[u8],
+    ) -> SecureVaultResult<Vec<u8>> {
+        self.encrypt(&self.chunk_nonce(index, last), plaintext)
+    }
+
+    /// 봉인된 청크를 엽니다.
+    ///
+    /// # 매개변수
+    /// * `index` - 기대하는 청크 번호
+    /// * `last` - 마지막 청크로 읽는지 여부
+    /// * `sealed` - 암호문 + 태그
+    ///
+    /// # 오류
+    /// * `CryptoError::DecryptionFailed` - 키가 틀렸거나, 청크 또는 헤더가 변조됐거나,
+    ///   청크의 위치(번호, 마지막 여부)가 봉인할 때와 다름
+    pub fn open_chunk(&self, index: u32, last: bool, sealed: &[u8]) -> SecureVaultResult<Vec<u8>> {
+        if sealed.len() < TAG_SIZE {
+            return Err(CryptoError::InvalidContainer("청크가 너무 짧습니다.".to_string()).into());
+        }
+        self.decrypt(&self.chunk_nonce(index, last), sealed)
+    }
+
+    /// 헤더를 추가 인증 데이터로 하여 암호화합니다.
+    fn encrypt(&self, nonce: &[u8; NONCE_SIZE], plaintext: &[u8]) -> SecureVaultResult<Vec<u8>> {
+        let payload = Payload {
+            msg: plaintext,
+            aad: &self.header_bytes,
+        };
+        let ciphertext = match self.algorithm {
+            EncryptionAlgorithm::AES256GCM => {
+                Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(self.key.expose()))
+                    .encrypt(Nonce::from_slice(nonce), payload)
+            }
+            EncryptionAlgorithm::ChaCha20Poly1305 => {
+                ChaCha20Poly1305::new(ChaChaKey::from_slice(self.key.expose()))
+                    .encrypt(ChaChaNonce::from_slice(nonce), payload)
+            }
+        }
+        .map_err(|_| CryptoError::EncryptionFailed)?;
+
+        Ok(ciphertext)
+    }
+
+    /// 헤더를 추가 인증 데이터로 하여 복호화합니다.
+    fn decrypt(&self, nonce: &[u8; NONCE_SIZE], ciphertext: &[u8]) -> SecureVaultResult<Vec<u8>> {
+        let payload = Payload {
+            msg: ciphertext,
+            aad: &self.header_bytes,
+        };
+        let plaintext = match self.algorithm {
+            EncryptionAlgorithm::AES256GCM => {
+                Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(self.key.expose()))
+                    .decrypt(Nonce::from_slice(nonce), payload)
+            }
+            EncryptionAlgorithm::ChaCha20Poly1305 => {
+                ChaCha20Poly1305::new(ChaChaKey::from_slice(self.key.expose()))
+                    .decrypt(ChaChaNonce::from_slice(nonce), payload)
+            }
+        }
+        .map_err(|_| CryptoError::DecryptionFailed)?;
+
+        Ok(plaintext)
+    }
+}
+
+/// 컨테이너 쓰기 도구
+///
+/// 생성 시 헤더를 쓰고, 이후 들어오는 평문을 청크 크기만큼 모아 차례로 봉인합니다.
+/// `finish`에서 남은 평문(빈 평문일 수 있음)을 마지막 청크로 봉인하므로 반드시 호출해야 합니다.
+pub struct ContainerWriter<W: Write> {
+    writer: W,
+    cipher: ChunkCipher,
+    chunk_size: usize,
+    buffer: Zeroizing<Vec<u8>>,
+    next_index: u32,
+    written: u64,
+}
+
+impl<W: Write> ContainerWriter<W> {
+    /// 헤더를 기록하고 쓰기 도구를 생성합니다.
+    ///
+    /// # 매개변수
+    /// * `writer` - 출력 스트림
+    /// * `key` - 파일 암호화 키
+    /// * `header` - 컨테이너 헤더 (현재 형식 버전만 쓸 수 있음)
+    pub fn new(mut writer: W, key: &SecretKey, header: ContainerHeader) -> SecureVaultResult<Self> {
+        if header.version != CONTAINER_VERSION {
+            return Err(CryptoError::UnsupportedContainerVersion(header.version).into());
+        }
+
+        let cipher = ChunkCipher::new(key, &header);
+        writer
+            .write_all(cipher.header_bytes())
+            .map_err(|e| CryptoError::InvalidData(format!("헤더 쓰기 실패: {}", e)))?;
+
+        let chunk_size = header.chunk_size as usize;
+        Ok(Self {
+            writer,
+            written: cipher.header_bytes().len() as u64,
+            cipher,
+            chunk_size,
+            buffer: Zeroizing::new(Vec::with_capacity(chunk_size)),
+            next_index: 0,
+        })
+    }
+
+    /// 이 컨테이너의 청크 암호화기를 반환합니다 (병렬 암호화용).
+    pub fn cipher(&self) -> &ChunkCipher {
+        &self.cipher
+    }
+
+    /// 다음에 기록할 청크 번호를 반환합니다.
+    pub fn next_index(&self) -> u32 {
+        self.next_index
+    }
+
+    /// 평문을 기록합니다.
+    ///
+    /// 길이에 제한은 없으며, 청크 크기만큼 모일 때마다 마지막이 아닌 청크로 봉인합니다.
+    ///
+    /// # 매개변수
+    /// * `data` - 평문 데이터
+    pub fn write_data(&mut self, mut data: &[u8]) -> SecureVaultResult<()> {
+        while !data.is_empty() {
+            let take = (self.chunk_size - self.buffer.len()).min(data.len());
+            self.buffer.extend_from_slice(&data[..take]);
+            data = &data[take..];
+
+            if self.buffer.len() == self.chunk_size {
+                let sealed = self
+                    .cipher
+                    .seal_chunk(self.next_index, false, &self.buffer)?;
+                self.buffer.zeroize();
+                self.write_raw(&sealed)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 이미 봉인된 청크를 기록합니다.
+    ///
+    /// 병렬 암호화에서 사용하며, `next_index()` 번호로 봉인한 마지막이 아닌 청크여야 합니다.
+    ///
+    /// # 매개변수
+    /// * `sealed` - `ChunkCipher::seal_chunk`로 만든 청크 크기만큼의 청크
+    pub fn write_sealed_chunk(&mut self, sealed: &[u8]) -> SecureVaultResult<()> {
+        if !self.buffer.is_empty() || sealed.len() != self.chunk_size + TAG_SIZE {
+            return Err(CryptoError::InvalidData(
+                "봉인된 청크는 청크 경계에서 청크 크기만큼만 기록할 수 있습니다.".to_string(),
+            )
+            .into());
+        }
+        self.write_raw(sealed)
+    }
+
+    /// 봉인된 청크를 출력에 쓰고 청크 번호를 올립니다.
+    fn write_raw(&mut self, sealed: &[u8]) -> SecureVaultResult<()> {
+        self.writer
+            .write_all(sealed)
+            .map_err(|e| CryptoError::InvalidData(format!("청크 쓰기 실패: {}", e)))?;
+
+        self.written += sealed.len() as u64;
+        self.next_index = self.next_index.checked_add(1).ok_or_else(|| {
+            CryptoError::InvalidData("청크 수가 형식의 한도를 넘었습니다.".to_string())
+        })?;
+        Ok(())
+    }
+
+    /// 남은 평문을 마지막 청크로 봉인하고, 출력을 플러시한 뒤 스트림과 기록한 전체 바이트 수를 반환합니다.
+    pub fn finish(mut self) -> SecureVaultResult<(W, u64)> {
+        let sealed = self
+            .cipher
+            .seal_chunk(self.next_index, true, &self.buffer)?;
+        self.buffer.zeroize();
+        self.write_raw(&sealed)?;
+
+        self.writer
+            .flush()
+            .map_err(|e| CryptoError::InvalidData(format!("파일 쓰기 완료 실패: {}", e)))?;
+        Ok((self.writer, self.written))
+    }
+}
+
+/// 컨테이너 읽기 도구
+pub struct ContainerReader<R: Read> {
+    reader: R,
+    cipher: ChunkCipher,
+    header: ContainerHeader,
+    next_index: u32,
+    lookahead: Option<u8>,
+    finished: bool,
+}
+
+impl<R: Read> ContainerReader<R> {
+    /// 헤더를 읽고 파일 ID, 키 ID, 키 커밋을 검증합니다.
+    ///
+    /// # 매개변수
+    /// * `reader` - 입력 스트림
+    /// * `key` - 파일 암호화 키
+    /// * `expected_file_id` - 열려는 파일의 ID
+    ///
+    /// # 오류
+    /// * `CryptoError::FileIdMismatch` - 다른 파일의 컨테이너임
+    /// * `CryptoError::KeyIdMismatch` - 다른 키로 암호화된 컨테이너임
+    /// * `CryptoError::KeyCommitmentMismatch` - 키 커밋이 맞지 않음
+    pub fn open(
+        mut reader: R,
+        key: &SecretKey,
+        expected_file_id: &Uuid,
+    ) -> SecureVaultResult<Self> {
+        let (header, header_bytes) = ContainerHeader::read_from(&mut reader)?;
+
+        if header.file_id != *expected_file_id {
+            return Err(CryptoError::FileIdMismatch.into());
+        }
+        if header.key_id != key_id(key) {
+            return Err(CryptoError::KeyIdMismatch.into());
+        }
+        header.verify_key_commitment(key)?;
+
+        let cipher =
+            ChunkCipher::with_header_bytes(ChunkCipher::chunk_key(key), &header, header_bytes);
+        Ok(Self {
+            reader,
+            cipher,
+            header,
+            next_index: 0,
+            lookahead: None,
+            finished: false,
+        })
+    }
+
+    /// 컨테이너 헤더를 반환합니다.
+    pub fn header(&self) -> &ContainerHeader {
+        &self.header
+    }
+
+    /// 다음 청크를 복호화합니다.
+    ///
+    /// 청크 크기보다 짧거나 뒤에 데이터가 없는 청크를 마지막 청크로 보고 엽니다.
+    /// 마지막 청크 없이 파일이 끝나면 오류를 반환합니다.
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<Option<Vec<u8>>>` - 평문 청크 (마지막 청크를 읽은 뒤에는 None)
+    pub fn next_chunk(&mut self) -> SecureVaultResult<Option<Vec<u8>>> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let sealed_len = self.header.chunk_size as usize + TAG_SIZE;
+        let mut sealed = vec![0u8; sealed_len];
+        let mut filled = 0;
+        if let Some(byte) = self.lookahead.take() {
+            sealed[0] = byte;
+            filled = 1;
+        }
+        filled += read_full(&mut self.reader, &mut sealed[filled..])?;
+
+        // 청크가 꽉 찼으면 뒤에 데이터가 더 있는지 1바이트 미리 읽어 마지막 여부를 판단
+        let last = if filled < sealed_len {
+            true
+        } else {
+            let mut probe = [0u8; 1];
+            if read_full(&mut self.reader, &mut probe)? == 0 {
+                true
+            } else {
+                self.lookahead = Some(probe[0]);
+                false
+            }
+        };
+
+        if filled < TAG_SIZE {
+            return Err(CryptoError::InvalidContainer(
+                "마지막 청크가 없습니다. 파일이 잘렸을 수 있습니다.".to_string(),
+            )
+            .into());
+        }
+        sealed.truncate(filled);
+
+        let plaintext = self.cipher.open_chunk(self.next_index, last, &sealed)?;
+        self.next_index = self.next_index.checked_add(1).ok_or_else(|| {
+            CryptoError::InvalidContainer("청크 수가 형식의 한도를 넘었습니다.".to_string())
+        })?;
+        self.finished = last;
+
+        Ok(Some(plaintext))
+    }
+
+    /// 남은 모든 청크를 복호화하여 이어 붙입니다.
+    pub fn read_to_end(mut self) -> SecureVaultResult<Vec<u8>> {
+        let mut plaintext = Vec::new();
+        while let Some(mut chunk) = self.next_chunk()? {
+            plaintext.extend_from_slice(&chunk);
+            SecureMemory::clear_vec(&mut chunk);
+        }
+        Ok(plaintext)
+    }
+}
+
+/// 버퍼를 가능한 만큼 채우고 읽은 바이트 수를 반환합니다 (EOF면 버퍼보다 작음).
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> SecureVaultResult<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(CryptoError::InvalidData(format!("파일 읽기 실패: {}", e)).into()),
+        }
+    }
+    Ok(filled)
+}
+
+/// 메모리의 데이터를 컨테이너로 암호화합니다.
+///
+/// # 매개변수
+/// * `data` - 평문 데이터
+/// * `key` - 파일 암호화 키
+/// * `file_id` - 파일 ID
+/// * `algorithm` - 암호화 알고리즘
+///
+/// # 반환값
+/// * `SecureVaultResult<Vec<u8>>` - 컨테이너 바이트
+pub fn encrypt_container(
+    data: &[u8],
+    key: &SecretKey,
+    file_id: &Uuid,
+    algorithm: EncryptionAlgorithm,
+) -> SecureVaultResult<Vec<u8>> {
+    let header = ContainerHeader::new(algorithm, DEFAULT_CHUNK_SIZE, *file_id, key);
+    let mut writer = ContainerWriter::new(Vec::with_capacity(data.len() + 64), key, header)?;
+    writer.write_data(data)?;
+    let (bytes, _) = writer.finish()?;
+    Ok(bytes)
+}
+
+/// 메모리의 컨테이너를 복호화합니다.
+///
+/// # 매개변수
+/// * `data` - 컨테이너 바이트
+/// * `key` - 파일 암호화 키
+/// * `file_id` - 열려는 파일의 ID
+///
+/// # 반환값
+/// * `SecureVaultResult<Vec<u8>>` - 평문 데이터
+pub fn decrypt_container(
+    data: &[u8],
+    key: &SecretKey,
+    file_id: &Uuid,
+) -> SecureVaultResult<Vec<u8>> {
+    ContainerReader::open(data, key, file_id)?.read_to_end()
+}
+
+/// 헤더 없는 이전 형식의 레이아웃
+#[derive(Debug, PartialEq, Eq)]
+pub enum LegacyLayout<'a> {
+    /// IV + 암호문 + 태그 단일 블록 (폴더 업로드, 새 파일 생성 등)
+    Single,
+    /// `[4바이트 길이][블록]` 반복 (스트리밍 업로드 형식)
+    Chunked(Vec<&'a [u8]>),
+    /// `[4바이트 청크 수]` 뒤에 `[4바이트 길이][블록]` 반복 (병렬 암호화 형식)
+    CountedChunks(Vec<&'a [u8]>),
+}
+
+impl LegacyLayout<'_> {
+    /// 레이아웃의 블록 수를 반환합니다.
+    pub fn chunk_count(&self) -> usize {
+        match self {
+            Self::Single => 1,
+            Self::Chunked(chunks) | Self::CountedChunks(chunks) => chunks.len(),
+        }
+    }
+}
+
+/// 이전 형식 파일의 레이아웃을 구조로 판별합니다.
+///
+/// 복호화를 차례로 시도하지 않고, 길이 필드가 파일 전체를 정확히 나누는지로만 판단합니다.
+///
+/// # 매개변수
+/// * `data` - 헤더 없는 암호화 파일 내용
+///
+/// # 반환값
+/// * `LegacyLayout` - 판별된 레이아웃
+pub fn detect_legacy_layout(data: &[u8]) -> LegacyLayout<'_> {
+    if let Some(chunks) = split_length_prefixed(data) {
+        return LegacyLayout::Chunked(chunks);
+    }
+
+    if data.len() > 4 {
+        let count = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
+        if let Some(chunks) = split_length_prefixed(&data[4..]) {
+            if chunks.len() == count {
+                return LegacyLayout::CountedChunks(chunks);
+            }
+        }
+    }
+
+    LegacyLayout::Single
+}
+
+/// `[4바이트 길이][블록]` 반복으로 데이터를 정확히 나눌 수 있으면 블록 목록을 반환합니다.
+fn split_length_prefixed(data: &[u8]) -> Option<Vec<&[u8]>> {
+    let mut chunks = Vec::new();
+    let mut cursor = 0;
+
+    while cursor < data.len() {
+        if data.len() - cursor < 4 {
+            return None;
+        }
+        let len = u32::from_le_bytes([
+            data[cursor],
+            data[cursor + 1],
+            data[cursor + 2],
+            data[cursor + 3],
+        ]) as usize;
+        cursor += 4;
+
+        if len < NONCE_SIZE + TAG_SIZE || len > data.len() - cursor {
+            return None;
+        }
+        chunks.push(&data[cursor..cursor + len]);
+        cursor += len;
+    }
+
+    if chunks.is_empty() {
+        None
+    } else {
+        Some(chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_container_round_trip() {
+        let key = SecretKey::from_bytes(&mut [5u8; 32]);
+        let file_id = Uuid::new_v4();
+        let data: Vec<u8> = (0..(DEFAULT_CHUNK_SIZE as usize + 1000))
+            .map(|i| i as u8)
+            .collect();
+
+        for algorithm in [
+            EncryptionAlgorithm::AES256GCM,
+            EncryptionAlgorithm::ChaCha20Poly1305,
+        ] {
+            let encrypted = encrypt_container(&data, &key, &file_id, algorithm.clone()).unwrap();
+            assert!(is_container(&encrypted));
+
+            let header = ContainerHeader::parse(&encrypted[..CONTAINER_HEADER_LEN]).unwrap();
+            assert_eq!(header.algorithm, algorithm);
+            assert_eq!(header.file_id, file_id);
+            assert_eq!(header.key_id, key_id(&key));
+
+            assert_eq!(decrypt_container(&encrypted, &key, &file_id).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_container_rejects_wrong_file_and_key() {
+        let key = SecretKey::from_bytes(&mut [5u8; 32]);
+        let file_id = Uuid::new_v4();
+        let encrypted =
+            encrypt_container(b"hello", &key, &file_id, EncryptionAlgorithm::AES256GCM).unwrap();
+
+        let wrong_file = decrypt_container(&encrypted, &key, &Uuid::new_v4()).unwrap_err();
+        assert!(wrong_file
+            .to_string()
+            .contains(&CryptoError::FileIdMismatch.to_string()));
+
+        let wrong_key =
+            decrypt_container(&encrypted, &SecretKey::from_bytes(&mut [6u8; 32]), &file_id)
+                .unwrap_err();
+        assert!(wrong_key
+            .to_string()
+            .contains(&CryptoError::KeyIdMismatch.to_string()));
+    }
+
+    #[test]
+    fn test_container_header_is_authenticated() {
+        let key = SecretKey::from_bytes(&mut [5u8; 32]);
+        let file_id = Uuid::new_v4();
+        let mut encrypted =
+            encrypt_container(b"hello", &key, &file_id, EncryptionAlgorithm::AES256GCM).unwrap();
+
+        // 청크 크기 필드를 바꾸면 파싱은 되지만 인증에 실패
+        encrypted[12] ^= 0x01;
+        assert!(decrypt_container(&encrypted, &key, &file_id).is_err());
+        encrypted[12] ^= 0x01;
+
+        // 알 수 없는 버전
+        encrypted[6] = 9;
+        let err = decrypt_container(&encrypted, &key, &file_id).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&CryptoError::UnsupportedContainerVersion(9).to_string()));
+    }
+
+    #[test]
+    fn test_container_verifies_key_commitment() {
+        let key = SecretKey::from_bytes(&mut [5u8; 32]);
+        let file_id = Uuid::new_v4();
+        let mut encrypted =
+            encrypt_container(b"hello", &key, &file_id, EncryptionAlgorithm::AES256GCM).unwrap();
+
+        let header = ContainerHeader::parse(&encrypted[..CONTAINER_HEADER_LEN]).unwrap();
+        assert!(header.verify_key_commitment(&key).is_ok());
+        assert!(header
+            .verify_key_commitment(&SecretKey::from_bytes(&mut [6u8; 32]))
+            .is_err());
+
+        // 키 커밋이 맞지 않으면 청크를 열기 전에 거부
+        encrypted[CONTAINER_HEADER_LEN - 1] ^= 0x01;
+        let err = decrypt_container(&encrypted, &key, &file_id).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&CryptoError::KeyCommitmentMismatch.to_string()));
+    }
+
+    /// 청크 크기 16바이트로 컨테이너를 만듭니다.
+    fn small_container(data: &[u8], key: &SecretKey, file_id: &Uuid) -> Vec<u8> {
+        let header = ContainerHeader::new(EncryptionAlgorithm::AES256GCM, 16, *file_id, key);
+        let mut writer = ContainerWriter::new(Vec::new(), key, header).unwrap();
+        writer.write_data(data).unwrap();
+        writer.finish().unwrap().0
+    }
+
+    #[test]
+    fn test_stream_rejects_truncation_and_reordering() {
+        let key = SecretKey::from_bytes(&mut [5u8; 32]);
+        let file_id = Uuid::new_v4();
+        let data: Vec<u8> = (0..50u8).collect();
+        let encrypted = small_container(&data, &key, &file_id);
+
+        // 청크 3개(16바이트) + 마지막 청크(2바이트)
+        let (header, body) = encrypted.split_at(CONTAINER_HEADER_LEN);
+        let chunks: Vec<&[u8]> = body.chunks(16 + TAG_SIZE).collect();
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(decrypt_container(&encrypted, &key, &file_id).unwrap(), data);
+
+        let rebuild = |order: &[usize]| {
+            let mut bytes = header.to_vec();
+            for &i in order {
+                bytes.extend_from_slice(chunks[i]);
+            }
+            bytes
+        };
+
+        // 청크 경계에서 잘라냄, 중간 청크 삭제, 순서 변경, 중복, 본문 없음
+        for order in [
+            &[0, 1, 2][..],
+            &[0, 2, 3][..],
+            &[1, 0, 2, 3][..],
+            &[0, 1, 1, 2, 3][..],
+            &[][..],
+        ] {
+            assert!(
+                decrypt_container(&rebuild(order), &key, &file_id).is_err(),
+                "{:?}",
+                order
+            );
+        }
+
+        // 마지막 청크 뒤에 덧붙인 데이터
+        let mut appended = encrypted.clone();
+        appended.push(0);
+        assert!(decrypt_container(&appended, &key, &file_id).is_err());
+    }
+
+    #[test]
+    fn test_stream_exact_multiple_ends_with_empty_chunk() {
+        let key = SecretKey::from_bytes(&mut [5u8; 32]);
+        let file_id = Uuid::new_v4();
+        let data = vec![9u8; 32];
+        let encrypted = small_container(&data, &key, &file_id);
+
+        assert_eq!(
+            encrypted.len(),
+            CONTAINER_HEADER_LEN + 2 * (16 + TAG_SIZE) + TAG_SIZE
+        );
+        assert_eq!(decrypt_container(&encrypted, &key, &file_id).unwrap(), data);
+
+        // 빈 마지막 청크를 떼어내면 잘린 파일로 감지
+        let truncated = &encrypted[..encrypted.len() - TAG_SIZE];
+        assert!(decrypt_container(truncated, &key, &file_id).is_err());
+    }
+
+    #[test]
+    fn test_chunk_key_and_commitment_key_are_separate() {
+        let key = SecretKey::from_bytes(&mut [5u8; 32]);
+        let chunk_key = ChunkCipher::chunk_key(&key);
+        let commitment_key = derive_subkey(&key, KEY_COMMITMENT_CONTEXT);
+
+        // AEAD와 키 커밋 모두 파일 키를 직접 쓰지 않고 서로 다른 하위 키를 사용
+        assert_ne!(chunk_key.expose(), key.expose());
+        assert_ne!(commitment_key.expose(), key.expose());
+        assert_ne!(chunk_key.expose(), commitment_key.expose());
+
+        let header = ContainerHeader::new(EncryptionAlgorithm::AES256GCM, 16, Uuid::new_v4(), &key);
+        let mut raw_commitment = <Hmac<Sha256> as Mac>::new_from_slice(key.expose()).unwrap();
+        raw_commitment.update(&header.commitment_prefix());
+        assert!(raw_commitment.verify_slice(&header.key_commitment).is_err());
+    }
+
+    #[test]
+    fn test_detect_legacy_layout() {
+        let blob_a = vec![1u8; 40];
+        let blob_b = vec![2u8; 30];
+
+        let mut chunked = Vec::new();
+        for blob in [&blob_a, &blob_b] {
+            chunked.extend_from_slice(&(blob.len() as u32).to_le_bytes());
+            chunked.extend_from_slice(blob);
+        }
+        assert_eq!(
+            detect_legacy_layout(&chunked),
+            LegacyLayout::Chunked(vec![&blob_a[..], &blob_b[..]])
+        );
+
+        let mut counted = 2u32.to_le_bytes().to_vec();
+        counted.extend_from_slice(&chunked);
+        assert_eq!(
+            detect_legacy_layout(&counted),
+            LegacyLayout::CountedChunks(vec![&blob_a[..], &blob_b[..]])
+        );
+
+        // 길이 필드가 전체를 나누지 못하면 단일 블록
+        assert_eq!(
+            detect_legacy_layout(&chunked[..chunked.len() - 1]),
+            LegacyLayout::Single
+        );
+        assert!(!is_container(&chunked));
+    }
+}
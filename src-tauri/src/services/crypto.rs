@@ -2,26 +2,48 @@
 // 파일 암호화/복호화, 키 관리, 메모리 보안 등을 담당합니다.
 
 use crate::models::{
-    CryptoError, EncryptedData, EncryptionAlgorithm, EncryptionMetadata, KeyDerivationParams,
-    SecureMemory, SecureRandom,
+    CryptoError, EncryptedData, EncryptionAlgorithm, EncryptionMetadata, KdfAlgorithm, KdfParams,
+    KeyDerivationParams, SecretKey, SecureMemory, SecureRandom,
 };
 use crate::SecureVaultResult;
 use aes_gcm::{
-    aead::{Aead, KeyInit},
+    aead::{Aead, KeyInit, Payload},
     Aes256Gcm, Key, Nonce,
 };
 use chacha20poly1305::{ChaCha20Poly1305, Key as ChaChaKey, Nonce as ChaChaNonce};
+use hmac::{Hmac, Mac};
 use pbkdf2::pbkdf2_hmac;
 use sha2::{Digest, Sha256};
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+/// 키 래핑 시 인증에 포함되는 추가 데이터
+const KEY_WRAP_AAD: &[u8] = b"SecureVault data key v1";
+
+/// 파일 키 래핑 시 인증에 포함되는 추가 데이터 (뒤에 파일 ID가 붙음)
+const FILE_KEY_WRAP_AAD: &[u8] = b"SecureVault file key v1";
+
+/// 메타데이터 데이터베이스 키 유도 시 사용하는 컨텍스트
+const DATABASE_KEY_CONTEXT: &[u8] = b"SecureVault metadata database key v1";
+
+/// 로그 파일 암호화 키 유도 시 사용하는 컨텍스트
+const LOG_KEY_CONTEXT: &[u8] = b"SecureVault log key v1";
+
+/// 익명 PIN 슬롯 정보 봉인 키 유도 시 사용하는 컨텍스트
+const SLOT_SEAL_KEY_CONTEXT: &[u8] = b"SecureVault pin slot seal key v1";
+
+/// 익명 PIN 슬롯 정보 봉인 시 인증에 포함되는 추가 데이터
+const SLOT_SEAL_AAD: &[u8] = b"SecureVault pin slot v1";
+
 /// 암호화 서비스
 /// 파일 암호화/복호화와 키 관리를 담당합니다.
-#[derive(Debug, Clone)]
+///
+/// 마스터 키를 복사하지 않도록 `Clone`을 구현하지 않습니다.
+#[derive(Debug)]
 pub struct CryptoService {
-    /// 마스터 키 (메모리에서만 존재)
-    master_key: Option<[u8; 32]>,
+    /// 마스터 키 (메모리에서만 존재, 파일 서비스와 공유)
+    master_key: Option<Arc<SecretKey>>,
 
     /// 키 유도 매개변수
     kdf_params: KeyDerivationParams,
@@ -47,15 +69,13 @@ impl CryptoService {
 
     /// PIN으로부터 마스터 키를 유도합니다.
     ///
-    /// C# EncryptionService.DeriveKeyFromPin()과 동일한 로직을 사용합니다:
-    /// - PBKDF2-HMAC-SHA256 알고리즘
-    /// - 100,000회 반복 (C# 버전과 동일)
-    /// - 32바이트 솔트 사용
-    /// - 256비트(32바이트) 마스터 키 생성
+    /// 저장된 키 유도 매개변수를 그대로 사용하므로, 기존 볼트(PBKDF2-HMAC-SHA256
+    /// 100,000회, C# 버전과 동일)와 새 볼트(Argon2id) 모두 같은 키를 재현합니다.
     ///
     /// # 매개변수
     /// * `pin` - 사용자 PIN (4-8자리 숫자)
     /// * `salt` - 32바이트 키 유도용 솔트
+    /// * `params` - 키 유도 매개변수
     ///
     /// # 반환값
     /// * `SecureVaultResult<()>` - 키 유도 결과
@@ -63,7 +83,12 @@ impl CryptoService {
     /// # 오류
     /// * `CryptoError::InvalidPin` - PIN이 비어있거나 형식이 잘못됨
     /// * `CryptoError::InvalidSalt` - 솔트가 32바이트가 아님
-    pub fn derive_master_key(&mut self, pin: &str, salt: &[u8]) -> SecureVaultResult<()> {
+    pub fn derive_master_key(
+        &mut self,
+        pin: &str,
+        salt: &[u8],
+        params: &KdfParams,
+    ) -> SecureVaultResult<()> {
         // PIN 유효성 검사 (C# 버전과 동일)
         if pin.is_empty() {
             return Err(CryptoError::InvalidPin("PIN이 비어있습니다.".to_string()).into());
@@ -74,28 +99,146 @@ impl CryptoService {
             return Err(CryptoError::InvalidSalt("솔트는 32바이트여야 합니다.".to_string()).into());
         }
 
-        let mut key = [0u8; 32];
+        let key = Self::derive_key_with_params(pin.as_bytes(), salt, params)?;
+        self.master_key = Some(Arc::new(key));
 
-        // PBKDF2-HMAC-SHA256으로 키 유도 (C# 버전과 동일한 100,000회 반복)
-        pbkdf2_hmac::<Sha256>(
-            pin.as_bytes(),
-            salt,
-            100_000, // C# 버전과 동일한 반복 횟수
-            &mut key,
-        );
+        log::info!("마스터 키가 성공적으로 유도되었습니다. ({:?})", params.algorithm);
+        Ok(())
+    }
 
-        self.master_key = Some(key);
+    /// 지정한 매개변수로 비밀값에서 256비트 키를 유도합니다.
+    ///
+    /// # 매개변수
+    /// * `secret` - 비밀값 (PIN 등)
+    /// * `salt` - 솔트
+    /// * `params` - 키 유도 매개변수
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<SecretKey>` - 유도된 키
+    ///
+    /// # 오류
+    /// * `CryptoError::KeyDerivationFailed` - Argon2 매개변수가 잘못되었거나 유도 실패
+    pub fn derive_key_with_params(
+        secret: &[u8],
+        salt: &[u8],
+        params: &KdfParams,
+    ) -> SecureVaultResult<SecretKey> {
+        let mut key = SecretKey::zeroed();
+
+        match params.algorithm {
+            KdfAlgorithm::Sha256 => {
+                let mut hasher = Sha256::new();
+                hasher.update(secret);
+                hasher.update(salt);
+                key.expose_mut().copy_from_slice(&hasher.finalize());
+            }
+            KdfAlgorithm::Pbkdf2Sha256 => {
+                pbkdf2_hmac::<Sha256>(secret, salt, params.iterations, key.expose_mut());
+            }
+            KdfAlgorithm::Argon2id => {
+                let argon2_params = argon2::Params::new(
+                    params.memory_kib,
+                    params.iterations,
+                    params.parallelism,
+                    Some(key.expose().len()),
+                )
+                .map_err(|_| CryptoError::KeyDerivationFailed)?;
+
+                argon2::Argon2::new(
+                    argon2::Algorithm::Argon2id,
+                    argon2::Version::V0x13,
+                    argon2_params,
+                )
+                .hash_password_into(secret, salt, key.expose_mut())
+                .map_err(|_| CryptoError::KeyDerivationFailed)?;
+            }
+        }
 
-        log::info!("마스터 키가 성공적으로 유도되었습니다. (PBKDF2-SHA256, 100,000회 반복)");
-        Ok(())
+        Ok(key)
+    }
+
+    /// 현재 PC에서 키 한 번을 유도하는 데 목표 시간이 걸리는 Argon2id 매개변수를 찾습니다.
+    ///
+    /// 기본 메모리 비용으로 한 패스를 측정해 비용을 추정한 뒤, 최소 시간 비용을 유지하면서
+    /// 메모리 비용을 먼저 늘리고(상한 `ARGON2_MAX_MEMORY_KIB`), 남는 시간은 패스 수로 채웁니다.
+    /// 선택한 매개변수를 다시 측정하여 목표를 크게 넘으면 패스 수를 줄입니다.
+    /// 목표 시간이 짧거나 PC가 느려도 `KdfParams::default()`보다 약해지지 않습니다.
+    ///
+    /// # 매개변수
+    /// * `target` - 키 한 번 유도의 목표 시간
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<(KdfParams, Duration)>` - 선택된 매개변수와 실제 측정 시간
+    ///
+    /// # 오류
+    /// * `CryptoError::KeyDerivationFailed` - 측정용 키 유도 실패 (메모리 부족 등)
+    pub fn calibrate_kdf(target: Duration) -> SecureVaultResult<(KdfParams, Duration)> {
+        let mut secret = [0u8; 32];
+        SecureRandom::fill_bytes(&mut secret);
+        let salt = SecureRandom::generate_salt();
+
+        let measure = |params: &KdfParams| -> SecureVaultResult<Duration> {
+            let start_time = Instant::now();
+            Self::derive_key_with_params(&secret, &salt, params)?;
+            Ok(start_time.elapsed())
+        };
+
+        // 기본 메모리 비용 1패스의 시간으로 "KiB × 패스" 단위 비용을 추정
+        let probe = KdfParams::argon2id(
+            KdfParams::ARGON2_MEMORY_KIB,
+            1,
+            KdfParams::ARGON2_PARALLELISM,
+        );
+        let probe_time = measure(&probe)?.max(Duration::from_micros(1));
+        let budget = target.as_secs_f64() / probe_time.as_secs_f64()
+            * f64::from(KdfParams::ARGON2_MEMORY_KIB);
+
+        let min_memory = f64::from(KdfParams::ARGON2_MEMORY_KIB);
+        let max_memory = f64::from(KdfParams::ARGON2_MAX_MEMORY_KIB);
+        let min_iterations = f64::from(KdfParams::ARGON2_ITERATIONS);
+        let max_iterations = f64::from(KdfParams::ARGON2_MAX_ITERATIONS);
+
+        // 메모리 비용은 MiB 단위로 맞춤
+        let memory_kib =
+            (budget / min_iterations).clamp(min_memory, max_memory) as u32 / 1024 * 1024;
+        let iterations =
+            (budget / f64::from(memory_kib)).clamp(min_iterations, max_iterations) as u32;
+        let mut params = KdfParams::argon2id(memory_kib, iterations, KdfParams::ARGON2_PARALLELISM);
+
+        let mut measured = measure(&params)?;
+        if measured > target.mul_f64(1.25) && params.iterations > KdfParams::ARGON2_ITERATIONS {
+            let scaled =
+                f64::from(params.iterations) * target.as_secs_f64() / measured.as_secs_f64();
+            params.iterations = (scaled as u32).max(KdfParams::ARGON2_ITERATIONS);
+            measured = measure(&params)?;
+        }
+
+        SecureMemory::clear_bytes(&mut secret);
+        log::info!(
+            "키 유도 비용 보정 완료: 메모리 {}MiB, 패스 {}회, {}ms",
+            params.memory_kib / 1024,
+            params.iterations,
+            measured.as_millis()
+        );
+        Ok((params, measured))
     }
 
-    /// 현재 마스터 키를 반환합니다.
+    /// 현재 마스터 키를 반환합니다. 키를 복사하지 않고 공유합니다.
     ///
     /// # 반환값
-    /// * `Option<[u8; 32]>` - 마스터 키 (초기화되지 않은 경우 None)
-    pub fn get_master_key(&self) -> Option<[u8; 32]> {
-        self.master_key
+    /// * `Option<Arc<SecretKey>>` - 마스터 키 (초기화되지 않은 경우 None)
+    pub fn get_master_key(&self) -> Option<Arc<SecretKey>> {
+        self.master_key.clone()
+    }
+
+    /// 언래핑된 볼트 데이터 키를 마스터 키로 설정합니다.
+    ///
+    /// 이전 키는 다른 곳에서 공유하고 있지 않으면 즉시 0으로 덮어써집니다.
+    ///
+    /// # 매개변수
+    /// * `key` - 256비트 데이터 키
+    pub fn set_master_key(&mut self, key: Arc<SecretKey>) {
+        self.master_key = Some(key);
     }
 
     /// 32바이트 랜덤 솔트를 생성합니다.
@@ -120,6 +263,254 @@ impl CryptoService {
         recovery_key
     }
 
+    /// 256비트 볼트 데이터 키(DEK)를 생성합니다.
+    ///
+    /// 파일은 이 키로 암호화되고, 이 키는 PIN/복구 키에서 유도한 KEK로 래핑되어 저장됩니다.
+    ///
+    /// # 반환값
+    /// * `SecretKey` - 256비트 데이터 키
+    pub fn generate_data_key() -> SecretKey {
+        SecretKey::generate()
+    }
+
+    /// 볼트 데이터 키에서 메타데이터 데이터베이스(SQLCipher) 키를 유도합니다.
+    ///
+    /// 데이터 키를 그대로 쓰지 않고 용도별 컨텍스트로 HMAC-SHA256을 거쳐 분리합니다.
+    ///
+    /// # 매개변수
+    /// * `data_key` - 볼트 데이터 키
+    ///
+    /// # 반환값
+    /// * `SecretKey` - 데이터베이스 키
+    pub fn derive_database_key(data_key: &SecretKey) -> SecretKey {
+        Self::derive_subkey(data_key, DATABASE_KEY_CONTEXT)
+    }
+
+    /// 볼트 데이터 키에서 로그 파일 암호화 키를 유도합니다.
+    ///
+    /// # 매개변수
+    /// * `data_key` - 볼트 데이터 키
+    ///
+    /// # 반환값
+    /// * `SecretKey` - 로그 키
+    pub fn derive_log_key(data_key: &SecretKey) -> SecretKey {
+        Self::derive_subkey(data_key, LOG_KEY_CONTEXT)
+    }
+
+    /// 볼트 데이터 키에서 익명 PIN 슬롯 정보 봉인 키를 유도합니다.
+    ///
+    /// # 매개변수
+    /// * `data_key` - 볼트 데이터 키
+    ///
+    /// # 반환값
+    /// * `SecretKey` - 봉인 키
+    pub fn derive_slot_seal_key(data_key: &SecretKey) -> SecretKey {
+        Self::derive_subkey(data_key, SLOT_SEAL_KEY_CONTEXT)
+    }
+
+    /// 데이터 키와 용도별 컨텍스트로 HMAC-SHA256 하위 키를 유도합니다.
+    fn derive_subkey(data_key: &SecretKey, context: &[u8]) -> SecretKey {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(data_key.expose())
+            .expect("HMAC은 모든 키 길이를 허용합니다");
+        mac.update(context);
+
+        let mut subkey = SecretKey::zeroed();
+        subkey
+            .expose_mut()
+            .copy_from_slice(&mac.finalize().into_bytes());
+        subkey
+    }
+
+    /// 키 암호화 키(KEK)로 데이터 키를 래핑합니다.
+    ///
+    /// 결과 형식: IV(12) + 암호문(32) + 인증태그(16)
+    ///
+    /// # 매개변수
+    /// * `kek` - 키 암호화 키
+    /// * `key` - 래핑할 데이터 키
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<Vec<u8>>` - 래핑된 키
+    pub fn wrap_key(kek: &SecretKey, key: &SecretKey) -> SecureVaultResult<Vec<u8>> {
+        Self::wrap_key_with_aad(kek, key, KEY_WRAP_AAD)
+    }
+
+    /// 래핑된 데이터 키를 KEK로 언래핑합니다.
+    ///
+    /// # 매개변수
+    /// * `kek` - 키 암호화 키
+    /// * `wrapped` - `wrap_key`로 만든 래핑된 키
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<SecretKey>` - 데이터 키
+    ///
+    /// # 오류
+    /// * `CryptoError::DecryptionFailed` - KEK가 틀렸거나 래핑된 키가 손상됨
+    pub fn unwrap_key(kek: &SecretKey, wrapped: &[u8]) -> SecureVaultResult<SecretKey> {
+        Self::unwrap_key_with_aad(kek, wrapped, KEY_WRAP_AAD)
+    }
+
+    /// 익명 PIN 슬롯 정보를 봉인 키로 암호화합니다.
+    ///
+    /// 결과 형식: IV(12) + 암호문 + 인증태그(16)
+    ///
+    /// # 매개변수
+    /// * `seal_key` - `derive_slot_seal_key`로 유도한 봉인 키
+    /// * `data` - 암호화할 데이터
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<Vec<u8>>` - 봉인된 데이터
+    pub fn seal_slot_data(seal_key: &SecretKey, data: &[u8]) -> SecureVaultResult<Vec<u8>> {
+        let mut iv = [0u8; 12];
+        SecureRandom::fill_bytes(&mut iv);
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(seal_key.expose()));
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&iv),
+                Payload {
+                    msg: data,
+                    aad: SLOT_SEAL_AAD,
+                },
+            )
+            .map_err(|_| CryptoError::EncryptionFailed)?;
+
+        let mut result = Vec::with_capacity(iv.len() + ciphertext.len());
+        result.extend_from_slice(&iv);
+        result.extend_from_slice(&ciphertext);
+        Ok(result)
+    }
+
+    /// `seal_slot_data`로 봉인한 데이터를 복호화합니다.
+    ///
+    /// # 매개변수
+    /// * `seal_key` - `derive_slot_seal_key`로 유도한 봉인 키
+    /// * `sealed` - 봉인된 데이터
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<Vec<u8>>` - 복호화된 데이터
+    ///
+    /// # 오류
+    /// * `CryptoError::DecryptionFailed` - 다른 볼트의 키이거나 임의 데이터로 채운 슬롯
+    pub fn open_slot_data(seal_key: &SecretKey, sealed: &[u8]) -> SecureVaultResult<Vec<u8>> {
+        if sealed.len() < 12 + 16 {
+            return Err(CryptoError::DecryptionFailed.into());
+        }
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(seal_key.expose()));
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(&sealed[..12]),
+                Payload {
+                    msg: &sealed[12..],
+                    aad: SLOT_SEAL_AAD,
+                },
+            )
+            .map_err(|_| CryptoError::DecryptionFailed)?;
+
+        Ok(plaintext)
+    }
+
+    /// 파일 데이터 키를 볼트 키로 래핑합니다.
+    ///
+    /// 파일 ID를 인증 데이터에 포함하므로, 래핑된 키를 다른 파일의 메타데이터로 옮기면
+    /// 언래핑에 실패합니다. 결과 형식은 `wrap_key`와 같습니다.
+    ///
+    /// # 매개변수
+    /// * `kek` - 볼트 키 (파일 보안 등급에 맞는 키)
+    /// * `file_key` - 래핑할 파일 데이터 키
+    /// * `file_id` - 파일 ID
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<Vec<u8>>` - 래핑된 파일 키
+    pub fn wrap_file_key(
+        kek: &SecretKey,
+        file_key: &SecretKey,
+        file_id: &Uuid,
+    ) -> SecureVaultResult<Vec<u8>> {
+        Self::wrap_key_with_aad(kek, file_key, &Self::file_key_aad(file_id))
+    }
+
+    /// 래핑된 파일 데이터 키를 언래핑합니다.
+    ///
+    /// # 매개변수
+    /// * `kek` - 볼트 키 (파일 보안 등급에 맞는 키)
+    /// * `wrapped` - `wrap_file_key`로 만든 래핑된 키
+    /// * `file_id` - 파일 ID
+    ///
+    /// # 반환값
+    /// * `SecureVaultResult<SecretKey>` - 파일 데이터 키
+    ///
+    /// # 오류
+    /// * `CryptoError::DecryptionFailed` - 키가 틀렸거나, 래핑된 키가 손상됐거나, 다른 파일의 키임
+    pub fn unwrap_file_key(
+        kek: &SecretKey,
+        wrapped: &[u8],
+        file_id: &Uuid,
+    ) -> SecureVaultResult<SecretKey> {
+        Self::unwrap_key_with_aad(kek, wrapped, &Self::file_key_aad(file_id))
+    }
+
+    /// 파일 키 래핑에 사용하는 인증 데이터를 만듭니다.
+    fn file_key_aad(file_id: &Uuid) -> Vec<u8> {
+        let mut aad = Vec::with_capacity(FILE_KEY_WRAP_AAD.len() + 16);
+        aad.extend_from_slice(FILE_KEY_WRAP_AAD);
+        aad.extend_from_slice(file_id.as_bytes());
+        aad
+    }
+
+    /// 주어진 인증 데이터로 키를 래핑합니다.
+    fn wrap_key_with_aad(
+        kek: &SecretKey,
+        key: &SecretKey,
+        aad: &[u8],
+    ) -> SecureVaultResult<Vec<u8>> {
+        let mut iv = [0u8; 12];
+        SecureRandom::fill_bytes(&mut iv);
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(kek.expose()));
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&iv),
+                Payload {
+                    msg: key.expose(),
+                    aad,
+                },
+            )
+            .map_err(|_| CryptoError::EncryptionFailed)?;
+
+        let mut result = Vec::with_capacity(iv.len() + ciphertext.len());
+        result.extend_from_slice(&iv);
+        result.extend_from_slice(&ciphertext);
+        Ok(result)
+    }
+
+    /// 주어진 인증 데이터로 래핑된 키를 언래핑합니다.
+    fn unwrap_key_with_aad(
+        kek: &SecretKey,
+        wrapped: &[u8],
+        aad: &[u8],
+    ) -> SecureVaultResult<SecretKey> {
+        if wrapped.len() != 12 + 32 + 16 {
+            return Err(CryptoError::InvalidKey("래핑된 키의 길이가 올바르지 않습니다.".to_string()).into());
+        }
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(kek.expose()));
+        let mut plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(&wrapped[..12]),
+                Payload {
+                    msg: &wrapped[12..],
+                    aad,
+                },
+            )
+            .map_err(|_| CryptoError::DecryptionFailed)?;
+
+        let key = SecretKey::from_slice(&plaintext);
+        SecureMemory::clear_vec(&mut plaintext);
+        key.ok_or_else(|| CryptoError::DecryptionFailed.into())
+    }
+
     /// 데이터를 C# 버전과 호환되는 형식으로 암호화합니다.
     ///
     /// C# EncryptionService.EncryptData()와 동일한 형식을 사용합니다:
@@ -290,13 +681,13 @@ impl CryptoService {
         let start_time = Instant::now();
 
         // 마스터 키 확인
-        let master_key = self.master_key.ok_or(CryptoError::NoMasterKey)?;
+        let master_key = self.master_key.as_deref().ok_or(CryptoError::NoMasterKey)?;
 
         // 파일별 고유 키 유도
-        let file_key = self.derive_file_key(&master_key, file_id)?;
+        let file_key = self.derive_file_key(master_key, file_id)?;
 
         // C# 호환 형식으로 암호화
-        let encrypted_bytes = self.encrypt_data_csharp_compatible(data, &file_key)?;
+        let encrypted_bytes = self.encrypt_data_csharp_compatible(data, file_key.expose())?;
 
         // 데이터 해시 계산
         let data_hash = self.calculate_data_hash(data);
@@ -348,7 +739,7 @@ impl CryptoService {
         let start_time = Instant::now();
 
         // 마스터 키 확인
-        let master_key = self.master_key.ok_or(CryptoError::NoMasterKey)?;
+        let master_key = self.master_key.as_deref().ok_or(CryptoError::NoMasterKey)?;
 
         // 메타데이터 유효성 검증
         if !encrypted_data.metadata.is_valid() {
@@ -356,11 +747,11 @@ impl CryptoService {
         }
 
         // 파일별 고유 키 유도
-        let file_key = self.derive_file_key(&master_key, file_id)?;
+        let file_key = self.derive_file_key(master_key, file_id)?;
 
         // C# 호환 형식으로 복호화
         let plaintext =
-            self.decrypt_data_csharp_compatible(&encrypted_data.ciphertext, &file_key)?;
+            self.decrypt_data_csharp_compatible(&encrypted_data.ciphertext, file_key.expose())?;
 
         // 데이터 무결성 검증
         let calculated_hash = self.calculate_data_hash(&plaintext);
@@ -398,13 +789,13 @@ impl CryptoService {
         chunk_index: u32,
     ) -> SecureVaultResult<Vec<u8>> {
         // 마스터 키 확인
-        let master_key = self.master_key.ok_or(CryptoError::NoMasterKey)?;
+        let master_key = self.master_key.as_deref().ok_or(CryptoError::NoMasterKey)?;
 
         // 청크별 고유 키 유도 (파일 ID + 청크 인덱스)
         let mut chunk_id_bytes = file_id.as_bytes().to_vec();
         chunk_id_bytes.extend_from_slice(&chunk_index.to_le_bytes());
 
-        let chunk_key = self.derive_chunk_key(&master_key, &chunk_id_bytes)?;
+        let chunk_key = self.derive_chunk_key(master_key, &chunk_id_bytes)?;
 
         // 논스 생성 (청크별 고유)
         let nonce_bytes = SecureRandom::generate_nonce(&self.default_algorithm);
@@ -412,10 +803,10 @@ impl CryptoService {
         // 암호화
         let (mut ciphertext, tag) = match self.default_algorithm {
             EncryptionAlgorithm::AES256GCM => {
-                self.encrypt_with_aes256gcm(&chunk_key, &nonce_bytes, input_data)?
+                self.encrypt_with_aes256gcm(chunk_key.expose(), &nonce_bytes, input_data)?
             }
             EncryptionAlgorithm::ChaCha20Poly1305 => {
-                self.encrypt_with_chacha20poly1305(&chunk_key, &nonce_bytes, input_data)?
+                self.encrypt_with_chacha20poly1305(chunk_key.expose(), &nonce_bytes, input_data)?
             }
         };
 
@@ -444,13 +835,13 @@ impl CryptoService {
         chunk_index: u32,
     ) -> SecureVaultResult<Vec<u8>> {
         // 마스터 키 확인
-        let master_key = self.master_key.ok_or(CryptoError::NoMasterKey)?;
+        let master_key = self.master_key.as_deref().ok_or(CryptoError::NoMasterKey)?;
 
         // 청크별 고유 키 유도
         let mut chunk_id_bytes = file_id.as_bytes().to_vec();
         chunk_id_bytes.extend_from_slice(&chunk_index.to_le_bytes());
 
-        let chunk_key = self.derive_chunk_key(&master_key, &chunk_id_bytes)?;
+        let chunk_key = self.derive_chunk_key(master_key, &chunk_id_bytes)?;
 
         // 논스, 태그, 암호문 분리
         let nonce_size = self.default_algorithm.nonce_size();
@@ -467,10 +858,10 @@ impl CryptoService {
         // 복호화
         let plaintext = match self.default_algorithm {
             EncryptionAlgorithm::AES256GCM => {
-                self.decrypt_with_aes256gcm(&chunk_key, nonce, ciphertext, tag)?
+                self.decrypt_with_aes256gcm(chunk_key.expose(), nonce, ciphertext, tag)?
             }
             EncryptionAlgorithm::ChaCha20Poly1305 => {
-                self.decrypt_with_chacha20poly1305(&chunk_key, nonce, ciphertext, tag)?
+                self.decrypt_with_chacha20poly1305(chunk_key.expose(), nonce, ciphertext, tag)?
             }
         };
 
@@ -482,9 +873,7 @@ impl CryptoService {
     /// 애플리케이션 종료 시나 로그아웃 시 호출하여
     /// 메모리에 남아있는 키 정보를 안전하게 삭제합니다.
     pub fn clear_sensitive_data(&mut self) {
-        if let Some(ref mut key) = self.master_key {
-            SecureMemory::clear_bytes(key);
-        }
+        // 공유 중인 복사본이 모두 해제되면 SecretKey가 스스로 0으로 덮어씀
         self.master_key = None;
 
         // KDF 매개변수의 솔트도 클리어
@@ -520,26 +909,29 @@ impl CryptoService {
 
     /// 파일별 고유 키를 유도합니다.
     ///
+    /// 256비트 마스터 키에서 유도하므로 반복 횟수가 보안에 기여하지 않으며,
+    /// 이전 형식 파일과의 호환을 위해 고정값을 사용합니다 (키 유도 비용 보정 대상이 아님).
+    ///
     /// # 매개변수
     /// * `master_key` - 마스터 키
     /// * `file_id` - 파일 고유 ID
     ///
     /// # 반환값
-    /// * `SecureVaultResult<[u8; 32]>` - 유도된 파일 키
+    /// * `SecureVaultResult<SecretKey>` - 유도된 파일 키
     pub fn derive_file_key(
         &self,
-        master_key: &[u8; 32],
+        master_key: &SecretKey,
         file_id: &Uuid,
-    ) -> SecureVaultResult<[u8; 32]> {
-        let mut file_key = [0u8; 32];
+    ) -> SecureVaultResult<SecretKey> {
+        let mut file_key = SecretKey::zeroed();
         let file_id_bytes = file_id.as_bytes();
 
         // PBKDF2-HMAC-SHA256으로 파일별 키 유도
         pbkdf2_hmac::<Sha256>(
-            master_key,
+            master_key.expose(),
             file_id_bytes,
             10_000, // 파일 키는 상대적으로 적은 반복 횟수 사용
-            &mut file_key,
+            file_key.expose_mut(),
         );
 
         Ok(file_key)
@@ -547,25 +939,27 @@ impl CryptoService {
 
     /// 청크별 고유 키를 유도합니다.
     ///
+    /// 파일 키와 같은 이유로 반복 횟수는 고정값입니다.
+    ///
     /// # 매개변수
     /// * `master_key` - 마스터 키
     /// * `chunk_id` - 청크 식별자
     ///
     /// # 반환값
-    /// * `SecureVaultResult<[u8; 32]>` - 유도된 청크 키
+    /// * `SecureVaultResult<SecretKey>` - 유도된 청크 키
     fn derive_chunk_key(
         &self,
-        master_key: &[u8; 32],
+        master_key: &SecretKey,
         chunk_id: &[u8],
-    ) -> SecureVaultResult<[u8; 32]> {
-        let mut chunk_key = [0u8; 32];
+    ) -> SecureVaultResult<SecretKey> {
+        let mut chunk_key = SecretKey::zeroed();
 
         // PBKDF2-HMAC-SHA256으로 청크별 키 유도
         pbkdf2_hmac::<Sha256>(
-            master_key,
+            master_key.expose(),
             chunk_id,
             5_000, // 청크 키는 더 적은 반복 횟수 사용 (성능 고려)
-            &mut chunk_key,
+            chunk_key.expose_mut(),
         );
 
         Ok(chunk_key)
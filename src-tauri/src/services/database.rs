@@ -1,9 +1,17 @@
-use crate::models::{error::VaultError, file::FileEntry, folder::FolderEntry};
+use crate::models::{
+    encryption::EncryptionAlgorithm, error::VaultError, file::FileEntry, folder::FolderEntry,
+    SecretKey,
+};
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, Result as SqliteResult, Row};
 use serde_json;
+use std::io::Read;
 use std::path::Path;
 use uuid::Uuid;
+use zeroize::Zeroizing;
+
+/// 암호화되지 않은 SQLite 데이터베이스 파일의 시작 문자열
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
 
 /// 데이터베이스 서비스
 /// SQLite 기반 메타데이터 데이터베이스를 관리합니다.
@@ -43,12 +51,19 @@ impl DatabaseService {
 
     /// 데이터베이스를 초기화합니다.
     ///
+    /// 메타데이터 DB는 SQLCipher로 암호화되며, 이전 버전이 만든 평문 DB는
+    /// 처음 열 때 같은 자리에서 암호화된 DB로 변환됩니다.
+    ///
     /// # 매개변수
     /// * `vault_path` - 볼트 경로
+    /// * `db_key` - 데이터베이스 키 (`CryptoService::derive_database_key`로 유도)
     ///
     /// # 반환값
     /// * `Result<(), VaultError>` - 초기화 결과
-    pub fn initialize(&mut self, vault_path: &str) -> Result<(), VaultError> {
+    ///
+    /// # 오류
+    /// * `VaultError::DatabaseError` - 키가 틀렸거나 DB 파일이 손상됨
+    pub fn initialize(&mut self, vault_path: &str, db_key: &SecretKey) -> Result<(), VaultError> {
         let db_path = Path::new(vault_path)
             .join(".securevault")
             .join("metadata.db");
@@ -59,9 +74,29 @@ impl DatabaseService {
                 .map_err(|e| VaultError::DatabaseError(format!("디렉토리 생성 실패: {}", e)))?;
         }
 
+        // 평문 DB가 남아 있다면 먼저 암호화된 DB로 변환
+        if Self::is_plaintext_database(&db_path) {
+            Self::encrypt_plaintext_database(&db_path, db_key)?;
+        } else {
+            // 변환 도중 중단되어 덮어쓰지 못한 평문 사본 정리
+            Self::overwrite_plaintext_copy(&db_path);
+        }
+
         // SQLite 연결 생성
         let conn = Connection::open(&db_path)
             .map_err(|e| VaultError::DatabaseError(format!("데이터베이스 연결 실패: {}", e)))?;
+        Self::apply_key(&conn, "key", db_key)?;
+
+        // 키가 맞는지 확인 (SQLCipher는 첫 읽기에서 복호화를 시도함)
+        conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| {
+            row.get::<_, i64>(0)
+        })
+        .map_err(|_| {
+            VaultError::DatabaseError(
+                "데이터베이스를 열 수 없습니다. 키가 올바르지 않거나 파일이 손상되었습니다."
+                    .to_string(),
+            )
+        })?;
 
         // 스키마 생성
         self.create_schema(&conn)?;
@@ -72,10 +107,150 @@ impl DatabaseService {
         self.connection = Some(conn);
         self.db_path = Some(db_path.to_string_lossy().to_string());
 
-        log::info!("데이터베이스 초기화 완료: {:?}", db_path);
+        log::info!("데이터베이스 초기화 완료");
+        Ok(())
+    }
+
+    /// 데이터베이스를 초기화합니다. 새 키로 열 수 없으면 이전 키로 열어 새 키로 교체합니다.
+    ///
+    /// 볼트 데이터 키를 교체한 뒤 DB 키를 바꾸기 전에 중단된 볼트를 열 때 사용합니다.
+    ///
+    /// # 매개변수
+    /// * `vault_path` - 볼트 경로
+    /// * `db_key` - 현재 데이터베이스 키
+    /// * `previous_key` - 데이터 키 교체 중이면 이전 데이터베이스 키
+    ///
+    /// # 반환값
+    /// * `Result<(), VaultError>` - 초기화 결과
+    pub fn initialize_or_rekey(
+        &mut self,
+        vault_path: &str,
+        db_key: &SecretKey,
+        previous_key: Option<&SecretKey>,
+    ) -> Result<(), VaultError> {
+        match (self.initialize(vault_path, db_key), previous_key) {
+            (Ok(()), _) => Ok(()),
+            (Err(_), Some(previous_key)) => {
+                self.initialize(vault_path, previous_key)?;
+                self.rekey(db_key)
+            }
+            (Err(e), None) => Err(e),
+        }
+    }
+
+    /// 데이터베이스 연결을 닫습니다. 볼트를 잠글 때 호출하여 메모리의 DB 키도 함께 제거합니다.
+    pub fn close(&mut self) {
+        self.connection = None;
+    }
+
+    /// 데이터베이스를 새 키로 다시 암호화합니다.
+    ///
+    /// 볼트 데이터 키가 바뀌는 등 키 계층이 변경되면 호출해야 합니다.
+    ///
+    /// # 매개변수
+    /// * `new_key` - 새 데이터베이스 키
+    ///
+    /// # 반환값
+    /// * `Result<(), VaultError>` - 재암호화 결과
+    pub fn rekey(&mut self, new_key: &SecretKey) -> Result<(), VaultError> {
+        let conn = self.connection.as_ref().ok_or(VaultError::NotInitialized)?;
+        Self::apply_key(conn, "rekey", new_key)?;
+
+        log::info!("데이터베이스 키를 교체했습니다.");
+        Ok(())
+    }
+
+    /// SQLCipher 키 PRAGMA(`key` 또는 `rekey`)를 원시 키 형식으로 실행합니다.
+    ///
+    /// 키가 이미 256비트 랜덤 값이므로 SQLCipher 자체 PBKDF2를 거치지 않도록 `x'...'` 형식을 사용합니다.
+    fn apply_key(conn: &Connection, pragma: &str, key: &SecretKey) -> Result<(), VaultError> {
+        let raw_key = Zeroizing::new(format!("x'{}'", hex::encode(key.expose())));
+        conn.pragma_update(None, pragma, raw_key.as_str())
+            .map_err(|e| VaultError::DatabaseError(format!("데이터베이스 키 설정 실패: {}", e)))
+    }
+
+    /// 파일이 암호화되지 않은 SQLite DB인지 확인합니다.
+    ///
+    /// SQLCipher DB는 첫 16바이트도 암호화(솔트)되어 있으므로 SQLite 헤더 문자열이 나타나지 않습니다.
+    fn is_plaintext_database(db_path: &Path) -> bool {
+        let mut header = [0u8; 16];
+        std::fs::File::open(db_path)
+            .and_then(|mut file| file.read_exact(&mut header))
+            .is_ok_and(|_| &header == SQLITE_HEADER)
+    }
+
+    /// 평문 DB를 `sqlcipher_export`로 암호화된 DB로 변환하고 원래 파일을 대체합니다.
+    ///
+    /// 새 DB를 임시 파일에 완성한 뒤 이름을 바꾸므로, 도중에 중단되어도 원래 DB는 그대로 남습니다.
+    /// 이름을 바꾸기 전에 평문 DB에 하드 링크를 걸어 두었다가, 교체 후 그 내용을 0으로 덮어쓰고 삭제합니다.
+    /// 하드 링크를 지원하지 않는 파일 시스템(FAT 등)에서는 평문 DB를 삭제만 하므로 내용이 디스크에 남을 수 있습니다.
+    fn encrypt_plaintext_database(db_path: &Path, db_key: &SecretKey) -> Result<(), VaultError> {
+        let temp_path = db_path.with_extension("db.encrypting");
+        let plaintext_copy = db_path.with_extension("db.plaintext");
+        let _ = std::fs::remove_file(&temp_path);
+        // 이전 시도의 링크는 아직 평문 DB 자체를 가리키므로 이름만 삭제
+        let _ = std::fs::remove_file(&plaintext_copy);
+
+        {
+            let conn = Connection::open(db_path).map_err(|e| {
+                VaultError::DatabaseError(format!("평문 데이터베이스 열기 실패: {}", e))
+            })?;
+            let raw_key = Zeroizing::new(format!("x'{}'", hex::encode(db_key.expose())));
+
+            conn.execute(
+                "ATTACH DATABASE ?1 AS encrypted KEY ?2",
+                params![temp_path.to_string_lossy(), raw_key.as_str()],
+            )
+            .and_then(|_| conn.query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(())))
+            .and_then(|_| conn.execute("DETACH DATABASE encrypted", []))
+            .map_err(|e| {
+                let _ = std::fs::remove_file(&temp_path);
+                VaultError::DatabaseError(format!("데이터베이스 암호화 변환 실패: {}", e))
+            })?;
+        }
+
+        let linked = std::fs::hard_link(db_path, &plaintext_copy).is_ok();
+        std::fs::rename(&temp_path, db_path).map_err(|e| {
+            let _ = std::fs::remove_file(&plaintext_copy);
+            VaultError::DatabaseError(format!("암호화된 데이터베이스 교체 실패: {}", e))
+        })?;
+
+        if linked {
+            Self::overwrite_plaintext_copy(db_path);
+        } else {
+            log::warn!("평문 데이터베이스를 덮어쓸 수 없는 파일 시스템이어서 삭제만 했습니다.");
+        }
+
+        // 평문 DB의 저널 파일에도 메타데이터가 남아 있을 수 있음
+        for suffix in ["-wal", "-shm", "-journal"] {
+            let mut journal = db_path.as_os_str().to_owned();
+            journal.push(suffix);
+            let journal = Path::new(&journal);
+            if journal.exists() {
+                if let Err(e) = crate::utils::overwrite_and_remove_file(journal) {
+                    log::warn!("평문 데이터베이스 저널 삭제 실패: {}", e);
+                }
+            }
+        }
+
+        log::info!("평문 메타데이터 데이터베이스를 암호화했습니다.");
         Ok(())
     }
 
+    /// 암호화 변환 때 남겨 둔 평문 DB 하드 링크의 내용을 0으로 덮어쓰고 삭제합니다.
+    ///
+    /// `db_path`가 이미 암호화된 DB로 교체된 뒤에만 호출해야 합니다.
+    fn overwrite_plaintext_copy(db_path: &Path) {
+        let plaintext_copy = db_path.with_extension("db.plaintext");
+        if !plaintext_copy.exists() {
+            return;
+        }
+
+        if let Err(e) = crate::utils::overwrite_and_remove_file(&plaintext_copy) {
+            log::warn!("평문 데이터베이스 덮어쓰기 실패: {}", e);
+        }
+    }
+
     /// 데이터베이스 스키마를 생성합니다.
     /// C# 버전의 FileMetadata와 VaultConfig 구조를 기반으로 설계
     fn create_schema(&self, conn: &Connection) -> Result<(), VaultError> {
@@ -107,7 +282,8 @@ impl DatabaseService {
                 deleted_date TEXT,
                 custom_properties TEXT DEFAULT '{}',
                 access_count INTEGER DEFAULT 0,
-                security_level INTEGER DEFAULT 0
+                security_level INTEGER DEFAULT 0,
+                wrapped_key BLOB
             )
             "#,
             [],
@@ -191,9 +367,12 @@ impl DatabaseService {
         if schema_version < 1 {
             self.migrate_to_version_1(conn)?;
         }
+        if schema_version < 2 {
+            self.migrate_to_version_2(conn)?;
+        }
 
         // 최신 버전으로 업데이트
-        self.set_schema_version(conn, 1)?;
+        self.set_schema_version(conn, 2)?;
 
         log::info!("데이터베이스 마이그레이션 완료");
         Ok(())
@@ -300,6 +479,27 @@ impl DatabaseService {
         Ok(())
     }
 
+    /// 버전 2로 마이그레이션: 파일별 래핑된 데이터 키 컬럼 추가
+    ///
+    /// 기존 파일은 NULL로 남아 보안 등급 키로 직접 복호화됩니다.
+    fn migrate_to_version_2(&self, conn: &Connection) -> Result<(), VaultError> {
+        log::info!("스키마 버전 2로 마이그레이션 시작");
+
+        let has_wrapped_key_column = conn
+            .prepare("SELECT wrapped_key FROM files LIMIT 1")
+            .is_ok();
+
+        if !has_wrapped_key_column {
+            conn.execute("ALTER TABLE files ADD COLUMN wrapped_key BLOB", [])
+                .map_err(|e| {
+                    VaultError::DatabaseError(format!("wrapped_key 컬럼 추가 실패: {}", e))
+                })?;
+            log::info!("files 테이블에 wrapped_key 컬럼 추가");
+        }
+
+        Ok(())
+    }
+
     /// 파일 메타데이터를 추가합니다.
     ///
     /// # 매개변수
@@ -326,10 +526,10 @@ impl DatabaseService {
                 folder_id, encrypted_file_name, encrypted_size, is_compressed,
                 compressed_size, compression_ratio, tags, description,
                 version, is_favorite, is_deleted, deleted_date, custom_properties,
-                access_count, security_level
+                access_count, security_level, wrapped_key
             ) VALUES (
                 ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10,
-                ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25
+                ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26
             )
             "#,
             params![
@@ -357,7 +557,8 @@ impl DatabaseService {
                 file_entry.deleted_date.map(|d| d.to_rfc3339()),
                 custom_properties_json,
                 file_entry.access_count as i32,
-                file_entry.security_level as i32
+                file_entry.security_level as i32,
+                file_entry.wrapped_key
             ],
         )
         .map_err(|e| VaultError::DatabaseError(format!("파일 추가 실패: {}", e)))?;
@@ -393,10 +594,10 @@ impl DatabaseService {
                         folder_id, encrypted_file_name, encrypted_size, is_compressed,
                         compressed_size, compression_ratio, tags, description,
                         version, is_favorite, is_deleted, deleted_date, custom_properties,
-                        access_count, security_level
+                        access_count, security_level, wrapped_key
                     ) VALUES (
                         ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10,
-                        ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25
+                        ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26
                     )
                     "#,
                 )
@@ -436,7 +637,8 @@ impl DatabaseService {
                     file_entry.deleted_date.map(|d| d.to_rfc3339()),
                     custom_properties_json,
                     file_entry.access_count as i32,
-                    file_entry.security_level as i32
+                    file_entry.security_level as i32,
+                    file_entry.wrapped_key
                 ])
                 .map_err(|e| VaultError::DatabaseError(format!("파일 배포 추가 실패: {}", e)))?;
             }
@@ -543,6 +745,33 @@ impl DatabaseService {
         }
     }
 
+    /// 삭제되지 않은 모든 파일 메타데이터를 ID 순서로 조회합니다.
+    ///
+    /// # 반환값
+    /// * `Result<Vec<FileEntry>, VaultError>` - 파일 목록
+    pub fn get_all_files(&self) -> Result<Vec<FileEntry>, VaultError> {
+        let conn = self.connection.as_ref().ok_or_else(|| {
+            VaultError::DatabaseError("데이터베이스가 초기화되지 않았습니다.".to_string())
+        })?;
+
+        let mut stmt = conn
+            .prepare("SELECT * FROM files WHERE is_deleted = 0 ORDER BY id")
+            .map_err(|e| VaultError::DatabaseError(format!("쿼리 준비 실패: {}", e)))?;
+
+        let file_iter = stmt
+            .query_map([], |row| self.row_to_file_entry(row))
+            .map_err(|e| VaultError::DatabaseError(format!("파일 목록 조회 실패: {}", e)))?;
+
+        let mut files = Vec::new();
+        for file_result in file_iter {
+            match file_result {
+                Ok(file_entry) => files.push(file_entry),
+                Err(e) => log::warn!("파일 엔트리 변환 실패: {}", e),
+            }
+        }
+        Ok(files)
+    }
+
     /// 파일 메타데이터를 삭제합니다.
     ///
     /// # 매개변수
@@ -731,7 +960,8 @@ impl DatabaseService {
                 folder_id = ?10, encrypted_file_name = ?11, encrypted_size = ?12, is_compressed = ?13,
                 compressed_size = ?14, compression_ratio = ?15, tags = ?16,
                 description = ?17, version = ?18, is_favorite = ?19, is_deleted = ?20,
-                deleted_date = ?21, custom_properties = ?22, access_count = ?23, security_level = ?24
+                deleted_date = ?21, custom_properties = ?22, access_count = ?23, security_level = ?24,
+                wrapped_key = ?25
             WHERE id = ?1
             "#,
             params![
@@ -758,7 +988,8 @@ impl DatabaseService {
                 file_entry.deleted_date.map(|d| d.to_rfc3339()),
                 custom_properties_json,
                 file_entry.access_count as i32,
-                file_entry.security_level as i32
+                file_entry.security_level as i32,
+                file_entry.wrapped_key
             ],
         ).map_err(|e| VaultError::DatabaseError(format!("파일 업데이트 실패: {}", e)))?;
 
@@ -866,6 +1097,7 @@ impl DatabaseService {
             security_level: crate::models::file::FileSecurityLevel::from(
                 row.get::<_, i32>("security_level")?,
             ),
+            wrapped_key: row.get("wrapped_key")?,
         })
     }
 
@@ -1075,6 +1307,114 @@ impl DatabaseService {
 
         Ok(subfolder_count as u32)
     }
+
+    /// 볼트의 파일 암호화 알고리즘 설정을 조회합니다.
+    ///
+    /// 설정이 없는 볼트는 기본 알고리즘(AES-256-GCM)을 사용합니다.
+    ///
+    /// # 반환값
+    /// * `Result<EncryptionAlgorithm, VaultError>` - 새 파일에 사용할 알고리즘
+    pub fn get_encryption_algorithm(&self) -> Result<EncryptionAlgorithm, VaultError> {
+        let conn = self.connection.as_ref().ok_or_else(|| {
+            VaultError::DatabaseError("데이터베이스가 초기화되지 않았습니다.".to_string())
+        })?;
+
+        let value = conn.query_row(
+            "SELECT value FROM vault_config WHERE key = 'encryption_algorithm'",
+            [],
+            |row| row.get::<_, String>(0),
+        );
+
+        match value {
+            Ok(value) => value
+                .parse::<u8>()
+                .ok()
+                .and_then(EncryptionAlgorithm::from_container_id)
+                .ok_or_else(|| {
+                    VaultError::DatabaseError(format!(
+                        "알 수 없는 암호화 알고리즘 설정: {}",
+                        value
+                    ))
+                }),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(EncryptionAlgorithm::default()),
+            Err(e) => Err(VaultError::DatabaseError(format!(
+                "암호화 알고리즘 설정 조회 실패: {}",
+                e
+            ))),
+        }
+    }
+
+    /// 볼트의 파일 암호화 알고리즘 설정을 저장합니다.
+    ///
+    /// 알고리즘은 컨테이너 헤더와 같은 알고리즘 ID로 저장됩니다.
+    ///
+    /// # 매개변수
+    /// * `algorithm` - 새 파일에 사용할 알고리즘
+    pub fn set_encryption_algorithm(
+        &self,
+        algorithm: &EncryptionAlgorithm,
+    ) -> Result<(), VaultError> {
+        let conn = self.connection.as_ref().ok_or_else(|| {
+            VaultError::DatabaseError("데이터베이스가 초기화되지 않았습니다.".to_string())
+        })?;
+        let now = Utc::now().to_rfc3339();
+
+        conn.execute(
+            r#"
+            INSERT OR REPLACE INTO vault_config (key, value, created_date, modified_date)
+            VALUES ('encryption_algorithm', ?1, ?2, ?3)
+            "#,
+            params![algorithm.container_id().to_string(), now, now],
+        )
+        .map_err(|e| VaultError::DatabaseError(format!("암호화 알고리즘 설정 저장 실패: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// 재암호화 작업 체크포인트(JSON)를 조회합니다.
+    ///
+    /// # 반환값
+    /// * `Result<Option<String>, VaultError>` - 체크포인트가 없으면 None
+    pub fn get_reencryption_checkpoint(&self) -> Result<Option<String>, VaultError> {
+        let conn = self.connection.as_ref().ok_or_else(|| {
+            VaultError::DatabaseError("데이터베이스가 초기화되지 않았습니다.".to_string())
+        })?;
+
+        match conn.query_row(
+            "SELECT value FROM vault_config WHERE key = 'reencryption_checkpoint'",
+            [],
+            |row| row.get::<_, String>(0),
+        ) {
+            Ok(value) => Ok(Some(value)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(VaultError::DatabaseError(format!(
+                "재암호화 체크포인트 조회 실패: {}",
+                e
+            ))),
+        }
+    }
+
+    /// 재암호화 작업 체크포인트(JSON)를 저장합니다.
+    ///
+    /// # 매개변수
+    /// * `checkpoint` - 직렬화된 체크포인트
+    pub fn set_reencryption_checkpoint(&self, checkpoint: &str) -> Result<(), VaultError> {
+        let conn = self.connection.as_ref().ok_or_else(|| {
+            VaultError::DatabaseError("데이터베이스가 초기화되지 않았습니다.".to_string())
+        })?;
+        let now = Utc::now().to_rfc3339();
+
+        conn.execute(
+            r#"
+            INSERT OR REPLACE INTO vault_config (key, value, created_date, modified_date)
+            VALUES ('reencryption_checkpoint', ?1, ?2, ?3)
+            "#,
+            params![checkpoint, now, now],
+        )
+        .map_err(|e| VaultError::DatabaseError(format!("재암호화 체크포인트 저장 실패: {}", e)))?;
+
+        Ok(())
+    }
 }
 
 impl Default for DatabaseService {
@@ -1088,6 +1428,16 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    fn test_key(byte: u8) -> SecretKey {
+        SecretKey::from_bytes(&mut [byte; 32])
+    }
+
+    fn db_file(vault_path: &str) -> std::path::PathBuf {
+        Path::new(vault_path)
+            .join(".securevault")
+            .join("metadata.db")
+    }
+
     #[test]
     fn test_database_initialization() {
         // 임시 디렉토리 생성
@@ -1096,7 +1446,7 @@ mod tests {
 
         // 데이터베이스 서비스 생성 및 초기화
         let mut db_service = DatabaseService::new();
-        let result = db_service.initialize(vault_path);
+        let result = db_service.initialize(vault_path, &test_key(7));
 
         assert!(result.is_ok());
         assert!(db_service.connection.is_some());
@@ -1110,7 +1460,7 @@ mod tests {
 
         // 데이터베이스 서비스 초기화
         let mut db_service = DatabaseService::new();
-        db_service.initialize(vault_path).unwrap();
+        db_service.initialize(vault_path, &test_key(7)).unwrap();
 
         // 폴더 생성
         let folder_entry =
@@ -1136,4 +1486,139 @@ mod tests {
         let deleted_folder = db_service.get_folder(&folder_id).unwrap();
         assert!(deleted_folder.is_none());
     }
+
+    #[test]
+    fn test_encryption_algorithm_setting() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().to_str().unwrap();
+
+        let mut db_service = DatabaseService::new();
+        db_service.initialize(vault_path, &test_key(7)).unwrap();
+        assert_eq!(
+            db_service.get_encryption_algorithm().unwrap(),
+            EncryptionAlgorithm::AES256GCM
+        );
+
+        db_service
+            .set_encryption_algorithm(&EncryptionAlgorithm::ChaCha20Poly1305)
+            .unwrap();
+
+        // 다시 열어도 설정이 유지됨
+        let mut reopened = DatabaseService::new();
+        reopened.initialize(vault_path, &test_key(7)).unwrap();
+        assert_eq!(
+            reopened.get_encryption_algorithm().unwrap(),
+            EncryptionAlgorithm::ChaCha20Poly1305
+        );
+    }
+
+    #[test]
+    fn test_database_file_is_encrypted() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().to_str().unwrap();
+
+        let mut db_service = DatabaseService::new();
+        db_service.initialize(vault_path, &test_key(7)).unwrap();
+        let folder_entry = FolderEntry::new("비밀폴더".to_string(), None, "/비밀폴더".to_string());
+        db_service.add_folder(&folder_entry).unwrap();
+        db_service.close();
+
+        let bytes = std::fs::read(db_file(vault_path)).unwrap();
+        assert!(!bytes.starts_with(SQLITE_HEADER));
+        assert!(!bytes
+            .windows("비밀폴더".len())
+            .any(|window| window == "비밀폴더".as_bytes()));
+
+        // 다른 키로는 열 수 없음
+        let mut wrong_key = DatabaseService::new();
+        assert!(wrong_key.initialize(vault_path, &test_key(8)).is_err());
+    }
+
+    #[test]
+    fn test_plaintext_database_is_migrated() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().to_str().unwrap();
+        std::fs::create_dir_all(db_file(vault_path).parent().unwrap()).unwrap();
+
+        // 이전 버전처럼 키 없이 만든 DB
+        {
+            let conn = Connection::open(db_file(vault_path)).unwrap();
+            let plain = DatabaseService::new();
+            plain.create_schema(&conn).unwrap();
+            plain.migrate_schema(&conn).unwrap();
+            conn.execute(
+                "INSERT INTO folders (id, name, path, created_at, modified_at) VALUES (?1, ?2, ?3, ?4, ?4)",
+                params![Uuid::new_v4().to_string(), "기존폴더", "/기존폴더", Utc::now().to_rfc3339()],
+            )
+            .unwrap();
+        }
+        assert!(DatabaseService::is_plaintext_database(&db_file(vault_path)));
+        // 원래 평문 DB의 블록을 그대로 가리키는 이름
+        let witness = temp_dir.path().join("witness.db");
+        std::fs::hard_link(db_file(vault_path), &witness).unwrap();
+
+        let mut db_service = DatabaseService::new();
+        db_service.initialize(vault_path, &test_key(7)).unwrap();
+        assert!(!DatabaseService::is_plaintext_database(&db_file(
+            vault_path
+        )));
+
+        // 교체된 평문 DB의 내용은 0으로 덮어써지고 사본도 남지 않음
+        assert!(std::fs::read(&witness).unwrap().iter().all(|&b| b == 0));
+        assert!(!db_file(vault_path).with_extension("db.plaintext").exists());
+
+        let folders = db_service.get_all_folders().unwrap();
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0].name, "기존폴더");
+    }
+
+    #[test]
+    fn test_rekey_database() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().to_str().unwrap();
+        let new_key = test_key(9);
+
+        let mut db_service = DatabaseService::new();
+        db_service.initialize(vault_path, &test_key(7)).unwrap();
+        db_service
+            .set_encryption_algorithm(&EncryptionAlgorithm::ChaCha20Poly1305)
+            .unwrap();
+        db_service.rekey(&new_key).unwrap();
+        db_service.close();
+
+        assert!(DatabaseService::new()
+            .initialize(vault_path, &test_key(7))
+            .is_err());
+
+        let mut reopened = DatabaseService::new();
+        reopened.initialize(vault_path, &new_key).unwrap();
+        assert_eq!(
+            reopened.get_encryption_algorithm().unwrap(),
+            EncryptionAlgorithm::ChaCha20Poly1305
+        );
+    }
+
+    #[test]
+    fn test_initialize_or_rekey_falls_back_to_previous_key() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().to_str().unwrap();
+
+        let mut db_service = DatabaseService::new();
+        db_service.initialize(vault_path, &test_key(7)).unwrap();
+        db_service.close();
+
+        // 새 키로 열 수 없으면 이전 키로 열어 새 키로 교체
+        let mut reopened = DatabaseService::new();
+        assert!(reopened
+            .initialize_or_rekey(vault_path, &test_key(9), None)
+            .is_err());
+        reopened
+            .initialize_or_rekey(vault_path, &test_key(9), Some(&test_key(7)))
+            .unwrap();
+        reopened.close();
+
+        assert!(DatabaseService::new()
+            .initialize(vault_path, &test_key(9))
+            .is_ok());
+    }
 }
@@ -1,16 +1,66 @@
 use crate::models::{
     error::VaultError,
-    file::{calculate_file_hash, calculate_file_hash_parallel, FileEntry, FileSortBy},
+    file::{
+        calculate_file_hash, calculate_file_hash_parallel, FileEntry, FileSecurityLevel,
+        FileSortBy, LegacyMigrationReport,
+    },
+    EncryptionAlgorithm, SecretKey, SecureMemory,
 };
 use crate::services::{
-    compression::CompressionService, crypto::CryptoService, database::DatabaseService,
+    compression::CompressionService,
+    container::{self, ContainerHeader, ContainerReader, ContainerWriter, LegacyLayout},
+    crypto::CryptoService,
+    database::DatabaseService,
+    reencryption::{PendingSwap, PreparedReencryption, ReencryptionCheckpoint},
 };
 use chrono::Utc;
 use std::fs;
 use std::io::{Seek, Write};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use tempfile::NamedTempFile;
 use uuid::Uuid;
+use zeroize::Zeroize;
+
+/// 복제본 간에 공유되는 마스터 키 슬롯
+///
+/// 업로드 스레드 등에서 복제한 `FileService`도 같은 슬롯을 참조하므로,
+/// 잠금 시 한 번 지우면 모든 복제본에서 키가 사라집니다.
+/// 키 바이트는 복사하지 않고 `Arc`로 공유하며, 마지막 참조가 사라질 때 0으로 덮어써집니다.
+#[derive(Clone, Default)]
+struct SharedMasterKey(Arc<Mutex<Option<Arc<SecretKey>>>>);
+
+impl SharedMasterKey {
+    /// 키에 대한 공유 참조를 반환합니다.
+    fn get(&self) -> Option<Arc<SecretKey>> {
+        self.0.lock().ok().and_then(|slot| slot.clone())
+    }
+
+    /// 키를 설정합니다.
+    fn set(&self, key: Arc<SecretKey>) {
+        if let Ok(mut slot) = self.0.lock() {
+            *slot = Some(key);
+        }
+    }
+
+    /// 키를 제거합니다.
+    fn clear(&self) {
+        if let Ok(mut slot) = self.0.lock() {
+            *slot = None;
+        }
+    }
+
+    /// 키가 설정되어 있는지 확인합니다.
+    fn is_set(&self) -> bool {
+        self.0.lock().is_ok_and(|slot| slot.is_some())
+    }
+}
+
+impl std::fmt::Debug for SharedMasterKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SharedMasterKey(..)")
+    }
+}
 
 /// 파일 관리 서비스
 /// C# FileManagerService를 완전히 포팅
@@ -21,14 +71,22 @@ pub struct FileService {
     vault_path: Option<PathBuf>,
     /// 암호화된 파일 저장 경로
     encrypted_files_path: Option<PathBuf>,
-    /// 마스터 키 (메모리 보안을 위해 Option으로 관리)
-    master_key: Option<[u8; 32]>,
-    /// 암호화 서비스
-    crypto_service: CryptoService,
+    /// 마스터 키 (복제본과 공유되며 잠금 시 한 번에 제거됨)
+    master_key: SharedMasterKey,
+    /// 최고 보안 등급 파일 키 (PIN 재확인과 최고 보안 암호 입력 후에만 설정됨)
+    critical_key: SharedMasterKey,
+    /// 데이터 키 교체 중 아직 다시 암호화되지 않은 파일을 여는 이전 마스터 키
+    previous_master_key: SharedMasterKey,
+    /// 데이터 키 교체 중 아직 다시 암호화되지 않은 최고 보안 파일을 여는 이전 키
+    previous_critical_key: SharedMasterKey,
+    /// 암호화 서비스 (마스터 키 없이 복호화 함수만 사용하므로 복제본 간에 공유)
+    crypto_service: Arc<CryptoService>,
     /// 데이터베이스 서비스
     database_service: DatabaseService,
     /// 압축 서비스
     compression_service: CompressionService,
+    /// 새로 암호화하는 파일에 사용할 알고리즘 (볼트 설정, 파일별 알고리즘은 컨테이너 헤더에 기록됨)
+    encryption_algorithm: EncryptionAlgorithm,
 }
 
 impl FileService {
@@ -40,16 +98,165 @@ impl FileService {
         Self {
             vault_path: None,
             encrypted_files_path: None,
-            master_key: None,
-            crypto_service: CryptoService::new(),
+            master_key: SharedMasterKey::default(),
+            critical_key: SharedMasterKey::default(),
+            previous_master_key: SharedMasterKey::default(),
+            previous_critical_key: SharedMasterKey::default(),
+            crypto_service: Arc::new(CryptoService::new()),
             database_service: DatabaseService::new(),
             compression_service: CompressionService::new_with_defaults(),
+            encryption_algorithm: EncryptionAlgorithm::default(),
+        }
+    }
+
+    /// 마스터 키에 대한 공유 참조를 반환합니다.
+    pub fn get_master_key(&self) -> Option<Arc<SecretKey>> {
+        self.master_key.get()
+    }
+
+    /// 마스터 키를 메모리에서 제거합니다.
+    ///
+    /// 이 서비스의 모든 복제본(업로드 스레드 등)에서도 함께 제거됩니다.
+    pub fn clear_master_key(&self) {
+        self.master_key.clear();
+        self.critical_key.clear();
+        self.previous_master_key.clear();
+        self.previous_critical_key.clear();
+    }
+
+    /// 최고 보안 등급 파일 키를 설정(Some)하거나 제거(None)합니다.
+    ///
+    /// 제거하면 데이터 키 교체 중의 이전 최고 보안 파일 키도 함께 제거됩니다.
+    ///
+    /// # 매개변수
+    /// * `key` - 최고 보안 파일 키
+    pub fn set_critical_key(&self, key: Option<Arc<SecretKey>>) {
+        match key {
+            Some(key) => self.critical_key.set(key),
+            None => {
+                self.critical_key.clear();
+                self.previous_critical_key.clear();
+            }
+        }
+    }
+
+    /// 데이터 키 교체 중 아직 다시 암호화되지 않은 파일을 열 이전 마스터 키를 설정(Some)하거나 제거(None)합니다.
+    ///
+    /// 이전 키는 읽기에만 쓰이며, 새로 암호화하는 파일은 항상 현재 키를 사용합니다.
+    ///
+    /// # 매개변수
+    /// * `key` - 이전 마스터 키
+    pub fn set_previous_master_key(&self, key: Option<Arc<SecretKey>>) {
+        match key {
+            Some(key) => self.previous_master_key.set(key),
+            None => self.previous_master_key.clear(),
+        }
+    }
+
+    /// 데이터 키 교체 중 아직 다시 암호화되지 않은 최고 보안 파일을 열 이전 키를 설정(Some)하거나 제거(None)합니다.
+    ///
+    /// # 매개변수
+    /// * `key` - 이전 최고 보안 파일 키
+    pub fn set_previous_critical_key(&self, key: Option<Arc<SecretKey>>) {
+        match key {
+            Some(key) => self.previous_critical_key.set(key),
+            None => self.previous_critical_key.clear(),
+        }
+    }
+
+    /// 데이터 키 교체 중이면 보안 등급에 맞는 이전 키를 반환합니다.
+    fn previous_key_for_level(&self, level: FileSecurityLevel) -> Option<Arc<SecretKey>> {
+        match level {
+            FileSecurityLevel::Critical => self.previous_critical_key.get(),
+            FileSecurityLevel::Normal | FileSecurityLevel::High => self.previous_master_key.get(),
+        }
+    }
+
+    /// 보안 등급에 맞는 파일 암호화 키를 반환합니다.
+    ///
+    /// `Critical` 파일은 별도의 최고 보안 파일 키로, 나머지는 마스터 키로 암호화됩니다.
+    ///
+    /// # 매개변수
+    /// * `level` - 파일 보안 등급
+    ///
+    /// # 반환값
+    /// * `Result<Arc<SecretKey>, VaultError>` - 파일 암호화 키
+    fn key_for_level(&self, level: FileSecurityLevel) -> Result<Arc<SecretKey>, VaultError> {
+        match level {
+            FileSecurityLevel::Critical => self
+                .critical_key
+                .get()
+                .ok_or(VaultError::CriticalKeyRequired),
+            FileSecurityLevel::Normal | FileSecurityLevel::High => {
+                self.master_key.get().ok_or(VaultError::NotInitialized)
+            }
+        }
+    }
+
+    /// 새 파일 데이터 키를 만들고 보안 등급 키로 래핑합니다.
+    ///
+    /// 파일은 각자의 무작위 256비트 키로 암호화되고, 래핑된 키는 파일 메타데이터
+    /// (`FileEntry::wrapped_key`)에 저장됩니다.
+    ///
+    /// # 매개변수
+    /// * `level` - 파일 보안 등급
+    /// * `file_id` - 파일 ID (래핑 인증 데이터에 포함됨)
+    ///
+    /// # 반환값
+    /// * `Result<(SecretKey, Vec<u8>), VaultError>` - (파일 데이터 키, 래핑된 파일 키)
+    pub fn generate_file_key(
+        &self,
+        level: FileSecurityLevel,
+        file_id: &Uuid,
+    ) -> Result<(SecretKey, Vec<u8>), VaultError> {
+        let level_key = self.key_for_level(level)?;
+        let file_key = CryptoService::generate_data_key();
+        let wrapped_key = CryptoService::wrap_file_key(&level_key, &file_key, file_id)?;
+
+        Ok((file_key, wrapped_key))
+    }
+
+    /// 파일을 복호화할 키를 반환합니다.
+    ///
+    /// 래핑된 파일 데이터 키가 있으면 보안 등급 키로 언래핑하고, 없는 이전 파일은
+    /// 보안 등급 키를 그대로 사용합니다. 데이터 키 교체 중이면 아직 다시 암호화되지 않은
+    /// 파일은 이전 키로 엽니다.
+    ///
+    /// # 매개변수
+    /// * `file_entry` - 파일 엔트리
+    ///
+    /// # 반환값
+    /// * `Result<Arc<SecretKey>, VaultError>` - 파일 암호화 키
+    fn file_key(&self, file_entry: &FileEntry) -> Result<Arc<SecretKey>, VaultError> {
+        let level_key = self.key_for_level(file_entry.security_level)?;
+        let previous_key = self.previous_key_for_level(file_entry.security_level);
+        match file_entry.wrapped_key.as_deref() {
+            Some(wrapped_key) => {
+                match CryptoService::unwrap_file_key(&level_key, wrapped_key, &file_entry.id) {
+                    Ok(file_key) => Ok(Arc::new(file_key)),
+                    Err(e) => match previous_key {
+                        Some(previous_key) => CryptoService::unwrap_file_key(
+                            &previous_key,
+                            wrapped_key,
+                            &file_entry.id,
+                        )
+                        .map(Arc::new),
+                        None => Err(e),
+                    },
+                }
+            }
+            // 파일 데이터 키가 없는 이전 파일은 교체 전의 마스터 키로 암호화되어 있음
+            None => Ok(previous_key.unwrap_or(level_key)),
         }
     }
 
-    /// 마스터 키의 복사본을 반환합니다.
-    pub fn get_master_key(&self) -> Option<[u8; 32]> {
-        self.master_key
+    /// 파일 ID로 메타데이터를 조회하여 파일을 복호화할 키를 반환합니다.
+    fn file_key_by_id(&self, file_id: &Uuid) -> Result<Arc<SecretKey>, VaultError> {
+        let file_entry = self
+            .database_service
+            .get_file(file_id)?
+            .ok_or_else(|| VaultError::DatabaseError("파일을 찾을 수 없습니다.".to_string()))?;
+        self.file_key(&file_entry)
     }
 
     /// 볼트 경로를 반환합니다.
@@ -68,7 +275,7 @@ impl FileService {
     pub async fn initialize(
         &mut self,
         vault_path: &str,
-        master_key: [u8; 32],
+        master_key: Arc<SecretKey>,
     ) -> Result<(), VaultError> {
         let vault_path_buf = PathBuf::from(vault_path);
 
@@ -92,12 +299,12 @@ impl FileService {
         }
 
         // 데이터베이스 서비스 초기화
-        self.database_service.initialize(vault_path)?;
+        self.open_database(vault_path, &master_key)?;
 
         // 상태 설정
         self.vault_path = Some(vault_path_buf);
         self.encrypted_files_path = Some(encrypted_files_path);
-        self.master_key = Some(master_key);
+        self.master_key.set(master_key);
 
         log::info!("파일 관리 서비스 초기화 완료: {}", vault_path);
         Ok(())
@@ -108,13 +315,18 @@ impl FileService {
     /// # 매개변수
     /// * `vault_path` - 볼트 경로
     /// * `master_key` - 마스터 키
-    pub fn set_vault_info(&mut self, vault_path: &str, master_key: [u8; 32]) {
-        log::info!(
-            "DEBUG: set_vault_info called. Setting Master Key0={:02X}",
-            master_key[0]
-        );
-        self.vault_path = Some(PathBuf::from(vault_path));
-        self.master_key = Some(master_key);
+    pub fn set_vault_info(&mut self, vault_path: &str, master_key: Arc<SecretKey>) {
+        let vault_path = PathBuf::from(vault_path);
+
+        // 다른 볼트(예: 위장 볼트)로 전환되면 캐시된 경로와 DB 연결을 버리고 지연 초기화
+        if self.vault_path.as_ref() != Some(&vault_path) {
+            self.encrypted_files_path = None;
+            self.database_service = DatabaseService::new();
+            self.encryption_algorithm = EncryptionAlgorithm::default();
+        }
+
+        self.vault_path = Some(vault_path);
+        self.master_key.set(master_key);
     }
 
     /// 서비스가 초기화되었는지 확인합니다.
@@ -122,12 +334,12 @@ impl FileService {
     /// # 반환값
     /// * `bool` - 초기화 여부
     pub fn is_initialized(&self) -> bool {
-        self.master_key.is_some() && self.vault_path.is_some()
+        self.master_key.is_set() && self.vault_path.is_some()
     }
 
     /// 서비스가 초기화되었는지 확인하고, 필요시 초기화를 수행합니다.
     fn ensure_initialized(&mut self) -> Result<(), VaultError> {
-        if self.master_key.is_none() || self.vault_path.is_none() {
+        if !self.master_key.is_set() || self.vault_path.is_none() {
             return Err(VaultError::NotInitialized);
         }
 
@@ -147,14 +359,93 @@ impl FileService {
         // Clone()된 FileService는 DB 연결이 끊어져 있으므로 반드시 확인해야 함
         if !self.database_service.is_initialized() {
             let vault_path = self.vault_path.as_ref().unwrap();
-            if let Some(vault_path_str) = vault_path.to_str() {
-                self.database_service.initialize(vault_path_str)?;
+            if let Some(vault_path_str) = vault_path.to_str().map(str::to_string) {
+                let master_key = self.master_key.get().ok_or(VaultError::NotInitialized)?;
+                self.open_database(&vault_path_str, &master_key)?;
             }
         }
 
         Ok(())
     }
 
+    /// 볼트 데이터 키에서 유도한 키로 메타데이터 데이터베이스를 엽니다.
+    ///
+    /// 데이터 키 교체 직후 DB 키를 바꾸기 전에 중단된 볼트는 이전 키로 열어 새 키로 교체합니다.
+    fn open_database(
+        &mut self,
+        vault_path: &str,
+        master_key: &SecretKey,
+    ) -> Result<(), VaultError> {
+        let db_key = CryptoService::derive_database_key(master_key);
+        let previous_db_key = self
+            .previous_master_key
+            .get()
+            .map(|previous_key| CryptoService::derive_database_key(&previous_key));
+        self.database_service
+            .initialize_or_rekey(vault_path, &db_key, previous_db_key.as_ref())?;
+
+        self.encryption_algorithm = self.database_service.get_encryption_algorithm()?;
+        Ok(())
+    }
+
+    /// 메타데이터 데이터베이스 연결을 닫습니다.
+    ///
+    /// 볼트를 잠글 때 호출하며, 다음 작업에서 다시 열려면 볼트 키가 필요합니다.
+    pub fn close_database(&mut self) {
+        self.database_service.close();
+    }
+
+    /// 메타데이터 데이터베이스를 새 볼트 데이터 키에서 유도한 키로 다시 암호화합니다.
+    ///
+    /// 볼트 데이터 키가 바뀌는 등 키 계층이 변경될 때, 새 키를 설정하기 전에 호출해야 합니다.
+    ///
+    /// # 매개변수
+    /// * `new_master_key` - 새 볼트 데이터 키
+    ///
+    /// # 반환값
+    /// * `Result<(), VaultError>` - 재암호화 결과
+    pub fn rekey_database(&mut self, new_master_key: &SecretKey) -> Result<(), VaultError> {
+        self.ensure_initialized()?;
+
+        let db_key = CryptoService::derive_database_key(new_master_key);
+        self.database_service.rekey(&db_key)
+    }
+
+    /// 새로 암호화하는 파일에 사용할 알고리즘을 반환합니다.
+    ///
+    /// # 반환값
+    /// * `&EncryptionAlgorithm` - 볼트의 암호화 알고리즘 설정
+    pub fn encryption_algorithm(&self) -> &EncryptionAlgorithm {
+        &self.encryption_algorithm
+    }
+
+    /// 볼트 설정에서 암호화 알고리즘을 읽어옵니다.
+    ///
+    /// `set_vault_info`로 볼트를 지정한 직후, 복제본(업로드 스레드 등)이 만들어지기 전에 호출합니다.
+    pub fn load_encryption_algorithm(&mut self) -> Result<(), VaultError> {
+        self.ensure_initialized()?;
+        self.encryption_algorithm = self.database_service.get_encryption_algorithm()?;
+        Ok(())
+    }
+
+    /// 볼트의 암호화 알고리즘 설정을 바꾸고 저장합니다.
+    ///
+    /// 이후에 암호화하는 파일부터 적용되며, 기존 파일은 컨테이너 헤더에 기록된
+    /// 알고리즘으로 계속 복호화됩니다.
+    ///
+    /// # 매개변수
+    /// * `algorithm` - 새 파일에 사용할 알고리즘
+    pub fn set_encryption_algorithm(
+        &mut self,
+        algorithm: EncryptionAlgorithm,
+    ) -> Result<(), VaultError> {
+        self.ensure_initialized()?;
+        self.database_service.set_encryption_algorithm(&algorithm)?;
+        log::info!("볼트 암호화 알고리즘이 {:?}로 변경되었습니다.", algorithm);
+        self.encryption_algorithm = algorithm;
+        Ok(())
+    }
+
     /// 파일을 볼트에 추가합니다.
     ///
     /// # 매개변수
@@ -239,20 +530,14 @@ impl FileService {
         log::info!("파일 추가 - 추출된 확장자: '{}'", file_extension);
         log::info!("파일 추가 - 볼트 파일명: '{}'", vault_file_name);
 
-        // 파일 암호화
-        let master_key = self.master_key.ok_or(VaultError::NotInitialized)?;
-
-        log::info!(
-            "DEBUG: Encrypting file with Master Key0={:02X}",
-            master_key[0]
-        );
-
-        let encrypted_data = self
-            .crypto_service
-            .encrypt_data_csharp_compatible(&file_data, &master_key)
-            .map_err(|e| {
-                VaultError::DatabaseError(format!("파일 암호화 중 오류가 발생했습니다: {}", e))
-            })?;
+        // 파일 데이터 키 생성 및 암호화
+        let (file_key, wrapped_key) =
+            self.generate_file_key(FileSecurityLevel::Normal, &file_id)?;
+        let encrypted_data = self.encrypt_for_storage(&file_data, &file_key, &file_id);
+        drop(file_key);
+        let encrypted_data = encrypted_data.map_err(|e| {
+            VaultError::DatabaseError(format!("파일 암호화 중 오류가 발생했습니다: {}", e))
+        })?;
 
         if encrypted_data.is_empty() {
             return Err(VaultError::DatabaseError(
@@ -291,6 +576,7 @@ impl FileService {
         // FileEntry::new는 랜덤 ID를 생성하므로, 우리가 생성한 file_id로 덮어씌워야 합니다.
         // 그렇지 않으면 media.rs에서 DB ID로 파일을 찾을 때 파일이 존재하지 않는 오류가 발생합니다.
         file_entry.id = file_id;
+        file_entry.wrapped_key = Some(wrapped_key);
 
         // 데이터베이스에 메타데이터 추가
         self.database_service.add_file(&file_entry)?;
@@ -400,19 +686,23 @@ impl FileService {
         );
 
         // 마스터 키 확인 디버그 로그
-        if let Some(key) = self.master_key {
-            log::info!("파일 추가 시 마스터 키: Key0={:02X}", key[0]);
-        } else {
+        if !self.master_key.is_set() {
             log::error!("파일 추가 시 마스터 키가 설정되지 않음!");
         }
 
         // 스트리밍 암호화 + 진행률 콜백 사용
+        let (file_key, wrapped_key) =
+            self.generate_file_key(FileSecurityLevel::Normal, &file_id)?;
         let encrypted_size = self.encrypt_file_streaming_with_progress(
             source_path,
             &encrypted_file_path,
+            &file_id,
+            &file_key,
             cancellation_token,
             &progress_callback,
-        )?;
+        );
+        drop(file_key);
+        let encrypted_size = encrypted_size?;
 
         // 해시 계산 (별도 패스 - 스트리밍 중에 처리하려면 더 복잡해짐)
         // 취소 체크
@@ -465,6 +755,7 @@ impl FileService {
         );
 
         file_entry.id = file_id;
+        file_entry.wrapped_key = Some(wrapped_key);
 
         // 데이터베이스에 메타데이터 추가
         self.database_service.add_file(&file_entry)?;
@@ -557,11 +848,8 @@ impl FileService {
             .map_err(|e| VaultError::DatabaseError(format!("암호화된 파일 읽기 실패: {}", e)))?;
 
         // 파일 복호화
-        let master_key = self.master_key.ok_or(VaultError::NotInitialized)?;
-        let decrypted_data = self
-            .crypto_service
-            .decrypt_data_csharp_compatible(&encrypted_data, &master_key)
-            .map_err(|e| VaultError::DatabaseError(format!("파일 복호화 실패: {}", e)))?;
+        let file_key = self.file_key(&file_entry)?;
+        let decrypted_data = self.decrypt_stored_data(&encrypted_data, &file_key, file_id)?;
 
         // 임시 파일 생성
         let temp_file_path = std::env::temp_dir().join(format!(
@@ -576,25 +864,53 @@ impl FileService {
         Ok(temp_file_path.to_string_lossy().to_string())
     }
 
-    /// 파일 데이터를 암호화합니다.
+    /// 파일 데이터를 새 파일 데이터 키로 암호화합니다.
     ///
     /// # 매개변수
     /// * `data` - 암호화할 데이터
+    /// * `file_id` - 파일 ID (컨테이너 헤더에 기록됨)
     ///
     /// # 반환값
-    /// * `Result<Vec<u8>, VaultError>` - 암호화된 데이터
-    pub fn encrypt_file_data(&self, data: &[u8]) -> Result<Vec<u8>, VaultError> {
-        let master_key = self.master_key.ok_or(VaultError::NotInitialized)?;
-        self.crypto_service
-            .encrypt_data_csharp_compatible(data, &master_key)
+    /// * `Result<(Vec<u8>, Vec<u8>), VaultError>` - (암호화된 데이터, `FileEntry::wrapped_key`에 저장할 래핑된 파일 키)
+    pub fn encrypt_file_data(
+        &self,
+        data: &[u8],
+        file_id: &Uuid,
+    ) -> Result<(Vec<u8>, Vec<u8>), VaultError> {
+        let (file_key, wrapped_key) = self.generate_file_key(FileSecurityLevel::Normal, file_id)?;
+        let encrypted_data = self.encrypt_for_storage(data, &file_key, file_id);
+        drop(file_key);
+
+        encrypted_data
+            .map(|encrypted_data| (encrypted_data, wrapped_key))
             .map_err(|e| VaultError::DatabaseError(format!("파일 암호화 실패: {}", e)))
     }
 
+    /// 데이터를 저장용 컨테이너 형식으로 암호화합니다.
+    ///
+    /// # 매개변수
+    /// * `data` - 평문 데이터
+    /// * `key` - 파일 암호화 키
+    /// * `file_id` - 파일 ID
+    ///
+    /// # 반환값
+    /// * `Result<Vec<u8>, VaultError>` - 컨테이너 바이트
+    fn encrypt_for_storage(
+        &self,
+        data: &[u8],
+        key: &SecretKey,
+        file_id: &Uuid,
+    ) -> Result<Vec<u8>, VaultError> {
+        container::encrypt_container(data, key, file_id, self.encryption_algorithm.clone())
+    }
+
     /// 파일을 병렬 스트리밍 방식으로 암호화합니다 (최고 성능).
     ///
     /// # 매개변수
     /// * `input_path` - 입력 파일 경로
     /// * `output_path` - 출력 파일 경로
+    /// * `file_id` - 파일 ID (컨테이너 헤더에 기록됨)
+    /// * `file_key` - 파일 데이터 키 (`generate_file_key`로 생성)
     ///
     /// # 반환값
     /// * `Result<u64, VaultError>` - 암호화된 파일 크기
@@ -602,13 +918,13 @@ impl FileService {
         &self,
         input_path: P,
         output_path: P,
+        file_id: &Uuid,
+        file_key: &SecretKey,
     ) -> Result<u64, VaultError> {
-        use std::io::{BufWriter, Write};
-        use std::sync::{Arc, Mutex};
+        use std::io::BufWriter;
+        use std::sync::Mutex;
         use std::thread;
 
-        let master_key = self.master_key.ok_or(VaultError::NotInitialized)?;
-
         // 파일 크기 확인
         let total_size = std::fs::metadata(&input_path)
             .map_err(|e| VaultError::DatabaseError(format!("파일 크기 확인 실패: {}", e)))?
@@ -617,7 +933,7 @@ impl FileService {
         // 작은 파일은 기존 방식 사용
         if total_size < 100 * 1024 * 1024 {
             // 100MB 미만
-            return self.encrypt_file_streaming(input_path, output_path);
+            return self.encrypt_file_streaming(input_path, output_path, file_id, file_key);
         }
 
         log::info!(
@@ -641,74 +957,61 @@ impl FileService {
         let input_data = std::fs::read(&input_path)
             .map_err(|e| VaultError::DatabaseError(format!("파일 읽기 실패: {}", e)))?;
 
-        // 병렬 암호화 처리
-        let encrypted_chunks = Arc::new(Mutex::new(Vec::with_capacity(num_chunks)));
-        let mut handles = Vec::new();
-
-        for chunk_idx in 0..num_chunks {
-            let start = chunk_idx * PARALLEL_CHUNK_SIZE;
-            let end = std::cmp::min(start + PARALLEL_CHUNK_SIZE, input_data.len());
-            let chunk_data = input_data[start..end].to_vec();
-
-            let crypto_service = self.crypto_service.clone();
-            let master_key_copy = master_key;
-            let encrypted_chunks_clone = Arc::clone(&encrypted_chunks);
-
-            let handle = thread::spawn(move || {
-                let encrypted_chunk =
-                    crypto_service.encrypt_data_csharp_compatible(&chunk_data, &master_key_copy)?;
-
-                let mut chunks = encrypted_chunks_clone.lock().unwrap();
-                chunks.push((chunk_idx, encrypted_chunk));
-
-                Ok::<(), VaultError>(())
-            });
-
-            handles.push(handle);
-        }
-
-        // 모든 스레드 완료 대기
-        for handle in handles {
-            handle
-                .join()
-                .map_err(|_| VaultError::DatabaseError("병렬 암호화 스레드 실패".to_string()))?
-                .map_err(|e| e)?;
-        }
-
-        // 결과 정렬 및 파일 쓰기
-        let mut encrypted_chunks = encrypted_chunks.lock().unwrap();
-        encrypted_chunks.sort_by_key(|(idx, _)| *idx);
-
+        // 모든 스레드가 같은 헤더를 인증 데이터로, 청크 번호를 논스로 사용하여 청크를 봉인
+        let header = ContainerHeader::new(
+            self.encryption_algorithm.clone(),
+            PARALLEL_CHUNK_SIZE as u32,
+            *file_id,
+            file_key,
+        );
         let output_file = std::fs::File::create(&output_path)
             .map_err(|e| VaultError::DatabaseError(format!("출력 파일 생성 실패: {}", e)))?;
-        let mut writer = BufWriter::new(output_file);
-
-        // 병렬 암호화 헤더 작성 (청크 수 정보)
-        let chunk_count = encrypted_chunks.len() as u32;
-        writer
-            .write_all(&chunk_count.to_le_bytes())
-            .map_err(|e| VaultError::DatabaseError(format!("헤더 쓰기 실패: {}", e)))?;
+        let mut writer = ContainerWriter::new(BufWriter::new(output_file), file_key, header)?;
+
+        // 병렬 암호화 처리 (청크 크기를 꽉 채운 청크만, 나머지는 마지막 청크로 따로 봉인)
+        // 스레드는 쓰기 도구의 암호화기를 빌려 쓰므로 키가 스레드마다 복사되지 않음
+        let full_chunks = input_data.len() / PARALLEL_CHUNK_SIZE;
+        let cipher = writer.cipher();
+        let encrypted_chunks = Mutex::new(Vec::with_capacity(full_chunks));
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..full_chunks)
+                .map(|chunk_idx| {
+                    let start = chunk_idx * PARALLEL_CHUNK_SIZE;
+                    let chunk_data = &input_data[start..start + PARALLEL_CHUNK_SIZE];
+                    let encrypted_chunks = &encrypted_chunks;
+
+                    scope.spawn(move || {
+                        let encrypted_chunk =
+                            cipher.seal_chunk(chunk_idx as u32, false, chunk_data)?;
+
+                        let mut chunks = encrypted_chunks.lock().unwrap();
+                        chunks.push((chunk_idx, encrypted_chunk));
+
+                        Ok::<(), VaultError>(())
+                    })
+                })
+                .collect();
+
+            // 모든 스레드 완료 대기
+            for handle in handles {
+                handle.join().map_err(|_| {
+                    VaultError::DatabaseError("병렬 암호화 스레드 실패".to_string())
+                })??;
+            }
+            Ok::<(), VaultError>(())
+        })?;
 
-        let mut total_encrypted_size = 4u64; // 헤더 크기
+        // 결과 정렬 및 파일 쓰기
+        let mut encrypted_chunks = encrypted_chunks.into_inner().unwrap();
+        encrypted_chunks.sort_by_key(|(idx, _)| *idx);
 
         for (_, encrypted_chunk) in encrypted_chunks.iter() {
-            // 청크 크기 저장 (4바이트)
-            let chunk_size = encrypted_chunk.len() as u32;
-            writer
-                .write_all(&chunk_size.to_le_bytes())
-                .map_err(|e| VaultError::DatabaseError(format!("청크 크기 쓰기 실패: {}", e)))?;
-
-            // 암호화된 청크 데이터 저장
-            writer.write_all(encrypted_chunk).map_err(|e| {
-                VaultError::DatabaseError(format!("암호화된 청크 쓰기 실패: {}", e))
-            })?;
-
-            total_encrypted_size += 4 + encrypted_chunk.len() as u64;
+            writer.write_sealed_chunk(encrypted_chunk)?;
         }
+        writer.write_data(&input_data[full_chunks * PARALLEL_CHUNK_SIZE..])?;
 
-        writer
-            .flush()
-            .map_err(|e| VaultError::DatabaseError(format!("파일 쓰기 완료 실패: {}", e)))?;
+        let (_, total_encrypted_size) = writer.finish()?;
 
         log::info!(
             "병렬 스트리밍 암호화 완료: {}MB -> {}MB ({}개 청크, {} 스레드)",
@@ -726,6 +1029,8 @@ impl FileService {
     /// # 매개변수
     /// * `input_path` - 입력 파일 경로
     /// * `output_path` - 출력 파일 경로
+    /// * `file_id` - 파일 ID (컨테이너 헤더에 기록됨)
+    /// * `file_key` - 파일 데이터 키 (`generate_file_key`로 생성)
     ///
     /// # 반환값
     /// * `Result<u64, VaultError>` - 암호화된 파일 크기
@@ -733,10 +1038,10 @@ impl FileService {
         &self,
         input_path: P,
         output_path: P,
+        file_id: &Uuid,
+        file_key: &SecretKey,
     ) -> Result<u64, VaultError> {
-        use std::io::{BufReader, BufWriter, Read, Write};
-
-        let master_key = self.master_key.ok_or(VaultError::NotInitialized)?;
+        use std::io::{BufReader, BufWriter, Read};
 
         // 파일 열기
         let input_file = std::fs::File::open(&input_path)
@@ -744,13 +1049,18 @@ impl FileService {
         let output_file = std::fs::File::create(&output_path)
             .map_err(|e| VaultError::DatabaseError(format!("출력 파일 생성 실패: {}", e)))?;
 
-        let mut reader = BufReader::new(input_file);
-        let mut writer = BufWriter::new(output_file);
-
-        let mut total_encrypted_size = 0u64;
-
         // 청크 단위로 암호화 (큰 파일용 10MB 청크로 성능 최적화)
         const CHUNK_SIZE: usize = 10 * 1024 * 1024; // 10MB 청크로 증가
+
+        let mut reader = BufReader::new(input_file);
+        let header = ContainerHeader::new(
+            self.encryption_algorithm.clone(),
+            CHUNK_SIZE as u32,
+            *file_id,
+            file_key,
+        );
+        let mut writer = ContainerWriter::new(BufWriter::new(output_file), file_key, header)?;
+
         let mut buffer = vec![0u8; CHUNK_SIZE];
         let mut chunk_counter = 0u64;
 
@@ -770,24 +1080,11 @@ impl FileService {
                 break; // EOF
             }
 
-            // 청크 암호화 (기존 암호화 서비스 사용)
-            let encrypted_chunk = self
-                .crypto_service
-                .encrypt_data_csharp_compatible(&buffer[..bytes_read], &master_key)
-                .map_err(|e| VaultError::DatabaseError(format!("청크 암호화 실패: {}", e)))?;
-
-            // 암호화된 청크 크기를 먼저 저장 (4바이트)
-            let chunk_size = encrypted_chunk.len() as u32;
+            // 청크 암호화 및 저장
             writer
-                .write_all(&chunk_size.to_le_bytes())
-                .map_err(|e| VaultError::DatabaseError(format!("청크 크기 쓰기 실패: {}", e)))?;
-
-            // 암호화된 청크 데이터 저장
-            writer.write_all(&encrypted_chunk).map_err(|e| {
-                VaultError::DatabaseError(format!("암호화된 청크 쓰기 실패: {}", e))
-            })?;
+                .write_data(&buffer[..bytes_read])
+                .map_err(|e| VaultError::DatabaseError(format!("청크 암호화 실패: {}", e)))?;
 
-            total_encrypted_size += 4 + encrypted_chunk.len() as u64;
             chunk_counter += 1;
 
             // 주기적으로 진행 상황 로그 (100MB마다 또는 5% 진행마다)
@@ -807,9 +1104,8 @@ impl FileService {
         }
 
         // 버퍼 플러시
-        writer
-            .flush()
-            .map_err(|e| VaultError::DatabaseError(format!("파일 쓰기 완료 실패: {}", e)))?;
+        let (_, total_encrypted_size) = writer.finish()?;
+        SecureMemory::clear_vec(&mut buffer);
 
         log::info!(
             "스트리밍 암호화 완료: {} 청크, {}MB -> {}MB",
@@ -825,6 +1121,8 @@ impl FileService {
     /// # 매개변수
     /// * `input_path` - 입력 파일 경로
     /// * `output_path` - 출력 파일 경로
+    /// * `file_id` - 파일 ID (컨테이너 헤더에 기록됨)
+    /// * `file_key` - 파일 데이터 키 (`generate_file_key`로 생성)
     /// * `cancellation_token` - 취소 토큰 (None이면 취소 불가)
     /// * `progress_callback` - 청크 처리 후 호출되는 콜백 (처리된 바이트, 전체 바이트)
     ///
@@ -834,6 +1132,8 @@ impl FileService {
         &self,
         input_path: P,
         output_path: P,
+        file_id: &Uuid,
+        file_key: &SecretKey,
         cancellation_token: Option<&crate::services::upload_manager::CancellationToken>,
         progress_callback: F,
     ) -> Result<u64, VaultError>
@@ -841,10 +1141,7 @@ impl FileService {
         P: AsRef<Path>,
         F: Fn(u64, u64), // (처리된 바이트, 전체 바이트)
     {
-        use std::io::{BufReader, BufWriter, Read, Write};
-
-        let master_key = self.master_key.ok_or(VaultError::NotInitialized)?;
-        log::info!("스트리밍 암호화: 마스터 키 (Key0={:02X})", master_key[0]);
+        use std::io::{BufReader, BufWriter, Read};
 
         // 파일 열기
         let input_file = std::fs::File::open(&input_path)
@@ -852,14 +1149,19 @@ impl FileService {
         let output_file = std::fs::File::create(&output_path)
             .map_err(|e| VaultError::DatabaseError(format!("출력 파일 생성 실패: {}", e)))?;
 
+        // 청크 단위로 암호화 (20MB 청크)
+        const CHUNK_SIZE: usize = 20 * 1024 * 1024;
+
         let mut reader = BufReader::new(input_file);
-        let mut writer = BufWriter::new(output_file);
+        let header = ContainerHeader::new(
+            self.encryption_algorithm.clone(),
+            CHUNK_SIZE as u32,
+            *file_id,
+            file_key,
+        );
+        let mut writer = ContainerWriter::new(BufWriter::new(output_file), file_key, header)?;
 
-        let mut total_encrypted_size = 0u64;
         let mut bytes_processed = 0u64;
-
-        // 청크 단위로 암호화 (20MB 청크)
-        const CHUNK_SIZE: usize = 20 * 1024 * 1024;
         let mut buffer = vec![0u8; CHUNK_SIZE];
         let mut chunk_counter = 0u64;
 
@@ -892,24 +1194,11 @@ impl FileService {
                 break; // EOF
             }
 
-            // 청크 암호화
-            let encrypted_chunk = self
-                .crypto_service
-                .encrypt_data_csharp_compatible(&buffer[..bytes_read], &master_key)
-                .map_err(|e| VaultError::DatabaseError(format!("청크 암호화 실패: {}", e)))?;
-
-            // 암호화된 청크 크기를 먼저 저장 (4바이트)
-            let chunk_size = encrypted_chunk.len() as u32;
+            // 청크 암호화 및 저장
             writer
-                .write_all(&chunk_size.to_le_bytes())
-                .map_err(|e| VaultError::DatabaseError(format!("청크 크기 쓰기 실패: {}", e)))?;
-
-            // 암호화된 청크 데이터 저장
-            writer.write_all(&encrypted_chunk).map_err(|e| {
-                VaultError::DatabaseError(format!("암호화된 청크 쓰기 실패: {}", e))
-            })?;
+                .write_data(&buffer[..bytes_read])
+                .map_err(|e| VaultError::DatabaseError(format!("청크 암호화 실패: {}", e)))?;
 
-            total_encrypted_size += 4 + encrypted_chunk.len() as u64;
             bytes_processed += bytes_read as u64;
             chunk_counter += 1;
 
@@ -929,9 +1218,8 @@ impl FileService {
         }
 
         // 버퍼 플러시
-        writer
-            .flush()
-            .map_err(|e| VaultError::DatabaseError(format!("파일 쓰기 완료 실패: {}", e)))?;
+        let (_, total_encrypted_size) = writer.finish()?;
+        SecureMemory::clear_vec(&mut buffer);
 
         // 최종 진행률 콜백
         progress_callback(total_size, total_size);
@@ -992,18 +1280,12 @@ impl FileService {
             .map_err(|e| VaultError::DatabaseError(format!("암호화된 파일 읽기 실패: {}", e)))?;
 
         // 파일 복호화
-        let master_key = self.master_key.ok_or(VaultError::NotInitialized)?;
-        log::info!(
-            "DEBUG: Export File Decryption - Master Key[0]: {:02X}",
-            master_key[0]
-        );
+        let file_key = self.file_key(&file_entry)?;
         let mut decrypted_data = self
-            .crypto_service
-            .decrypt_data_csharp_compatible(&encrypted_data, &master_key)
+            .decrypt_stored_data(&encrypted_data, &file_key, file_id)
             .map_err(|e| {
                 VaultError::DatabaseError(format!(
-                    "파일 복호화 실패 (Key[0]={:02X}, Len={}): {}",
-                    master_key[0],
+                    "파일 복호화 실패 (Len={}): {}",
                     encrypted_data.len(),
                     e
                 ))
@@ -1067,14 +1349,12 @@ impl FileService {
             Err(_) => return Ok(false),
         };
 
-        let master_key = self.master_key.ok_or(VaultError::NotInitialized)?;
-        let decrypted_data = match self
-            .crypto_service
-            .decrypt_data_csharp_compatible(&encrypted_data, &master_key)
-        {
-            Ok(data) => data.to_vec(),
-            Err(_) => return Ok(false),
-        };
+        let file_key = self.file_key(file_entry)?;
+        let decrypted_data =
+            match self.decrypt_stored_data(&encrypted_data, &file_key, &file_entry.id) {
+                Ok(data) => data,
+                Err(_) => return Ok(false),
+            };
 
         // 체크섬 계산 및 비교
         let current_checksum = calculate_file_hash(&decrypted_data);
@@ -1301,11 +1581,11 @@ impl FileService {
             VaultError::DatabaseError(format!("파일 ID '{}'를 찾을 수 없습니다.", file_id))
         })?;
 
-        // 새로운 파일 데이터 암호화
-        let master_key = self.master_key.ok_or(VaultError::NotInitialized)?;
-        let encrypted_data = self
-            .crypto_service
-            .encrypt_data_csharp_compatible(new_content, &master_key)
+        // 새로운 파일 데이터 암호화 (새 파일 데이터 키를 보안 등급에 맞는 키로 래핑)
+        let (file_key, wrapped_key) = self.generate_file_key(file_entry.security_level, file_id)?;
+        let encrypted_data = self.encrypt_for_storage(new_content, &file_key, file_id);
+        drop(file_key);
+        let encrypted_data = encrypted_data
             .map_err(|e| VaultError::DatabaseError(format!("파일 암호화 실패: {}", e)))?;
 
         // 암호화된 파일 저장 경로
@@ -1332,6 +1612,7 @@ impl FileService {
                 file_entry.encrypted_size = encrypted_data.len() as u64;
                 file_entry.modified_date = Utc::now();
                 file_entry.checksum = calculate_file_hash(new_content);
+                file_entry.wrapped_key = Some(wrapped_key);
 
                 // 데이터베이스 업데이트
                 match self.database_service.update_file(&file_entry) {
@@ -1453,87 +1734,579 @@ impl FileService {
         // 실제 파일이 존재하면 복호화하여 반환
         log::info!("암호화된 파일 읽기 성공: {} bytes", encrypted_data.len());
 
-        // 파일 복호화
-        let master_key = self.master_key.ok_or(VaultError::NotInitialized)?;
-
-        // 1. 청크 단위 복호화 시도 (업로드된 파일 형식)
-        let mut decrypted_buffer = Vec::new();
-        let mut cursor = 0;
-        let mut success = true;
-
-        while cursor < encrypted_data.len() {
-            // 헤더(길이) 읽기 (4바이트)
-            if cursor + 4 > encrypted_data.len() {
-                success = false;
-                break;
-            }
-
-            let mut len_bytes = [0u8; 4];
-            len_bytes.copy_from_slice(&encrypted_data[cursor..cursor + 4]);
-            let chunk_len = u32::from_le_bytes(len_bytes) as usize;
-            cursor += 4;
-
-            // 데이터 읽기
-            if cursor + chunk_len > encrypted_data.len() {
-                success = false;
-                break;
-            }
-
-            let chunk = &encrypted_data[cursor..cursor + chunk_len];
-            cursor += chunk_len;
+        // 파일 복호화 (보안 등급에 맞는 키로 파일 데이터 키를 언래핑)
+        let file_key = self.file_key(&file_entry)?;
+        self.decrypt_stored_data(&encrypted_data, &file_key, &uuid)
+    }
 
-            // 청크 복호화
-            match self
-                .crypto_service
-                .decrypt_data_csharp_compatible(chunk, &master_key)
-            {
-                Ok(data) => decrypted_buffer.extend_from_slice(&data),
-                Err(_) => {
-                    success = false;
-                    break;
-                }
-            }
+    /// 저장된 암호문을 복호화합니다.
+    ///
+    /// 컨테이너 형식이면 헤더에 기록된 알고리즘으로 복호화하고 파일 ID와 키 ID를 검증합니다.
+    /// 매직이 없는 이전 형식은 구조로 레이아웃을 판별한 뒤 그 레이아웃으로만 복호화합니다.
+    ///
+    /// # 매개변수
+    /// * `encrypted_data` - 암호화된 파일 내용
+    /// * `key` - 파일 암호화 키
+    /// * `file_id` - 파일 ID
+    ///
+    /// # 반환값
+    /// * `Result<Vec<u8>, VaultError>` - 복호화된 데이터
+    fn decrypt_stored_data(
+        &self,
+        encrypted_data: &[u8],
+        key: &SecretKey,
+        file_id: &Uuid,
+    ) -> Result<Vec<u8>, VaultError> {
+        if container::is_container(encrypted_data) {
+            return container::decrypt_container(encrypted_data, key, file_id)
+                .map_err(|e| VaultError::DatabaseError(format!("파일 복호화 실패: {}", e)));
         }
 
-        if success && !decrypted_buffer.is_empty() {
-            log::info!("청크 단위 복호화 성공: {} bytes", decrypted_buffer.len());
-            return Ok(decrypted_buffer);
-        }
+        self.decrypt_legacy_data(encrypted_data, key, file_id)
+    }
 
-        // 2. 실패 시 단일 블록 복호화 시도 (기존/수정된 파일 형식)
-        log::warn!(
-            "청크 복호화 실패, 단일 블록 복호화 시도 (Legacy/Single): {}",
-            file_id
+    /// 헤더 없는 이전 형식의 암호문을 복호화합니다.
+    ///
+    /// # 매개변수
+    /// * `encrypted_data` - 암호화된 파일 내용
+    /// * `key` - 파일 암호화 키
+    /// * `file_id` - 로그용 파일 ID
+    ///
+    /// # 반환값
+    /// * `Result<Vec<u8>, VaultError>` - 복호화된 데이터
+    fn decrypt_legacy_data(
+        &self,
+        encrypted_data: &[u8],
+        key: &SecretKey,
+        file_id: &Uuid,
+    ) -> Result<Vec<u8>, VaultError> {
+        let layout = container::detect_legacy_layout(encrypted_data);
+        log::info!(
+            "이전 형식 파일 복호화: {} ({}개 블록)",
+            file_id,
+            layout.chunk_count()
         );
 
-        let decrypted_data = self
-            .crypto_service
-            .decrypt_data_csharp_compatible(&encrypted_data, &master_key)
-            .map_err(|e| VaultError::DatabaseError(format!("파일 복호화 실패: {}", e)))?;
+        let decrypt_blob = |blob: &[u8]| {
+            self.crypto_service
+                .decrypt_data_csharp_compatible(blob, key.expose())
+                .map_err(|e| VaultError::DatabaseError(format!("파일 복호화 실패: {}", e)))
+        };
 
-        log::info!("파일 복호화 성공: {} bytes", decrypted_data.len());
-        Ok(decrypted_data)
+        match layout {
+            LegacyLayout::Single => decrypt_blob(encrypted_data).or_else(|e| {
+                // 이전 바이너리 파일 생성 커맨드는 파일별 유도 키로 암호화했음
+                let file_key = self.crypto_service.derive_file_key(key, file_id)?;
+                self.crypto_service
+                    .decrypt_data_csharp_compatible(encrypted_data, file_key.expose())
+                    .map_err(|_| e)
+            }),
+            LegacyLayout::Chunked(chunks) | LegacyLayout::CountedChunks(chunks) => {
+                let mut decrypted_buffer = Vec::with_capacity(encrypted_data.len());
+                for chunk in chunks {
+                    let mut data = decrypt_blob(chunk)?;
+                    decrypted_buffer.extend_from_slice(&data);
+                    SecureMemory::clear_vec(&mut data);
+                }
+                Ok(decrypted_buffer)
+            }
+        }
     }
 
-    /// 파일 내용을 업데이트합니다 (뷰어용)
+    /// 헤더 없는 이전 형식과 이전 버전 컨테이너 파일을 현재 컨테이너 형식으로 변환합니다.
     ///
-    /// # 매개변수
-    /// * `file_id` - 파일 ID (문자열)
-    /// * `content` - 새로운 파일 내용
+    /// 파일마다 임시 파일에 쓴 뒤 교체하므로 중간에 중단되어도 원본이 남습니다.
+    /// 최고 보안 파일은 최고 보안 키가 설정된 경우에만 변환하고, 그렇지 않으면 건너뜁니다.
     ///
     /// # 반환값
-    /// * `Result<(), VaultError>` - 업데이트 결과
-    pub fn update_file_content(
-        &mut self,
-        file_id: &str,
-        content: Vec<u8>,
-    ) -> Result<(), VaultError> {
+    /// * `Result<LegacyMigrationReport, VaultError>` - 변환 결과
+    pub fn migrate_legacy_files(&mut self) -> Result<LegacyMigrationReport, VaultError> {
         self.ensure_initialized()?;
 
-        // 문자열 ID를 UUID로 변환
-        let uuid = Uuid::parse_str(file_id)
-            .map_err(|_| VaultError::DatabaseError("잘못된 파일 ID 형식입니다.".to_string()))?;
-
+        let encrypted_files_path = self
+            .encrypted_files_path
+            .clone()
+            .ok_or(VaultError::NotInitialized)?;
+
+        let mut report = LegacyMigrationReport::default();
+
+        for mut file_entry in self.database_service.get_all_files()? {
+            let encrypted_file_path = encrypted_files_path.join(&file_entry.encrypted_file_name);
+            let encrypted_data = match fs::read(&encrypted_file_path) {
+                Ok(data) => data,
+                Err(e) => {
+                    log::warn!("이전 형식 변환 - 파일 읽기 실패: {} ({})", file_entry.id, e);
+                    report.failed.push(file_entry.id);
+                    continue;
+                }
+            };
+
+            if container::container_version(&encrypted_data) == Some(container::CONTAINER_VERSION) {
+                report.already_current += 1;
+                continue;
+            }
+
+            if self.key_for_level(file_entry.security_level).is_err() {
+                report.skipped.push(file_entry.id);
+                continue;
+            }
+
+            // 변환하면서 파일 데이터 키가 없는 파일에는 새 파일 데이터 키를 부여
+            let result = self
+                .file_key(&file_entry)
+                .and_then(|key| self.decrypt_stored_data(&encrypted_data, &key, &file_entry.id))
+                .and_then(|mut plain_data| {
+                    let (file_key, wrapped_key) =
+                        self.generate_file_key(file_entry.security_level, &file_entry.id)?;
+                    let encrypted =
+                        self.encrypt_for_storage(&plain_data, &file_key, &file_entry.id);
+                    plain_data.zeroize();
+                    Ok((encrypted?, wrapped_key))
+                })
+                .and_then(|(converted, wrapped_key)| {
+                    let temp_path = encrypted_file_path.with_extension("enc.migrate");
+                    fs::write(&temp_path, &converted)
+                        .and_then(|_| fs::rename(&temp_path, &encrypted_file_path))
+                        .map_err(|e| {
+                            let _ = fs::remove_file(&temp_path);
+                            VaultError::DatabaseError(format!("변환 파일 저장 실패: {}", e))
+                        })?;
+                    Ok((converted.len() as u64, wrapped_key))
+                });
+
+            match result {
+                Ok((encrypted_size, wrapped_key)) => {
+                    file_entry.encrypted_size = encrypted_size;
+                    file_entry.wrapped_key = Some(wrapped_key);
+                    if let Err(e) = self.database_service.update_file(&file_entry) {
+                        // 새 키가 기록되지 않았으므로 원본 암호문으로 되돌림
+                        let _ = fs::write(&encrypted_file_path, &encrypted_data);
+                        return Err(e);
+                    }
+                    report.migrated += 1;
+                }
+                Err(e) => {
+                    log::warn!("이전 형식 변환 실패: {} ({})", file_entry.id, e);
+                    report.failed.push(file_entry.id);
+                }
+            }
+        }
+
+        log::info!(
+            "이전 형식 파일 변환 완료: 변환 {}개, 최신 {}개, 건너뜀 {}개, 실패 {}개",
+            report.migrated,
+            report.already_current,
+            report.skipped.len(),
+            report.failed.len()
+        );
+        Ok(report)
+    }
+
+    /// 삭제되지 않은 모든 파일의 ID를 ID 순서로 반환합니다.
+    pub fn all_file_ids(&mut self) -> Result<Vec<Uuid>, VaultError> {
+        self.ensure_initialized()?;
+        Ok(self
+            .database_service
+            .get_all_files()?
+            .into_iter()
+            .map(|file_entry| file_entry.id)
+            .collect())
+    }
+
+    /// 파일을 새 파일 데이터 키와 지정한 알고리즘으로 임시 파일에 다시 암호화합니다.
+    ///
+    /// 원본 파일과 메타데이터는 바꾸지 않으므로 파일 서비스 잠금 없이 복제본에서 실행할 수 있으며,
+    /// 교체는 `commit_reencryption`으로 합니다.
+    ///
+    /// # 매개변수
+    /// * `file_id` - 파일 ID
+    /// * `algorithm` - 새 암호화 알고리즘
+    ///
+    /// # 반환값
+    /// * `Result<Option<PreparedReencryption>, VaultError>` - 준비된 교체 (파일이 삭제되었으면 None)
+    pub fn prepare_reencryption(
+        &mut self,
+        file_id: &Uuid,
+        algorithm: &EncryptionAlgorithm,
+    ) -> Result<Option<PreparedReencryption>, VaultError> {
+        self.ensure_initialized()?;
+
+        let Some(file_entry) = self.database_service.get_file(file_id)? else {
+            return Ok(None);
+        };
+
+        let encrypted_file_path = self
+            .encrypted_files_path
+            .as_ref()
+            .ok_or(VaultError::NotInitialized)?
+            .join(&file_entry.encrypted_file_name);
+        let temp_path = encrypted_file_path.with_extension("enc.reencrypt");
+
+        let old_key = self.file_key(&file_entry)?;
+        let (new_key, wrapped_key) = self.generate_file_key(file_entry.security_level, file_id)?;
+
+        let result = self.write_reencrypted(
+            &encrypted_file_path,
+            &temp_path,
+            &old_key,
+            &new_key,
+            file_id,
+            algorithm,
+        );
+        drop(old_key);
+        drop(new_key);
+
+        let encrypted_size = result.inspect_err(|_| {
+            let _ = fs::remove_file(&temp_path);
+        })?;
+
+        Ok(Some(PreparedReencryption {
+            file_id: *file_id,
+            previous_wrapped_key: file_entry.wrapped_key,
+            temp_path,
+            wrapped_key,
+            encrypted_size,
+        }))
+    }
+
+    /// 암호화된 파일을 복호화하면서 새 키의 컨테이너로 임시 파일에 기록합니다.
+    ///
+    /// 컨테이너 파일은 청크 단위로 처리하고, 이전 형식 파일은 전체를 복호화합니다.
+    ///
+    /// # 반환값
+    /// * `Result<u64, VaultError>` - 새 암호화된 파일 크기
+    fn write_reencrypted(
+        &self,
+        encrypted_file_path: &Path,
+        temp_path: &Path,
+        old_key: &SecretKey,
+        new_key: &SecretKey,
+        file_id: &Uuid,
+        algorithm: &EncryptionAlgorithm,
+    ) -> Result<u64, VaultError> {
+        use std::io::{BufRead, BufReader, BufWriter, Read};
+
+        let input = fs::File::open(encrypted_file_path)
+            .map_err(|e| VaultError::DatabaseError(format!("암호화된 파일 열기 실패: {}", e)))?;
+        let mut reader = BufReader::new(input);
+
+        let output = fs::File::create(temp_path)
+            .map_err(|e| VaultError::DatabaseError(format!("임시 파일 생성 실패: {}", e)))?;
+        let header = ContainerHeader::new(
+            algorithm.clone(),
+            container::DEFAULT_CHUNK_SIZE,
+            *file_id,
+            new_key,
+        );
+        let mut writer = ContainerWriter::new(BufWriter::new(output), new_key, header)?;
+
+        let is_container = container::is_container(reader.fill_buf().map_err(|e| {
+            VaultError::DatabaseError(format!("암호화된 파일 읽기 실패: {}", e))
+        })?);
+
+        if is_container {
+            let mut container_reader = ContainerReader::open(reader, old_key, file_id)?;
+            while let Some(mut chunk) = container_reader.next_chunk()? {
+                let written = writer.write_data(&chunk);
+                chunk.zeroize();
+                written?;
+            }
+        } else {
+            let mut encrypted_data = Vec::new();
+            reader.read_to_end(&mut encrypted_data).map_err(|e| {
+                VaultError::DatabaseError(format!("암호화된 파일 읽기 실패: {}", e))
+            })?;
+            let mut plain_data = self.decrypt_stored_data(&encrypted_data, old_key, file_id)?;
+            let written = writer.write_data(&plain_data);
+            plain_data.zeroize();
+            written?;
+        }
+
+        // 교체 전에 새 파일이 디스크에 기록되었는지 보장
+        let (writer, encrypted_size) = writer.finish()?;
+        writer
+            .into_inner()
+            .map_err(|e| VaultError::DatabaseError(format!("임시 파일 쓰기 실패: {}", e)))?
+            .sync_all()
+            .map_err(|e| VaultError::DatabaseError(format!("임시 파일 동기화 실패: {}", e)))?;
+
+        Ok(encrypted_size)
+    }
+
+    /// 준비된 재암호화 파일로 원본을 교체하고 체크포인트를 갱신합니다.
+    ///
+    /// 파일 서비스 잠금 아래에서 호출해야 합니다. 새 래핑 키를 체크포인트에 먼저 기록한 뒤
+    /// 임시 파일을 원본 위치로 옮기고 메타데이터를 갱신하므로, 어느 단계에서 중단되어도
+    /// `recover_reencryption`으로 일관된 상태로 되돌릴 수 있습니다. 준비 이후 파일이 수정되었으면
+    /// 이미 새 키로 다시 저장된 것이므로 준비한 파일을 버리고 처리한 것으로 봅니다.
+    ///
+    /// # 매개변수
+    /// * `prepared` - `prepare_reencryption`으로 준비한 파일
+    /// * `checkpoint` - 진행 중인 작업의 체크포인트
+    pub fn commit_reencryption(
+        &mut self,
+        prepared: PreparedReencryption,
+        checkpoint: &mut ReencryptionCheckpoint,
+    ) -> Result<(), VaultError> {
+        let discard = |prepared: &PreparedReencryption| {
+            let _ = fs::remove_file(&prepared.temp_path);
+        };
+
+        if let Err(e) = self.ensure_initialized() {
+            discard(&prepared);
+            return Err(e);
+        }
+
+        let file_entry = match self.database_service.get_file(&prepared.file_id) {
+            Ok(file_entry) => file_entry,
+            Err(e) => {
+                discard(&prepared);
+                return Err(e);
+            }
+        };
+
+        let mut file_entry = match file_entry {
+            Some(file_entry) if file_entry.wrapped_key == prepared.previous_wrapped_key => {
+                file_entry
+            }
+            _ => {
+                // 준비하는 동안 삭제되었거나 새 키로 다시 저장됨
+                discard(&prepared);
+                checkpoint.advance(prepared.file_id);
+                return self.save_reencryption_checkpoint(checkpoint);
+            }
+        };
+
+        let encrypted_file_path = self
+            .encrypted_files_path
+            .as_ref()
+            .ok_or(VaultError::NotInitialized)?
+            .join(&file_entry.encrypted_file_name);
+
+        checkpoint.pending = Some(PendingSwap {
+            file_id: prepared.file_id,
+            wrapped_key: prepared.wrapped_key.clone(),
+            encrypted_size: prepared.encrypted_size,
+        });
+        if let Err(e) = self.save_reencryption_checkpoint(checkpoint) {
+            checkpoint.pending = None;
+            discard(&prepared);
+            return Err(e);
+        }
+
+        if let Err(e) = fs::rename(&prepared.temp_path, &encrypted_file_path) {
+            discard(&prepared);
+            checkpoint.pending = None;
+            self.save_reencryption_checkpoint(checkpoint)?;
+            return Err(VaultError::DatabaseError(format!(
+                "재암호화 파일 교체 실패: {}",
+                e
+            )));
+        }
+
+        file_entry.wrapped_key = Some(prepared.wrapped_key);
+        file_entry.encrypted_size = prepared.encrypted_size;
+        self.database_service.update_file(&file_entry)?;
+
+        checkpoint.pending = None;
+        checkpoint.advance(prepared.file_id);
+        self.save_reencryption_checkpoint(checkpoint)
+    }
+
+    /// 중단된 재암호화 교체를 마무리합니다.
+    ///
+    /// 체크포인트에 진행 중인 교체가 있으면 디스크에 있는 컨테이너의 키 ID로 원본이 이미
+    /// 교체되었는지 확인합니다. 교체되었으면 메타데이터에 새 래핑 키를 기록하고, 아니면 남은 임시
+    /// 파일을 지워 그 파일부터 다시 진행하도록 합니다. 작업을 재개하기 전에 파일 서비스 잠금
+    /// 아래에서 호출해야 합니다.
+    ///
+    /// # 매개변수
+    /// * `checkpoint` - 재개할 작업의 체크포인트
+    pub fn recover_reencryption(
+        &mut self,
+        checkpoint: &mut ReencryptionCheckpoint,
+    ) -> Result<(), VaultError> {
+        self.ensure_initialized()?;
+
+        let Some(pending) = checkpoint.pending.clone() else {
+            return Ok(());
+        };
+
+        if let Some(mut file_entry) = self.database_service.get_file(&pending.file_id)? {
+            let encrypted_file_path = self
+                .encrypted_files_path
+                .as_ref()
+                .ok_or(VaultError::NotInitialized)?
+                .join(&file_entry.encrypted_file_name);
+
+            let level_key = self.key_for_level(file_entry.security_level)?;
+            let new_key =
+                CryptoService::unwrap_file_key(&level_key, &pending.wrapped_key, &pending.file_id)?;
+
+            let swapped = fs::File::open(&encrypted_file_path)
+                .ok()
+                .and_then(|mut file| ContainerHeader::read_from(&mut file).ok())
+                .is_some_and(|(header, _)| header.key_id == container::key_id(&new_key));
+
+            if swapped {
+                file_entry.wrapped_key = Some(pending.wrapped_key);
+                file_entry.encrypted_size = pending.encrypted_size;
+                self.database_service.update_file(&file_entry)?;
+                checkpoint.advance(pending.file_id);
+                log::info!("중단된 재암호화 교체를 마무리했습니다: {}", pending.file_id);
+            }
+
+            let _ = fs::remove_file(encrypted_file_path.with_extension("enc.reencrypt"));
+        }
+
+        checkpoint.pending = None;
+        self.save_reencryption_checkpoint(checkpoint)
+    }
+
+    /// 저장된 재암호화 작업 체크포인트를 불러옵니다.
+    ///
+    /// # 반환값
+    /// * `Result<Option<ReencryptionCheckpoint>, VaultError>` - 작업을 시작한 적이 없으면 None
+    pub fn load_reencryption_checkpoint(
+        &mut self,
+    ) -> Result<Option<ReencryptionCheckpoint>, VaultError> {
+        self.ensure_initialized()?;
+        self.database_service
+            .get_reencryption_checkpoint()?
+            .map(|json| serde_json::from_str(&json).map_err(VaultError::from))
+            .transpose()
+    }
+
+    /// 재암호화 작업 체크포인트를 저장합니다.
+    ///
+    /// 볼트가 잠겨 키가 지워진 뒤에도 이미 연결된 데이터베이스에는 저장할 수 있습니다.
+    ///
+    /// # 매개변수
+    /// * `checkpoint` - 저장할 체크포인트
+    pub fn save_reencryption_checkpoint(
+        &mut self,
+        checkpoint: &ReencryptionCheckpoint,
+    ) -> Result<(), VaultError> {
+        if !self.database_service.is_initialized() {
+            self.ensure_initialized()?;
+        }
+        let json = serde_json::to_string(checkpoint)?;
+        self.database_service.set_reencryption_checkpoint(&json)
+    }
+
+    /// 파일의 보안 등급을 변경하고 필요하면 파일 데이터 키를 새 등급의 키로 다시 래핑합니다.
+    ///
+    /// `Critical`로 올리거나 `Critical`에서 내리면 등급 키가 바뀌므로 파일 데이터 키만
+    /// 새 등급의 키로 다시 래핑합니다. 파일 데이터 키가 없는 이전 파일은 전체를 복호화한 뒤
+    /// 새 파일 데이터 키로 암호화하여 원자적으로 교체합니다. `Normal`과 `High` 사이의
+    /// 변경은 같은 키를 사용하므로 메타데이터만 갱신합니다.
+    ///
+    /// # 매개변수
+    /// * `file_id` - 파일 ID
+    /// * `level` - 새 보안 등급
+    ///
+    /// # 반환값
+    /// * `Result<FileEntry, VaultError>` - 갱신된 파일 엔트리
+    pub fn change_security_level(
+        &mut self,
+        file_id: &Uuid,
+        level: FileSecurityLevel,
+    ) -> Result<FileEntry, VaultError> {
+        self.ensure_initialized()?;
+
+        let mut file_entry = self
+            .database_service
+            .get_file(file_id)?
+            .ok_or_else(|| VaultError::DatabaseError("파일을 찾을 수 없습니다.".to_string()))?;
+
+        if file_entry.security_level == level {
+            return Ok(file_entry);
+        }
+
+        let old_key = self.key_for_level(file_entry.security_level)?;
+        let new_key = self.key_for_level(level)?;
+
+        let encrypted_file_path = self
+            .encrypted_files_path
+            .as_ref()
+            .ok_or(VaultError::NotInitialized)?
+            .join(&file_entry.encrypted_file_name);
+
+        // DB 갱신에 실패하면 되돌리기 위한 기존 암호문
+        let mut original_data = None;
+
+        if old_key != new_key {
+            // 파일 데이터 키가 있으면 다시 래핑만 하므로 암호화된 파일은 그대로 둠
+            if let Some(wrapped_key) = file_entry.wrapped_key.as_deref() {
+                let file_key = CryptoService::unwrap_file_key(&old_key, wrapped_key, file_id)?;
+                file_entry.wrapped_key =
+                    Some(CryptoService::wrap_file_key(&new_key, &file_key, file_id)?);
+            } else {
+                let encrypted_data = fs::read(&encrypted_file_path).map_err(|e| {
+                    VaultError::DatabaseError(format!("암호화된 파일 읽기 실패: {}", e))
+                })?;
+
+                let mut plain_data =
+                    self.decrypt_stored_data(&encrypted_data, &old_key, file_id)?;
+                let (file_key, wrapped_key) = self.generate_file_key(level, file_id)?;
+                let reencrypted = self
+                    .encrypt_for_storage(&plain_data, &file_key, file_id)
+                    .map_err(|e| VaultError::DatabaseError(format!("파일 암호화 실패: {}", e)));
+                plain_data.zeroize();
+                drop(file_key);
+                let reencrypted = reencrypted?;
+
+                // 임시 파일에 쓴 뒤 교체하여 중간에 실패해도 원본이 남도록 함
+                let temp_path = encrypted_file_path.with_extension("enc.rekey");
+                fs::write(&temp_path, &reencrypted)
+                    .and_then(|_| fs::rename(&temp_path, &encrypted_file_path))
+                    .map_err(|e| {
+                        let _ = fs::remove_file(&temp_path);
+                        VaultError::DatabaseError(format!("재암호화 파일 저장 실패: {}", e))
+                    })?;
+
+                file_entry.encrypted_size = reencrypted.len() as u64;
+                file_entry.wrapped_key = Some(wrapped_key);
+                original_data = Some(encrypted_data);
+            }
+        }
+
+        file_entry.security_level = level;
+        file_entry.modified_date = Utc::now();
+        if let Err(e) = self.database_service.update_file(&file_entry) {
+            if let Some(original_data) = original_data {
+                let _ = fs::write(&encrypted_file_path, &original_data);
+            }
+            return Err(e);
+        }
+
+        log::info!(
+            "파일 보안 등급 변경: {} -> {}",
+            file_id,
+            level.description()
+        );
+        Ok(file_entry)
+    }
+
+    /// 파일 내용을 업데이트합니다 (뷰어용)
+    ///
+    /// # 매개변수
+    /// * `file_id` - 파일 ID (문자열)
+    /// * `content` - 새로운 파일 내용
+    ///
+    /// # 반환값
+    /// * `Result<(), VaultError>` - 업데이트 결과
+    pub fn update_file_content(
+        &mut self,
+        file_id: &str,
+        content: Vec<u8>,
+    ) -> Result<(), VaultError> {
+        self.ensure_initialized()?;
+
+        // 문자열 ID를 UUID로 변환
+        let uuid = Uuid::parse_str(file_id)
+            .map_err(|_| VaultError::DatabaseError("잘못된 파일 ID 형식입니다.".to_string()))?;
+
         log::info!(
             "파일 내용 업데이트 시작: file_id={}, content_size={}",
             file_id,
@@ -1699,16 +2472,18 @@ impl FileService {
         Ok(())
     }
 
-    /// 스트리밍 방식으로 암호화된 파일을 복호화합니다 (전체 파일 한번에 처리 - 레거시).
+    /// 스트리밍 방식으로 암호화된 파일을 복호화합니다 (전체 파일 한번에 처리).
     ///
     /// # 매개변수
     /// * `encrypted_file_path` - 암호화된 파일 경로
+    /// * `file_id` - 파일 ID
     ///
     /// # 반환값
     /// * `Result<Vec<u8>, VaultError>` - 복호화된 데이터
     pub async fn decrypt_file_streaming<P: AsRef<Path>>(
         &self,
         encrypted_file_path: P,
+        file_id: &Uuid,
     ) -> Result<Vec<u8>, VaultError> {
         let encrypted_file_path = encrypted_file_path.as_ref();
 
@@ -1723,26 +2498,25 @@ impl FileService {
             .map_err(|e| VaultError::DatabaseError(format!("암호화된 파일 읽기 실패: {}", e)))?;
 
         // 파일 복호화
-        let master_key = self.master_key.ok_or(VaultError::NotInitialized)?;
-        let decrypted_data = self
-            .crypto_service
-            .decrypt_data_csharp_compatible(&encrypted_data, &master_key)
-            .map_err(|e| VaultError::DatabaseError(format!("파일 복호화 실패: {}", e)))?;
-
-        Ok(decrypted_data)
+        let file_key = self.file_key_by_id(file_id)?;
+        self.decrypt_stored_data(&encrypted_data, &file_key, file_id)
     }
 
     /// 청크 기반 스트리밍 방식으로 암호화된 파일을 복호화합니다.
-    /// 암호화 형식: [4바이트 청크 크기][암호화된 청크 데이터]...
+    ///
+    /// 컨테이너 형식은 청크 단위로 읽으며 복호화하고, 헤더가 없는 이전 형식은
+    /// `[4바이트 청크 크기][암호화된 청크 데이터]...` 레이아웃으로 읽습니다.
     ///
     /// # 매개변수
     /// * `encrypted_file_path` - 암호화된 파일 경로
+    /// * `file_id` - 파일 ID
     ///
     /// # 반환값
     /// * `Result<Vec<u8>, VaultError>` - 복호화된 데이터
     pub fn decrypt_file_streaming_chunked<P: AsRef<Path>>(
         &self,
         encrypted_file_path: P,
+        file_id: &Uuid,
     ) -> Result<Vec<u8>, VaultError> {
         use std::io::{BufReader, Read};
 
@@ -1755,8 +2529,7 @@ impl FileService {
             ));
         }
 
-        let master_key = self.master_key.ok_or(VaultError::NotInitialized)?;
-        log::info!("복호화 마스터 키: Key0={:02X}", master_key[0]);
+        let file_key = self.file_key_by_id(file_id)?;
 
         let file = fs::File::open(encrypted_file_path)
             .map_err(|e| VaultError::DatabaseError(format!("암호화된 파일 열기 실패: {}", e)))?;
@@ -1765,6 +2538,32 @@ impl FileService {
         let mut decrypted_data = Vec::new();
         let mut chunk_count = 0u64;
 
+        // 매직으로 형식을 판별 (컨테이너는 헤더의 알고리즘과 청크 크기를 따름)
+        let is_container = {
+            use std::io::BufRead;
+            let prefix = reader.fill_buf().map_err(|e| {
+                VaultError::DatabaseError(format!("암호화된 파일 읽기 실패: {}", e))
+            })?;
+            container::is_container(prefix)
+        };
+
+        if is_container {
+            let mut container_reader = ContainerReader::open(reader, &file_key, file_id)?;
+            while let Some(chunk) = container_reader.next_chunk().map_err(|e| {
+                VaultError::DatabaseError(format!("청크 {} 복호화 실패: {}", chunk_count, e))
+            })? {
+                decrypted_data.extend_from_slice(&chunk);
+                chunk_count += 1;
+            }
+
+            log::info!(
+                "청크 스트리밍 복호화 완료: {} 청크, {} bytes",
+                chunk_count,
+                decrypted_data.len()
+            );
+            return Ok(decrypted_data);
+        }
+
         loop {
             // 청크 크기 읽기 (4바이트, little-endian)
             let mut size_buf = [0u8; 4];
@@ -1800,7 +2599,7 @@ impl FileService {
             // 청크 복호화
             let decrypted_chunk = self
                 .crypto_service
-                .decrypt_data_csharp_compatible(&encrypted_chunk, &master_key)
+                .decrypt_data_csharp_compatible(&encrypted_chunk, file_key.expose())
                 .map_err(|e| {
                     VaultError::DatabaseError(format!("청크 {} 복호화 실패: {}", chunk_count, e))
                 })?;
@@ -1830,6 +2629,10 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    fn test_key(byte: u8) -> Arc<SecretKey> {
+        Arc::new(SecretKey::from_bytes(&mut [byte; 32]))
+    }
+
     #[tokio::test]
     async fn test_file_service_initialization() {
         // 임시 디렉토리 생성
@@ -1838,7 +2641,7 @@ mod tests {
 
         // 파일 서비스 생성 및 초기화
         let mut file_service = FileService::new();
-        let master_key = [0u8; 32]; // 테스트용 키
+        let master_key = test_key(0); // 테스트용 키
 
         let result = file_service.initialize(vault_path, master_key).await;
         assert!(result.is_ok());
@@ -1852,7 +2655,7 @@ mod tests {
 
         // 파일 서비스 초기화
         let mut file_service = FileService::new();
-        let master_key = [0u8; 32]; // 테스트용 키
+        let master_key = test_key(0); // 테스트용 키
         file_service
             .initialize(vault_path, master_key)
             .await
@@ -1878,7 +2681,7 @@ mod tests {
 
         // 파일 서비스 초기화
         let mut file_service = FileService::new();
-        let master_key = [0u8; 32]; // 테스트용 키
+        let master_key = test_key(0); // 테스트용 키
         file_service
             .initialize(vault_path, master_key)
             .await
@@ -1905,4 +2708,297 @@ mod tests {
         let files_after_delete = file_service.get_files_by_folder(None).await.unwrap();
         assert_eq!(files_after_delete.len(), 0);
     }
+
+    #[test]
+    fn test_clear_master_key_wipes_clones() {
+        let mut file_service = FileService::new();
+        file_service.set_vault_info(".", test_key(7));
+
+        // 업로드 스레드처럼 복제본을 만든 뒤 원본에서 키를 제거
+        let upload_copy = file_service.clone();
+        assert_eq!(upload_copy.get_master_key(), Some(test_key(7)));
+
+        file_service.clear_master_key();
+        assert!(upload_copy.get_master_key().is_none());
+        assert!(!upload_copy.is_initialized());
+    }
+
+    #[tokio::test]
+    async fn test_critical_level_reencrypts_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().to_str().unwrap();
+
+        let mut file_service = FileService::new();
+        file_service.initialize(vault_path, test_key(3)).await.unwrap();
+        let file_entry = file_service
+            .create_new_file(None, "secret.txt", "최고 보안 내용")
+            .await
+            .unwrap();
+        let file_id = file_entry.id.to_string();
+
+        // 최고 보안 파일 키 없이는 등급을 올릴 수 없음
+        assert!(matches!(
+            file_service.change_security_level(&file_entry.id, FileSecurityLevel::Critical),
+            Err(VaultError::CriticalKeyRequired)
+        ));
+
+        file_service.set_critical_key(Some(test_key(9)));
+        file_service
+            .change_security_level(&file_entry.id, FileSecurityLevel::Critical)
+            .unwrap();
+        assert_eq!(
+            file_service.get_file_content(&file_id).unwrap(),
+            "최고 보안 내용".as_bytes()
+        );
+
+        // 마스터 키로는 더 이상 복호화되지 않음
+        let encrypted_path = temp_dir
+            .path()
+            .join(".securevault")
+            .join("files")
+            .join(&file_entry.encrypted_file_name);
+        let encrypted = fs::read(&encrypted_path).unwrap();
+        assert!(file_service
+            .decrypt_stored_data(&encrypted, &test_key(3), &file_entry.id)
+            .is_err());
+
+        // 파일 데이터 키는 최고 보안 키로 다시 래핑됨
+        let wrapped_key = file_service
+            .database_service
+            .get_file(&file_entry.id)
+            .unwrap()
+            .unwrap()
+            .wrapped_key
+            .unwrap();
+        assert!(
+            CryptoService::unwrap_file_key(&test_key(3), &wrapped_key, &file_entry.id).is_err()
+        );
+        assert!(CryptoService::unwrap_file_key(&test_key(9), &wrapped_key, &file_entry.id).is_ok());
+
+        // 키가 제거되면 읽을 수 없고, 다시 내리면 마스터 키로 복호화됨
+        file_service.set_critical_key(None);
+        assert!(matches!(
+            file_service.get_file_content(&file_id),
+            Err(VaultError::CriticalKeyRequired)
+        ));
+        file_service.set_critical_key(Some(test_key(9)));
+        file_service
+            .change_security_level(&file_entry.id, FileSecurityLevel::Normal)
+            .unwrap();
+        file_service.set_critical_key(None);
+        assert_eq!(
+            file_service.get_file_content(&file_id).unwrap(),
+            "최고 보안 내용".as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_each_file_has_own_wrapped_key() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().to_str().unwrap();
+
+        let mut file_service = FileService::new();
+        file_service.initialize(vault_path, test_key(5)).await.unwrap();
+        let first = file_service
+            .create_new_file(None, "a.txt", "같은 내용")
+            .await
+            .unwrap();
+        let second = file_service
+            .create_new_file(None, "b.txt", "같은 내용")
+            .await
+            .unwrap();
+
+        let first_key = file_service.file_key(&first).unwrap();
+        let second_key = file_service.file_key(&second).unwrap();
+        assert_ne!(first_key, test_key(5));
+        assert_ne!(first_key, second_key);
+
+        // 래핑된 키는 파일 ID에 묶여 있어 다른 파일로 옮기면 언래핑에 실패
+        let mut swapped = second.clone();
+        swapped.wrapped_key = first.wrapped_key.clone();
+        assert!(file_service.file_key(&swapped).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_swapped_encrypted_files_fail() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().to_str().unwrap();
+
+        let mut file_service = FileService::new();
+        file_service.initialize(vault_path, test_key(5)).await.unwrap();
+        let first = file_service
+            .create_new_file(None, "a.txt", "첫 번째")
+            .await
+            .unwrap();
+        let second = file_service
+            .create_new_file(None, "b.txt", "두 번째")
+            .await
+            .unwrap();
+
+        // USB에서 두 `.enc` 파일을 맞바꿈
+        let files_dir = temp_dir.path().join(".securevault").join("files");
+        let first_path = files_dir.join(&first.encrypted_file_name);
+        let second_path = files_dir.join(&second.encrypted_file_name);
+        let first_data = fs::read(&first_path).unwrap();
+        fs::copy(&second_path, &first_path).unwrap();
+        fs::write(&second_path, &first_data).unwrap();
+
+        assert!(file_service.get_file_content(&first.id.to_string()).is_err());
+        assert!(file_service.get_file_content(&second.id.to_string()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_encryption_algorithms_coexist() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().to_str().unwrap();
+
+        let mut file_service = FileService::new();
+        file_service.initialize(vault_path, test_key(5)).await.unwrap();
+        let aes_file = file_service
+            .create_new_file(None, "aes.txt", "AES 내용")
+            .await
+            .unwrap();
+
+        file_service
+            .set_encryption_algorithm(EncryptionAlgorithm::ChaCha20Poly1305)
+            .unwrap();
+        let chacha_file = file_service
+            .create_new_file(None, "chacha.txt", "ChaCha 내용")
+            .await
+            .unwrap();
+
+        // 파일마다 사용한 알고리즘이 컨테이너 헤더에 기록됨
+        let files_dir = temp_dir.path().join(".securevault").join("files");
+        let header_of = |entry: &FileEntry| {
+            let data = fs::read(files_dir.join(&entry.encrypted_file_name)).unwrap();
+            ContainerHeader::parse(&data[..container::CONTAINER_HEADER_LEN]).unwrap()
+        };
+        assert_eq!(header_of(&aes_file).algorithm, EncryptionAlgorithm::AES256GCM);
+        assert_eq!(
+            header_of(&chacha_file).algorithm,
+            EncryptionAlgorithm::ChaCha20Poly1305
+        );
+
+        // 설정은 볼트에 저장되고 두 파일 모두 복호화됨
+        let mut reopened = FileService::new();
+        reopened.initialize(vault_path, test_key(5)).await.unwrap();
+        assert_eq!(
+            reopened.encryption_algorithm(),
+            &EncryptionAlgorithm::ChaCha20Poly1305
+        );
+        assert_eq!(
+            reopened.get_file_content(&aes_file.id.to_string()).unwrap(),
+            "AES 내용".as_bytes()
+        );
+        assert_eq!(
+            reopened.get_file_content(&chacha_file.id.to_string()).unwrap(),
+            "ChaCha 내용".as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_file_data_uses_vault_algorithm() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().to_str().unwrap();
+
+        let mut file_service = FileService::new();
+        file_service
+            .initialize(vault_path, test_key(6))
+            .await
+            .unwrap();
+        file_service
+            .set_encryption_algorithm(EncryptionAlgorithm::ChaCha20Poly1305)
+            .unwrap();
+
+        // 커맨드의 새 파일 생성 경로(텍스트·바이너리)가 사용하는 암호화
+        let file_id = Uuid::new_v4();
+        let (encrypted, wrapped_key) = file_service
+            .encrypt_file_data("ChaCha 새 파일".as_bytes(), &file_id)
+            .unwrap();
+
+        let header = ContainerHeader::parse(&encrypted[..container::CONTAINER_HEADER_LEN]).unwrap();
+        assert_eq!(header.algorithm, EncryptionAlgorithm::ChaCha20Poly1305);
+
+        let file_key =
+            CryptoService::unwrap_file_key(&test_key(6), &wrapped_key, &file_id).unwrap();
+        assert_eq!(
+            container::decrypt_container(&encrypted, &file_key, &file_id).unwrap(),
+            "ChaCha 새 파일".as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_legacy_file_is_read_and_migrated() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().to_str().unwrap();
+
+        let mut file_service = FileService::new();
+        file_service
+            .initialize(vault_path, test_key(4))
+            .await
+            .unwrap();
+        let file_entry = file_service
+            .create_new_file(None, "legacy.txt", "placeholder")
+            .await
+            .unwrap();
+        let other_entry = file_service
+            .create_new_file(None, "other.txt", "다른 파일")
+            .await
+            .unwrap();
+        let files_dir = temp_dir.path().join(".securevault").join("files");
+        let encrypted_path = files_dir.join(&file_entry.encrypted_file_name);
+
+        // 새로 만든 파일은 컨테이너 형식
+        assert!(container::is_container(&fs::read(&encrypted_path).unwrap()));
+
+        // 이전 업로드 형식([4바이트 길이][블록]...)으로 덮어씀
+        let mut legacy = Vec::new();
+        for part in ["이전 ", "형식 내용"] {
+            let blob = file_service
+                .crypto_service
+                .encrypt_data_csharp_compatible(part.as_bytes(), &[4u8; 32])
+                .unwrap();
+            legacy.extend_from_slice(&(blob.len() as u32).to_le_bytes());
+            legacy.extend_from_slice(&blob);
+        }
+        fs::write(&encrypted_path, &legacy).unwrap();
+
+        // 이전 파일에는 파일 데이터 키가 없음
+        let mut legacy_entry = file_entry.clone();
+        legacy_entry.wrapped_key = None;
+        file_service
+            .database_service
+            .update_file(&legacy_entry)
+            .unwrap();
+
+        let file_id = file_entry.id.to_string();
+        assert_eq!(
+            file_service.get_file_content(&file_id).unwrap(),
+            "이전 형식 내용".as_bytes()
+        );
+
+        let report = file_service.migrate_legacy_files().unwrap();
+        assert_eq!(report.migrated, 1);
+        assert_eq!(report.already_current, 1);
+        assert!(container::is_container(&fs::read(&encrypted_path).unwrap()));
+        assert!(file_service
+            .database_service
+            .get_file(&file_entry.id)
+            .unwrap()
+            .unwrap()
+            .wrapped_key
+            .is_some());
+        assert_eq!(
+            file_service.get_file_content(&file_id).unwrap(),
+            "이전 형식 내용".as_bytes()
+        );
+
+        // 다른 파일의 암호문으로 바꿔치기하면 파일 ID 검증에서 실패
+        fs::copy(
+            files_dir.join(&other_entry.encrypted_file_name),
+            &encrypted_path,
+        )
+        .unwrap();
+        assert!(file_service.get_file_content(&file_id).is_err());
+    }
 }
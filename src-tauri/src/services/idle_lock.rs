@@ -0,0 +1,127 @@
+// 유휴 잠금 서비스
+// 마지막 사용자 활동 시각을 추적하여, 설정된 시간 동안 활동이 없으면 볼트를 잠글 수 있게 합니다.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 볼트가 잠겼을 때 프론트엔드로 보내는 이벤트 이름
+pub const VAULT_LOCKED_EVENT: &str = "vault-locked";
+
+/// 유휴 상태 확인 주기
+pub const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 사용자 활동으로 보지 않는 커맨드 (프론트엔드 상태 폴링용)
+///
+/// 이 커맨드들이 활동으로 집계되면 화면을 켜 두기만 해도 잠기지 않습니다.
+const PASSIVE_COMMANDS: &[&str] = &[
+    "check_auth_status",
+    "get_session_remaining_time",
+    "get_security_status",
+    "check_network_access",
+    "get_upload_status",
+    "get_all_uploads",
+    "has_master_key",
+    "has_pin_set",
+    "has_recovery_key_set",
+    "has_key_file",
+    "get_pin_requirement",
+];
+
+/// 마지막 사용자 활동 시각 (UNIX 초, 0이면 기록 없음)
+static LAST_ACTIVITY: AtomicU64 = AtomicU64::new(0);
+
+/// 볼트 잠금 사유
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockReason {
+    /// 자동 잠금 시간 동안 활동 없음
+    Idle,
+    /// 인증 세션 만료
+    SessionExpired,
+}
+
+/// 잠금 이벤트 데이터
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultLockedPayload {
+    /// 잠금 사유
+    pub reason: LockReason,
+}
+
+fn now_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// 현재 시각을 마지막 사용자 활동으로 기록합니다.
+pub fn record_activity() {
+    LAST_ACTIVITY.store(now_seconds(), Ordering::Relaxed);
+}
+
+/// 커맨드 호출을 사용자 활동으로 기록합니다. 상태 폴링 커맨드는 무시합니다.
+///
+/// # 매개변수
+/// * `command` - 호출된 커맨드 이름
+///
+/// # 반환값
+/// * `bool` - 활동으로 기록되었는지 여부
+pub fn record_command(command: &str) -> bool {
+    if is_passive_command(command) {
+        return false;
+    }
+
+    record_activity();
+    true
+}
+
+/// 상태 폴링용 커맨드인지 확인합니다.
+pub fn is_passive_command(command: &str) -> bool {
+    PASSIVE_COMMANDS.contains(&command)
+}
+
+/// 마지막 활동 이후 지난 시간(초)을 반환합니다.
+pub fn idle_seconds() -> u64 {
+    idle_seconds_since(LAST_ACTIVITY.load(Ordering::Relaxed), now_seconds())
+}
+
+/// 마지막 활동 시각과 현재 시각으로 유휴 시간을 계산합니다.
+/// 기록이 없으면 유휴 시간은 0입니다.
+fn idle_seconds_since(last_activity: u64, now: u64) -> u64 {
+    if last_activity == 0 {
+        return 0;
+    }
+
+    now.saturating_sub(last_activity)
+}
+
+/// 자동 잠금 시간이 지났는지 확인합니다.
+///
+/// # 매개변수
+/// * `auto_lock_seconds` - 자동 잠금 시간 (초, 0이면 자동 잠금 없음)
+pub fn is_idle(auto_lock_seconds: u64) -> bool {
+    auto_lock_seconds > 0 && idle_seconds() >= auto_lock_seconds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polling_commands_are_not_activity() {
+        assert!(is_passive_command("get_session_remaining_time"));
+        assert!(is_passive_command("check_auth_status"));
+        assert!(!is_passive_command("get_files_in_folder"));
+        assert!(!record_command("get_all_uploads"));
+    }
+
+    #[test]
+    fn test_idle_seconds_since() {
+        assert_eq!(idle_seconds_since(0, 1_000), 0);
+        assert_eq!(idle_seconds_since(900, 1_000), 100);
+        // 시계가 뒤로 간 경우에도 음수가 되지 않음
+        assert_eq!(idle_seconds_since(1_100, 1_000), 0);
+        assert!(!is_idle(0));
+    }
+}
@@ -0,0 +1,745 @@
+// 로그 싱크 서비스
+// 키, 경로, 파일명을 가린 로그를 볼트 키로 암호화하여 `.securevault/logs`에 크기 기준으로 순환 저장합니다.
+
+use crate::models::{SecretKey, VaultError};
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use log::{LevelFilter, Log, Metadata, Record};
+use rand::{rngs::OsRng, RngCore};
+use regex::Regex;
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// 현재 기록 중인 로그 파일 이름 (순환된 파일은 `.1`, `.2` ... 접미사가 붙음)
+pub const LOG_FILE_NAME: &str = "securevault.log";
+
+/// 로그 파일 하나의 최대 크기 (바이트)
+pub const MAX_LOG_FILE_SIZE: u64 = 1024 * 1024;
+
+/// 보관하는 순환 로그 파일 수 (현재 파일 제외)
+pub const MAX_ROTATED_LOG_FILES: usize = 4;
+
+/// 잠금 해제 전에 메모리에 보관하는 최대 로그 기록 수
+const MAX_PENDING_RECORDS: usize = 1000;
+
+/// 암호화된 로그 파일 식별자
+const LOG_FILE_MAGIC: &[u8; 8] = b"SVLOG\0\0\x01";
+
+/// 로그 파일 ID 길이 (헤더에 저장되며 기록 인증에 포함됨)
+const LOG_FILE_ID_LEN: usize = 16;
+
+/// 암호화된 로그 파일 헤더 길이
+const LOG_HEADER_LEN: usize = LOG_FILE_MAGIC.len() + LOG_FILE_ID_LEN;
+
+/// 기록 암호화 시 인증에 포함되는 추가 데이터 (뒤에 파일 ID와 기록 번호가 붙음)
+const LOG_RECORD_AAD: &[u8] = b"SecureVault log record v1";
+
+/// AES-GCM nonce 길이
+const NONCE_LEN: usize = 12;
+
+/// AES-GCM 인증 태그 길이
+const TAG_LEN: usize = 16;
+
+/// 가림 처리의 대상이 되는 정보 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactionKind {
+    /// 키, PIN, 솔트, 해시 등 비밀 값
+    Secret,
+    /// Base64 등으로 인코딩된 데이터
+    EncodedData,
+    /// 절대 경로
+    Path,
+    /// 파일명
+    FileName,
+}
+
+impl RedactionKind {
+    /// 가려진 자리에 남기는 표시를 반환합니다.
+    pub fn placeholder(self) -> &'static str {
+        match self {
+            RedactionKind::Secret => "[REDACTED:secret]",
+            RedactionKind::EncodedData => "[REDACTED:data]",
+            RedactionKind::Path => "[REDACTED:path]",
+            RedactionKind::FileName => "[REDACTED:file]",
+        }
+    }
+}
+
+/// 가림 규칙
+struct RedactionRule {
+    /// 가리는 정보 종류
+    kind: RedactionKind,
+    /// 가릴 부분을 찾는 정규식
+    pattern: Regex,
+    /// 치환 문자열 (`${1}`은 앞부분의 구분자, 따옴표, 키 이름을 그대로 남김)
+    replacement: String,
+}
+
+/// 가림 규칙 목록 (적용 순서대로)
+fn redaction_rules() -> &'static [RedactionRule] {
+    static RULES: OnceLock<Vec<RedactionRule>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let rule = |kind: RedactionKind, pattern: &str, replacement: &str| RedactionRule {
+            kind,
+            pattern: Regex::new(pattern).expect("가림 규칙 정규식이 올바르지 않습니다"),
+            replacement: replacement.replace("{}", kind.placeholder()),
+        };
+
+        vec![
+            // `key=...`, `PIN: ...` 처럼 이름이 붙은 비밀 값
+            rule(
+                RedactionKind::Secret,
+                r"(?i)\b((?:key|pin|passphrase|password|secret|token|salt|nonce|iv)[0-9_]*\s*[=:]\s*)[^\s,;)]+",
+                "${1}{}",
+            ),
+            // `[12, 34, ...]` 형태의 바이트 배열
+            rule(
+                RedactionKind::Secret,
+                r"\[(?:\s*\d{1,3}\s*,){7,}\s*\d{1,3}\s*\]",
+                "{}",
+            ),
+            // 따옴표로 감싼 절대 경로 (공백이 포함될 수 있음)
+            rule(
+                RedactionKind::Path,
+                r#"(["'])(?:[A-Za-z]:[\\/]|\\\\|~?/)[^"'\n]*["']"#,
+                "${1}{}${1}",
+            ),
+            rule(
+                RedactionKind::Path,
+                r#"(^|[\s(=\[])(?:[A-Za-z]:[\\/]|\\\\|~?/)[^\s"'<>|,;)\]]+"#,
+                "${1}{}",
+            ),
+            // 16자리 이상의 16진수 (키 조각, 해시)
+            rule(RedactionKind::Secret, r"\b[0-9A-Fa-f]{16,}\b", "{}"),
+            rule(
+                RedactionKind::EncodedData,
+                r"[A-Za-z0-9+/]{32,}={0,2}",
+                "{}",
+            ),
+            // 따옴표로 감싼 파일명 (공백이 포함될 수 있음)
+            rule(
+                RedactionKind::FileName,
+                r#"(["'])[^"'\n]*\.[A-Za-z][A-Za-z0-9]{0,7}["']"#,
+                "${1}{}${1}",
+            ),
+            rule(
+                RedactionKind::FileName,
+                r#"[^\s/\\:'"\[\]()<>,;=]+\.[A-Za-z][A-Za-z0-9]{0,7}\b"#,
+                "{}",
+            ),
+        ]
+    })
+}
+
+/// 로그 메시지에서 비밀 값과 개인 정보를 종류별로 가립니다.
+///
+/// 키/PIN/솔트 값, 바이트 배열, 긴 16진수, 인코딩된 데이터, 절대 경로, 확장자가 있는 파일명을
+/// `[REDACTED:<종류>]`로 바꿉니다. 폴더 이름처럼 형식으로 구분할 수 없는 값은 가려지지 않으므로
+/// 로그 메시지에는 ID를 남기는 것이 좋습니다.
+///
+/// # 매개변수
+/// * `message` - 원본 로그 메시지
+///
+/// # 반환값
+/// * `String` - 가림 처리된 메시지
+pub fn redact(message: &str) -> String {
+    let mut redacted = message.to_string();
+    for rule in redaction_rules() {
+        if rule.pattern.is_match(&redacted) {
+            redacted = rule
+                .pattern
+                .replace_all(&redacted, rule.replacement.as_str())
+                .into_owned();
+        }
+    }
+    redacted
+}
+
+/// 메시지에 해당 종류의 가림 대상이 있는지 확인합니다.
+///
+/// # 매개변수
+/// * `message` - 로그 메시지
+/// * `kind` - 확인할 정보 종류
+///
+/// # 반환값
+/// * `bool` - 가림 대상이 있으면 true
+pub fn contains_sensitive(message: &str, kind: RedactionKind) -> bool {
+    redaction_rules()
+        .iter()
+        .filter(|rule| rule.kind == kind)
+        .any(|rule| rule.pattern.is_match(message))
+}
+
+/// 볼트 루트의 로그 디렉토리 경로를 반환합니다.
+///
+/// # 매개변수
+/// * `vault_root` - 볼트 루트 디렉토리
+///
+/// # 반환값
+/// * `PathBuf` - `<루트>/.securevault/logs`
+pub fn log_directory(vault_root: &Path) -> PathBuf {
+    vault_root.join(".securevault").join("logs")
+}
+
+/// 순환 번호에 해당하는 로그 파일 경로 (0이면 현재 파일)
+fn log_file_path(log_dir: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        log_dir.join(LOG_FILE_NAME)
+    } else {
+        log_dir.join(format!("{}.{}", LOG_FILE_NAME, index))
+    }
+}
+
+/// 기록 인증에 포함되는 추가 데이터
+fn record_aad(file_id: &[u8], sequence: u64) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(LOG_RECORD_AAD.len() + file_id.len() + 8);
+    aad.extend_from_slice(LOG_RECORD_AAD);
+    aad.extend_from_slice(file_id);
+    aad.extend_from_slice(&sequence.to_le_bytes());
+    aad
+}
+
+/// 로그 한 줄을 암호화된 기록으로 만듭니다.
+///
+/// 형식: 길이(4, LE) + nonce(12) + 암호문 + 인증태그(16)
+fn seal_record(key: &SecretKey, file_id: &[u8], sequence: u64, line: &str) -> io::Result<Vec<u8>> {
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.expose()));
+    let aad = record_aad(file_id, sequence);
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: line.as_bytes(),
+                aad: &aad,
+            },
+        )
+        .map_err(|_| io::Error::other("로그 기록 암호화 실패"))?;
+
+    let body_len = (NONCE_LEN + ciphertext.len()) as u32;
+    let mut record = Vec::with_capacity(4 + body_len as usize);
+    record.extend_from_slice(&body_len.to_le_bytes());
+    record.extend_from_slice(&nonce);
+    record.extend_from_slice(&ciphertext);
+    Ok(record)
+}
+
+/// 암호화된 로그 파일 내용에서 완전한 기록들을 나눕니다.
+///
+/// # 반환값
+/// * `(Vec<&[u8]>, usize)` - 기록 본문(nonce + 암호문) 목록과 마지막 완전한 기록의 끝 위치
+fn split_records(bytes: &[u8]) -> (Vec<&[u8]>, usize) {
+    let mut records = Vec::new();
+    let mut offset = LOG_HEADER_LEN.min(bytes.len());
+
+    while offset + 4 <= bytes.len() {
+        let mut len_bytes = [0u8; 4];
+        len_bytes.copy_from_slice(&bytes[offset..offset + 4]);
+        let body_len = u32::from_le_bytes(len_bytes) as usize;
+
+        let end = offset + 4 + body_len;
+        if body_len < NONCE_LEN + TAG_LEN || end > bytes.len() {
+            // 쓰는 도중 종료되어 잘린 기록
+            break;
+        }
+        records.push(&bytes[offset + 4..end]);
+        offset = end;
+    }
+
+    (records, offset)
+}
+
+/// 암호화된 로그 파일 하나를 복호화합니다.
+///
+/// 복호화할 수 없는 기록(다른 키로 기록되었거나 변조된 기록)은 건너뛰고 개수를 함께 반환합니다.
+fn open_log_file(key: &SecretKey, bytes: &[u8]) -> (Vec<String>, usize) {
+    let file_id = &bytes[LOG_FILE_MAGIC.len()..LOG_HEADER_LEN];
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.expose()));
+    let (records, _) = split_records(bytes);
+
+    let mut lines = Vec::with_capacity(records.len());
+    let mut unreadable = 0;
+    for (sequence, body) in records.iter().enumerate() {
+        let aad = record_aad(file_id, sequence as u64);
+        match cipher.decrypt(
+            Nonce::from_slice(&body[..NONCE_LEN]),
+            Payload {
+                msg: &body[NONCE_LEN..],
+                aad: &aad,
+            },
+        ) {
+            Ok(plaintext) => lines.push(String::from_utf8_lossy(&plaintext).into_owned()),
+            Err(_) => unreadable += 1,
+        }
+    }
+
+    (lines, unreadable)
+}
+
+/// 로그 디렉토리의 모든 로그를 오래된 순서로 읽습니다.
+///
+/// 암호화된 파일은 로그 키로 복호화하고, 로그 암호화가 꺼져 있을 때 기록된 파일은 그대로 읽습니다.
+///
+/// # 매개변수
+/// * `log_dir` - 로그 디렉토리
+/// * `key` - 로그 키 (`CryptoService::derive_log_key`로 유도)
+///
+/// # 반환값
+/// * `Result<Vec<String>, VaultError>` - 로그 줄 목록
+///
+/// # 오류
+/// * 로그 파일을 읽을 수 없는 경우
+pub fn read_logs(log_dir: &Path, key: &SecretKey) -> Result<Vec<String>, VaultError> {
+    let mut lines = Vec::new();
+
+    for index in (0..=MAX_ROTATED_LOG_FILES).rev() {
+        let path = log_file_path(log_dir, index);
+        if !path.exists() {
+            continue;
+        }
+
+        let bytes = fs::read(&path)?;
+        if bytes.len() >= LOG_HEADER_LEN && bytes.starts_with(LOG_FILE_MAGIC) {
+            let (file_lines, unreadable) = open_log_file(key, &bytes);
+            lines.extend(file_lines);
+            if unreadable > 0 {
+                lines.push(format!(
+                    "[복호화할 수 없는 로그 기록 {}개를 건너뛰었습니다]",
+                    unreadable
+                ));
+            }
+        } else {
+            lines.extend(String::from_utf8_lossy(&bytes).lines().map(str::to_string));
+        }
+    }
+
+    Ok(lines)
+}
+
+/// 로그 디렉토리에 기록하는 순환 파일 작성기
+struct LogWriter {
+    /// 로그 디렉토리
+    log_dir: PathBuf,
+    /// 로그 키 (None이면 가림 처리만 한 평문으로 기록)
+    key: Option<SecretKey>,
+    /// 현재 로그 파일
+    file: File,
+    /// 현재 파일 ID (암호화된 파일만 사용)
+    file_id: [u8; LOG_FILE_ID_LEN],
+    /// 현재 파일에 기록된 기록 수
+    sequence: u64,
+    /// 현재 파일 크기
+    size: u64,
+}
+
+impl LogWriter {
+    /// 로그 디렉토리의 현재 파일을 열어 이어서 기록합니다.
+    ///
+    /// 현재 파일의 형식(암호화/평문)이 요청한 형식과 다르면 먼저 순환합니다.
+    fn open(log_dir: &Path, key: Option<SecretKey>) -> io::Result<Self> {
+        fs::create_dir_all(log_dir)?;
+        let current = log_file_path(log_dir, 0);
+
+        if let Ok(bytes) = fs::read(&current) {
+            let encrypted = bytes.starts_with(LOG_FILE_MAGIC) && bytes.len() >= LOG_HEADER_LEN;
+            if !bytes.is_empty() && encrypted == key.is_some() {
+                if encrypted {
+                    let (records, valid_len) = split_records(&bytes);
+                    let mut file_id = [0u8; LOG_FILE_ID_LEN];
+                    file_id.copy_from_slice(&bytes[LOG_FILE_MAGIC.len()..LOG_HEADER_LEN]);
+
+                    let file = OpenOptions::new().append(true).open(&current)?;
+                    // 잘린 기록 뒤에 이어 쓰지 않도록 마지막 완전한 기록까지만 남김
+                    file.set_len(valid_len as u64)?;
+                    return Ok(Self {
+                        log_dir: log_dir.to_path_buf(),
+                        key,
+                        file,
+                        file_id,
+                        sequence: records.len() as u64,
+                        size: valid_len as u64,
+                    });
+                }
+
+                let file = OpenOptions::new().append(true).open(&current)?;
+                return Ok(Self {
+                    log_dir: log_dir.to_path_buf(),
+                    key,
+                    file,
+                    file_id: [0u8; LOG_FILE_ID_LEN],
+                    sequence: 0,
+                    size: bytes.len() as u64,
+                });
+            }
+
+            if !bytes.is_empty() {
+                Self::shift_rotated_files(log_dir)?;
+            }
+        }
+
+        Self::create(log_dir, key)
+    }
+
+    /// 새 현재 로그 파일을 만듭니다.
+    fn create(log_dir: &Path, key: Option<SecretKey>) -> io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(log_file_path(log_dir, 0))?;
+
+        let mut file_id = [0u8; LOG_FILE_ID_LEN];
+        let mut size = 0;
+        if key.is_some() {
+            OsRng.fill_bytes(&mut file_id);
+            file.write_all(LOG_FILE_MAGIC)?;
+            file.write_all(&file_id)?;
+            size = LOG_HEADER_LEN as u64;
+        }
+
+        Ok(Self {
+            log_dir: log_dir.to_path_buf(),
+            key,
+            file,
+            file_id,
+            sequence: 0,
+            size,
+        })
+    }
+
+    /// 순환 파일 번호를 하나씩 밀고 가장 오래된 파일을 삭제합니다.
+    fn shift_rotated_files(log_dir: &Path) -> io::Result<()> {
+        let oldest = log_file_path(log_dir, MAX_ROTATED_LOG_FILES);
+        if oldest.exists() {
+            fs::remove_file(&oldest)?;
+        }
+        for index in (0..MAX_ROTATED_LOG_FILES).rev() {
+            let path = log_file_path(log_dir, index);
+            if path.exists() {
+                fs::rename(&path, log_file_path(log_dir, index + 1))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 현재 파일을 순환하고 새 파일로 교체합니다.
+    fn rotate(&mut self) -> io::Result<()> {
+        Self::shift_rotated_files(&self.log_dir)?;
+        let key = self.key.take();
+        *self = Self::create(&self.log_dir, key)?;
+        Ok(())
+    }
+
+    /// 로그 한 줄을 기록합니다. 파일이 최대 크기를 넘으면 먼저 순환합니다.
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        if self.size >= MAX_LOG_FILE_SIZE {
+            self.rotate()?;
+        }
+
+        let bytes = match &self.key {
+            Some(key) => seal_record(key, &self.file_id, self.sequence, line)?,
+            None => format!("{}\n", line).into_bytes(),
+        };
+        self.file.write_all(&bytes)?;
+        self.size += bytes.len() as u64;
+        self.sequence += 1;
+        Ok(())
+    }
+}
+
+/// 로그 싱크 상태
+#[derive(Default)]
+struct LogSinkState {
+    /// 볼트가 열려 있을 때의 파일 작성기
+    writer: Option<LogWriter>,
+    /// 볼트가 잠겨 있는 동안 쌓인 기록 (가림 처리 완료)
+    pending: VecDeque<String>,
+}
+
+impl LogSinkState {
+    /// 기록을 파일에 쓰거나, 볼트가 잠겨 있으면 메모리에 보관합니다.
+    fn push(&mut self, line: String) {
+        if let Some(writer) = self.writer.as_mut() {
+            if writer.write_line(&line).is_ok() {
+                return;
+            }
+            // 기록에 실패하면 (USB 분리 등) 다음 잠금 해제 때 다시 시도
+            self.writer = None;
+        }
+
+        if self.pending.len() >= MAX_PENDING_RECORDS {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(line);
+    }
+
+    /// 파일 작성기를 설정하고 보관 중인 기록을 씁니다.
+    fn attach(&mut self, writer: LogWriter) {
+        self.writer = Some(writer);
+
+        let pending: Vec<String> = self.pending.drain(..).collect();
+        for line in pending {
+            self.push(line);
+        }
+    }
+
+    /// 파일 작성기(로그 키 포함)와 아직 쓰지 못한 기록을 모두 버립니다.
+    ///
+    /// 보관 중인 기록은 잠긴 볼트의 것이므로 다음에 여는 볼트(위장 볼트 등)의 로그에 섞이지 않아야 합니다.
+    fn detach(&mut self) {
+        self.writer = None;
+        self.pending.clear();
+    }
+}
+
+/// 로그 싱크 전역 상태
+fn sink_state() -> &'static Mutex<LogSinkState> {
+    static STATE: OnceLock<Mutex<LogSinkState>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(LogSinkState::default()))
+}
+
+/// `log` 크레이트 백엔드
+///
+/// 모든 기록을 가림 처리한 뒤 로그 싱크로 보내며, 개발 빌드에서는 표준 에러에도 출력합니다.
+struct VaultLogger {
+    level: LevelFilter,
+}
+
+impl Log for VaultLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format!(
+            "{} {:<5} {}: {}",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            record.level(),
+            record.target(),
+            redact(&record.args().to_string())
+        );
+
+        if cfg!(debug_assertions) {
+            eprintln!("{}", line);
+        }
+
+        if let Ok(mut state) = sink_state().lock() {
+            state.push(line);
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut state) = sink_state().lock() {
+            if let Some(writer) = state.writer.as_mut() {
+                let _ = writer.file.flush();
+            }
+        }
+    }
+}
+
+/// 로그 백엔드를 설치합니다.
+///
+/// 로그 수준은 `RUST_LOG`(예: `debug`)로 지정하며 기본값은 `info`입니다.
+/// 볼트가 열리기 전까지의 기록은 메모리에 보관했다가 `attach` 시 파일에 씁니다.
+pub fn init() {
+    let level = std::env::var("RUST_LOG")
+        .ok()
+        .and_then(|value| value.parse::<LevelFilter>().ok())
+        .unwrap_or(LevelFilter::Info);
+
+    let logger: &'static VaultLogger = Box::leak(Box::new(VaultLogger { level }));
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+/// 열린 볼트의 로그 디렉토리에 기록을 시작하고 보관 중인 기록을 씁니다.
+///
+/// # 매개변수
+/// * `log_dir` - 로그 디렉토리 (`log_directory`)
+/// * `key` - 로그 키 (None이면 가림 처리만 한 평문으로 기록)
+///
+/// # 반환값
+/// * `Result<(), VaultError>` - 로그 파일을 열었는지 여부
+///
+/// # 오류
+/// * 로그 디렉토리나 파일을 만들 수 없는 경우
+pub fn attach(log_dir: &Path, key: Option<SecretKey>) -> Result<(), VaultError> {
+    let writer = LogWriter::open(log_dir, key)?;
+
+    let mut state = sink_state().lock().map_err(|_| VaultError::LockFailed)?;
+    state.attach(writer);
+    Ok(())
+}
+
+/// 로그 파일 기록을 멈추고 로그 키를 메모리에서 제거합니다.
+///
+/// 기록하지 못하고 보관 중이던 이 볼트의 기록도 버리며, 이후 기록은 다음 `attach`까지 메모리에 보관됩니다.
+pub fn detach() {
+    if let Ok(mut state) = sink_state().lock() {
+        state.detach();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_key(byte: u8) -> SecretKey {
+        SecretKey::from_bytes(&mut [byte; 32])
+    }
+
+    #[test]
+    fn test_redact_by_kind() {
+        let message = "Key0=A1 원본 경로: C:\\Users\\홍길동\\Documents\\여권 사본.pdf";
+        let redacted = redact(message);
+        assert!(redacted.contains("Key0=[REDACTED:secret]"));
+        assert!(redacted.contains("[REDACTED:path]"));
+        assert!(!redacted.contains("홍길동"));
+        assert!(!redacted.contains("여권"));
+
+        let redacted =
+            redact("볼트 파일명: '가족 사진.jpg', 해시 9f86d081884c7d659a2feaa0c55ad015");
+        assert_eq!(
+            redacted,
+            "볼트 파일명: '[REDACTED:file]', 해시 [REDACTED:secret]"
+        );
+
+        let redacted =
+            redact("임시 파일 삭제 실패: /home/user/.securevault/data/temp/x.tmp - 권한 없음");
+        assert_eq!(redacted, "임시 파일 삭제 실패: [REDACTED:path] - 권한 없음");
+
+        assert!(contains_sensitive("report.docx", RedactionKind::FileName));
+        assert!(!contains_sensitive(
+            "파일 서비스 초기화 완료",
+            RedactionKind::FileName
+        ));
+        assert_eq!(
+            redact("업로드 작업 3개 취소 (ID: 42)"),
+            "업로드 작업 3개 취소 (ID: 42)"
+        );
+    }
+
+    #[test]
+    fn test_encrypted_log_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut writer = LogWriter::open(temp_dir.path(), Some(test_key(7))).unwrap();
+        writer.write_line("첫 번째 기록").unwrap();
+        writer.write_line("두 번째 기록").unwrap();
+        drop(writer);
+
+        // 다시 열어도 기록 번호가 이어짐
+        let mut writer = LogWriter::open(temp_dir.path(), Some(test_key(7))).unwrap();
+        writer.write_line("세 번째 기록").unwrap();
+        drop(writer);
+
+        let raw = fs::read(log_file_path(temp_dir.path(), 0)).unwrap();
+        assert!(raw.starts_with(LOG_FILE_MAGIC));
+        assert!(!String::from_utf8_lossy(&raw).contains("기록"));
+
+        let lines = read_logs(temp_dir.path(), &test_key(7)).unwrap();
+        assert_eq!(lines, vec!["첫 번째 기록", "두 번째 기록", "세 번째 기록"]);
+
+        let lines = read_logs(temp_dir.path(), &test_key(8)).unwrap();
+        assert_eq!(
+            lines,
+            vec!["[복호화할 수 없는 로그 기록 3개를 건너뛰었습니다]"]
+        );
+    }
+
+    #[test]
+    fn test_detach_discards_pending_records() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        let mut state = LogSinkState::default();
+
+        state.attach(LogWriter::open(first.path(), Some(test_key(7))).unwrap());
+        state.push("첫 번째 볼트 기록".to_string());
+        // 로그 파일을 쓸 수 없게 된 동안의 기록은 메모리에 보관됨
+        state.writer = None;
+        state.push("첫 번째 볼트의 보관 기록".to_string());
+        state.detach();
+
+        state.attach(LogWriter::open(second.path(), Some(test_key(8))).unwrap());
+        state.push("두 번째 볼트 기록".to_string());
+        drop(state);
+
+        let lines = read_logs(second.path(), &test_key(8)).unwrap();
+        assert_eq!(lines, vec!["두 번째 볼트 기록"]);
+    }
+
+    #[test]
+    fn test_truncated_record_is_discarded() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut writer = LogWriter::open(temp_dir.path(), Some(test_key(1))).unwrap();
+        writer.write_line("완전한 기록").unwrap();
+        writer.write_line("잘릴 기록").unwrap();
+        drop(writer);
+
+        let path = log_file_path(temp_dir.path(), 0);
+        let len = fs::metadata(&path).unwrap().len();
+        OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(len - 5)
+            .unwrap();
+
+        let mut writer = LogWriter::open(temp_dir.path(), Some(test_key(1))).unwrap();
+        writer.write_line("이어 쓴 기록").unwrap();
+        drop(writer);
+
+        let lines = read_logs(temp_dir.path(), &test_key(1)).unwrap();
+        assert_eq!(lines, vec!["완전한 기록", "이어 쓴 기록"]);
+    }
+
+    #[test]
+    fn test_rotation_keeps_bounded_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut writer = LogWriter::open(temp_dir.path(), Some(test_key(3))).unwrap();
+        let line = "x".repeat(64 * 1024);
+        for _ in 0..((MAX_ROTATED_LOG_FILES + 3) * 17) {
+            writer.write_line(&line).unwrap();
+        }
+        writer.write_line("마지막 기록").unwrap();
+        drop(writer);
+
+        assert!(log_file_path(temp_dir.path(), MAX_ROTATED_LOG_FILES).exists());
+        assert!(!log_file_path(temp_dir.path(), MAX_ROTATED_LOG_FILES + 1).exists());
+        for index in 0..=MAX_ROTATED_LOG_FILES {
+            let size = fs::metadata(log_file_path(temp_dir.path(), index))
+                .unwrap()
+                .len();
+            assert!(size <= MAX_LOG_FILE_SIZE + 64 * 1024 + 64);
+        }
+
+        let lines = read_logs(temp_dir.path(), &test_key(3)).unwrap();
+        assert_eq!(lines.last().map(String::as_str), Some("마지막 기록"));
+    }
+
+    #[test]
+    fn test_switching_to_plaintext_rotates_encrypted_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut writer = LogWriter::open(temp_dir.path(), Some(test_key(5))).unwrap();
+        writer.write_line("암호화된 기록").unwrap();
+        drop(writer);
+
+        let mut writer = LogWriter::open(temp_dir.path(), None).unwrap();
+        writer.write_line("평문 기록").unwrap();
+        drop(writer);
+
+        let lines = read_logs(temp_dir.path(), &test_key(5)).unwrap();
+        assert_eq!(lines, vec!["암호화된 기록", "평문 기록"]);
+    }
+}
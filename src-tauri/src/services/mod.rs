@@ -3,13 +3,19 @@
 
 pub mod auth;
 pub mod compression;
+pub mod container;
 pub mod crypto;
 pub mod database;
 pub mod file;
 pub mod folder;
+pub mod idle_lock;
+pub mod log_sink;
 pub mod media;
 pub mod network_guard;
+pub mod process_guard;
 pub mod recovery;
+pub mod recovery_sheet;
+pub mod reencryption;
 pub mod upload_manager;
 pub mod viewer;
 
@@ -22,6 +28,7 @@ pub use file::FileService;
 pub use folder::FolderService;
 pub use media::MediaService;
 pub use network_guard::{NetworkBlockedError, NetworkGuard, NetworkSecurityReport, SecurityLevel};
+pub use process_guard::{ProcessGuardPolicy, ProcessHardeningReport, TracerAction};
 pub use recovery::RecoveryService;
 pub use upload_manager::{
     CancellationToken, ProgressTracker, UploadJob, UploadManager, UploadStatus,
@@ -0,0 +1,323 @@
+// 프로세스 보호 서비스
+// 메모리에 있는 평문과 키가 코어 덤프, 스왑, 디버거를 통해 새어 나가지 않도록 시작 시 프로세스를 보호합니다.
+
+use log::{error, info, warn};
+use serde::Serialize;
+
+/// 디버거가 붙어 있어도 실행을 허용하는 환경 변수 (`1`이면 경고만 남김)
+pub const ALLOW_DEBUGGER_ENV: &str = "SECUREVAULT_ALLOW_DEBUGGER";
+
+/// 프로세스 전체 메모리 고정(`mlockall`)을 켜는 환경 변수 (`1`이면 사용)
+pub const LOCK_ALL_MEMORY_ENV: &str = "SECUREVAULT_MLOCKALL";
+
+/// 디버거/트레이서가 감지되었을 때의 동작
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TracerAction {
+    /// 실행을 거부하고 종료
+    Refuse,
+    /// 경고만 남기고 계속 실행
+    Warn,
+}
+
+/// 프로세스 보호 정책
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessGuardPolicy {
+    /// 프로세스 전체 메모리를 고정할지 여부
+    ///
+    /// WebView를 포함한 모든 메모리가 고정되므로 `RLIMIT_MEMLOCK`이 작으면 이후 메모리 할당이
+    /// 실패할 수 있어 기본값은 꺼져 있습니다. 키 자체는 `SecretKey`가 따로 고정합니다.
+    pub lock_all_memory: bool,
+    /// 디버거/트레이서가 감지되었을 때의 동작
+    pub on_tracer: TracerAction,
+}
+
+impl ProcessGuardPolicy {
+    /// 환경 변수로 정책을 만듭니다.
+    ///
+    /// # 반환값
+    /// * `Self` - `SECUREVAULT_MLOCKALL`, `SECUREVAULT_ALLOW_DEBUGGER`가 반영된 정책
+    pub fn from_env() -> Self {
+        let enabled = |name: &str| std::env::var(name).is_ok_and(|value| value == "1");
+        let mut policy = Self {
+            lock_all_memory: enabled(LOCK_ALL_MEMORY_ENV),
+            ..Self::default()
+        };
+
+        if enabled(ALLOW_DEBUGGER_ENV) {
+            policy.on_tracer = TracerAction::Warn;
+        }
+        policy
+    }
+}
+
+impl Default for ProcessGuardPolicy {
+    /// 릴리스 빌드는 디버거가 붙어 있으면 실행을 거부하고, 개발 빌드는 경고만 남깁니다.
+    fn default() -> Self {
+        Self {
+            lock_all_memory: false,
+            on_tracer: if cfg!(debug_assertions) {
+                TracerAction::Warn
+            } else {
+                TracerAction::Refuse
+            },
+        }
+    }
+}
+
+/// 프로세스 보호 적용 결과
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProcessHardeningReport {
+    /// 현재 플랫폼에서 프로세스 보호를 지원하는지 여부
+    pub supported: bool,
+    /// 코어 덤프 크기 제한을 0으로 설정했는지 여부
+    pub core_dumps_disabled: bool,
+    /// 프로세스를 덤프 불가(다른 프로세스의 ptrace 연결 불가)로 표시했는지 여부
+    pub non_dumpable: bool,
+    /// 프로세스 전체 메모리 고정을 요청했는지 여부
+    pub memory_lock_requested: bool,
+    /// 프로세스 전체 메모리를 고정했는지 여부
+    pub memory_locked: bool,
+    /// 연결된 디버거/트레이서의 PID (없으면 None)
+    pub tracer_pid: Option<u32>,
+    /// 실패했거나 주의가 필요한 항목
+    pub warnings: Vec<String>,
+}
+
+impl ProcessHardeningReport {
+    /// 실패한 보호 항목이나 연결된 트레이서가 있는지 확인합니다.
+    ///
+    /// # 반환값
+    /// * `bool` - 주의가 필요한 항목이 있으면 true
+    pub fn is_degraded(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+}
+
+/// 정책에 따라 프로세스 보호를 적용합니다.
+///
+/// # 매개변수
+/// * `policy` - 프로세스 보호 정책
+///
+/// # 반환값
+/// * `ProcessHardeningReport` - 항목별 적용 결과
+#[cfg(target_os = "linux")]
+pub fn harden_process(policy: &ProcessGuardPolicy) -> ProcessHardeningReport {
+    let os_result = |ret: libc::c_int| {
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    };
+
+    let no_core = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: 유효한 rlimit 구조체의 포인터를 전달함
+    let core_dumps = os_result(unsafe { libc::setrlimit(libc::RLIMIT_CORE, &no_core) });
+
+    // 덤프 불가로 표시하면 같은 사용자의 다른 프로세스도 ptrace로 연결하거나 /proc/<pid>/mem을 읽을 수 없음
+    // SAFETY: PR_SET_DUMPABLE은 정수 인자만 사용함
+    let non_dumpable = os_result(unsafe { libc::prctl(libc::PR_SET_DUMPABLE, 0, 0, 0, 0) });
+
+    let memory_lock = policy.lock_all_memory.then(|| {
+        // SAFETY: 플래그 인자만 사용하며 메모리 내용을 변경하지 않음
+        os_result(unsafe { libc::mlockall(libc::MCL_CURRENT | libc::MCL_FUTURE) })
+    });
+
+    // 덤프 불가 설정 이전에 이미 연결된 트레이서가 있는지 확인
+    let status = std::fs::read_to_string("/proc/self/status").ok();
+
+    build_report(
+        policy,
+        core_dumps,
+        non_dumpable,
+        memory_lock,
+        status.as_deref(),
+    )
+}
+
+/// 각 보호 항목의 시스템 호출 결과로 보고서를 만듭니다.
+///
+/// # 매개변수
+/// * `policy` - 프로세스 보호 정책
+/// * `core_dumps` - 코어 덤프 크기 제한 설정 결과
+/// * `non_dumpable` - 덤프 불가 설정 결과
+/// * `memory_lock` - 메모리 고정 결과 (요청하지 않았으면 None)
+/// * `status` - `/proc/self/status` 내용 (읽지 못했으면 None)
+///
+/// # 반환값
+/// * `ProcessHardeningReport` - 항목별 적용 결과
+#[cfg(target_os = "linux")]
+fn build_report(
+    policy: &ProcessGuardPolicy,
+    core_dumps: std::io::Result<()>,
+    non_dumpable: std::io::Result<()>,
+    memory_lock: Option<std::io::Result<()>>,
+    status: Option<&str>,
+) -> ProcessHardeningReport {
+    let mut report = ProcessHardeningReport {
+        supported: true,
+        memory_lock_requested: policy.lock_all_memory,
+        ..Default::default()
+    };
+
+    match core_dumps {
+        Ok(()) => report.core_dumps_disabled = true,
+        Err(e) => report
+            .warnings
+            .push(format!("코어 덤프를 비활성화하지 못했습니다: {}", e)),
+    }
+
+    match non_dumpable {
+        Ok(()) => report.non_dumpable = true,
+        Err(e) => report
+            .warnings
+            .push(format!("프로세스를 덤프 불가로 설정하지 못했습니다: {}", e)),
+    }
+
+    match memory_lock {
+        Some(Ok(())) => report.memory_locked = true,
+        Some(Err(e)) => report
+            .warnings
+            .push(format!("프로세스 메모리를 고정하지 못했습니다: {}", e)),
+        None => {}
+    }
+
+    report.tracer_pid = status.and_then(parse_tracer_pid);
+    if let Some(pid) = report.tracer_pid {
+        report.warnings.push(format!(
+            "디버거 또는 트레이서가 연결되어 있습니다 (PID {}).",
+            pid
+        ));
+    }
+
+    report
+}
+
+/// 정책에 따라 프로세스 보호를 적용합니다. Linux 외의 플랫폼에서는 아무것도 하지 않습니다.
+#[cfg(not(target_os = "linux"))]
+pub fn harden_process(policy: &ProcessGuardPolicy) -> ProcessHardeningReport {
+    ProcessHardeningReport {
+        memory_lock_requested: policy.lock_all_memory,
+        ..Default::default()
+    }
+}
+
+/// `/proc/self/status` 내용에서 트레이서 PID를 읽습니다.
+///
+/// # 매개변수
+/// * `status` - `/proc/<pid>/status` 파일 내용
+///
+/// # 반환값
+/// * `Option<u32>` - 트레이서 PID (연결되지 않았거나 항목이 없으면 None)
+pub fn parse_tracer_pid(status: &str) -> Option<u32> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("TracerPid:"))
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .filter(|&pid| pid != 0)
+}
+
+/// 시작 시 프로세스 보호를 적용합니다.
+///
+/// 디버거가 연결되어 있고 정책이 `TracerAction::Refuse`이면 키를 다루기 전에 프로세스를 종료합니다.
+///
+/// # 반환값
+/// * `ProcessHardeningReport` - 항목별 적용 결과
+pub fn initialize_process_guard() -> ProcessHardeningReport {
+    let policy = ProcessGuardPolicy::from_env();
+    let report = harden_process(&policy);
+
+    if !report.supported {
+        info!("이 플랫폼에서는 프로세스 보호(코어 덤프/ptrace 차단)를 지원하지 않습니다.");
+        return report;
+    }
+
+    if report.tracer_pid.is_some() && policy.on_tracer == TracerAction::Refuse {
+        error!("디버거가 연결된 상태에서는 SecureVault를 실행할 수 없습니다.");
+        eprintln!("디버거가 연결된 상태에서는 SecureVault를 실행할 수 없습니다.");
+        std::process::exit(1);
+    }
+
+    for warning in &report.warnings {
+        warn!("프로세스 보호: {}", warning);
+    }
+    info!(
+        "프로세스 보호 적용: 코어 덤프 차단={}, 덤프 불가={}, 메모리 고정={}",
+        report.core_dumps_disabled, report.non_dumpable, report.memory_locked
+    );
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tracer_pid() {
+        let traced = "Name:\tsecure-vault\nState:\tS (sleeping)\nTracerPid:\t4242\nUid:\t1000\n";
+        let untraced = "Name:\tsecure-vault\nTracerPid:\t0\n";
+
+        assert_eq!(parse_tracer_pid(traced), Some(4242));
+        assert_eq!(parse_tracer_pid(untraced), None);
+        assert_eq!(parse_tracer_pid("Name:\tsecure-vault\n"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_build_report() {
+        let policy = ProcessGuardPolicy {
+            lock_all_memory: false,
+            on_tracer: TracerAction::Warn,
+        };
+        let report = build_report(&policy, Ok(()), Ok(()), None, Some("TracerPid:\t0\n"));
+
+        assert!(report.supported);
+        assert!(report.core_dumps_disabled);
+        assert!(report.non_dumpable);
+        assert!(!report.memory_lock_requested);
+        assert!(!report.memory_locked);
+        assert!(!report.is_degraded());
+
+        let policy = ProcessGuardPolicy {
+            lock_all_memory: true,
+            ..policy
+        };
+        let denied = || Err(std::io::Error::from_raw_os_error(libc::EPERM));
+        let report = build_report(
+            &policy,
+            denied(),
+            Ok(()),
+            Some(denied()),
+            Some("TracerPid:\t4242\n"),
+        );
+
+        assert!(!report.core_dumps_disabled);
+        assert!(report.non_dumpable);
+        assert!(report.memory_lock_requested);
+        assert!(!report.memory_locked);
+        assert_eq!(report.tracer_pid, Some(4242));
+        assert_eq!(report.warnings.len(), 3);
+        assert!(report.is_degraded());
+    }
+
+    /// 테스트 프로세스 전체에 코어 덤프 차단과 덤프 불가 설정이 적용되므로 따로 실행합니다.
+    #[cfg(target_os = "linux")]
+    #[test]
+    #[ignore = "테스트 프로세스 자체에 setrlimit/PR_SET_DUMPABLE을 적용함"]
+    fn test_harden_process_disables_core_dumps() {
+        let report = harden_process(&ProcessGuardPolicy {
+            lock_all_memory: false,
+            on_tracer: TracerAction::Warn,
+        });
+
+        assert!(report.supported);
+        assert!(report.core_dumps_disabled);
+        assert!(report.non_dumpable);
+        assert!(!report.memory_lock_requested);
+        assert!(!report.memory_locked);
+    }
+}
@@ -1,12 +1,16 @@
 use crate::models::recovery::{RecoveryError, RecoveryKeyInfo, RecoveryVerificationResult};
+use crate::utils::mnemonic::{decode_recovery_key, encode_recovery_mnemonic};
+use crate::utils::shamir::{combine_shares, looks_like_share, split_secret};
 use base64::{Engine as _, engine::general_purpose};
 use rand::RngCore;
 use sha2::{Digest, Sha256};
 use std::sync::Mutex;
+use zeroize::Zeroize;
 
 /// 복구 키 서비스
 /// C# SecurityService의 복구 키 기능을 완전히 포팅
-/// 256비트 Base64 복구 키 생성, 검증, 키 유도 기능 제공
+/// 256비트 복구 키(24단어 니모닉) 생성, 검증, 키 유도 기능 제공
+/// 기존 Base64 형식 복구 키도 그대로 입력할 수 있음
 #[derive(Debug)]
 pub struct RecoveryService {
     /// 현재 복구 키 정보 (메모리에만 저장)
@@ -21,18 +25,18 @@ impl RecoveryService {
         }
     }
 
-    /// 복구 키 생성 (C# SecurityService와 동일한 256비트 Base64 키)
+    /// 복구 키 생성 (256비트 키를 체크섬이 포함된 24단어로 인코딩)
     /// 
     /// # 반환값
-    /// * `Ok(String)` - Base64로 인코딩된 256비트 복구 키
+    /// * `Ok(String)` - 공백으로 구분된 24단어 복구 키
     /// * `Err(RecoveryError)` - 키 생성 실패
     pub fn generate_recovery_key(&self) -> Result<String, RecoveryError> {
         // 256비트(32바이트) 랜덤 키 생성
         let mut key_bytes = [0u8; 32];
         rand::thread_rng().fill_bytes(&mut key_bytes);
 
-        // Base64로 인코딩 (C# Convert.ToBase64String과 동일)
-        let recovery_key = general_purpose::STANDARD.encode(&key_bytes);
+        // 24단어 니모닉으로 인코딩 (마지막 단어에 SHA-256 체크섬 8비트 포함)
+        let recovery_key = encode_recovery_mnemonic(&key_bytes);
 
         // 복구 키 해시 생성 (저장용)
         let hash = self.hash_recovery_key_internal(&recovery_key)?;
@@ -42,8 +46,7 @@ impl RecoveryService {
         *self.current_recovery_key.lock().unwrap() = Some(recovery_info);
 
         // 메모리에서 원본 키 바이트 삭제 (보안)
-        let mut key_bytes_mut = key_bytes;
-        key_bytes_mut.fill(0);
+        key_bytes.zeroize();
 
         Ok(recovery_key)
     }
@@ -51,10 +54,10 @@ impl RecoveryService {
     /// 복구 키를 SHA-256으로 해시화 (C# HashRecoveryKey 포팅)
     /// 
     /// # 매개변수
-    /// * `recovery_key` - Base64 형식의 복구 키
+    /// * `recovery_key` - 24단어 또는 기존 Base64 형식의 복구 키
     /// 
     /// # 반환값
-    /// * `Ok(String)` - Base64로 인코딩된 SHA-256 해시값
+    /// * `Ok(String)` - Base64로 인코딩된 SHA-256 해시값 (두 형식 모두 같은 키 바이트의 해시)
     /// * `Err(RecoveryError)` - 해시 생성 실패
     pub fn hash_recovery_key(&self, recovery_key: &str) -> Result<String, RecoveryError> {
         self.hash_recovery_key_internal(recovery_key)
@@ -66,20 +69,15 @@ impl RecoveryService {
             return Err(RecoveryError::InvalidFormat("복구 키가 비어있습니다.".to_string()));
         }
 
-        // Base64 디코딩 (C# Convert.FromBase64String과 동일)
-        let key_bytes = general_purpose::STANDARD
-            .decode(recovery_key)
-            .map_err(|_| RecoveryError::InvalidFormat("올바르지 않은 Base64 형식의 복구 키입니다.".to_string()))?;
-
-        // 32바이트(256비트) 검증
-        if key_bytes.len() != 32 {
-            return Err(RecoveryError::InvalidFormat("복구 키는 32바이트(256비트)여야 합니다.".to_string()));
-        }
+        // 24단어 또는 Base64 디코딩 (32바이트 길이 검증 포함)
+        let mut key_bytes = decode_recovery_key(recovery_key)
+            .map_err(|e| RecoveryError::InvalidFormat(e.to_string()))?;
 
         // SHA-256 해시 계산 (C# SHA256.ComputeHash와 동일)
         let mut hasher = Sha256::new();
-        hasher.update(&key_bytes);
+        hasher.update(key_bytes);
         let hash = hasher.finalize();
+        key_bytes.zeroize();
 
         // Base64로 인코딩하여 반환
         Ok(general_purpose::STANDARD.encode(&hash))
@@ -88,7 +86,7 @@ impl RecoveryService {
     /// 복구 키 검증 (C# VerifyRecoveryKey 포팅)
     /// 
     /// # 매개변수
-    /// * `input_recovery_key` - 입력된 복구 키 (Base64 문자열)
+    /// * `input_recovery_key` - 입력된 복구 키 (24단어 또는 Base64 문자열)
     /// * `stored_hash` - 저장된 복구 키 해시값
     /// 
     /// # 반환값
@@ -112,7 +110,7 @@ impl RecoveryService {
     /// 복구 키로부터 마스터 키 유도 (C# DeriveKeyFromRecoveryKey 포팅)
     /// 
     /// # 매개변수
-    /// * `recovery_key` - 복구 키 (Base64 문자열)
+    /// * `recovery_key` - 복구 키 (24단어 또는 Base64 문자열)
     /// * `salt` - 32바이트 솔트
     /// * `iterations` - PBKDF2 반복 횟수 (기본값: 100,000)
     /// 
@@ -133,14 +131,8 @@ impl RecoveryService {
             return Err(RecoveryError::InvalidFormat("솔트는 32바이트여야 합니다.".to_string()));
         }
 
-        // Base64 디코딩
-        let key_bytes = general_purpose::STANDARD
-            .decode(recovery_key)
-            .map_err(|_| RecoveryError::InvalidFormat("올바르지 않은 Base64 형식의 복구 키입니다.".to_string()))?;
-
-        if key_bytes.len() != 32 {
-            return Err(RecoveryError::InvalidFormat("복구 키는 32바이트(256비트)여야 합니다.".to_string()));
-        }
+        let mut key_bytes = decode_recovery_key(recovery_key)
+            .map_err(|e| RecoveryError::InvalidFormat(e.to_string()))?;
 
         // PBKDF2-HMAC-SHA256으로 키 유도 (C# Rfc2898DeriveBytes와 동일)
         let iterations = iterations.unwrap_or(100_000);
@@ -148,6 +140,7 @@ impl RecoveryService {
         use pbkdf2::pbkdf2_hmac;
         let mut master_key = [0u8; 32];
         pbkdf2_hmac::<sha2::Sha256>(&key_bytes, salt, iterations, &mut master_key);
+        key_bytes.zeroize();
 
         Ok(master_key)
     }
@@ -183,54 +176,101 @@ impl RecoveryService {
         }
     }
 
-    /// 복구 키 형식 검증 (Base64 및 길이 확인)
+    /// 복구 키 형식 검증 (24단어 체크섬 또는 기존 Base64 길이 확인)
     /// 
     /// # 매개변수
     /// * `recovery_key` - 검증할 복구 키
     /// 
     /// # 반환값
     /// * `Ok(())` - 형식이 올바름
-    /// * `Err(RecoveryError)` - 형식 오류
+    /// * `Err(RecoveryError)` - 형식 오류 (잘못된 단어가 있으면 그 위치와 단어를 포함)
     pub fn validate_recovery_key_format(&self, recovery_key: &str) -> Result<(), RecoveryError> {
-        if recovery_key.is_empty() {
-            return Err(RecoveryError::InvalidFormat("복구 키를 입력해주세요.".to_string()));
-        }
-
-        // Base64 디코딩 시도
-        let key_bytes = general_purpose::STANDARD
-            .decode(recovery_key)
-            .map_err(|_| RecoveryError::InvalidFormat("올바르지 않은 Base64 형식입니다.".to_string()))?;
-
-        // 길이 검증 (32바이트 = 256비트)
-        if key_bytes.len() != 32 {
-            return Err(RecoveryError::InvalidFormat("복구 키는 256비트(32바이트)여야 합니다.".to_string()));
-        }
+        let mut key_bytes = decode_recovery_key(recovery_key)
+            .map_err(|e| RecoveryError::InvalidFormat(e.to_string()))?;
+        key_bytes.zeroize();
 
         Ok(())
     }
 
-    /// 현재 복구 키 정보 조회
-    pub fn get_current_recovery_key_info(&self) -> Option<RecoveryKeyInfo> {
-        self.current_recovery_key.lock().unwrap().clone()
+    /// 복구 키를 N개의 조각으로 나눕니다. 그중 M개가 모이면 복구 키를 복원할 수 있습니다.
+    /// 
+    /// # 매개변수
+    /// * `recovery_key` - 나눌 복구 키 (24단어 또는 기존 Base64)
+    /// * `threshold` - 복원에 필요한 조각 수 (M, 2 이상)
+    /// * `share_count` - 전체 조각 수 (N)
+    /// 
+    /// # 반환값
+    /// * `Ok(Vec<String>)` - 세트 식별자·번호·체크섬이 포함된 조각 목록
+    /// * `Err(RecoveryError)` - 복구 키 형식 또는 조각 수 오류
+    pub fn split_recovery_key(
+        &self,
+        recovery_key: &str,
+        threshold: u8,
+        share_count: u8,
+    ) -> Result<Vec<String>, RecoveryError> {
+        let mut key_bytes = decode_recovery_key(recovery_key)
+            .map_err(|e| RecoveryError::InvalidFormat(e.to_string()))?;
+        let shares = split_secret(&key_bytes, threshold, share_count);
+        key_bytes.zeroize();
+
+        shares.map_err(|e| RecoveryError::InvalidFormat(e.to_string()))
     }
 
-    /// 복구 키 사용 표시 (한 번만 사용 가능)
-    pub fn mark_recovery_key_used(&self) -> Result<(), RecoveryError> {
-        let mut recovery_key = self.current_recovery_key.lock().unwrap();
-        if let Some(ref mut info) = *recovery_key {
-            if info.used {
-                return Err(RecoveryError::VerificationFailed);
+    /// 조각들로 복구 키를 복원합니다.
+    /// 
+    /// # 매개변수
+    /// * `shares` - M개 이상의 복구 키 조각
+    /// 
+    /// # 반환값
+    /// * `Ok(String)` - 복원된 24단어 복구 키
+    /// * `Err(RecoveryError)` - 조각 형식·체크섬 오류 또는 조각 부족
+    pub fn combine_recovery_shares(&self, shares: &[String]) -> Result<String, RecoveryError> {
+        let mut key_bytes =
+            combine_shares(shares).map_err(|e| RecoveryError::InvalidFormat(e.to_string()))?;
+        let recovery_key = encode_recovery_mnemonic(&key_bytes);
+        key_bytes.zeroize();
+
+        Ok(recovery_key)
+    }
+
+    /// 복구 키 또는 조각 목록 중 입력된 쪽으로 복구 키를 결정합니다.
+    /// 
+    /// 복구 키 입력란에 조각을 한 줄에 하나씩 붙여 넣었다면 조각 목록으로 처리합니다.
+    /// 
+    /// # 매개변수
+    /// * `recovery_key` - 전체 복구 키 또는 줄마다 하나씩 입력한 조각 (선택)
+    /// * `shares` - 복구 키 조각 목록 (선택, 비어 있지 않으면 우선 사용)
+    /// 
+    /// # 반환값
+    /// * `Ok(String)` - 검증에 사용할 복구 키
+    /// * `Err(RecoveryError)` - 둘 다 없거나 조각 복원 실패
+    pub fn resolve_recovery_input(
+        &self,
+        recovery_key: Option<&str>,
+        shares: Option<&[String]>,
+    ) -> Result<String, RecoveryError> {
+        match (recovery_key, shares) {
+            (_, Some(shares)) if !shares.is_empty() => self.combine_recovery_shares(shares),
+            (Some(key), _) => {
+                let lines: Vec<String> = key
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                if !lines.is_empty() && lines.iter().all(|line| looks_like_share(line)) {
+                    self.combine_recovery_shares(&lines)
+                } else {
+                    Ok(key.to_string())
+                }
             }
-            info.used = true;
-            Ok(())
-        } else {
-            Err(RecoveryError::InternalError("복구 키 정보가 없습니다.".to_string()))
+            _ => Err(RecoveryError::InvalidFormat("복구 키를 입력해주세요.".to_string())),
         }
     }
 
-    /// 복구 키 정보 초기화 (보안을 위해)
-    pub fn clear_recovery_key(&self) {
-        *self.current_recovery_key.lock().unwrap() = None;
+    /// 현재 복구 키 정보 조회
+    pub fn get_current_recovery_key_info(&self) -> Option<RecoveryKeyInfo> {
+        self.current_recovery_key.lock().unwrap().clone()
     }
 }
 
@@ -249,12 +289,12 @@ mod tests {
         let service = RecoveryService::new();
         let recovery_key = service.generate_recovery_key().unwrap();
         
-        // Base64 형식 검증
-        assert!(general_purpose::STANDARD.decode(&recovery_key).is_ok());
+        // 24단어 형식 검증
+        assert_eq!(recovery_key.split(' ').count(), 24);
         
-        // 길이 검증 (Base64로 인코딩된 32바이트는 44자)
-        let decoded = general_purpose::STANDARD.decode(&recovery_key).unwrap();
-        assert_eq!(decoded.len(), 32);
+        // 체크섬을 포함해 32바이트 키로 디코딩되는지 확인
+        assert!(decode_recovery_key(&recovery_key).is_ok());
+        assert!(service.validate_recovery_key_format(&recovery_key).is_ok());
     }
 
     #[test]
@@ -316,6 +356,65 @@ mod tests {
         
         // 빈 문자열
         assert!(service.validate_recovery_key_format("").is_err());
+        
+        // 잘못된 단어는 위치와 함께 보고
+        let recovery_key = service.generate_recovery_key().unwrap();
+        let mut words: Vec<&str> = recovery_key.split(' ').collect();
+        words[2] = "vaultx";
+        let message = service.validate_recovery_key_format(&words.join(" ")).unwrap_err().to_string();
+        assert!(message.contains("3번째 단어 'vaultx'"));
+    }
+
+    #[test]
+    fn test_legacy_base64_key_matches_mnemonic() {
+        let service = RecoveryService::new();
+        let key_bytes = [5u8; 32];
+        let legacy = general_purpose::STANDARD.encode(key_bytes);
+        let mnemonic = encode_recovery_mnemonic(&key_bytes);
+        
+        // 같은 키 바이트라면 기존 Base64 해시로 새 24단어 키를 검증할 수 있음
+        let legacy_hash = service.hash_recovery_key(&legacy).unwrap();
+        assert!(service.verify_recovery_key(&mnemonic, &legacy_hash).unwrap());
+        assert!(service.verify_recovery_key(&mnemonic.to_uppercase(), &legacy_hash).unwrap());
+        
+        let salt = [4u8; 32];
+        assert_eq!(
+            service.derive_key_from_recovery_key(&legacy, &salt, Some(1000)).unwrap(),
+            service.derive_key_from_recovery_key(&mnemonic, &salt, Some(1000)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_recovery_shares_reconstruct_key() {
+        let service = RecoveryService::new();
+        let recovery_key = service.generate_recovery_key().unwrap();
+        let hash = service.hash_recovery_key(&recovery_key).unwrap();
+        
+        let shares = service.split_recovery_key(&recovery_key, 2, 3).unwrap();
+        assert_eq!(shares.len(), 3);
+        
+        // 조각 두 개로 복원한 키가 저장된 해시와 일치
+        let restored = service
+            .resolve_recovery_input(None, Some(&shares[1..]))
+            .unwrap();
+        assert_eq!(restored, recovery_key);
+        assert!(service.verify_recovery_key(&restored, &hash).unwrap());
+        
+        // 복구 키 입력란에 줄마다 붙여 넣은 조각도 조각으로 처리
+        let pasted = format!("{}\n\n  {}  \n", shares[0], shares[2]);
+        assert_eq!(
+            service.resolve_recovery_input(Some(&pasted), None).unwrap(),
+            recovery_key
+        );
+        assert_eq!(
+            service.resolve_recovery_input(Some(&recovery_key), None).unwrap(),
+            recovery_key
+        );
+        
+        // 조각 하나만으로는 복원 불가
+        assert!(service.combine_recovery_shares(&shares[..1]).is_err());
+        assert!(service.resolve_recovery_input(Some(&shares[0]), None).is_err());
+        assert!(service.resolve_recovery_input(None, None).is_err());
     }
 
     #[test]
@@ -0,0 +1,423 @@
+// 복구 키 시트 렌더링 서비스
+// 복구 키(24단어), 볼트 ID, 발급일, QR 코드를 담은 인쇄용 시트를 메모리에서 SVG/PNG 바이트로 만듭니다.
+// 네트워크나 외부 도구를 사용하지 않으며, 결과를 디스크에 기록하지 않습니다.
+
+use crate::models::recovery::{RecoveryError, RecoverySheetFormat};
+use crate::utils::mnemonic::{decode_recovery_key, encode_recovery_mnemonic};
+use chrono::{DateTime, Utc};
+use qrcode::{Color, EcLevel, QrCode};
+use uuid::Uuid;
+use zeroize::Zeroize;
+
+/// A4 용지 크기 (mm)
+const PAGE_WIDTH_MM: f64 = 210.0;
+const PAGE_HEIGHT_MM: f64 = 297.0;
+
+/// PNG 출력 크기 (A4, 150dpi)
+const PNG_WIDTH: usize = 1240;
+const PNG_HEIGHT: usize = 1754;
+
+/// 단어 표의 열 수 (24단어 = 3열 × 8행)
+const WORD_COLUMNS: usize = 3;
+
+/// QR 코드 주변 여백 (모듈 수, QR 규격 권장값)
+const QR_QUIET_ZONE: usize = 4;
+
+/// 인쇄용 복구 키 시트
+///
+/// 복구 키는 항상 24단어 형식으로 표시하며, 시트가 해제될 때 메모리에서 지웁니다.
+pub struct RecoverySheet {
+    vault_id: Uuid,
+    issued_at: DateTime<Utc>,
+    mnemonic: String,
+}
+
+impl RecoverySheet {
+    /// 새 복구 키 시트를 만듭니다.
+    ///
+    /// # 매개변수
+    /// * `vault_id` - 볼트 ID
+    /// * `issued_at` - 복구 키 발급 일시
+    /// * `recovery_key` - 복구 키 (24단어 또는 기존 Base64, 시트에는 24단어로 표시)
+    ///
+    /// # 반환값
+    /// * `Result<Self, RecoveryError>` - 시트 또는 복구 키 형식 오류
+    pub fn new(
+        vault_id: Uuid,
+        issued_at: DateTime<Utc>,
+        recovery_key: &str,
+    ) -> Result<Self, RecoveryError> {
+        let mut key_bytes = decode_recovery_key(recovery_key)
+            .map_err(|e| RecoveryError::InvalidFormat(e.to_string()))?;
+        let mnemonic = encode_recovery_mnemonic(&key_bytes);
+        key_bytes.zeroize();
+
+        Ok(Self {
+            vault_id,
+            issued_at,
+            mnemonic,
+        })
+    }
+
+    /// 지정한 형식으로 시트를 렌더링합니다.
+    ///
+    /// # 매개변수
+    /// * `format` - 출력 형식 (SVG 또는 PNG)
+    ///
+    /// # 반환값
+    /// * `Result<Vec<u8>, RecoveryError>` - 렌더링된 파일 바이트
+    pub fn render(&self, format: RecoverySheetFormat) -> Result<Vec<u8>, RecoveryError> {
+        match format {
+            RecoverySheetFormat::Svg => self.render_svg(),
+            RecoverySheetFormat::Png => self.render_png(),
+        }
+    }
+
+    /// A4 SVG 문서로 렌더링합니다.
+    pub fn render_svg(&self) -> Result<Vec<u8>, RecoveryError> {
+        let qr = self.qr_code()?;
+        let mut svg = String::with_capacity(64 * 1024);
+
+        svg.push_str(&format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}mm\" height=\"{h}mm\" viewBox=\"0 0 {w} {h}\">\n",
+            w = PAGE_WIDTH_MM,
+            h = PAGE_HEIGHT_MM
+        ));
+        svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>\n");
+        svg.push_str("<g font-family=\"'Malgun Gothic', 'Apple SD Gothic Neo', 'Noto Sans KR', sans-serif\" fill=\"#000000\">\n");
+
+        svg.push_str(&svg_text(20.0, 25.0, 8.0, "bold", "SecureVault 복구 키 시트"));
+        svg.push_str(&svg_text(
+            20.0,
+            33.0,
+            3.5,
+            "normal",
+            "이 시트를 가진 사람은 PIN 없이 볼트를 열 수 있습니다. 인터넷에 연결되지 않은 안전한 곳에 보관하세요.",
+        ));
+        svg.push_str(&svg_text(20.0, 45.0, 4.0, "normal", &format!("볼트 ID: {}", self.vault_id)));
+        svg.push_str(&svg_text(20.0, 52.0, 4.0, "normal", &format!("발급일: {}", self.issued_date())));
+
+        // 24단어 표 (번호와 함께 3열 × 8행)
+        svg.push_str(&svg_text(20.0, 66.0, 4.5, "bold", "복구 단어 (순서대로 입력)"));
+        for (i, word) in self.mnemonic.split(' ').enumerate() {
+            let column = i / (24 / WORD_COLUMNS);
+            let row = i % (24 / WORD_COLUMNS);
+            let x = 20.0 + column as f64 * 60.0;
+            let y = 76.0 + row as f64 * 8.0;
+            svg.push_str(&svg_text(x, y, 4.5, "normal", &format!("{:>2}. {}", i + 1, word)));
+        }
+        svg.push_str("</g>\n");
+
+        // QR 코드 (어두운 모듈을 행 단위로 묶어 사각형으로 출력)
+        let qr_size_mm = 90.0;
+        let total_modules = qr.width + QR_QUIET_ZONE * 2;
+        let module_mm = qr_size_mm / total_modules as f64;
+        let origin_x = (PAGE_WIDTH_MM - qr_size_mm) / 2.0 + QR_QUIET_ZONE as f64 * module_mm;
+        let origin_y = 150.0 + QR_QUIET_ZONE as f64 * module_mm;
+
+        svg.push_str("<g fill=\"#000000\" shape-rendering=\"crispEdges\">\n");
+        for y in 0..qr.width {
+            let mut x = 0;
+            while x < qr.width {
+                if qr.is_dark(x, y) {
+                    let start = x;
+                    while x < qr.width && qr.is_dark(x, y) {
+                        x += 1;
+                    }
+                    svg.push_str(&format!(
+                        "<rect x=\"{:.3}\" y=\"{:.3}\" width=\"{:.3}\" height=\"{:.3}\"/>\n",
+                        origin_x + start as f64 * module_mm,
+                        origin_y + y as f64 * module_mm,
+                        (x - start) as f64 * module_mm,
+                        module_mm
+                    ));
+                } else {
+                    x += 1;
+                }
+            }
+        }
+        svg.push_str("</g>\n");
+
+        svg.push_str("<g font-family=\"'Malgun Gothic', 'Apple SD Gothic Neo', 'Noto Sans KR', sans-serif\" fill=\"#000000\">\n");
+        svg.push_str(&svg_text(
+            20.0,
+            255.0,
+            3.5,
+            "normal",
+            "QR 코드에는 위의 24단어가 들어 있습니다. 복구 화면에서 단어를 직접 입력하거나 스캔한 내용을 붙여 넣으세요.",
+        ));
+        svg.push_str(&svg_text(
+            20.0,
+            262.0,
+            3.5,
+            "normal",
+            "새 복구 키를 발급하면 이 시트는 더 이상 사용할 수 없습니다. 필요 없어진 시트는 파쇄하세요.",
+        ));
+        svg.push_str("</g>\n</svg>\n");
+
+        Ok(svg.into_bytes())
+    }
+
+    /// A4 150dpi 흑백 PNG로 렌더링합니다.
+    ///
+    /// 외부 글꼴 없이 내장 5×7 비트맵 글꼴을 사용하므로 문구는 영문 대문자로 표시합니다.
+    /// (복구 단어는 대소문자를 구분하지 않음)
+    pub fn render_png(&self) -> Result<Vec<u8>, RecoveryError> {
+        let qr = self.qr_code()?;
+        let mut canvas = Canvas::new(PNG_WIDTH, PNG_HEIGHT);
+
+        canvas.draw_text(100, 100, 6, "SECUREVAULT RECOVERY SHEET");
+        canvas.draw_text(100, 180, 3, "ANYONE WITH THIS SHEET CAN OPEN THE VAULT WITHOUT THE PIN.");
+        canvas.draw_text(100, 240, 4, &format!("VAULT ID: {}", self.vault_id));
+        canvas.draw_text(100, 290, 4, &format!("ISSUED: {}", self.issued_date()));
+
+        for (i, word) in self.mnemonic.split(' ').enumerate() {
+            let column = i / (24 / WORD_COLUMNS);
+            let row = i % (24 / WORD_COLUMNS);
+            let text = format!("{:02}. {}", i + 1, word.to_uppercase());
+            canvas.draw_text(100 + column * 360, 380 + row * 60, 4, &text);
+        }
+
+        // QR 코드는 정수 픽셀 모듈로 그려 스캔이 잘 되도록 함
+        let total_modules = qr.width + QR_QUIET_ZONE * 2;
+        let module_px = (720 / total_modules).max(1);
+        let qr_px = total_modules * module_px;
+        let origin_x = (PNG_WIDTH - qr_px) / 2 + QR_QUIET_ZONE * module_px;
+        let origin_y = 880 + QR_QUIET_ZONE * module_px;
+        for y in 0..qr.width {
+            for x in 0..qr.width {
+                if qr.is_dark(x, y) {
+                    canvas.fill_rect(
+                        origin_x + x * module_px,
+                        origin_y + y * module_px,
+                        module_px,
+                        module_px,
+                    );
+                }
+            }
+        }
+
+        canvas.draw_text(100, 1660, 3, "THE QR CODE HOLDS THE 24 WORDS ABOVE. KEEP THIS SHEET OFFLINE.");
+
+        canvas.encode_png()
+    }
+
+    /// 발급일 문자열 (YYYY-MM-DD, UTC)
+    fn issued_date(&self) -> String {
+        self.issued_at.format("%Y-%m-%d").to_string()
+    }
+
+    /// 24단어를 담은 QR 코드를 만듭니다.
+    fn qr_code(&self) -> Result<QrMatrix, RecoveryError> {
+        let code = QrCode::with_error_correction_level(self.mnemonic.as_bytes(), EcLevel::M)
+            .map_err(|e| RecoveryError::InternalError(format!("QR 코드 생성 실패: {}", e)))?;
+
+        Ok(QrMatrix {
+            width: code.width(),
+            modules: code.to_colors().into_iter().map(|c| c == Color::Dark).collect(),
+        })
+    }
+}
+
+impl Drop for RecoverySheet {
+    fn drop(&mut self) {
+        self.mnemonic.zeroize();
+    }
+}
+
+/// QR 코드 모듈 배열
+struct QrMatrix {
+    width: usize,
+    modules: Vec<bool>,
+}
+
+impl QrMatrix {
+    fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.width + x]
+    }
+}
+
+impl Drop for QrMatrix {
+    fn drop(&mut self) {
+        // QR 모듈에도 복구 키 정보가 들어 있으므로 지움
+        self.modules.iter_mut().for_each(|m| *m = false);
+    }
+}
+
+/// SVG 텍스트 요소를 만듭니다.
+fn svg_text(x: f64, y: f64, size: f64, weight: &str, text: &str) -> String {
+    format!(
+        "<text x=\"{}\" y=\"{}\" font-size=\"{}\" font-weight=\"{}\">{}</text>\n",
+        x,
+        y,
+        size,
+        weight,
+        escape_xml(text)
+    )
+}
+
+/// XML 특수 문자를 이스케이프합니다.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// 8비트 흑백 캔버스 (0 = 검정, 255 = 흰색)
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![255; width * height],
+        }
+    }
+
+    /// 검은 사각형을 그립니다. 캔버스 밖은 잘라냅니다.
+    fn fill_rect(&mut self, x: usize, y: usize, w: usize, h: usize) {
+        for row in y..(y + h).min(self.height) {
+            let start = row * self.width + x.min(self.width);
+            let end = row * self.width + (x + w).min(self.width);
+            self.pixels[start..end].fill(0);
+        }
+    }
+
+    /// 5×7 비트맵 글꼴로 텍스트를 그립니다.
+    fn draw_text(&mut self, x: usize, y: usize, scale: usize, text: &str) {
+        let advance = 6 * scale;
+        for (i, ch) in text.chars().enumerate() {
+            let glyph = glyph(ch);
+            let gx = x + i * advance;
+            for (row, bits) in glyph.iter().enumerate() {
+                for col in 0..5 {
+                    if bits & (0b10000 >> col) != 0 {
+                        self.fill_rect(gx + col * scale, y + row * scale, scale, scale);
+                    }
+                }
+            }
+        }
+    }
+
+    /// PNG 바이트로 인코딩합니다.
+    fn encode_png(&self) -> Result<Vec<u8>, RecoveryError> {
+        let mut bytes = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut bytes, self.width as u32, self.height as u32);
+            encoder.set_color(png::ColorType::Grayscale);
+            encoder.set_depth(png::BitDepth::Eight);
+
+            let mut writer = encoder
+                .write_header()
+                .map_err(|e| RecoveryError::InternalError(format!("PNG 헤더 기록 실패: {}", e)))?;
+            writer
+                .write_image_data(&self.pixels)
+                .map_err(|e| RecoveryError::InternalError(format!("PNG 데이터 기록 실패: {}", e)))?;
+        }
+        Ok(bytes)
+    }
+}
+
+impl Drop for Canvas {
+    fn drop(&mut self) {
+        self.pixels.zeroize();
+    }
+}
+
+/// 5×7 비트맵 글꼴 (행마다 하위 5비트 사용). 지원하지 않는 문자는 공백으로 그립니다.
+fn glyph(ch: char) -> [u8; 7] {
+    match ch.to_ascii_uppercase() {
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        '-' => [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000],
+        ':' => [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000],
+        '.' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100],
+        ',' => [0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b00100, 0b01000],
+        '/' => [0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000],
+        _ => [0; 7],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_sheet() -> RecoverySheet {
+        let recovery_key = encode_recovery_mnemonic(&[11u8; 32]);
+        RecoverySheet::new(Uuid::nil(), Utc::now(), &recovery_key).unwrap()
+    }
+
+    #[test]
+    fn test_render_svg_contains_key_and_vault_id() {
+        let sheet = sample_sheet();
+        let svg = String::from_utf8(sheet.render(RecoverySheetFormat::Svg).unwrap()).unwrap();
+
+        assert!(svg.starts_with("<?xml"));
+        assert!(svg.contains(&Uuid::nil().to_string()));
+        for (i, word) in sheet.mnemonic.split(' ').enumerate() {
+            assert!(svg.contains(&format!("{:>2}. {}", i + 1, word)));
+        }
+        assert!(svg.contains("<rect x="));
+    }
+
+    #[test]
+    fn test_render_png_is_valid_image() {
+        let png_bytes = sample_sheet().render(RecoverySheetFormat::Png).unwrap();
+
+        let decoder = png::Decoder::new(png_bytes.as_slice());
+        let reader = decoder.read_info().unwrap();
+        assert_eq!(reader.info().width as usize, PNG_WIDTH);
+        assert_eq!(reader.info().height as usize, PNG_HEIGHT);
+    }
+
+    #[test]
+    fn test_legacy_key_is_shown_as_mnemonic() {
+        use base64::{engine::general_purpose, Engine as _};
+
+        let legacy = general_purpose::STANDARD.encode([11u8; 32]);
+        let sheet = RecoverySheet::new(Uuid::nil(), Utc::now(), &legacy).unwrap();
+        assert_eq!(sheet.mnemonic, sample_sheet().mnemonic);
+
+        assert!(RecoverySheet::new(Uuid::nil(), Utc::now(), "not a key").is_err());
+    }
+}
@@ -0,0 +1,578 @@
+// 재암호화 작업 서비스
+// 볼트의 모든 파일을 새 파일 데이터 키와 알고리즘으로 다시 암호화하는 재개 가능한 백그라운드 작업을 관리합니다.
+//
+// 작업은 파일 ID 순서로 진행하며, 파일마다 다음 순서로 교체합니다.
+//   1. 복제된 파일 서비스에서 임시 파일(`<id>.enc.reencrypt`)에 새 키로 다시 암호화 (잠금 없음)
+//   2. 파일 서비스 잠금 아래에서 새 래핑 키를 체크포인트에 기록한 뒤 임시 파일을 원본 위치로 이동
+//   3. 메타데이터의 래핑 키를 갱신하고 체크포인트의 커서를 다음 파일로 이동
+//
+// 2와 3 사이에 중단되면 재개할 때 디스크의 컨테이너 키 ID와 체크포인트의 새 키를 비교해
+// 교체가 끝났는지 판단하므로, 어느 시점에 중단되어도 파일을 잃지 않습니다.
+
+use crate::models::{EncryptionAlgorithm, VaultError};
+use crate::services::file::FileService;
+use crate::services::upload_manager::CancellationToken;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// 재암호화 진행률 이벤트 이름
+pub const REENCRYPTION_PROGRESS_EVENT: &str = "reencrypt://progress";
+
+/// 재암호화 작업 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReencryptionStatus {
+    /// 처리 중
+    Running,
+    /// 일시 중지됨 (볼트 잠금, 사용자 요청 또는 비정상 종료, 이어서 진행 가능)
+    Paused,
+    /// 완료됨
+    Completed,
+    /// 실패함 (원인을 해결한 뒤 이어서 진행 가능)
+    Failed,
+}
+
+/// 재암호화 작업 정보
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReencryptionJob {
+    /// 작업 ID
+    pub id: Uuid,
+    /// 대상 암호화 알고리즘
+    pub algorithm: EncryptionAlgorithm,
+    /// 작업 상태
+    pub status: ReencryptionStatus,
+    /// 전체 파일 수
+    pub total_files: u64,
+    /// 처리한 파일 수 (실패와 건너뜀 포함)
+    pub processed_files: u64,
+    /// 마지막으로 처리한 파일 ID (파일 ID 순서로 진행)
+    pub cursor: Option<Uuid>,
+    /// 재암호화에 실패한 파일 ID 목록
+    pub failed: Vec<Uuid>,
+    /// 최고 보안 키가 없어 건너뛴 파일 ID 목록
+    pub skipped: Vec<Uuid>,
+    /// 시작 시간
+    pub started_at: DateTime<Utc>,
+    /// 마지막 체크포인트 시간
+    pub updated_at: DateTime<Utc>,
+    /// 완료 시간
+    pub completed_at: Option<DateTime<Utc>>,
+    /// 오류 메시지
+    pub error: Option<String>,
+}
+
+impl ReencryptionJob {
+    /// 새로운 재암호화 작업을 생성합니다.
+    ///
+    /// # 매개변수
+    /// * `algorithm` - 대상 암호화 알고리즘
+    pub fn new(algorithm: EncryptionAlgorithm) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            algorithm,
+            status: ReencryptionStatus::Running,
+            total_files: 0,
+            processed_files: 0,
+            cursor: None,
+            failed: Vec::new(),
+            skipped: Vec::new(),
+            started_at: now,
+            updated_at: now,
+            completed_at: None,
+            error: None,
+        }
+    }
+
+    /// 이어서 진행할 수 있는 작업인지 확인합니다.
+    ///
+    /// 비정상 종료로 `Running` 상태가 남은 체크포인트도 이어서 진행할 수 있습니다.
+    pub fn is_resumable(&self) -> bool {
+        self.status != ReencryptionStatus::Completed
+    }
+
+    /// 진행률을 반환합니다 (0.0 ~ 1.0).
+    pub fn progress(&self) -> f64 {
+        if self.total_files == 0 {
+            return 1.0;
+        }
+        (self.processed_files as f64 / self.total_files as f64).min(1.0)
+    }
+}
+
+/// 원본 위치로 옮겼지만 메타데이터에 아직 기록되지 않았을 수 있는 교체
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingSwap {
+    /// 파일 ID
+    pub file_id: Uuid,
+    /// 새 래핑된 파일 데이터 키
+    pub wrapped_key: Vec<u8>,
+    /// 새 암호화된 파일 크기
+    pub encrypted_size: u64,
+}
+
+/// 볼트 메타데이터에 저장되는 재암호화 체크포인트
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReencryptionCheckpoint {
+    /// 작업 정보
+    pub job: ReencryptionJob,
+    /// 진행 중인 교체 (래핑된 키는 프론트엔드로 보내지 않도록 작업 정보와 분리)
+    #[serde(default)]
+    pub pending: Option<PendingSwap>,
+}
+
+impl ReencryptionCheckpoint {
+    /// 새 작업의 체크포인트를 생성합니다.
+    pub fn new(algorithm: EncryptionAlgorithm) -> Self {
+        Self {
+            job: ReencryptionJob::new(algorithm),
+            pending: None,
+        }
+    }
+
+    /// 파일 하나의 처리를 마치고 커서를 옮깁니다.
+    ///
+    /// # 매개변수
+    /// * `file_id` - 처리한 파일 ID
+    pub fn advance(&mut self, file_id: Uuid) {
+        self.job.cursor = Some(file_id);
+        self.job.processed_files += 1;
+        self.job.updated_at = Utc::now();
+    }
+}
+
+/// 임시 파일에 다시 암호화된, 교체를 기다리는 파일
+#[derive(Debug)]
+pub struct PreparedReencryption {
+    /// 파일 ID
+    pub file_id: Uuid,
+    /// 다시 암호화할 때 읽은 래핑된 키 (교체 전에 파일이 수정되지 않았는지 확인)
+    pub(crate) previous_wrapped_key: Option<Vec<u8>>,
+    /// 새 컨테이너가 기록된 임시 파일 경로
+    pub(crate) temp_path: PathBuf,
+    /// 새 래핑된 파일 데이터 키
+    pub(crate) wrapped_key: Vec<u8>,
+    /// 새 암호화된 파일 크기
+    pub(crate) encrypted_size: u64,
+}
+
+/// 재암호화 작업을 실행합니다.
+///
+/// 체크포인트의 커서 다음 파일부터 파일 ID 순서로 진행하며, 파일마다 `worker`에서 다시 암호화하고
+/// `commit`으로 교체합니다. `commit`은 볼트의 파일 서비스 잠금 아래에서
+/// `FileService::commit_reencryption`을 호출해야 합니다. 파일마다 체크포인트를 저장하므로
+/// 취소되거나 중단되어도 같은 체크포인트로 이어서 진행할 수 있습니다.
+///
+/// # 매개변수
+/// * `worker` - 재암호화에 사용할 파일 서비스 복제본
+/// * `checkpoint` - 진행할 작업의 체크포인트
+/// * `cancellation_token` - 일시 중지 신호
+/// * `commit` - 준비된 파일을 교체하는 함수
+/// * `progress` - 파일을 처리할 때마다 호출되는 콜백
+///
+/// # 반환값
+/// * `Result<(), VaultError>` - 교체 중 오류 (개별 파일의 복호화 실패는 작업 정보에 기록하고 계속 진행)
+pub fn run_reencryption<C, P>(
+    worker: &mut FileService,
+    checkpoint: &mut ReencryptionCheckpoint,
+    cancellation_token: &CancellationToken,
+    mut commit: C,
+    progress: P,
+) -> Result<(), VaultError>
+where
+    C: FnMut(PreparedReencryption, &mut ReencryptionCheckpoint) -> Result<(), VaultError>,
+    P: Fn(&ReencryptionJob),
+{
+    let cursor = checkpoint.job.cursor;
+    let file_ids: Vec<Uuid> = worker
+        .all_file_ids()?
+        .into_iter()
+        .filter(|id| cursor.is_none_or(|cursor| *id > cursor))
+        .collect();
+
+    checkpoint.job.status = ReencryptionStatus::Running;
+    checkpoint.job.error = None;
+    checkpoint.job.total_files = checkpoint.job.processed_files + file_ids.len() as u64;
+    worker.save_reencryption_checkpoint(checkpoint)?;
+    progress(&checkpoint.job);
+
+    let algorithm = checkpoint.job.algorithm.clone();
+    for file_id in file_ids {
+        if cancellation_token.is_cancelled() {
+            return pause(worker, checkpoint);
+        }
+
+        match worker.prepare_reencryption(&file_id, &algorithm) {
+            Ok(Some(prepared)) => {
+                if let Err(e) = commit(prepared, checkpoint) {
+                    if cancellation_token.is_cancelled() {
+                        return pause(worker, checkpoint);
+                    }
+                    return Err(e);
+                }
+            }
+            Ok(None) => {
+                // 작업 중에 삭제된 파일
+                checkpoint.advance(file_id);
+                worker.save_reencryption_checkpoint(checkpoint)?;
+            }
+            // 잠금으로 키가 지워져 실패한 경우는 기록하지 않고 그 파일부터 다시 진행
+            Err(_) if cancellation_token.is_cancelled() => {
+                return pause(worker, checkpoint);
+            }
+            Err(VaultError::CriticalKeyRequired) => {
+                checkpoint.job.skipped.push(file_id);
+                checkpoint.advance(file_id);
+                worker.save_reencryption_checkpoint(checkpoint)?;
+            }
+            Err(e) => {
+                log::warn!("재암호화 실패: {} ({})", file_id, e);
+                checkpoint.job.failed.push(file_id);
+                checkpoint.advance(file_id);
+                worker.save_reencryption_checkpoint(checkpoint)?;
+            }
+        }
+
+        progress(&checkpoint.job);
+    }
+
+    checkpoint.job.status = ReencryptionStatus::Completed;
+    checkpoint.job.completed_at = Some(Utc::now());
+    checkpoint.job.updated_at = Utc::now();
+    worker.save_reencryption_checkpoint(checkpoint)?;
+
+    log::info!(
+        "재암호화 작업 완료: {} (처리 {}개, 건너뜀 {}개, 실패 {}개)",
+        checkpoint.job.id,
+        checkpoint.job.processed_files,
+        checkpoint.job.skipped.len(),
+        checkpoint.job.failed.len()
+    );
+    Ok(())
+}
+
+/// 작업을 일시 중지 상태로 저장합니다.
+///
+/// 볼트가 잠겨 저장할 수 없으면 마지막 체크포인트가 그대로 남아 이어서 진행할 수 있습니다.
+fn pause(
+    worker: &mut FileService,
+    checkpoint: &mut ReencryptionCheckpoint,
+) -> Result<(), VaultError> {
+    checkpoint.job.status = ReencryptionStatus::Paused;
+    checkpoint.job.updated_at = Utc::now();
+    if let Err(e) = worker.save_reencryption_checkpoint(checkpoint) {
+        log::warn!("재암호화 일시 중지 상태 저장 실패: {}", e);
+    }
+
+    log::info!(
+        "재암호화 작업 일시 중지: {} ({}/{})",
+        checkpoint.job.id,
+        checkpoint.job.processed_files,
+        checkpoint.job.total_files
+    );
+    Ok(())
+}
+
+/// 재암호화 관리자
+/// 실행 중인 재암호화 작업의 상태와 일시 중지 신호를 관리합니다.
+#[derive(Debug, Clone, Default)]
+pub struct ReencryptionManager {
+    /// 실행 중이거나 마지막으로 실행한 작업
+    job: Arc<Mutex<Option<ReencryptionJob>>>,
+    /// 실행 중인 작업의 취소 토큰
+    cancellation_token: Arc<Mutex<Option<CancellationToken>>>,
+}
+
+impl ReencryptionManager {
+    /// 새로운 재암호화 관리자를 생성합니다.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 작업이 실행 중인지 확인합니다.
+    pub fn is_running(&self) -> bool {
+        self.cancellation_token.lock().unwrap().is_some()
+    }
+
+    /// 작업 시작을 표시합니다.
+    ///
+    /// # 반환값
+    /// * `Option<CancellationToken>` - 이미 실행 중인 작업이 있으면 None
+    pub fn mark_started(&self, job: &ReencryptionJob) -> Option<CancellationToken> {
+        let mut token = self.cancellation_token.lock().unwrap();
+        if token.is_some() {
+            return None;
+        }
+
+        let new_token = CancellationToken::new();
+        *token = Some(new_token.clone());
+        *self.job.lock().unwrap() = Some(job.clone());
+        log::info!("재암호화 작업 시작: {}", job.id);
+        Some(new_token)
+    }
+
+    /// 작업 진행 상태를 갱신합니다.
+    pub fn update(&self, job: &ReencryptionJob) {
+        *self.job.lock().unwrap() = Some(job.clone());
+    }
+
+    /// 작업 종료(완료, 일시 중지, 실패)를 표시합니다.
+    pub fn mark_finished(&self, job: &ReencryptionJob) {
+        self.update(job);
+        *self.cancellation_token.lock().unwrap() = None;
+    }
+
+    /// 실행 중인 작업을 일시 중지합니다.
+    ///
+    /// # 반환값
+    /// * `bool` - 실행 중인 작업이 있었는지 여부
+    pub fn pause(&self) -> bool {
+        match self.cancellation_token.lock().unwrap().as_ref() {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 이 세션에서 실행한 작업 정보를 조회합니다.
+    pub fn get_job(&self) -> Option<ReencryptionJob> {
+        self.job.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SecretKey;
+    use crate::services::container::{self, ContainerHeader};
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// 파일 두 개가 있는 볼트를 만듭니다. 파일은 작업 순서와 같은 ID 순으로 내용과 함께 반환됩니다.
+    async fn vault_with_files(temp_dir: &TempDir) -> (FileService, Vec<(Uuid, &'static str)>) {
+        let vault_path = temp_dir.path().to_str().unwrap();
+        let mut file_service = FileService::new();
+        file_service
+            .initialize(vault_path, Arc::new(SecretKey::from_bytes(&mut [5u8; 32])))
+            .await
+            .unwrap();
+
+        let mut files = Vec::new();
+        for (name, content) in [("a.txt", "첫 번째"), ("b.txt", "두 번째")] {
+            let entry = file_service
+                .create_new_file(None, name, content)
+                .await
+                .unwrap();
+            files.push((entry.id, content));
+        }
+        files.sort();
+        (file_service, files)
+    }
+
+    /// 디스크에 있는 파일의 컨테이너 헤더를 읽습니다.
+    fn header_on_disk(temp_dir: &TempDir, file_id: &Uuid) -> ContainerHeader {
+        let path = temp_dir
+            .path()
+            .join(".securevault")
+            .join("files")
+            .join(format!("{}.enc", file_id));
+        let data = fs::read(path).unwrap();
+        ContainerHeader::parse(&data[..container::CONTAINER_HEADER_LEN]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reencrypts_every_file_to_new_algorithm() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut file_service, files) = vault_with_files(&temp_dir).await;
+        let old_key_ids: Vec<_> = files
+            .iter()
+            .map(|(id, _)| header_on_disk(&temp_dir, id).key_id)
+            .collect();
+
+        let mut worker = file_service.clone();
+        let mut checkpoint = ReencryptionCheckpoint::new(EncryptionAlgorithm::ChaCha20Poly1305);
+        run_reencryption(
+            &mut worker,
+            &mut checkpoint,
+            &CancellationToken::new(),
+            |prepared, checkpoint| file_service.commit_reencryption(prepared, checkpoint),
+            |_| {},
+        )
+        .unwrap();
+
+        assert_eq!(checkpoint.job.status, ReencryptionStatus::Completed);
+        assert_eq!(checkpoint.job.processed_files, 2);
+        assert!(checkpoint.job.failed.is_empty());
+
+        for ((id, content), old_key_id) in files.iter().zip(old_key_ids) {
+            let header = header_on_disk(&temp_dir, id);
+            assert_eq!(header.algorithm, EncryptionAlgorithm::ChaCha20Poly1305);
+            assert_ne!(header.key_id, old_key_id);
+            assert_eq!(
+                file_service.get_file_content(&id.to_string()).unwrap(),
+                content.as_bytes()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_paused_job_resumes_from_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut file_service, files) = vault_with_files(&temp_dir).await;
+
+        // 첫 파일을 교체한 직후 일시 중지
+        let token = CancellationToken::new();
+        let mut worker = file_service.clone();
+        let mut checkpoint = ReencryptionCheckpoint::new(EncryptionAlgorithm::ChaCha20Poly1305);
+        run_reencryption(
+            &mut worker,
+            &mut checkpoint,
+            &token,
+            |prepared, checkpoint| {
+                let result = file_service.commit_reencryption(prepared, checkpoint);
+                token.cancel();
+                result
+            },
+            |_| {},
+        )
+        .unwrap();
+
+        let saved = file_service
+            .load_reencryption_checkpoint()
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.job.status, ReencryptionStatus::Paused);
+        assert_eq!(saved.job.cursor, Some(files[0].0));
+        assert_eq!(
+            header_on_disk(&temp_dir, &files[1].0).algorithm,
+            EncryptionAlgorithm::AES256GCM
+        );
+
+        // 저장된 체크포인트로 이어서 진행
+        let mut checkpoint = saved;
+        run_reencryption(
+            &mut worker,
+            &mut checkpoint,
+            &CancellationToken::new(),
+            |prepared, checkpoint| file_service.commit_reencryption(prepared, checkpoint),
+            |_| {},
+        )
+        .unwrap();
+
+        assert_eq!(checkpoint.job.status, ReencryptionStatus::Completed);
+        assert_eq!(checkpoint.job.processed_files, 2);
+        assert_eq!(
+            header_on_disk(&temp_dir, &files[1].0).algorithm,
+            EncryptionAlgorithm::ChaCha20Poly1305
+        );
+        assert_eq!(
+            file_service
+                .get_file_content(&files[1].0.to_string())
+                .unwrap(),
+            files[1].1.as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recovers_swap_interrupted_before_metadata_update() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut file_service, files) = vault_with_files(&temp_dir).await;
+
+        // 새 파일을 원본 위치로 옮긴 직후(메타데이터 갱신 전) 중단된 상황을 재현
+        let mut checkpoint = ReencryptionCheckpoint::new(EncryptionAlgorithm::ChaCha20Poly1305);
+        let prepared = file_service
+            .prepare_reencryption(&files[0].0, &EncryptionAlgorithm::ChaCha20Poly1305)
+            .unwrap()
+            .unwrap();
+        let encrypted_path = prepared.temp_path.with_extension("");
+        checkpoint.pending = Some(PendingSwap {
+            file_id: files[0].0,
+            wrapped_key: prepared.wrapped_key.clone(),
+            encrypted_size: prepared.encrypted_size,
+        });
+        fs::rename(&prepared.temp_path, &encrypted_path).unwrap();
+        assert!(file_service
+            .get_file_content(&files[0].0.to_string())
+            .is_err());
+
+        file_service.recover_reencryption(&mut checkpoint).unwrap();
+        assert!(checkpoint.pending.is_none());
+        assert_eq!(checkpoint.job.cursor, Some(files[0].0));
+        assert_eq!(
+            file_service
+                .get_file_content(&files[0].0.to_string())
+                .unwrap(),
+            files[0].1.as_bytes()
+        );
+
+        // 이동 전에 중단되었다면 원본을 그대로 두고 같은 파일부터 다시 진행
+        let mut checkpoint = ReencryptionCheckpoint::new(EncryptionAlgorithm::ChaCha20Poly1305);
+        let prepared = file_service
+            .prepare_reencryption(&files[1].0, &EncryptionAlgorithm::ChaCha20Poly1305)
+            .unwrap()
+            .unwrap();
+        checkpoint.pending = Some(PendingSwap {
+            file_id: files[1].0,
+            wrapped_key: prepared.wrapped_key.clone(),
+            encrypted_size: prepared.encrypted_size,
+        });
+
+        file_service.recover_reencryption(&mut checkpoint).unwrap();
+        assert!(checkpoint.pending.is_none());
+        assert_eq!(checkpoint.job.cursor, None);
+        assert!(!prepared.temp_path.exists());
+        assert_eq!(
+            file_service
+                .get_file_content(&files[1].0.to_string())
+                .unwrap(),
+            files[1].1.as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reencrypts_every_file_under_rotated_data_key() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().to_str().unwrap();
+        let (mut file_service, files) = vault_with_files(&temp_dir).await;
+
+        // 데이터 키를 교체하고 DB 키를 바꾼 뒤 이전 키는 읽기용으로만 남김
+        let previous_key = Arc::new(SecretKey::from_bytes(&mut [5u8; 32]));
+        let new_key = Arc::new(SecretKey::from_bytes(&mut [6u8; 32]));
+        file_service.rekey_database(&new_key).unwrap();
+        file_service.set_previous_master_key(Some(previous_key));
+        file_service.set_vault_info(vault_path, Arc::clone(&new_key));
+        assert_eq!(
+            file_service
+                .get_file_content(&files[0].0.to_string())
+                .unwrap(),
+            files[0].1.as_bytes()
+        );
+
+        let mut worker = file_service.clone();
+        let mut checkpoint = ReencryptionCheckpoint::new(EncryptionAlgorithm::AES256GCM);
+        run_reencryption(
+            &mut worker,
+            &mut checkpoint,
+            &CancellationToken::new(),
+            |prepared, checkpoint| file_service.commit_reencryption(prepared, checkpoint),
+            |_| {},
+        )
+        .unwrap();
+        assert!(checkpoint.job.failed.is_empty());
+
+        // 새 데이터 키만으로 모든 파일을 열 수 있음
+        let mut reopened = FileService::new();
+        reopened.initialize(vault_path, new_key).await.unwrap();
+        for (id, content) in &files {
+            assert_eq!(
+                reopened.get_file_content(&id.to_string()).unwrap(),
+                content.as_bytes()
+            );
+        }
+    }
+}
@@ -229,6 +229,18 @@ impl UploadManager {
         false
     }
 
+    /// 대기 중이거나 실행 중인 모든 작업을 취소합니다. (볼트 잠금 시)
+    ///
+    /// # 반환값
+    /// * `usize` - 취소된 작업 수
+    pub fn cancel_all_jobs(&self) -> usize {
+        let job_ids: Vec<Uuid> = self.jobs.lock().unwrap().keys().copied().collect();
+        job_ids
+            .iter()
+            .filter(|job_id| self.cancel_job(job_id))
+            .count()
+    }
+
     /// 작업 상태를 조회합니다.
     pub fn get_job(&self, job_id: &Uuid) -> Option<UploadJob> {
         let jobs = self.jobs.lock().unwrap();
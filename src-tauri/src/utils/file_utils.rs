@@ -111,6 +111,35 @@ pub fn format_file_size(size: u64) -> String {
     }
 }
 
+/// 파일 내용을 0으로 덮어쓰고 디스크에 반영한 뒤 삭제합니다.
+/// 
+/// 삭제만 하면 해제된 블록에 원래 내용이 남으므로 평문이나 키 재료가 들어 있던 파일을 지울 때 사용합니다.
+/// SSD의 웨어 레벨링이나 파일 시스템 저널에 남은 사본까지 지우지는 못합니다.
+/// 
+/// # 매개변수
+/// * `file_path` - 삭제할 파일 경로
+/// 
+/// # 반환값
+/// * `std::io::Result<()>` - 삭제 결과
+pub fn overwrite_and_remove_file(file_path: &Path) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let file_size = std::fs::metadata(file_path)?.len();
+    let mut file = std::fs::OpenOptions::new().write(true).open(file_path)?;
+
+    let zeros = [0u8; 8192];
+    let mut remaining = file_size;
+    while remaining > 0 {
+        let len = remaining.min(zeros.len() as u64) as usize;
+        file.write_all(&zeros[..len])?;
+        remaining -= len as u64;
+    }
+    file.sync_all()?;
+    drop(file);
+
+    std::fs::remove_file(file_path)
+}
+
 /// 안전한 파일명을 생성합니다.
 /// 
 /// 파일명에서 위험한 문자들을 제거하고 안전한 형태로 변환합니다.
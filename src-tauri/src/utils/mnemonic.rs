@@ -0,0 +1,256 @@
+// 복구 키 니모닉 유틸리티
+// 256비트 복구 키를 BIP39 영어 단어 목록 기반 24단어로 변환하고 다시 파싱합니다.
+
+use base64::{engine::general_purpose, Engine as _};
+use bip39::{Language, Mnemonic};
+use std::fmt;
+use zeroize::Zeroize;
+
+/// 복구 키 니모닉의 단어 수 (256비트 엔트로피 + 8비트 체크섬 = 24 × 11비트)
+pub const RECOVERY_MNEMONIC_WORD_COUNT: usize = 24;
+
+/// 복구 키 바이트 길이
+pub const RECOVERY_KEY_LENGTH: usize = 32;
+
+/// 복구 키 파싱 오류
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryKeyFormatError {
+    /// 입력이 비어 있음
+    Empty,
+    /// 단어 수가 24개가 아님
+    WordCount(usize),
+    /// 단어 목록에 없는 단어 (1부터 시작하는 위치)
+    UnknownWord { position: usize, word: String },
+    /// 체크섬 불일치 (단어 순서가 바뀌었거나 다른 단어로 잘못 입력됨)
+    Checksum,
+    /// 기존 Base64 형식 디코딩 실패
+    InvalidBase64,
+    /// 디코딩한 키 길이가 32바이트가 아님
+    InvalidLength(usize),
+}
+
+impl fmt::Display for RecoveryKeyFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "복구 키를 입력해주세요."),
+            Self::WordCount(count) => write!(
+                f,
+                "복구 키는 {}개의 단어여야 합니다. (입력된 단어: {}개)",
+                RECOVERY_MNEMONIC_WORD_COUNT, count
+            ),
+            Self::UnknownWord { position, word } => {
+                write!(f, "{}번째 단어 '{}'은(는) 올바른 복구 단어가 아닙니다.", position, word)
+            }
+            Self::Checksum => write!(
+                f,
+                "복구 키 체크섬이 일치하지 않습니다. 단어의 순서나 철자를 확인해주세요."
+            ),
+            Self::InvalidBase64 => write!(f, "올바르지 않은 Base64 형식의 복구 키입니다."),
+            Self::InvalidLength(len) => write!(
+                f,
+                "복구 키는 32바이트(256비트)여야 합니다. (현재: {}바이트)",
+                len
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RecoveryKeyFormatError {}
+
+/// 256비트 복구 키를 24단어 니모닉으로 인코딩합니다.
+///
+/// # 매개변수
+/// * `key` - 32바이트 복구 키
+///
+/// # 반환값
+/// * `String` - 공백으로 구분된 24개의 소문자 영어 단어
+pub fn encode_recovery_mnemonic(key: &[u8; RECOVERY_KEY_LENGTH]) -> String {
+    // 32바이트 엔트로피는 BIP39가 허용하는 길이이므로 실패하지 않음
+    let mut mnemonic = Mnemonic::from_entropy_in(Language::English, key)
+        .expect("32바이트 엔트로피는 항상 유효합니다");
+    let phrase = mnemonic.to_string();
+    mnemonic.zeroize();
+    phrase
+}
+
+/// 24단어 니모닉 또는 기존 Base64 복구 키를 32바이트 키로 디코딩합니다.
+///
+/// 대소문자와 공백(연속 공백, 줄바꿈, 탭 포함)의 차이는 무시합니다.
+/// 공백 없이 입력된 한 덩어리 문자열은 기존 Base64 복구 키로 처리합니다.
+///
+/// # 매개변수
+/// * `input` - 사용자가 입력한 복구 키
+///
+/// # 반환값
+/// * `Result<[u8; 32], RecoveryKeyFormatError>` - 복구 키 바이트 또는 어느 부분이 잘못됐는지 알려주는 오류
+pub fn decode_recovery_key(input: &str) -> Result<[u8; RECOVERY_KEY_LENGTH], RecoveryKeyFormatError> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(RecoveryKeyFormatError::Empty);
+    }
+
+    if trimmed.split_whitespace().nth(1).is_none() && !is_mnemonic_word(trimmed) {
+        return decode_legacy_base64(trimmed);
+    }
+
+    decode_mnemonic(trimmed)
+}
+
+/// 입력이 복구 키 형식으로 해석 가능한지 확인합니다.
+pub fn is_valid_recovery_key_format(input: &str) -> bool {
+    match decode_recovery_key(input) {
+        Ok(mut key) => {
+            key.zeroize();
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// 단어 하나가 니모닉 단어 목록에 있는지 확인합니다.
+fn is_mnemonic_word(word: &str) -> bool {
+    Language::English.find_word(&word.to_lowercase()).is_some()
+}
+
+/// 24단어 니모닉을 디코딩합니다.
+fn decode_mnemonic(input: &str) -> Result<[u8; RECOVERY_KEY_LENGTH], RecoveryKeyFormatError> {
+    let words: Vec<String> = input.split_whitespace().map(|w| w.to_lowercase()).collect();
+
+    // 어느 단어가 틀렸는지 먼저 알려주기 위해 단어 수보다 앞서 검사
+    if let Some((index, word)) = words
+        .iter()
+        .enumerate()
+        .find(|(_, w)| Language::English.find_word(w).is_none())
+    {
+        return Err(RecoveryKeyFormatError::UnknownWord {
+            position: index + 1,
+            word: word.clone(),
+        });
+    }
+
+    if words.len() != RECOVERY_MNEMONIC_WORD_COUNT {
+        return Err(RecoveryKeyFormatError::WordCount(words.len()));
+    }
+
+    let mut normalized = words.join(" ");
+    let parsed = Mnemonic::parse_in_normalized(Language::English, &normalized);
+    normalized.zeroize();
+
+    let mut mnemonic = parsed.map_err(|e| match e {
+        bip39::Error::InvalidChecksum => RecoveryKeyFormatError::Checksum,
+        bip39::Error::BadWordCount(count) => RecoveryKeyFormatError::WordCount(count),
+        bip39::Error::UnknownWord(index) => RecoveryKeyFormatError::UnknownWord {
+            position: index + 1,
+            word: words[index].clone(),
+        },
+        _ => RecoveryKeyFormatError::Checksum,
+    })?;
+
+    let (mut entropy, len) = mnemonic.to_entropy_array();
+    mnemonic.zeroize();
+
+    let mut key = [0u8; RECOVERY_KEY_LENGTH];
+    let result = if len == RECOVERY_KEY_LENGTH {
+        key.copy_from_slice(&entropy[..RECOVERY_KEY_LENGTH]);
+        Ok(key)
+    } else {
+        Err(RecoveryKeyFormatError::InvalidLength(len))
+    };
+    entropy.zeroize();
+
+    result
+}
+
+/// 기존 Base64 형식 복구 키를 디코딩합니다.
+fn decode_legacy_base64(input: &str) -> Result<[u8; RECOVERY_KEY_LENGTH], RecoveryKeyFormatError> {
+    let mut bytes = general_purpose::STANDARD
+        .decode(input)
+        .map_err(|_| RecoveryKeyFormatError::InvalidBase64)?;
+
+    let result = if bytes.len() == RECOVERY_KEY_LENGTH {
+        let mut key = [0u8; RECOVERY_KEY_LENGTH];
+        key.copy_from_slice(&bytes);
+        Ok(key)
+    } else {
+        Err(RecoveryKeyFormatError::InvalidLength(bytes.len()))
+    };
+    bytes.zeroize();
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mnemonic_round_trip() {
+        let key = [7u8; 32];
+        let phrase = encode_recovery_mnemonic(&key);
+
+        assert_eq!(phrase.split(' ').count(), RECOVERY_MNEMONIC_WORD_COUNT);
+        assert_eq!(decode_recovery_key(&phrase).unwrap(), key);
+
+        // 대소문자와 공백 차이는 무시
+        let messy = format!("  {}\n", phrase.to_uppercase().replace(' ', " \t "));
+        assert_eq!(decode_recovery_key(&messy).unwrap(), key);
+    }
+
+    #[test]
+    fn test_known_vector() {
+        // BIP39 공식 테스트 벡터 (엔트로피 0x00 × 32)
+        let phrase = encode_recovery_mnemonic(&[0u8; 32]);
+        assert_eq!(
+            phrase,
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art"
+        );
+    }
+
+    #[test]
+    fn test_reports_wrong_word() {
+        let phrase = encode_recovery_mnemonic(&[9u8; 32]);
+        let mut words: Vec<&str> = phrase.split(' ').collect();
+        words[4] = "notaword";
+
+        assert_eq!(
+            decode_recovery_key(&words.join(" ")),
+            Err(RecoveryKeyFormatError::UnknownWord {
+                position: 5,
+                word: "notaword".to_string(),
+            })
+        );
+
+        // 단어 목록에 있지만 다른 단어로 바뀌면 체크섬으로 감지
+        let mut words: Vec<&str> = phrase.split(' ').collect();
+        words.swap(0, 1);
+        if words[0] != words[1] {
+            assert_eq!(
+                decode_recovery_key(&words.join(" ")),
+                Err(RecoveryKeyFormatError::Checksum)
+            );
+        }
+
+        assert_eq!(
+            decode_recovery_key(&words[..23].join(" ")),
+            Err(RecoveryKeyFormatError::WordCount(23))
+        );
+    }
+
+    #[test]
+    fn test_accepts_legacy_base64() {
+        let key = [3u8; 32];
+        let legacy = general_purpose::STANDARD.encode(key);
+        assert_eq!(decode_recovery_key(&legacy).unwrap(), key);
+
+        let short = general_purpose::STANDARD.encode([3u8; 16]);
+        assert_eq!(
+            decode_recovery_key(&short),
+            Err(RecoveryKeyFormatError::InvalidLength(16))
+        );
+        assert_eq!(
+            decode_recovery_key("invalid_base64!"),
+            Err(RecoveryKeyFormatError::InvalidBase64)
+        );
+        assert_eq!(decode_recovery_key("   "), Err(RecoveryKeyFormatError::Empty));
+    }
+}
@@ -5,6 +5,8 @@ pub mod file_utils;
 pub mod crypto_utils;
 pub mod validation;
 pub mod parallel_benchmark;
+pub mod mnemonic;
+pub mod shamir;
 
 // 유틸리티 함수들을 재내보내기
 pub use file_utils::*;
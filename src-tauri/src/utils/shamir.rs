@@ -0,0 +1,359 @@
+// 복구 키 Shamir 비밀 분산 유틸리티
+// 256비트 복구 키를 N개의 조각으로 나누고, 그중 M개로 원래 키를 복원합니다.
+// GF(2^8) 위에서 바이트 단위로 (M-1)차 다항식을 만들어 x = 1..N 지점의 값을 조각으로 사용합니다.
+
+use rand::{rngs::OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fmt;
+use zeroize::Zeroize;
+
+/// 조각 문자열 접두사 (형식 버전 포함)
+pub const RECOVERY_SHARE_PREFIX: &str = "SVRS1";
+
+/// 최소 복원 조각 수 (한 사람이 혼자 복구할 수 없도록 2 이상)
+pub const MIN_SHARE_THRESHOLD: u8 = 2;
+
+/// 조각 세트 식별자 도메인 분리 문자열
+const SHARE_SET_CONTEXT: &[u8] = b"SecureVault-RecoveryShare-Set-v1";
+
+/// 조각 체크섬 도메인 분리 문자열
+const SHARE_CHECKSUM_CONTEXT: &[u8] = b"SecureVault-RecoveryShare-Checksum-v1";
+
+/// 복구 키 조각 오류
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryShareError {
+    /// 조각 수 또는 임계값이 잘못됨
+    InvalidParameters { threshold: u8, share_count: u8 },
+    /// 조각 문자열 형식이 잘못됨 (1부터 시작하는 입력 순서)
+    InvalidFormat(usize),
+    /// 조각 체크섬 불일치 (오타 등)
+    Checksum(usize),
+    /// 서로 다른 복구 키에서 만든 조각이 섞임
+    MixedSets,
+    /// 같은 번호의 조각이 중복됨
+    DuplicateIndex(u8),
+    /// 복원에 필요한 조각 수가 부족함
+    NotEnoughShares { required: u8, provided: usize },
+    /// 조각은 유효하지만 복원된 키가 세트와 일치하지 않음
+    ReconstructionFailed,
+}
+
+impl fmt::Display for RecoveryShareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidParameters { threshold, share_count } => write!(
+                f,
+                "복원 조각 수({})는 {} 이상이고 전체 조각 수({}) 이하여야 합니다.",
+                threshold, MIN_SHARE_THRESHOLD, share_count
+            ),
+            Self::InvalidFormat(position) => {
+                write!(f, "{}번째 복구 키 조각의 형식이 올바르지 않습니다.", position)
+            }
+            Self::Checksum(position) => write!(
+                f,
+                "{}번째 복구 키 조각의 체크섬이 일치하지 않습니다. 오타가 없는지 확인해주세요.",
+                position
+            ),
+            Self::MixedSets => write!(f, "서로 다른 복구 키의 조각이 섞여 있습니다."),
+            Self::DuplicateIndex(index) => write!(f, "{}번 조각이 중복 입력되었습니다.", index),
+            Self::NotEnoughShares { required, provided } => write!(
+                f,
+                "복구 키를 복원하려면 조각이 {}개 필요합니다. (입력된 조각: {}개)",
+                required, provided
+            ),
+            Self::ReconstructionFailed => write!(
+                f,
+                "조각으로 복구 키를 복원하지 못했습니다. 같은 시점에 나눈 조각인지 확인해주세요."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RecoveryShareError {}
+
+/// 파싱된 복구 키 조각
+#[derive(Clone)]
+struct RecoveryShare {
+    set_id: [u8; 4],
+    threshold: u8,
+    index: u8,
+    value: [u8; 32],
+}
+
+impl Drop for RecoveryShare {
+    fn drop(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+/// 복구 키를 `share_count`개의 조각으로 나눕니다. 그중 `threshold`개가 있으면 복원할 수 있습니다.
+///
+/// # 매개변수
+/// * `secret` - 32바이트 복구 키
+/// * `threshold` - 복원에 필요한 조각 수 (M)
+/// * `share_count` - 생성할 전체 조각 수 (N)
+///
+/// # 반환값
+/// * `Result<Vec<String>, RecoveryShareError>` - 번호·체크섬이 포함된 조각 문자열 목록
+pub fn split_secret(
+    secret: &[u8; 32],
+    threshold: u8,
+    share_count: u8,
+) -> Result<Vec<String>, RecoveryShareError> {
+    if threshold < MIN_SHARE_THRESHOLD || threshold > share_count {
+        return Err(RecoveryShareError::InvalidParameters { threshold, share_count });
+    }
+
+    let set_id = share_set_id(secret);
+
+    // 바이트마다 상수항이 비밀값인 (M-1)차 다항식의 계수
+    let mut coefficients = vec![0u8; 32 * (threshold as usize - 1)];
+    OsRng.fill_bytes(&mut coefficients);
+
+    let mut shares = Vec::with_capacity(share_count as usize);
+    for index in 1..=share_count {
+        let mut value = [0u8; 32];
+        for (i, byte) in value.iter_mut().enumerate() {
+            // 호너 방식으로 x = index 지점의 값 계산
+            let mut acc = 0u8;
+            for degree in (1..threshold as usize).rev() {
+                acc = gf_mul(acc, index) ^ coefficients[(degree - 1) * 32 + i];
+            }
+            *byte = gf_mul(acc, index) ^ secret[i];
+        }
+
+        let share = RecoveryShare { set_id, threshold, index, value };
+        shares.push(encode_share(&share));
+    }
+    coefficients.zeroize();
+
+    Ok(shares)
+}
+
+/// 조각들로 복구 키를 복원합니다.
+///
+/// # 매개변수
+/// * `shares` - 조각 문자열 목록 (대소문자와 공백은 무시)
+///
+/// # 반환값
+/// * `Result<[u8; 32], RecoveryShareError>` - 복원된 32바이트 복구 키
+pub fn combine_shares(shares: &[String]) -> Result<[u8; 32], RecoveryShareError> {
+    let parsed = shares
+        .iter()
+        .filter(|s| !s.trim().is_empty())
+        .enumerate()
+        .map(|(i, s)| decode_share(s, i + 1))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let first = parsed.first().ok_or(RecoveryShareError::NotEnoughShares {
+        required: MIN_SHARE_THRESHOLD,
+        provided: 0,
+    })?;
+    let (set_id, threshold) = (first.set_id, first.threshold);
+
+    if parsed.iter().any(|s| s.set_id != set_id || s.threshold != threshold) {
+        return Err(RecoveryShareError::MixedSets);
+    }
+
+    let mut seen = HashSet::new();
+    if let Some(dup) = parsed.iter().find(|s| !seen.insert(s.index)) {
+        return Err(RecoveryShareError::DuplicateIndex(dup.index));
+    }
+
+    if parsed.len() < threshold as usize {
+        return Err(RecoveryShareError::NotEnoughShares {
+            required: threshold,
+            provided: parsed.len(),
+        });
+    }
+
+    // 라그랑주 보간으로 x = 0 지점(상수항) 계산
+    let used = &parsed[..threshold as usize];
+    let mut secret = [0u8; 32];
+    for (j, share_j) in used.iter().enumerate() {
+        let mut basis = 1u8;
+        for (m, share_m) in used.iter().enumerate() {
+            if m != j {
+                // l_j(0) = Π x_m / (x_m - x_j), GF(2^8)에서 뺄셈은 XOR
+                basis = gf_mul(basis, gf_div(share_m.index, share_m.index ^ share_j.index));
+            }
+        }
+        for (byte, value) in secret.iter_mut().zip(share_j.value.iter()) {
+            *byte ^= gf_mul(basis, *value);
+        }
+    }
+
+    if share_set_id(&secret) != set_id {
+        secret.zeroize();
+        return Err(RecoveryShareError::ReconstructionFailed);
+    }
+
+    Ok(secret)
+}
+
+/// 입력이 복구 키 조각 형식처럼 보이는지 확인합니다.
+pub fn looks_like_share(input: &str) -> bool {
+    input
+        .trim()
+        .to_uppercase()
+        .starts_with(&format!("{}-", RECOVERY_SHARE_PREFIX))
+}
+
+/// 복구 키로부터 조각 세트 식별자를 계산합니다. 복원 결과 검증에도 사용합니다.
+fn share_set_id(secret: &[u8; 32]) -> [u8; 4] {
+    let mut hasher = Sha256::new();
+    hasher.update(SHARE_SET_CONTEXT);
+    hasher.update(secret);
+    let digest = hasher.finalize();
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+/// 조각 내용의 체크섬을 계산합니다.
+fn share_checksum(set_id: &[u8; 4], threshold: u8, index: u8, value: &[u8; 32]) -> [u8; 4] {
+    let mut hasher = Sha256::new();
+    hasher.update(SHARE_CHECKSUM_CONTEXT);
+    hasher.update(set_id);
+    hasher.update([threshold, index]);
+    hasher.update(value);
+    let digest = hasher.finalize();
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+/// 조각을 `SVRS1-세트-임계값-번호-값-체크섬` 형식 문자열로 인코딩합니다.
+fn encode_share(share: &RecoveryShare) -> String {
+    let checksum = share_checksum(&share.set_id, share.threshold, share.index, &share.value);
+    format!(
+        "{}-{}-{}-{}-{}-{}",
+        RECOVERY_SHARE_PREFIX,
+        hex::encode(share.set_id),
+        share.threshold,
+        share.index,
+        hex::encode(share.value),
+        hex::encode(checksum)
+    )
+}
+
+/// 조각 문자열을 파싱하고 체크섬을 검증합니다.
+fn decode_share(input: &str, position: usize) -> Result<RecoveryShare, RecoveryShareError> {
+    let normalized: String = input
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase();
+    let parts: Vec<&str> = normalized.split('-').collect();
+
+    let invalid = || RecoveryShareError::InvalidFormat(position);
+    if parts.len() != 6 || !parts[0].eq_ignore_ascii_case(RECOVERY_SHARE_PREFIX) {
+        return Err(invalid());
+    }
+
+    let set_id: [u8; 4] = hex::decode(parts[1])
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(invalid)?;
+    let threshold: u8 = parts[2].parse().map_err(|_| invalid())?;
+    let index: u8 = parts[3].parse().map_err(|_| invalid())?;
+    let mut value_bytes = hex::decode(parts[4]).map_err(|_| invalid())?;
+    let checksum = hex::decode(parts[5]).map_err(|_| invalid())?;
+
+    if value_bytes.len() != 32 || threshold < MIN_SHARE_THRESHOLD || index == 0 {
+        value_bytes.zeroize();
+        return Err(invalid());
+    }
+
+    let mut value = [0u8; 32];
+    value.copy_from_slice(&value_bytes);
+    value_bytes.zeroize();
+
+    let share = RecoveryShare { set_id, threshold, index, value };
+    if share_checksum(&set_id, threshold, index, &share.value)[..] != checksum[..] {
+        return Err(RecoveryShareError::Checksum(position));
+    }
+
+    Ok(share)
+}
+
+/// GF(2^8) 곱셈 (AES 기약 다항식 x^8 + x^4 + x^3 + x + 1). 테이블 없이 분기 없는 연산으로 계산합니다.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+/// GF(2^8) 나눗셈. 역원은 a^254로 계산합니다. (b는 0이 아니어야 함)
+fn gf_div(a: u8, b: u8) -> u8 {
+    let mut inverse = 1u8;
+    let mut base = b;
+    let mut exponent = 254u8;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            inverse = gf_mul(inverse, base);
+        }
+        base = gf_mul(base, base);
+        exponent >>= 1;
+    }
+    gf_mul(a, inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_and_combine_any_subset() {
+        let secret = [42u8; 32];
+        let shares = split_secret(&secret, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        // 임의의 3개 조합으로 복원
+        for subset in [[0, 1, 2], [0, 2, 4], [1, 3, 4], [4, 2, 1]] {
+            let picked: Vec<String> = subset.iter().map(|&i| shares[i].clone()).collect();
+            assert_eq!(combine_shares(&picked).unwrap(), secret);
+        }
+
+        // 조각이 더 많아도 복원 가능, 대소문자·공백 무시
+        let messy: Vec<String> = shares.iter().map(|s| format!(" {} ", s.to_uppercase())).collect();
+        assert_eq!(combine_shares(&messy).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_share_errors() {
+        let shares = split_secret(&[1u8; 32], 2, 3).unwrap();
+
+        assert_eq!(
+            combine_shares(&shares[..1]),
+            Err(RecoveryShareError::NotEnoughShares { required: 2, provided: 1 })
+        );
+        assert_eq!(
+            combine_shares(&[shares[0].clone(), shares[0].clone()]),
+            Err(RecoveryShareError::DuplicateIndex(1))
+        );
+
+        // 값 한 글자를 바꾸면 체크섬 오류
+        let mut typo = shares[1].clone();
+        let pos = typo.len() - 20;
+        let replacement = if &typo[pos..pos + 1] == "0" { "1" } else { "0" };
+        typo.replace_range(pos..pos + 1, replacement);
+        assert_eq!(
+            combine_shares(&[shares[0].clone(), typo]),
+            Err(RecoveryShareError::Checksum(2))
+        );
+
+        // 다른 키의 조각과 섞이면 거부
+        let other = split_secret(&[2u8; 32], 2, 3).unwrap();
+        assert_eq!(
+            combine_shares(&[shares[0].clone(), other[1].clone()]),
+            Err(RecoveryShareError::MixedSets)
+        );
+
+        assert!(split_secret(&[0u8; 32], 1, 3).is_err());
+        assert!(split_secret(&[0u8; 32], 4, 3).is_err());
+        assert!(looks_like_share(&shares[0]));
+    }
+}
@@ -61,6 +61,36 @@ export const remainingLockoutTime = derived(
   }
 );
 
+/** 볼트가 잠겨 있어 백엔드가 요청을 거부했을 때 오류 메시지 앞에 붙는 코드 */
+export const VAULT_LOCKED_CODE = '[VAULT_LOCKED]';
+
+/**
+ * 백엔드 오류가 볼트 잠김으로 인한 것인지 확인합니다.
+ */
+export function isVaultLockedError(error: unknown): boolean {
+  return typeof error === 'string' && error.startsWith(VAULT_LOCKED_CODE);
+}
+
+/** 높은 보안 등급 파일에 접근하려면 PIN 재확인이 필요할 때의 오류 코드 */
+export const STEP_UP_REQUIRED_CODE = '[STEP_UP_REQUIRED]';
+
+/** 최고 보안 등급 파일에 접근하려면 최고 보안 암호가 필요할 때의 오류 코드 */
+export const CRITICAL_KEY_REQUIRED_CODE = '[CRITICAL_KEY_REQUIRED]';
+
+/**
+ * 백엔드 오류가 PIN 재확인 요구인지 확인합니다.
+ */
+export function isStepUpRequiredError(error: unknown): boolean {
+  return typeof error === 'string' && error.startsWith(STEP_UP_REQUIRED_CODE);
+}
+
+/**
+ * 백엔드 오류가 최고 보안 암호 요구인지 확인합니다.
+ */
+export function isCriticalKeyRequiredError(error: unknown): boolean {
+  return typeof error === 'string' && error.startsWith(CRITICAL_KEY_REQUIRED_CODE);
+}
+
 /**
  * 인증 서비스 클래스
  * 백엔드 API와 통신하여 인증 관련 작업을 수행합니다.
@@ -172,14 +202,19 @@ export class AuthService {
 
   /**
    * 복구 키로 로그인을 시도합니다.
+   * 전체 복구 키 대신 M개 이상의 복구 키 조각(recoveryShares)으로도 로그인할 수 있습니다.
+   * 성공하면 사용한 복구 키를 대체하는 새 복구 키를 돌려주므로 바로 사용자에게 보여줘야 합니다.
    */
-  static async authenticateWithRecoveryKey(recoveryKey: string): Promise<{ success: boolean; error?: string }> {
+  static async authenticateWithRecoveryKey(recoveryKey: string, recoveryShares?: string[]): Promise<{ success: boolean; error?: string; newRecoveryKey?: string }> {
     try {
       authState.update(state => ({ ...state, isLoading: true }));
 
-      const result = await invoke<boolean>('authenticate_with_recovery_key', { recoveryKey });
+      const newRecoveryKey = await invoke<string | null>(
+        'authenticate_recovery_key',
+        recoveryShares && recoveryShares.length > 0 ? { recoveryShares } : { recoveryKey }
+      );
 
-      if (result) {
+      if (newRecoveryKey) {
         // 로그인 성공 - 상태만 업데이트하고 윈도우 크기 조정은 나중에
         const sessionRemainingTime = await invoke<number>('get_session_remaining_time');
 
@@ -193,7 +228,7 @@ export class AuthService {
           sessionRemainingTime,
         }));
 
-        return { success: true };
+        return { success: true, newRecoveryKey };
       } else {
         authState.update(state => ({ ...state, isLoading: false }));
         return { success: false, error: '복구 키가 올바르지 않습니다.' };
@@ -247,6 +282,55 @@ export class AuthService {
     }
   }
 
+  /**
+   * 높은 보안 등급 파일 접근을 위해 PIN을 다시 확인합니다.
+   */
+  static async stepUp(pin: string): Promise<{ success: boolean; error?: string }> {
+    try {
+      await invoke<number>('step_up_authentication', { pin });
+      return { success: true };
+    } catch (error) {
+      return {
+        success: false,
+        error: typeof error === 'string' ? error : 'PIN 재확인 중 오류가 발생했습니다.'
+      };
+    }
+  }
+
+  /**
+   * 복구 키를 N개의 조각으로 나눕니다. 그중 M개가 모이면 복구할 수 있습니다.
+   */
+  static async splitRecoveryKey(
+    recoveryKey: string,
+    threshold: number,
+    shareCount: number
+  ): Promise<{ success: boolean; shares?: string[]; error?: string }> {
+    try {
+      const shares = await invoke<string[]>('split_recovery_key', { recoveryKey, threshold, shareCount });
+      return { success: true, shares };
+    } catch (error) {
+      return {
+        success: false,
+        error: typeof error === 'string' ? error : '복구 키 분할 중 오류가 발생했습니다.'
+      };
+    }
+  }
+
+  /**
+   * 최고 보안 암호로 최고 보안 등급 파일을 잠금 해제합니다. (PIN 재확인 후 사용)
+   */
+  static async unlockCriticalFiles(passphrase: string): Promise<{ success: boolean; error?: string }> {
+    try {
+      await invoke('unlock_critical_files', { passphrase });
+      return { success: true };
+    } catch (error) {
+      return {
+        success: false,
+        error: typeof error === 'string' ? error : '최고 보안 파일 잠금 해제 중 오류가 발생했습니다.'
+      };
+    }
+  }
+
   /**
    * 세션 남은 시간을 업데이트합니다.
    */